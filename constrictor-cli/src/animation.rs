@@ -0,0 +1,77 @@
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    time::Duration,
+};
+
+use constrictor_core::{
+    models::SnakeSimulation,
+    render::{PALETTE, Raster},
+    replay::Replay,
+};
+use gif::{Encoder, Frame, Repeat};
+
+use crate::args::GifArgs;
+
+/// How long the last frame is held before the animation starts over.
+const FINAL_HOLD: Duration = Duration::from_secs(2);
+
+/// Browsers show frames shorter than this, in hundredths of a second, for
+/// much longer instead.
+const MIN_DELAY: u16 = 2;
+
+/// Plays back a replay and writes it as an animated GIF that loops forever,
+/// one frame per step at the pace the game was played.
+pub fn export_gif(args: GifArgs) -> Result<(), Box<dyn Error>> {
+    let replay: Replay = serde_json::from_str(&fs::read_to_string(&args.replay)?)?;
+    let mut playback = replay.play()?;
+
+    let draw = |sim: &SnakeSimulation| {
+        let view = sim.view();
+        Raster::new(view.board(), &view.frame(), args.cell_size)
+    };
+
+    let mut raster = draw(playback.simulation());
+    let too_big = |_| "the board is too big for a GIF at this cell size";
+    let width = u16::try_from(raster.width()).map_err(too_big)?;
+    let height = u16::try_from(raster.height()).map_err(too_big)?;
+
+    let output = args
+        .output
+        .unwrap_or_else(|| args.replay.with_extension("gif"));
+    let file = BufWriter::new(File::create(&output)?);
+    let mut encoder = Encoder::new(file, width, height, PALETTE.as_flattened())?;
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    // Delays are in hundredths of a second, so each frame is rounded down
+    // and the time lost is made up on later ones
+    let mut played = Duration::ZERO;
+    let mut shown = Duration::ZERO;
+    let mut steps = 0;
+    loop {
+        let interval = playback.simulation().tick_interval();
+        let stepped = playback.step();
+        played += if stepped { interval } else { FINAL_HOLD };
+
+        let delay = u16::try_from((played.saturating_sub(shown)).as_millis() / 10)
+            .unwrap_or(u16::MAX)
+            .max(MIN_DELAY);
+        shown += Duration::from_millis(u64::from(delay) * 10);
+
+        encoder.write_frame(&Frame {
+            delay,
+            ..Frame::from_indexed_pixels(width, height, raster.pixels(), None)
+        })?;
+
+        if !stepped {
+            break;
+        }
+
+        steps += 1;
+        raster = draw(playback.simulation());
+    }
+
+    println!("Wrote {steps} steps to {}", output.display());
+    Ok(())
+}
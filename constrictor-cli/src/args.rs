@@ -0,0 +1,630 @@
+#[cfg(feature = "serve")]
+use std::net::IpAddr;
+use std::{fs, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use constrictor_core::{
+    bots::{AStarBot, Controller, FloodFillBot, GreedyBot, RandomBot},
+    config::{BoardShape, Difficulty, GameConfig, Map, WallStyle},
+    math::Vector2,
+    models::{Appearance, Portal, Shrink, TimeLimit},
+};
+
+use crate::{io::KeyZone, rendering::RenderMode, skin::Skin, theme::ThemeName};
+
+/// Classic Snake, in your terminal.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Settings file to load defaults from. Defaults to
+    /// `constrictor/config.toml` in the platform's config directory.
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub display: DisplayArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// How boards are drawn on the terminal, for every command that draws one.
+#[derive(Debug, Args, Default)]
+pub struct DisplayArgs {
+    /// How the board looks. Defaults to the `theme` in the settings file, or
+    /// the classic theme.
+    #[arg(long, global = true, value_name = "NAME")]
+    pub theme: Option<ThemeName>,
+
+    /// Draw without colour, telling things apart by their characters alone.
+    /// Setting the `NO_COLOR` environment variable does the same.
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Draw with plain ASCII characters only. This happens anyway when the
+    /// terminal's locale isn't UTF-8, or `TERM` names one that predates
+    /// Unicode.
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Shade every other cell of the board, like a checkerboard, so that
+    /// distances are easier to judge. Themes can pick the shade as `checker`
+    /// in the settings file. Needs colour.
+    #[arg(long, global = true)]
+    pub checkerboard: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Play a game of Snake (the default).
+    Play(PlayArgs),
+
+    /// Pit the built-in bots against each other over many seeded games and
+    /// print the standings.
+    Tournament(TournamentArgs),
+
+    /// Measure how quickly each render mode can draw a recorded game.
+    BenchRender(BenchRenderArgs),
+
+    /// Draw the path the snake traced over a saved replay as an SVG image.
+    Trail(TrailArgs),
+
+    /// Render a saved replay as an animated GIF. Needs the `gif` feature.
+    Gif(GifArgs),
+
+    /// Watch a built-in bot play, with its reasoning drawn over the board.
+    Watch(WatchArgs),
+
+    /// Watch two built-in bots play against each other on the same board.
+    Spectate(SpectateArgs),
+
+    /// Play through a series of levels, each with a goal to meet before
+    /// moving on to the next. Progress is saved between levels.
+    Campaign(CampaignArgs),
+
+    /// Draw a map for `--map` in the terminal, and save it.
+    Edit(EditArgs),
+
+    /// Check the settings file for problems, and optionally repair it.
+    Doctor(DoctorArgs),
+
+    /// Run games in the background, driven and watched by other programs over
+    /// a Unix domain socket. Unix only.
+    Daemon(DaemonArgs),
+}
+
+/// Overrides for the [`GameConfig`] a command starts from.
+#[derive(Debug, Args, Default)]
+pub struct GameArgs {
+    /// Width of the board, in cells.
+    #[arg(long)]
+    pub width: Option<u16>,
+
+    /// Height of the board, in cells.
+    #[arg(long)]
+    pub height: Option<u16>,
+
+    /// Shape of the playable area within the board: rectangle, circle or
+    /// cross.
+    #[arg(long, value_parser = parse_shape)]
+    pub shape: Option<BoardShape>,
+
+    /// Play on the level drawn in this map file instead, which decides the
+    /// board's size and shape. Each line is a row of cells: `#` for wall,
+    /// `.` for floor, `S` where a snake starts, `F` where food appears, and
+    /// pairs of matching lowercase letters for portals.
+    #[arg(long, value_name = "FILE", value_parser = load_map)]
+    pub map: Option<Map>,
+
+    /// Seed from which all randomness is derived. Random if omitted.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Remove the walls, so the snake wraps around to the opposite side.
+    #[arg(long)]
+    pub wrap: bool,
+
+    /// How fast the snake starts, and how quickly it speeds up as it eats:
+    /// easy, normal or hard.
+    #[arg(long, value_parser = parse_difficulty)]
+    pub difficulty: Option<Difficulty>,
+
+    /// How many times the snake can die before the game is over. Until then,
+    /// it respawns somewhere safe at half its length.
+    #[arg(long)]
+    pub lives: Option<u32>,
+
+    /// Ignore turns that would kill the snake on the very next step.
+    #[arg(long)]
+    pub assist: bool,
+
+    /// Build a score multiplier by eating each piece of food within this many
+    /// steps of the last. It drops again after as long without eating.
+    #[arg(long, value_name = "STEPS")]
+    pub combo_window: Option<u32>,
+
+    /// Close the board in for a sudden-death finish, starting this many steps
+    /// in. Anything caught outside the shrinking area dies.
+    #[arg(long, value_name = "STEPS")]
+    pub shrink_after: Option<u64>,
+
+    /// How many steps apart the board closes in by another ring.
+    #[arg(
+        long,
+        value_name = "STEPS",
+        default_value_t = 20,
+        requires = "shrink_after"
+    )]
+    pub shrink_every: u64,
+
+    /// Play against the clock, scoring as much as possible in this many
+    /// steps.
+    #[arg(long, value_name = "STEPS")]
+    pub time_limit: Option<u64>,
+
+    /// How many more steps each piece of food is worth against the clock.
+    #[arg(
+        long,
+        value_name = "STEPS",
+        default_value_t = 10,
+        requires = "time_limit"
+    )]
+    pub time_bonus: u64,
+
+    /// Make the food run from the snake, moving a cell further away every
+    /// this many steps.
+    #[arg(long, value_name = "STEPS")]
+    pub fleeing_food: Option<u32>,
+
+    /// Cover the board in fog, so that only the cells within this many of
+    /// the snake's head can be seen.
+    #[arg(long, value_name = "CELLS")]
+    pub fog: Option<u32>,
+
+    /// Link two cells with a portal, written as `X1,Y1:X2,Y2`. Can be given
+    /// more than once.
+    #[arg(long = "portal", value_name = "X1,Y1:X2,Y2", value_parser = parse_portal)]
+    pub portals: Vec<Portal>,
+
+    /// Play the exact game described by a challenge code, as shown when a
+    /// game starts and printed after it ends. It decides the board, the rules, the speed and the seed.
+    #[arg(
+        long,
+        value_name = "CODE",
+        value_parser = parse_challenge,
+        conflicts_with_all = [
+            "width", "height", "shape", "map", "seed", "wrap", "difficulty", "lives", "assist",
+            "combo_window", "shrink_after", "time_limit", "fleeing_food", "fog", "portals",
+        ]
+    )]
+    pub challenge: Option<GameConfig>,
+}
+
+fn parse_difficulty(name: &str) -> Result<Difficulty, String> {
+    Difficulty::ALL
+        .into_iter()
+        .find(|difficulty| difficulty.to_string().eq_ignore_ascii_case(name))
+        .ok_or_else(|| "expected one of easy, normal or hard".to_string())
+}
+
+fn parse_shape(name: &str) -> Result<BoardShape, String> {
+    BoardShape::ALL
+        .into_iter()
+        .find(|shape| shape.to_string().eq_ignore_ascii_case(name))
+        .ok_or_else(|| "expected one of rectangle, circle or cross".to_string())
+}
+
+fn parse_challenge(code: &str) -> Result<GameConfig, String> {
+    GameConfig::from_challenge_code(code).map_err(|e| e.to_string())
+}
+
+fn load_map(path: &str) -> Result<Map, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("could not read it: {e}"))?;
+    text.parse::<Map>().map_err(|e| e.to_string())
+}
+
+fn parse_portal(ends: &str) -> Result<Portal, String> {
+    let cell = |text: &str| {
+        let (x, y) = text.split_once(',')?;
+        Some(Vector2 {
+            x: x.trim().parse().ok()?,
+            y: y.trim().parse().ok()?,
+        })
+    };
+
+    ends.split_once(':')
+        .and_then(|(a, b)| {
+            Some(Portal {
+                a: cell(a)?,
+                b: cell(b)?,
+            })
+        })
+        .ok_or_else(|| "expected two cells, such as 2,3:10,7".to_string())
+}
+
+impl GameArgs {
+    /// Applies any overrides given on the command line on top of `base`.
+    pub fn apply(&self, mut base: GameConfig) -> GameConfig {
+        if let Some(challenge) = &self.challenge {
+            return GameConfig {
+                player: base.player,
+                ..challenge.clone()
+            };
+        }
+
+        base.board.width = self.width.unwrap_or(base.board.width);
+        base.board.height = self.height.unwrap_or(base.board.height);
+        base.shape = self.shape.unwrap_or(base.shape);
+        base.map = self.map.clone().or(base.map);
+        base.seed = self.seed.or(base.seed);
+
+        if self.wrap {
+            base.walls = WallStyle::Wrap;
+        }
+
+        if !self.portals.is_empty() {
+            base.portals = self.portals.clone();
+        }
+
+        if self.assist {
+            base.rules.assist = true;
+        }
+
+        base.rules.lives = self.lives.unwrap_or(base.rules.lives);
+        base.rules.combo_window = self.combo_window.or(base.rules.combo_window);
+        base.rules.fleeing_food = self.fleeing_food.or(base.rules.fleeing_food);
+        base.rules.fog = self.fog.or(base.rules.fog);
+
+        if let Some(after) = self.shrink_after {
+            base.rules.shrink = Some(Shrink {
+                after,
+                every: self.shrink_every,
+            });
+        }
+
+        if let Some(steps) = self.time_limit {
+            base.rules.time_limit = Some(TimeLimit {
+                steps,
+                bonus_per_food: self.time_bonus,
+            });
+        }
+
+        if let Some(difficulty) = self.difficulty {
+            base.speed = difficulty.speed();
+        }
+
+        base
+    }
+}
+
+#[derive(Debug, Args, Default)]
+pub struct PlayArgs {
+    /// Which keys steer the snake. Defaults to both the arrow keys and WASD.
+    #[arg(long, value_enum)]
+    pub keys: Option<KeyZone>,
+
+    /// Show the title menu before starting. Always shown when no command is
+    /// given.
+    #[arg(long)]
+    pub menu: bool,
+
+    /// Draw the game under the prompt in the normal screen, rather than
+    /// taking over the whole terminal, and leave the last frame there
+    /// afterwards. Handy in tmux panes, screen recordings and terminals whose
+    /// alternate screen misbehaves.
+    #[arg(long, conflicts_with = "menu")]
+    pub inline: bool,
+
+    /// Ignore a repeated direction key pressed again within this many
+    /// milliseconds. Helps with terminals that send bursts of repeats.
+    #[arg(long, value_name = "MS")]
+    pub repeat_filter_ms: Option<u64>,
+
+    /// Seconds to count down before the snake starts moving, and again after
+    /// restarting. Defaults to 3; 0 starts straight away.
+    #[arg(long, value_name = "SECONDS")]
+    pub countdown: Option<u8>,
+
+    /// Seconds without a key pressed on the title menu before a bot starts
+    /// playing a demo game behind it. Defaults to 30; 0 never plays one.
+    #[arg(long, value_name = "SECONDS")]
+    pub attract_after: Option<u16>,
+
+    /// Steer by clicking or dragging on the board, relative to the snake's
+    /// head, and show clickable pause and quit buttons.
+    #[arg(long)]
+    pub mouse: bool,
+
+    /// Draw the board in braille dots, 2 by 4 cells to a character, so that
+    /// boards too big for the terminal fit. Terrain and fog don't show, and
+    /// the mouse can't steer.
+    #[arg(long, conflicts_with = "mouse")]
+    pub braille: bool,
+
+    /// Draw two rows of the board in each row of the terminal, as the halves
+    /// of a block, so that boards twice the size fit. Cells show only as
+    /// their colour.
+    #[arg(long, conflicts_with = "braille")]
+    pub half_block: bool,
+
+    /// How your snake is drawn, over the theme's glyphs and colours. Defaults
+    /// to the `skin` in the settings file, or the theme's own. With only
+    /// ASCII to draw in, skins change colours alone.
+    #[arg(long, value_enum, value_name = "SKIN")]
+    pub skin: Option<Skin>,
+
+    /// Go straight to the summary when the snake dies, without first
+    /// flashing it from head to tail and fading it out.
+    #[arg(long)]
+    pub no_death_animation: bool,
+
+    /// Issue the commands in this script on the ticks it gives, alongside the
+    /// keyboard. `-` reads the script from standard input. Each line looks
+    /// like `tick 12: Left`.
+    #[arg(long, value_name = "FILE")]
+    pub input_script: Option<PathBuf>,
+
+    /// Write every command acted on, with the tick it was issued on, to this
+    /// file once the game ends. The file can be played again with
+    /// `--input-script`, given the seed named at its top. Restarting without
+    /// `--seed` starts the recording over, since the new game's seed is
+    /// random. An `--opponent` bot's moves aren't commands, so it can't be
+    /// recorded.
+    #[arg(long, value_name = "FILE", conflicts_with = "opponent")]
+    pub record_input: Option<PathBuf>,
+
+    /// Record everything drawn on the terminal, with its timing, to this
+    /// file as an asciinema cast, so the game can be played back with
+    /// `asciinema play` or shared.
+    #[arg(long, value_name = "FILE")]
+    pub record_cast: Option<PathBuf>,
+
+    /// Size of each board cell, in pixels, in screenshots saved with the
+    /// screenshot key. Defaults to 8.
+    #[arg(long, value_name = "PIXELS")]
+    pub screenshot_cell_size: Option<u32>,
+
+    /// Let the chat of this Twitch channel steer the snake, alongside the
+    /// keyboard. Viewers vote with `!up`, `!down`, `!left` and `!right`, and
+    /// the most popular direction is taken every `--vote-window` ticks.
+    #[cfg(feature = "twitch")]
+    #[arg(long, value_name = "CHANNEL")]
+    pub twitch: Option<String>,
+
+    /// How many ticks Twitch chat votes are gathered for before the winner
+    /// is taken. Defaults to 5.
+    #[cfg(feature = "twitch")]
+    #[arg(long, value_name = "TICKS", requires = "twitch")]
+    pub vote_window: Option<u64>,
+
+    /// Serve a page on this port that shows the game live, so it can be
+    /// watched in a browser at `http://localhost:PORT` while it is played.
+    #[cfg(feature = "serve")]
+    #[arg(long, value_name = "PORT")]
+    pub serve: Option<u16>,
+
+    /// The address `--serve` listens on. Defaults to 127.0.0.1, so only this
+    /// machine can watch; give 0.0.0.0 to let anyone who can reach it in.
+    #[cfg(feature = "serve")]
+    #[arg(long, value_name = "IP", requires = "serve")]
+    pub serve_address: Option<IpAddr>,
+
+    /// Add a second snake, steered by this bot, to compete for the same
+    /// food. The last snake left wins. Replays don't record the bot, so
+    /// can't be saved.
+    #[arg(long, value_enum, value_name = "BOT", conflicts_with = "save_replay")]
+    pub opponent: Option<BotKind>,
+
+    /// Save a replay of the game to this file once it ends.
+    #[arg(long, value_name = "FILE")]
+    pub save_replay: Option<PathBuf>,
+
+    /// Append a summary of every game played to this file: its seed, board
+    /// size, mode, result, score, length, duration and ticks. Written as
+    /// JSON lines if the file is named `.json` or `.jsonl`, and as CSV
+    /// otherwise.
+    #[arg(long, value_name = "FILE")]
+    pub log_games: Option<PathBuf>,
+
+    /// Print nothing once the game ends but a summary of it on standard
+    /// error, as one line of JSON like `--log-games` writes, for scripts.
+    /// The leaderboard is left alone too. The exit code tells how the game
+    /// ended either way.
+    #[arg(long, short)]
+    pub quiet: bool,
+
+    /// Where the game is shown. `json` plays without the terminal, writing
+    /// the state after every tick to standard output as one line of JSON,
+    /// and takes commands only from `--input-script`.
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+impl PlayArgs {
+    /// Builds the bot asked for with `--opponent`, if any, and adds the snake
+    /// it steers to `config`. That snake is always the second, so has index
+    /// 1. Any randomness the bot uses is derived from `seed`.
+    pub fn add_opponent(&self, config: &mut GameConfig, seed: u64) -> Option<Box<dyn Controller>> {
+        let bot = self.opponent?.build(seed);
+        config.opponents = vec![Appearance {
+            name: Some(bot.name().to_string()),
+            color: 1,
+            ..Default::default()
+        }];
+
+        Some(bot)
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct TournamentArgs {
+    /// Number of games each bot plays.
+    #[arg(long, default_value_t = 100)]
+    pub games: usize,
+
+    /// Number of steps after which a game is cut short.
+    #[arg(long, default_value_t = 10_000)]
+    pub max_ticks: usize,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchRenderArgs {
+    /// Number of times the recorded game is rendered by each mode.
+    #[arg(long, default_value_t = 20)]
+    pub passes: usize,
+
+    /// Render modes to measure. Measures every mode if omitted.
+    #[arg(long, value_enum)]
+    pub mode: Vec<RenderMode>,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct TrailArgs {
+    /// Replay file saved with `play --save-replay`.
+    pub replay: PathBuf,
+
+    /// Where to write the image. Defaults to the replay's path with an `.svg`
+    /// extension.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Size of each board cell, in pixels.
+    #[arg(long, default_value_t = 16)]
+    pub cell_size: u32,
+}
+
+#[derive(Debug, Args)]
+pub struct GifArgs {
+    /// Replay file saved with `play --save-replay`.
+    pub replay: PathBuf,
+
+    /// Where to write the animation. Defaults to the replay's path with a
+    /// `.gif` extension.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Size of each board cell, in pixels.
+    #[arg(long, default_value_t = 8)]
+    pub cell_size: u32,
+}
+
+/// How `play` shows the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Drawn in the terminal, and played with the keyboard.
+    #[default]
+    Terminal,
+
+    /// Written to standard output as JSON, one line per tick.
+    Json,
+}
+
+/// The built-in bots, roughly from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BotKind {
+    Random,
+    Greedy,
+    AStar,
+    FloodFill,
+}
+
+impl BotKind {
+    /// Creates the bot, with any randomness it uses derived from `seed`.
+    pub fn build(self, seed: u64) -> Box<dyn Controller> {
+        match self {
+            Self::Random => Box::new(RandomBot::new(seed)),
+            Self::Greedy => Box::new(GreedyBot),
+            Self::AStar => Box::new(AStarBot),
+            Self::FloodFill => Box::new(FloodFillBot),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Which bot plays.
+    #[arg(long, value_enum, default_value_t = BotKind::Greedy)]
+    pub bot: BotKind,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct SpectateArgs {
+    /// Which bot steers the first snake.
+    #[arg(long, value_enum, default_value_t = BotKind::Greedy)]
+    pub bot: BotKind,
+
+    /// Which bot steers the second snake.
+    #[arg(long, value_enum, default_value_t = BotKind::FloodFill)]
+    pub opponent: BotKind,
+
+    /// Keep playing matches, each with a fresh seed, until told to quit.
+    /// The first match still uses `--seed` if given.
+    #[arg(long = "loop")]
+    pub repeat: bool,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct DaemonArgs {
+    /// Path of the socket to listen on.
+    #[arg(long, value_name = "PATH", default_value = "constrictor.sock")]
+    pub socket: PathBuf,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct CampaignArgs {
+    /// Level manifest to play instead of the bundled campaign. Written in
+    /// TOML, as a `title` and a `[[level]]` table for each level, giving its
+    /// `name`, its `goal` (such as `{ length = 10 }` or `{ survive = 300 }`)
+    /// and the `[level.game]` it is played under.
+    #[arg(long, value_name = "FILE")]
+    pub levels: Option<PathBuf>,
+
+    /// Start again from the first level, rather than where the saved
+    /// progress left off.
+    #[arg(long)]
+    pub restart: bool,
+
+    /// Which keys steer the snake. Defaults to both the arrow keys and WASD.
+    #[arg(long, value_enum)]
+    pub keys: Option<KeyZone>,
+}
+
+#[derive(Debug, Args)]
+pub struct EditArgs {
+    /// Map file to edit. A new map is started if it doesn't exist yet.
+    pub file: PathBuf,
+
+    /// Width of a new map, in cells. Ignored if the file already exists.
+    #[arg(long, default_value_t = 32, value_parser = clap::value_parser!(u16).range(1..))]
+    pub width: u16,
+
+    /// Height of a new map, in cells. Ignored if the file already exists.
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u16).range(1..))]
+    pub height: u16,
+}
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Migrate an outdated settings file, or move an unusable one aside.
+    /// The original is kept with a `.bak` extension.
+    #[arg(long)]
+    pub fix: bool,
+}
@@ -0,0 +1,122 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    str,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::json;
+
+/// A recording of everything written to the terminal, in asciinema's
+/// [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) format,
+/// so it can be played back with `asciinema play` or shared. The file starts
+/// with a header line, followed by one line per burst of output giving when
+/// it was written, in seconds since the recording started:
+///
+/// ```text
+/// {"version":2,"width":80,"height":24,"timestamp":1760000000,...}
+/// [0.0,"o","\u001b[?1049h"]
+/// [0.016542,"o","\u001b[12;30H█"]
+/// ```
+pub struct Asciicast {
+    file: BufWriter<File>,
+    start: Instant,
+
+    /// Output written since the last burst was recorded, which may end
+    /// part way through a character.
+    pending: Vec<u8>,
+}
+
+impl Asciicast {
+    /// Starts a recording in the file at `path`, of a terminal `width`
+    /// columns by `height` rows.
+    pub fn create(path: &Path, (width, height): (u16, u16)) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let header = json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": timestamp,
+            "title": "Constrictor",
+            "env": { "TERM": env::var("TERM").ok() },
+        });
+        writeln!(file, "{header}")?;
+
+        Ok(Self {
+            file,
+            start: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Records the output written since last time as one burst, stamped
+    /// with the time now. A character cut off at the end waits for the next
+    /// burst, since each burst must be valid text.
+    fn record(&mut self) -> io::Result<()> {
+        let complete = match str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+
+        if complete == 0 {
+            return Ok(());
+        }
+
+        let burst: Vec<u8> = self.pending.drain(..complete).collect();
+        let time = self.start.elapsed().as_micros() as f64 / 1_000_000.0;
+        let event = json!([time, "o", String::from_utf8_lossy(&burst)]);
+
+        writeln!(self.file, "{event}")
+    }
+}
+
+/// Passes everything written on to the terminal, and records it in an
+/// [`Asciicast`] too if there is one. Output is stamped each time it is
+/// flushed, which the game does once a frame.
+pub struct TeeWriter<W> {
+    inner: W,
+    cast: Option<Asciicast>,
+}
+
+impl<W: Write> TeeWriter<W> {
+    pub fn new(inner: W, cast: Option<Asciicast>) -> Self {
+        Self { inner, cast }
+    }
+
+    /// Records whatever output is left and finishes writing the recording.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush()?;
+
+        match self.cast.as_mut() {
+            Some(cast) => cast.file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Write for TeeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(cast) = self.cast.as_mut() {
+            cast.pending.extend_from_slice(&buf[..written]);
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+
+        match self.cast.as_mut() {
+            Some(cast) => cast.record(),
+            None => Ok(()),
+        }
+    }
+}
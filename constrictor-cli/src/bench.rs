@@ -0,0 +1,108 @@
+use std::{
+    error::Error,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use constrictor_core::{
+    bots::{Controller, GreedyBot},
+    config::GameConfig,
+    models::SnakeSimulation,
+};
+
+use crate::{
+    args::BenchRenderArgs,
+    rendering::{FrameRenderer, RenderMode},
+    theme::Theme,
+};
+
+/// A [`Write`] sink that discards everything, keeping count of how many bytes
+/// it was given.
+#[derive(Default)]
+struct CountingSink {
+    bytes: usize,
+}
+
+impl Write for CountingSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Records a game played by [`GreedyBot`], then renders it repeatedly with
+/// each requested [`RenderMode`] and reports the throughput of each.
+pub fn bench_render(args: BenchRenderArgs, theme: Theme) -> Result<(), Box<dyn Error>> {
+    let config = args.game.apply(GameConfig {
+        seed: Some(0),
+        ..Default::default()
+    });
+
+    let frames = record_game(config.build()?);
+
+    let modes = if args.mode.is_empty() {
+        RenderMode::value_variants().to_vec()
+    } else {
+        args.mode
+    };
+
+    println!(
+        "{} frames on a {}x{} board, {} passes per mode",
+        frames.len(),
+        config.board.width,
+        config.board.height,
+        args.passes
+    );
+    println!();
+    println!("{:<8}  {:>12}  {:>12}", "mode", "frames/sec", "bytes/frame");
+
+    for mode in modes {
+        let mut sink = CountingSink::default();
+        let mut elapsed = Duration::ZERO;
+
+        for _ in 0..args.passes {
+            let mut renderer = FrameRenderer::new(mode, theme.clone());
+            let start = Instant::now();
+
+            for frame in &frames {
+                renderer.draw(&frame.view(), &mut sink)?;
+                sink.flush()?;
+            }
+
+            elapsed += start.elapsed();
+        }
+
+        let frames_drawn = (frames.len() * args.passes) as f64;
+        let name = mode.to_possible_value().map(|v| v.get_name().to_owned());
+
+        println!(
+            "{:<8}  {:>12.0}  {:>12.0}",
+            name.unwrap_or_default(),
+            frames_drawn / elapsed.as_secs_f64(),
+            sink.bytes as f64 / frames_drawn
+        );
+    }
+
+    Ok(())
+}
+
+/// Plays `sim` to completion with a [`GreedyBot`], returning the state of the
+/// game at every step.
+fn record_game(mut sim: SnakeSimulation) -> Vec<SnakeSimulation> {
+    let mut bot = GreedyBot;
+    let mut frames = vec![sim.clone()];
+
+    while sim.result().is_none() {
+        bot.steer(&mut sim, 0);
+
+        sim.advance();
+        frames.push(sim.clone());
+    }
+
+    frames
+}
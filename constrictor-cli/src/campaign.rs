@@ -0,0 +1,571 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Write, stdout},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{
+    config::GameConfig,
+    models::{Clock, SimulationResult, SnakeSimulation, StopAt},
+};
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::{self, Stylize},
+    terminal::{self, ClearType},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    args::CampaignArgs,
+    io::{EventStream, GameCommand, KeyMap, ShutdownSignals},
+    rendering::{FrameRenderer, Hud, RenderMode, render_banner},
+    scope_guard::ScopeGuard,
+    settings::{self, Settings},
+    theme::Theme,
+};
+
+/// The campaign played when no other is given.
+const BUNDLED: &str = include_str!("../levels/campaign.toml");
+
+/// How long the player gets to find the keys before each level starts.
+const COUNTDOWN: Duration = Duration::from_secs(3);
+
+/// How long the end of a level stays up before moving on.
+const OUTCOME_SHOWN_FOR: Duration = Duration::from_millis(1500);
+
+/// The most steps the game loop will take at once to catch up after a stall.
+const MAX_CATCH_UP: u32 = 4;
+
+/// A series of [`Level`]s, played one after another. See
+/// [`CampaignArgs::levels`] for how one is written.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Campaign {
+    /// Names the campaign, and the progress saved through it.
+    title: String,
+
+    #[serde(rename = "level")]
+    levels: Vec<Level>,
+}
+
+/// One game of a [`Campaign`], and what it takes to clear it.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Level {
+    name: String,
+    goal: Goal,
+
+    #[serde(default)]
+    game: GameConfig,
+}
+
+/// What has to be done to clear a [`Level`]. Winning the game outright
+/// clears it too.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Goal {
+    /// Grow the snake to at least this many segments.
+    Length(usize),
+
+    /// Stay alive for this many steps.
+    Survive(u64),
+}
+
+impl Goal {
+    fn is_met(self, sim: &SnakeSimulation) -> bool {
+        match self {
+            Self::Length(length) => sim.snake().len() >= length,
+            Self::Survive(ticks) => sim.tick() >= ticks,
+        }
+    }
+
+    /// Describes how close `sim` is to meeting this goal.
+    fn progress(self, sim: &SnakeSimulation) -> String {
+        match self {
+            Self::Length(length) => format!("{}/{length}", sim.snake().len()),
+            Self::Survive(ticks) => format!("{} to go", ticks.saturating_sub(sim.tick())),
+        }
+    }
+}
+
+impl Display for Goal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Length(length) => write!(f, "reach length {length}"),
+            Self::Survive(ticks) => write!(f, "survive {ticks} steps"),
+        }
+    }
+}
+
+impl Campaign {
+    /// Loads the campaign in the level manifest at `path`, or the bundled
+    /// one if no path is given, checking that every level can be played.
+    fn load(path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let (source, text) = match path {
+            Some(path) => (
+                path.display().to_string(),
+                fs::read_to_string(path)
+                    .map_err(|e| format!("could not read {}: {e}", path.display()))?,
+            ),
+            None => ("the bundled campaign".to_string(), BUNDLED.to_string()),
+        };
+
+        let campaign: Self =
+            toml::from_str(&text).map_err(|e| format!("invalid levels in {source}:\n{e}"))?;
+
+        if campaign.levels.is_empty() {
+            return Err(format!("{source} has no levels").into());
+        }
+
+        for level in &campaign.levels {
+            level.game.validate().map_err(|e| {
+                format!("level \"{}\" in {source} can't be played: {e}", level.name)
+            })?;
+        }
+
+        Ok(campaign)
+    }
+}
+
+/// How far the player has got through a [`Campaign`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Checkpoint {
+    /// Which level is played next, counting from zero.
+    level: usize,
+
+    /// The score carried into that level from the ones before it.
+    score: usize,
+
+    /// The best final score of any run through the whole campaign.
+    best_score: usize,
+}
+
+/// Every [`Checkpoint`] saved, by [`Campaign::title`]. Kept in
+/// `campaign.toml`, next to the settings file.
+struct Progress {
+    path: Option<PathBuf>,
+    checkpoints: BTreeMap<String, Checkpoint>,
+}
+
+impl Progress {
+    /// Loads the progress saved next to the settings file at
+    /// `settings_path`, or at the default location if no path is given.
+    /// Nothing saved yet is not an error.
+    fn load(settings_path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let path = settings_path
+            .map(Path::to_owned)
+            .or_else(settings::default_path)
+            .map(|path| path.with_file_name("campaign.toml"));
+
+        let Some(path) = path else {
+            return Ok(Self {
+                path: None,
+                checkpoints: BTreeMap::new(),
+            });
+        };
+
+        let checkpoints = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("invalid campaign progress in {}:\n{e}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("could not read {}: {e}", path.display()).into()),
+        };
+
+        Ok(Self {
+            path: Some(path),
+            checkpoints,
+        })
+    }
+
+    /// Writes every checkpoint back out. There is nowhere to write them when
+    /// the platform has no configuration directory, so they are only kept
+    /// until the program ends.
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let failed = |e: io::Error| format!("could not save progress to {}: {e}", path.display());
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(failed)?;
+        }
+
+        fs::write(path, toml::to_string(&self.checkpoints)?).map_err(failed)?;
+        Ok(())
+    }
+}
+
+/// How playing a [`Level`] ended.
+enum Outcome {
+    /// The goal was met, scoring this many points.
+    Cleared(usize),
+
+    /// The snake died first.
+    Failed,
+
+    /// The player gave up on the campaign for now.
+    Quit,
+}
+
+/// Plays through the levels of a campaign from wherever the saved progress
+/// left off, saving again after each one.
+pub fn campaign(
+    args: CampaignArgs,
+    config: Option<&Path>,
+    theme: Theme,
+) -> Result<(), Box<dyn Error>> {
+    let settings = Settings::load(config)?;
+    let campaign = Campaign::load(args.levels.as_deref())?;
+    let mut progress = Progress::load(config)?;
+
+    // A campaign that has lost levels since it was saved starts over
+    let saved = progress
+        .checkpoints
+        .get(&campaign.title)
+        .copied()
+        .unwrap_or_default();
+    let mut checkpoint = if args.restart || saved.level >= campaign.levels.len() {
+        Checkpoint {
+            best_score: saved.best_score,
+            ..Default::default()
+        }
+    } else {
+        saved
+    };
+
+    let restore_terminal = ScopeGuard::new(|| {
+        _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        _ = terminal::disable_raw_mode();
+    });
+
+    let mut stdout = stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let signals = ShutdownSignals::register()?;
+    let mut events = EventStream::new()?;
+    let keys = settings.key_map(args.keys);
+    let mut heading = None;
+
+    let finished = loop {
+        let level = &campaign.levels[checkpoint.level];
+        let lines = [
+            heading.take().unwrap_or_default(),
+            format!(
+                "Level {} of {}: {}",
+                checkpoint.level + 1,
+                campaign.levels.len(),
+                level.name
+            ),
+            format!("Goal: {}", level.goal),
+            format!("Score: {}", checkpoint.score),
+        ];
+
+        let screen = Screen {
+            title: &campaign.title,
+            lines: &lines,
+            prompt: "Press enter to play, or q to quit",
+        };
+        if !screen.show(&mut events, &keys, &signals, &mut stdout)? {
+            break false;
+        }
+
+        match play_level(
+            level,
+            checkpoint.score,
+            &keys,
+            &signals,
+            &mut events,
+            &theme,
+            &mut stdout,
+        )? {
+            Outcome::Cleared(score) => {
+                let points = match score {
+                    1 => "1 point".to_string(),
+                    n => format!("{n} points"),
+                };
+                heading = Some(format!("{} cleared! +{points}", level.name));
+                checkpoint.score += score;
+                checkpoint.level += 1;
+            }
+            Outcome::Failed => heading = Some(format!("Crashed on {}. Try again!", level.name)),
+            Outcome::Quit => break false,
+        }
+
+        if checkpoint.level == campaign.levels.len() {
+            checkpoint.best_score = checkpoint.best_score.max(checkpoint.score);
+            break true;
+        }
+
+        progress
+            .checkpoints
+            .insert(campaign.title.clone(), checkpoint);
+        progress.save()?;
+    };
+
+    if finished {
+        let lines = [
+            "Campaign complete!".to_string(),
+            format!("Final score: {}", checkpoint.score),
+            format!("Best score: {}", checkpoint.best_score),
+        ];
+
+        // The next run starts from the beginning, remembering only the best
+        checkpoint = Checkpoint {
+            best_score: checkpoint.best_score,
+            ..Default::default()
+        };
+        progress
+            .checkpoints
+            .insert(campaign.title.clone(), checkpoint);
+        progress.save()?;
+
+        let screen = Screen {
+            title: &campaign.title,
+            lines: &lines,
+            prompt: "Press enter to finish",
+        };
+        screen.show(&mut events, &keys, &signals, &mut stdout)?;
+    }
+
+    // Put the terminal back first, so the summary outlives the game screen
+    drop(restore_terminal);
+    if !finished {
+        println!(
+            "Stopped at level {} of {} with a score of {}.",
+            checkpoint.level + 1,
+            campaign.levels.len(),
+            checkpoint.score
+        );
+    }
+
+    Ok(())
+}
+
+/// A page of text shown between levels.
+struct Screen<'a> {
+    title: &'a str,
+    lines: &'a [String],
+    prompt: &'a str,
+}
+
+impl Screen<'_> {
+    /// Shows the screen until the player moves on. Returns `false` if they
+    /// chose to quit instead.
+    fn show<W: Write>(
+        &self,
+        events: &mut EventStream,
+        keys: &KeyMap,
+        signals: &ShutdownSignals,
+        stream: &mut W,
+    ) -> Result<bool, Box<dyn Error>> {
+        self.draw(stream)?;
+
+        loop {
+            if signals.received().is_some() {
+                return Ok(false);
+            }
+
+            // Nothing moves on its own, so there is nothing to redraw until a
+            // key is pressed or the terminal is resized
+            if !events.wait(Duration::from_secs(1)) {
+                continue;
+            }
+
+            for event in events.by_ref() {
+                let Event::Key(key) = event? else {
+                    self.draw(stream)?;
+                    continue;
+                };
+
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char(' ') => return Ok(true),
+                    KeyCode::Esc => return Ok(false),
+                    _ => {}
+                }
+
+                if keys.command(key) == Some(GameCommand::Quit) {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    fn draw<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>> {
+        queue!(
+            stream,
+            style::ResetColor,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(2, 1),
+            style::PrintStyledContent(self.title.green().bold())
+        )?;
+
+        for (row, line) in (3..).zip(self.lines) {
+            queue!(stream, cursor::MoveTo(2, row), style::Print(line))?;
+        }
+
+        let row: u16 = (self.lines.len() + 4).try_into()?;
+        queue!(
+            stream,
+            cursor::MoveTo(2, row),
+            style::PrintStyledContent(self.prompt.dim())
+        )?;
+
+        stream.flush()?;
+        Ok(())
+    }
+}
+
+/// Plays `level` until its goal is met or the snake dies. `carried` is the
+/// score from the levels before, shown alongside the level's own.
+fn play_level<W: Write>(
+    level: &Level,
+    carried: usize,
+    keys: &KeyMap,
+    signals: &ShutdownSignals,
+    events: &mut EventStream,
+    theme: &Theme,
+    stream: &mut W,
+) -> Result<Outcome, Box<dyn Error>> {
+    let mut sim = GameConfig {
+        seed: Some(level.game.seed.unwrap_or_else(rand::random)),
+        ..level.game.clone()
+    }
+    .build()?;
+
+    let mut renderer = FrameRenderer::new(RenderMode::Diff, theme.clone());
+    let mut hud = Hud::new();
+    let mut clock = Clock::new();
+    let starts_at = Instant::now() + COUNTDOWN;
+    let mut banner_shown = false;
+    let mut previous_frame_start = Instant::now();
+
+    while sim.result().is_none() && !level.goal.is_met(&sim) {
+        let frame_start = Instant::now();
+        let counting_down = frame_start < starts_at;
+
+        // The banner isn't part of the frame, so only a full redraw clears it
+        if banner_shown && !counting_down {
+            renderer = FrameRenderer::new(RenderMode::Diff, theme.clone());
+            banner_shown = false;
+        }
+
+        let elapsed = (frame_start - previous_frame_start).min(sim.tick_interval() * MAX_CATCH_UP);
+        previous_frame_start = frame_start;
+
+        if !sim.is_paused() && !counting_down {
+            hud.elapse(elapsed);
+            clock.elapse(elapsed);
+        }
+
+        for event in events.by_ref() {
+            let Event::Key(key) = event? else {
+                continue;
+            };
+
+            match keys.command(key) {
+                Some(GameCommand::Quit) => return Ok(Outcome::Quit),
+                Some(GameCommand::ChangeDirection(direction)) => {
+                    sim.change_player_move_direction(direction)
+                }
+                Some(GameCommand::Pause) => sim.set_paused(!sim.is_paused()),
+                Some(GameCommand::ToggleHud) => hud.visible = !hud.visible,
+                _ => {}
+            }
+        }
+
+        if signals.received().is_some() {
+            return Ok(Outcome::Quit);
+        }
+
+        // One step at a time, so the level ends on the step the goal is met
+        while sim.result().is_none() && !level.goal.is_met(&sim) {
+            let next = sim.tick() + 1;
+            if sim.run(&mut StopAt::new(&mut clock, next)) == 0 {
+                break;
+            }
+        }
+
+        draw_level(level, &sim, carried, &mut renderer, &hud, stream)?;
+        if counting_down {
+            let left = (starts_at - frame_start).as_secs() + 1;
+            render_banner(&format!(" {left} "), sim.board(), &renderer, stream)?;
+            banner_shown = true;
+        }
+        stream.flush()?;
+
+        let wait_time = sim
+            .tick_interval()
+            .saturating_sub(clock.pending())
+            .saturating_sub(frame_start.elapsed());
+        if wait_time > Duration::ZERO {
+            events.wait(wait_time);
+        }
+    }
+
+    let cleared = level.goal.is_met(&sim) || sim.result() == Some(&SimulationResult::Won);
+    let banner = if cleared {
+        " Level cleared! "
+    } else {
+        " Crashed! "
+    };
+
+    draw_level(level, &sim, carried, &mut renderer, &hud, stream)?;
+    render_banner(banner, sim.board(), &renderer, stream)?;
+    stream.flush()?;
+
+    // Keys pressed in a panic as the level ends shouldn't skip the next screen
+    thread::sleep(OUTCOME_SHOWN_FOR);
+    events.by_ref().for_each(drop);
+
+    Ok(if cleared {
+        Outcome::Cleared(sim.score())
+    } else {
+        Outcome::Failed
+    })
+}
+
+/// Queues the board and the status line for `sim`, with a line underneath
+/// showing how close it is to the level's goal and the campaign's score.
+fn draw_level<W: Write>(
+    level: &Level,
+    sim: &SnakeSimulation,
+    carried: usize,
+    renderer: &mut FrameRenderer,
+    hud: &Hud,
+    stream: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    renderer.draw(&sim.view(), stream)?;
+    hud.draw(&sim.view(), sim.tick_interval(), renderer, stream)?;
+
+    // Underneath the status line
+    let row: u16 = (sim.board().height() + 3).try_into()?;
+    let status = format!(
+        "Goal: {} ({})  Campaign score {}",
+        level.goal,
+        level.goal.progress(sim),
+        carried + sim.score()
+    );
+
+    queue!(
+        stream,
+        cursor::MoveTo(0, row),
+        style::ResetColor,
+        terminal::Clear(ClearType::CurrentLine),
+        style::Print(status)
+    )?;
+
+    Ok(())
+}
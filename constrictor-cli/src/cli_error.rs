@@ -0,0 +1,117 @@
+#[cfg(feature = "serve")]
+use std::net::SocketAddr;
+use std::{error::Error, fmt::Display, io, path::PathBuf};
+
+use constrictor_core::config::ConfigError;
+
+use crate::{crash_dump::Crash, io::ScriptError, rendering::RenderError, settings::SettingsError};
+
+/// Describes why a command couldn't carry on, in terms of what the player
+/// can do about it. Each kind of failure exits with its own code; see
+/// [`exit_code::for_error`](crate::exit_code::for_error).
+#[derive(Debug)]
+pub enum CliError {
+    /// The terminal couldn't be put into the mode the game needs, or read
+    /// from.
+    Terminal(io::Error),
+
+    /// Drawing on the terminal failed.
+    Render(RenderError),
+
+    /// Some other input or output failed, such as watching for signals.
+    Io(io::Error),
+
+    /// Nothing could listen for spectators on the address given.
+    #[cfg(feature = "serve")]
+    Serve(SocketAddr, io::Error),
+
+    /// The settings file couldn't be loaded.
+    Settings(SettingsError),
+
+    /// The options given don't describe a game that can be played.
+    Config(ConfigError),
+
+    /// A file couldn't be read.
+    Load(ScriptError),
+
+    /// A file couldn't be written.
+    Save(PathBuf, io::Error),
+
+    /// The game reached a state it never should have.
+    Crash(Crash),
+
+    /// Anything else a command ran into.
+    Other(Box<dyn Error>),
+}
+
+impl Error for CliError {}
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Terminal(e) => write!(
+                f,
+                "could not set up the terminal ({e}); run the game in an interactive \
+                 terminal, or use `--output json` to play without one"
+            ),
+            Self::Render(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+            #[cfg(feature = "serve")]
+            Self::Serve(address, e) => write!(
+                f,
+                "could not listen for spectators on {address} ({e}); pick another \
+                 port with `--serve`, or address with `--serve-address`"
+            ),
+            Self::Settings(e) => write!(f, "{e}\nFix the file, or pick another with `--config`"),
+            Self::Config(e) => write!(f, "the game can't be played: {e}"),
+            Self::Load(e) => write!(f, "{e}"),
+            Self::Save(path, e) => write!(
+                f,
+                "could not save {} ({e}); check that its directory exists and can be \
+                 written to",
+                path.display()
+            ),
+            Self::Crash(e) => write!(f, "{e}"),
+            Self::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<RenderError> for CliError {
+    fn from(value: RenderError) -> Self {
+        Self::Render(value)
+    }
+}
+
+impl From<SettingsError> for CliError {
+    fn from(value: SettingsError) -> Self {
+        Self::Settings(value)
+    }
+}
+
+impl From<ConfigError> for CliError {
+    fn from(value: ConfigError) -> Self {
+        Self::Config(value)
+    }
+}
+
+impl From<ScriptError> for CliError {
+    fn from(value: ScriptError) -> Self {
+        Self::Load(value)
+    }
+}
+
+impl From<Crash> for CliError {
+    fn from(value: Crash) -> Self {
+        Self::Crash(value)
+    }
+}
+
+/// Keeps the kind of a [`CliError`] that was passed along as any error.
+impl From<Box<dyn Error>> for CliError {
+    fn from(value: Box<dyn Error>) -> Self {
+        match value.downcast::<Self>() {
+            Ok(e) => *e,
+            Err(e) => Self::Other(e),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use constrictor_core::{
+    config::GameConfig,
+    models::SnakeSimulation,
+    replay::{InputLog, Replay},
+};
+use serde::Serialize;
+
+/// The simulation was found in a state it should never be able to reach.
+/// Carries the location of the crash report, if one could be written.
+#[derive(Debug)]
+pub struct Crash {
+    reason: String,
+    report: io::Result<PathBuf>,
+}
+
+impl Crash {
+    /// Writes a crash report describing `sim` and how it got there.
+    pub fn report(
+        reason: String,
+        config: &GameConfig,
+        inputs: &InputLog,
+        sim: &SnakeSimulation,
+    ) -> Self {
+        let report = write_report(&reason, config, inputs, sim);
+        Self { reason, report }
+    }
+
+    /// Like [`Crash::report`], for a panic caught while advancing `sim`.
+    pub fn from_panic(
+        payload: Box<dyn Any + Send>,
+        config: &GameConfig,
+        inputs: &InputLog,
+        sim: &SnakeSimulation,
+    ) -> Self {
+        let reason = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("panicked"),
+            },
+        };
+
+        Self::report(reason, config, inputs, sim)
+    }
+}
+
+impl Error for Crash {}
+impl Display for Crash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "internal error: {}", self.reason)?;
+        match &self.report {
+            Ok(path) => write!(
+                f,
+                "A crash report was written to {}. Please attach it when reporting this issue.",
+                path.display()
+            ),
+            Err(e) => write!(f, "A crash report could not be written: {e}"),
+        }
+    }
+}
+
+/// What a crash report holds: the game as a [`Replay`], which plays back up
+/// to the tick of the crash, alongside what went wrong. The report loads as
+/// a replay itself, so it can be given to anything that takes one.
+#[derive(Serialize)]
+struct Report<'a> {
+    version: &'static str,
+    reason: &'a str,
+
+    /// The [`SnakeSimulation::state_hash`] at the crash, to check that
+    /// playing the replay back got to the same state.
+    state_hash: String,
+
+    #[serde(flatten)]
+    replay: Replay,
+}
+
+fn write_report(
+    reason: &str,
+    config: &GameConfig,
+    inputs: &InputLog,
+    sim: &SnakeSimulation,
+) -> io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let report = Report {
+        version: env!("CARGO_PKG_VERSION"),
+        reason,
+        state_hash: format!("{:016x}", sim.state_hash()),
+        replay: Replay {
+            config: config.clone(),
+            inputs: inputs.clone(),
+            ticks: sim.tick(),
+            result: sim.result().copied(),
+        },
+    };
+
+    let path = std::env::temp_dir().join(format!("constrictor-crash-{timestamp}.json"));
+    fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+
+    Ok(path)
+}
@@ -0,0 +1,221 @@
+pub mod protocol;
+
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::Path,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{
+    config::GameConfig,
+    models::{Clock, QuitReason, SnakeSimulation, StopAt},
+    render::FrameBuffer,
+};
+
+use crate::{
+    args::DaemonArgs,
+    headless::TickState,
+    io::{GameCommand, ShutdownSignals},
+};
+use protocol::Request;
+
+/// The longest the daemon goes without checking for new connections and
+/// requests.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The most a client may send between polls. No run of requests comes near
+/// it, so a client that sends more is dropped rather than kept in memory.
+const MAX_PENDING: usize = 8 * 1024;
+
+/// Runs games in the background, driven and observed by clients connected
+/// to a Unix domain socket. See [`Request`] for what clients can send.
+/// Games run at their normal pace, and a finished game waits to be
+/// restarted. Runs until a client asks it to shut down, or the process is
+/// signalled to stop.
+pub fn daemon(args: DaemonArgs) -> Result<(), Box<dyn Error>> {
+    let mut config = args.game.apply(GameConfig::default());
+    let fixed_seed = config.seed;
+    config.seed.get_or_insert_with(rand::random);
+    let mut sim = config.build()?;
+
+    // The game as of the latest tick, and the one before it, which the
+    // changes in each state are taken from
+    let mut shown = FrameBuffer::new(sim.board());
+    let mut before = shown.clone();
+
+    let listener = bind(&args.socket)?;
+    listener.set_nonblocking(true)?;
+    let signals = ShutdownSignals::register()?;
+
+    let mut clients: Vec<Client> = Vec::new();
+    let mut clock = Clock::new();
+    let mut previous = Instant::now();
+    let mut shutdown = false;
+
+    while !shutdown && signals.received().is_none() {
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => clients.push(Client::new(stream)?),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        for client in &mut clients {
+            for line in client.read_lines() {
+                let reply = match line.parse() {
+                    Ok(Request::Command(GameCommand::Restart)) => {
+                        config.seed = Some(fixed_seed.unwrap_or_else(rand::random));
+                        sim = config.build()?;
+                        clock = Clock::new();
+                        before = shown.clone();
+                        "ok".to_string()
+                    }
+                    Ok(Request::Command(command)) => {
+                        issue(&mut sim, command);
+                        "ok".to_string()
+                    }
+                    Ok(Request::State) => {
+                        let state = TickState::since(&mut before.clone(), &sim.view());
+                        serde_json::to_string(&state)?
+                    }
+                    Ok(Request::Watch) => {
+                        client.watching = true;
+                        "ok".to_string()
+                    }
+                    Ok(Request::Unwatch) => {
+                        client.watching = false;
+                        "ok".to_string()
+                    }
+                    Ok(Request::Shutdown) => {
+                        shutdown = true;
+                        "ok".to_string()
+                    }
+                    Err(e) => format!("error {e}"),
+                };
+
+                client.send(&reply);
+            }
+        }
+
+        let now = Instant::now();
+        clock.elapse(now - previous);
+        previous = now;
+
+        // One step at a time, so watchers see every tick
+        while sim.run(&mut StopAt::new(&mut clock, sim.tick() + 1)) > 0 {
+            before = shown.clone();
+            let state = serde_json::to_string(&TickState::since(&mut shown, &sim.view()))?;
+            for client in clients.iter_mut().filter(|client| client.watching) {
+                client.send(&state);
+            }
+        }
+
+        clients.retain(|client| client.connected);
+        sleep(POLL_INTERVAL.min(sim.tick_interval().saturating_sub(clock.pending())));
+    }
+
+    _ = fs::remove_file(&args.socket);
+    Ok(())
+}
+
+/// Listens on `path`, replacing a socket left behind by a daemon that is no
+/// longer running.
+fn bind(path: &Path) -> io::Result<UnixListener> {
+    match UnixListener::bind(path) {
+        Err(e) if e.kind() == io::ErrorKind::AddrInUse && UnixStream::connect(path).is_err() => {
+            fs::remove_file(path)?;
+            UnixListener::bind(path)
+        }
+        bound => bound,
+    }
+}
+
+/// Carries out a command that doesn't need the game to be rebuilt.
+fn issue(sim: &mut SnakeSimulation, command: GameCommand) {
+    match command {
+        GameCommand::ChangeDirection(direction) => sim.change_player_move_direction(direction),
+        GameCommand::Quit => sim.quit(QuitReason::UserQuit),
+        GameCommand::Pause => sim.set_paused(!sim.is_paused()),
+        GameCommand::SpeedUp => sim.set_speed(sim.speed().scaled(80)),
+        GameCommand::SpeedDown => sim.set_speed(sim.speed().scaled(125)),
+
+        // Not part of the protocol
+        GameCommand::Restart
+        | GameCommand::PointAt(_)
+        | GameCommand::ToggleHud
+        | GameCommand::Screenshot
+        | GameCommand::Boost
+        | GameCommand::StopBoost => {}
+    }
+}
+
+/// A connected client, and whatever it has sent that doesn't make up a
+/// whole line yet.
+struct Client {
+    stream: UnixStream,
+    buffer: Vec<u8>,
+    watching: bool,
+    connected: bool,
+}
+
+impl Client {
+    fn new(stream: UnixStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+            watching: false,
+            connected: true,
+        })
+    }
+
+    /// Reads whatever the client has sent, and takes the complete lines out
+    /// of it. A client that sends more than [`MAX_PENDING`] is disconnected.
+    fn read_lines(&mut self) -> Vec<String> {
+        let mut chunk = [0; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    self.connected = false;
+                    break;
+                }
+                Ok(read) if self.buffer.len() + read > MAX_PENDING => {
+                    self.connected = false;
+                    return Vec::new();
+                }
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => {
+                    self.connected = false;
+                    break;
+                }
+            }
+        }
+
+        let Some(end) = self.buffer.iter().rposition(|byte| *byte == b'\n') else {
+            return Vec::new();
+        };
+
+        let lines: Vec<u8> = self.buffer.drain(..=end).collect();
+        String::from_utf8_lossy(&lines)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Sends `line` to the client. A client that can't keep up is dropped,
+    /// rather than holding up the game.
+    fn send(&mut self, line: &str) {
+        if writeln!(self.stream, "{line}").is_err() {
+            self.connected = false;
+        }
+    }
+}
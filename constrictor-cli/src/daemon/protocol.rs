@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use constrictor_core::math::Direction;
+
+use crate::io::GameCommand;
+
+/// A line sent to the daemon by a client. Words are separated by spaces and
+/// matched without regard to case:
+///
+/// ```text
+/// turn left       changes the snake's direction (up, down, left or right)
+/// pause           pauses the game, or resumes it
+/// restart         starts a new game
+/// quit            ends the current game
+/// faster          speeds the game up
+/// slower          slows the game down
+/// state           replies with the state of the game as one line of JSON
+/// watch           replies with the state after every tick from now on
+/// unwatch         stops sending the state after every tick
+/// shutdown        stops the daemon
+/// ```
+///
+/// Commands that don't reply with the state reply `ok`. Lines that aren't
+/// understood reply `error`, followed by what was wrong.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Request {
+    /// Issue a command to the game, as a key press would.
+    Command(GameCommand),
+
+    State,
+    Watch,
+    Unwatch,
+    Shutdown,
+}
+
+impl FromStr for Request {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let lowercase: Vec<_> = words.iter().map(|word| word.to_ascii_lowercase()).collect();
+        let lowercase: Vec<_> = lowercase.iter().map(String::as_str).collect();
+
+        let request = match lowercase.as_slice() {
+            ["turn", direction] => {
+                let direction = match *direction {
+                    "up" => Direction::Up,
+                    "down" => Direction::Down,
+                    "left" => Direction::Left,
+                    "right" => Direction::Right,
+                    _ => return Err(format!("unknown direction `{}`", words[1])),
+                };
+
+                Self::Command(GameCommand::ChangeDirection(direction))
+            }
+            ["pause"] => Self::Command(GameCommand::Pause),
+            ["restart"] => Self::Command(GameCommand::Restart),
+            ["quit"] => Self::Command(GameCommand::Quit),
+            ["faster"] => Self::Command(GameCommand::SpeedUp),
+            ["slower"] => Self::Command(GameCommand::SpeedDown),
+            ["state"] => Self::State,
+            ["watch"] => Self::Watch,
+            ["unwatch"] => Self::Unwatch,
+            ["shutdown"] => Self::Shutdown,
+            _ => return Err(format!("unknown request `{}`", line.trim())),
+        };
+
+        Ok(request)
+    }
+}
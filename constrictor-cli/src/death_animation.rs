@@ -0,0 +1,77 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{math::Vector2, models::SimulationView};
+use crossterm::event::{Event, KeyEventKind};
+
+use crate::{
+    io::EventStream,
+    rendering::{FrameRenderer, RenderError, RenderMode},
+};
+
+/// How long the flash takes to run from the head to the tail.
+const FLASH: Duration = Duration::from_millis(600);
+
+/// How long each colour of the fade is shown for.
+const FADE_STEP: Duration = Duration::from_millis(200);
+
+/// How often the animation is drawn.
+const FRAME: Duration = Duration::from_millis(30);
+
+/// Plays out the death of the player's snake in `view` before the game
+/// screen goes: it flashes in the theme's colour for bad news, red in the
+/// classic theme, from the head to the tail, then fades to the colour of
+/// fog. Any key cuts it short. Nothing is played without colour, or in
+/// braille, which can't colour one cell apart from its neighbours.
+pub fn play_death<W: Write>(
+    view: &SimulationView,
+    renderer: &mut FrameRenderer,
+    events: &mut EventStream,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    let theme = renderer.theme();
+    if !theme.has_color() || renderer.mode() == RenderMode::Braille {
+        return Ok(());
+    }
+
+    let fade = [theme.tints.bad, theme.obstacle.color, theme.fog.color];
+    let body: Vec<Vector2> = view.player().body().copied().collect();
+    let started = Instant::now();
+
+    loop {
+        let elapsed = started.elapsed();
+        let colors: HashMap<_, _> = if elapsed < FLASH {
+            // Rounded up, so the head goes red straight away
+            let reached = (elapsed.as_millis() * body.len() as u128).div_ceil(FLASH.as_millis());
+            body.iter()
+                .take(reached as usize)
+                .map(|point| (*point, fade[0]))
+                .collect()
+        } else {
+            let step = (elapsed - FLASH).as_millis() / FADE_STEP.as_millis();
+            let Some(color) = fade.get(step as usize) else {
+                break;
+            };
+
+            body.iter().map(|point| (*point, *color)).collect()
+        };
+
+        renderer.set_highlights(colors);
+        renderer.draw(view, stream)?;
+        stream.flush()?;
+
+        events.wait(FRAME);
+        let skipped = events
+            .by_ref()
+            .any(|event| matches!(event, Ok(Event::Key(key)) if key.kind != KeyEventKind::Release));
+        if skipped {
+            break;
+        }
+    }
+
+    renderer.set_highlights(HashMap::new());
+    Ok(())
+}
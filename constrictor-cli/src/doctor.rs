@@ -0,0 +1,86 @@
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    args::DoctorArgs,
+    settings::{self, SETTINGS_VERSION, Settings, SettingsError},
+};
+
+/// Checks the settings file at `path`, or at the default location, and
+/// reports what it finds. With [`DoctorArgs::fix`], an outdated file is
+/// migrated and an unusable one is moved aside, so the defaults are used
+/// instead. Either way the original is kept with a `.bak` extension.
+/// Returns whether everything is usable once done.
+pub fn doctor(path: Option<&Path>, args: DoctorArgs) -> Result<bool, Box<dyn Error>> {
+    let Some(path) = path.map(Path::to_owned).or_else(settings::default_path) else {
+        println!("No settings location could be found; defaults are used");
+        return Ok(true);
+    };
+
+    println!("Settings file: {}", path.display());
+
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("  not found; defaults are used");
+            return Ok(true);
+        }
+        Err(e) => {
+            println!("  unreadable: {e}");
+            return Ok(false);
+        }
+    };
+
+    match Settings::migrate(&path, &text) {
+        Ok(migrated) if migrated.from_version == SETTINGS_VERSION => {
+            println!("  ok (version {SETTINGS_VERSION})");
+            Ok(true)
+        }
+        Ok(migrated) => {
+            println!(
+                "  written for version {}, migrated to {SETTINGS_VERSION} when loaded",
+                migrated.from_version
+            );
+
+            if args.fix {
+                let backup = back_up(&path)?;
+                fs::write(&path, migrated.table.to_string())?;
+                println!(
+                    "  rewrote it for version {SETTINGS_VERSION}; the original is in {backup}"
+                );
+            }
+
+            Ok(true)
+        }
+        Err(e @ SettingsError::TooNew(..)) => {
+            // Nothing to repair; a newer release can still use it
+            println!("  {e}");
+            Ok(false)
+        }
+        Err(e) => {
+            println!("  {e}");
+
+            if !args.fix {
+                return Ok(false);
+            }
+
+            let backup = back_up(&path)?;
+            fs::remove_file(&path)?;
+            println!("  moved it to {backup}; defaults are used");
+            Ok(true)
+        }
+    }
+}
+
+/// Copies `path` alongside itself with a `.bak` extension, returning where
+/// the copy went.
+fn back_up(path: &Path) -> io::Result<String> {
+    let mut backup = PathBuf::from(path);
+    backup.as_mut_os_string().push(".bak");
+
+    fs::copy(path, &backup)?;
+    Ok(backup.display().to_string())
+}
@@ -0,0 +1,284 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write, stdout},
+    path::PathBuf,
+    time::Duration,
+};
+
+use constrictor_core::{
+    config::{Map, Tile},
+    math::Vector2,
+};
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue, style,
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    args::EditArgs, io::EventStream, rendering::render_map, scope_guard::ScopeGuard, theme::Theme,
+};
+
+/// The keys the editor understands, shown underneath the map.
+const HELP: &str = "arrows move  space wall  s spawn  f food  p portal  ctrl+s save  q quit";
+
+/// Opens the map in `args.file` for editing in the terminal, or starts a new
+/// one of the size asked for if there is no such file. Nothing is written
+/// until the map is saved.
+pub fn edit(args: EditArgs, theme: Theme) -> Result<(), Box<dyn Error>> {
+    let map = match fs::read_to_string(&args.file) {
+        Ok(text) => text
+            .parse()
+            .map_err(|e| format!("could not load {}: {e}", args.file.display()))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Map::new(args.width.into(), args.height.into())
+        }
+        Err(e) => return Err(format!("could not read {}: {e}", args.file.display()).into()),
+    };
+
+    let mut editor = Editor::new(map, args.file, theme);
+
+    let _restore_terminal = ScopeGuard::new(|| {
+        _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        _ = terminal::disable_raw_mode();
+    });
+
+    let mut stdout = stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut events = EventStream::new()?;
+    editor.draw(&mut stdout)?;
+
+    loop {
+        // Nothing moves on its own, so there is nothing to redraw until a
+        // key is pressed or the terminal is resized
+        if !events.wait(Duration::from_secs(1)) {
+            continue;
+        }
+
+        for event in events.by_ref() {
+            if let Event::Key(key) = event?
+                && key.kind != KeyEventKind::Release
+                && editor.handle(key)
+            {
+                return Ok(());
+            }
+        }
+
+        editor.draw(&mut stdout)?;
+    }
+}
+
+/// A [`Map`] being edited, and where the editor is up to with it.
+struct Editor {
+    map: Map,
+    path: PathBuf,
+
+    /// The tile that the next change is made to.
+    cursor: Vector2,
+
+    /// Whether there are changes that haven't been saved.
+    dirty: bool,
+
+    /// Whether the player is being asked to save before quitting.
+    confirming_quit: bool,
+
+    /// The outcome of the last thing done, such as saving, shown until the
+    /// next key is pressed.
+    message: Option<String>,
+
+    theme: Theme,
+}
+
+impl Editor {
+    fn new(map: Map, path: PathBuf, theme: Theme) -> Self {
+        Self {
+            map,
+            path,
+            cursor: Vector2 { x: 0, y: 0 },
+            dirty: false,
+            confirming_quit: false,
+            message: None,
+            theme,
+        }
+    }
+
+    /// Reacts to one key press. Returns `true` once the editor should close.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        self.message = None;
+
+        if self.confirming_quit {
+            match key.code {
+                KeyCode::Char('y') => return self.save(),
+                KeyCode::Char('n') => return true,
+                KeyCode::Char('c') | KeyCode::Esc => self.confirming_quit = false,
+                _ => {}
+            }
+
+            return false;
+        }
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('s') => _ = self.save(),
+                KeyCode::Char('c') => return self.quit(),
+                _ => {}
+            }
+
+            return false;
+        }
+
+        match key.code {
+            KeyCode::Up => self.move_cursor(0, -1),
+            KeyCode::Down => self.move_cursor(0, 1),
+            KeyCode::Left => self.move_cursor(-1, 0),
+            KeyCode::Right => self.move_cursor(1, 0),
+            KeyCode::Char(' ' | '#') => self.toggle(Tile::Wall),
+            KeyCode::Char('s' | 'S') => self.toggle(Tile::Spawn),
+            KeyCode::Char('f' | 'F') => self.toggle(Tile::Food),
+            KeyCode::Char('p' | 'P') => self.toggle_portal(),
+            KeyCode::Char('q') | KeyCode::Esc => return self.quit(),
+            _ => {}
+        }
+
+        false
+    }
+
+    /// Moves the cursor by the given offset, stopping at the edges.
+    fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let max_x = self.map.width().saturating_sub(1) as i32;
+        let max_y = self.map.height().saturating_sub(1) as i32;
+
+        self.cursor = Vector2 {
+            x: (self.cursor.x + dx).clamp(0, max_x),
+            y: (self.cursor.y + dy).clamp(0, max_y),
+        };
+    }
+
+    /// Puts `tile` under the cursor, or floor if it is there already.
+    fn toggle(&mut self, tile: Tile) {
+        let tile = if self.map.tile(&self.cursor) == tile {
+            Tile::Floor
+        } else {
+            tile
+        };
+
+        self.place(tile);
+    }
+
+    /// Removes the portal under the cursor, both ends of it. Otherwise puts
+    /// down the second end of a portal that only has one, or the first end
+    /// of a new one.
+    fn toggle_portal(&mut self) {
+        if let Tile::Portal(_) = self.map.tile(&self.cursor) {
+            self.place(Tile::Floor);
+            return;
+        }
+
+        let ends = |letter: char| {
+            self.map
+                .cells()
+                .filter(|(_, tile)| *tile == Tile::Portal(letter))
+                .count()
+        };
+
+        let letters = 'a'..='z';
+        let letter = letters
+            .clone()
+            .find(|letter| ends(*letter) == 1)
+            .or_else(|| letters.clone().find(|letter| ends(*letter) == 0));
+
+        match letter {
+            Some(letter) => self.place(Tile::Portal(letter)),
+            None => self.message = Some("Every portal letter is taken".to_string()),
+        }
+    }
+
+    /// Puts `tile` under the cursor. A portal end it replaces takes its other
+    /// end with it, so that no portal is left half there by accident.
+    fn place(&mut self, tile: Tile) {
+        if let Tile::Portal(letter) = self.map.tile(&self.cursor) {
+            let other_end = self
+                .map
+                .cells()
+                .find(|(cell, found)| *found == Tile::Portal(letter) && *cell != self.cursor);
+
+            if let Some((cell, _)) = other_end {
+                self.map.set_tile(&cell, Tile::Floor);
+            }
+        }
+
+        self.map.set_tile(&self.cursor, tile);
+        self.dirty = true;
+    }
+
+    /// Writes the map to its file, as long as it can be read back. Returns
+    /// whether it was saved, with a message saying how it went either way.
+    fn save(&mut self) -> bool {
+        self.confirming_quit = false;
+
+        let text = self.map.to_string();
+        let outcome = text
+            .parse::<Map>()
+            .map_err(|e| format!("Not saved: {e}"))
+            .and_then(|_| fs::write(&self.path, text).map_err(|e| format!("Not saved: {e}")));
+
+        self.message = Some(match outcome {
+            Ok(()) => {
+                self.dirty = false;
+                format!("Saved to {}", self.path.display())
+            }
+            Err(message) => message,
+        });
+
+        !self.dirty
+    }
+
+    /// Closes the editor straight away if everything is saved, or asks
+    /// whether to save first. Returns whether to close.
+    fn quit(&mut self) -> bool {
+        self.confirming_quit = self.dirty;
+        !self.dirty
+    }
+
+    /// Draws the map, with the cursor's position and any message underneath.
+    fn draw<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>> {
+        queue!(
+            stream,
+            style::ResetColor,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+
+        let board = render_map(&self.map, self.cursor, &self.theme, stream)?;
+        let row: u16 = (board.height() + 2).try_into()?;
+
+        let name = self.path.display();
+        let changed = if self.dirty { " (modified)" } else { "" };
+        let status = match &self.message {
+            Some(message) => message.clone(),
+            None => format!("{name}{changed}  {},{}", self.cursor.x, self.cursor.y),
+        };
+
+        let prompt = if self.confirming_quit {
+            "Save changes before quitting? (y)es, (n)o or (c)ancel".to_string()
+        } else {
+            HELP.to_string()
+        };
+
+        queue!(
+            stream,
+            style::ResetColor,
+            cursor::MoveTo(0, row),
+            style::Print(status),
+            cursor::MoveTo(0, row + 1),
+            style::Print(prompt)
+        )?;
+
+        stream.flush()?;
+        Ok(())
+    }
+}
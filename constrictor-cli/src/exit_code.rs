@@ -0,0 +1,76 @@
+use std::process::ExitCode;
+
+use constrictor_core::models::{QuitReason, SimulationResult};
+
+use crate::cli_error::CliError;
+
+// Process exit codes, so that scripts wrapping the CLI can tell outcomes
+// apart. 2 is left to clap, which uses it for usage errors.
+
+/// The game was won, or played to an end that isn't a loss, such as time
+/// running out.
+pub const GAME_OVER: u8 = 0;
+
+/// The game could not continue because of an error.
+pub const ERROR: u8 = 1;
+
+/// The player quit.
+pub const USER_QUIT: u8 = 3;
+
+/// The terminal went away.
+pub const CONNECTION_LOST: u8 = 4;
+
+/// The process was asked to shut down.
+pub const HOST_SHUTDOWN: u8 = 5;
+
+/// The terminal couldn't be set up for the game.
+pub const TERMINAL: u8 = 6;
+
+/// The game couldn't be drawn on the terminal.
+pub const RENDER: u8 = 7;
+
+/// Some other input or output failed, such as listening for spectators.
+pub const IO: u8 = 8;
+
+/// The settings or options given don't describe a playable game.
+pub const CONFIG: u8 = 9;
+
+/// A file couldn't be read or written.
+pub const SAVE_LOAD: u8 = 10;
+
+/// The snake died, ran out of moves or was beaten by another snake.
+pub const LOST: u8 = 11;
+
+/// The game hit a bug, and left a crash report behind.
+pub const INTERNAL_ERROR: u8 = 12;
+
+/// Picks the [`ExitCode`] describing how a game ended.
+pub fn for_result(result: Option<&SimulationResult>) -> ExitCode {
+    ExitCode::from(match result {
+        Some(SimulationResult::ManuallyTerminated(reason)) => match reason {
+            QuitReason::UserQuit => USER_QUIT,
+            QuitReason::ConnectionLost => CONNECTION_LOST,
+            QuitReason::HostShutdown => HOST_SHUTDOWN,
+            QuitReason::Error => ERROR,
+        },
+        Some(SimulationResult::Died(_) | SimulationResult::Lost | SimulationResult::OutOfMoves) => {
+            LOST
+        }
+        Some(_) | None => GAME_OVER,
+    })
+}
+
+/// Picks the [`ExitCode`] describing why a command failed.
+pub fn for_error(error: &CliError) -> ExitCode {
+    ExitCode::from(match error {
+        CliError::Terminal(_) => TERMINAL,
+        CliError::Render(_) => RENDER,
+        CliError::Io(_) => IO,
+        #[cfg(feature = "serve")]
+        CliError::Serve(..) => IO,
+        CliError::Settings(_) | CliError::Config(_) => CONFIG,
+        CliError::Load(_) | CliError::Save(..) => SAVE_LOAD,
+        CliError::Crash(_) => INTERNAL_ERROR,
+        CliError::Other(_) => ERROR,
+    })
+}
@@ -0,0 +1,172 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use constrictor_core::{
+    config::GameConfig,
+    models::{DeathReason, QuitReason, SimulationResult, SnakeSimulation},
+};
+use serde::Serialize;
+
+use crate::cli_error::CliError;
+
+/// The columns of a CSV game log, in the order [`GameSummary`] writes them.
+const CSV_HEADER: &str = "seed,width,height,mode,result,score,length,duration_ms,ticks";
+
+/// A file that a summary of every game played is appended to, for
+/// `--log-games`. Files named `.json` or `.jsonl` get one JSON object per
+/// line, and anything else gets CSV with a header row.
+pub struct GameLog {
+    path: PathBuf,
+    json: bool,
+}
+
+/// How one game went, as a row of a [`GameLog`].
+#[derive(Debug, Serialize)]
+pub struct GameSummary {
+    seed: Option<u64>,
+    width: i32,
+    height: i32,
+    mode: String,
+    result: &'static str,
+    score: usize,
+    length: usize,
+
+    /// Time spent playing, not counting pauses.
+    duration_ms: u64,
+    ticks: u64,
+}
+
+impl GameLog {
+    pub fn new(path: &Path) -> Self {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+
+        Self {
+            path: path.to_path_buf(),
+            json: matches!(extension, Some("json" | "jsonl")),
+        }
+    }
+
+    /// Adds `summary` to the end of the log, creating the file if there
+    /// isn't one yet.
+    pub fn append(&self, summary: &GameSummary) -> Result<(), CliError> {
+        let describe = |e| CliError::Save(self.path.clone(), e);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(describe)?;
+
+        let mut line = if self.json {
+            summary.to_json()
+        } else {
+            summary.to_csv()
+        };
+        line.push('\n');
+
+        // A new CSV file starts with its column names
+        if !self.json && file.metadata().map_err(describe)?.len() == 0 {
+            line.insert_str(0, &format!("{CSV_HEADER}\n"));
+        }
+
+        file.write_all(line.as_bytes()).map_err(describe)?;
+        Ok(())
+    }
+}
+
+impl GameSummary {
+    /// Sums up the game `sim`, set up by `config` and played for `played`.
+    /// It needn't be over; a game abandoned by restarting is logged as
+    /// unfinished.
+    pub fn new(config: &GameConfig, sim: &SnakeSimulation, played: Duration) -> Self {
+        Self {
+            seed: config.seed,
+            width: sim.board().width(),
+            height: sim.board().height(),
+            mode: mode(config),
+            result: sim.result().map_or("unfinished", result_name),
+            score: sim.score(),
+            length: sim.snake().len(),
+            duration_ms: played.as_millis().try_into().unwrap_or(u64::MAX),
+            ticks: sim.tick(),
+        }
+    }
+
+    /// Writes the summary as one line of JSON.
+    pub fn to_json(&self) -> String {
+        // Every field is a plain value, so this can't fail
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn to_csv(&self) -> String {
+        let seed = self.seed.map(|seed| seed.to_string()).unwrap_or_default();
+
+        // Puzzle names come from config files, so may need quoting
+        let mode = if self.mode.contains([',', '"', '\n']) {
+            format!("\"{}\"", self.mode.replace('"', "\"\""))
+        } else {
+            self.mode.clone()
+        };
+
+        format!(
+            "{seed},{},{},{mode},{},{},{},{},{}",
+            self.width,
+            self.height,
+            self.result,
+            self.score,
+            self.length,
+            self.duration_ms,
+            self.ticks
+        )
+    }
+}
+
+/// Names the kind of game `config` sets up, such as `classic`, or
+/// `time-attack+fog` when several modes are combined.
+pub fn mode(config: &GameConfig) -> String {
+    let rules = &config.rules;
+    let scenario = config.scenario.as_ref().map(|scenario| {
+        // Puzzles are told apart by name, since each is its own challenge
+        format!("puzzle:{}", scenario.name)
+    });
+
+    let modes: Vec<String> = [
+        scenario,
+        rules.time_limit.map(|_| "time-attack".to_string()),
+        (!config.opponents.is_empty()).then(|| "versus".to_string()),
+        rules.shrink.map(|_| "shrink".to_string()),
+        rules.fog.map(|_| "fog".to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if modes.is_empty() {
+        "classic".to_string()
+    } else {
+        modes.join("+")
+    }
+}
+
+const fn result_name(result: &SimulationResult) -> &'static str {
+    match result {
+        SimulationResult::Died(DeathReason::HitWall) => "hit_wall",
+        SimulationResult::Died(DeathReason::HitSelf) => "hit_self",
+        SimulationResult::Died(DeathReason::HitSnake(_)) => "hit_snake",
+        SimulationResult::Died(DeathReason::HeadOn(_)) => "head_on",
+        SimulationResult::Died(DeathReason::Hazard) => "hazard",
+        SimulationResult::Won => "won",
+        SimulationResult::Lost => "lost",
+        SimulationResult::LeftBoard(_) => "left_board",
+        SimulationResult::OutOfMoves => "out_of_moves",
+        SimulationResult::TimeExpired => "time_expired",
+        SimulationResult::ManuallyTerminated(QuitReason::UserQuit) => "quit",
+        SimulationResult::ManuallyTerminated(QuitReason::ConnectionLost) => "connection_lost",
+        SimulationResult::ManuallyTerminated(QuitReason::HostShutdown) => "host_shutdown",
+        SimulationResult::ManuallyTerminated(QuitReason::Error) => "error",
+    }
+}
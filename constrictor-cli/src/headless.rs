@@ -0,0 +1,214 @@
+use std::{
+    error::Error,
+    fs,
+    io::{Write, stdout},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{
+    math::{Direction, Vector2},
+    models::{Clock, QuitReason, SimulationResult, SimulationView, StopAt},
+    render::{Cell, FrameBuffer},
+    replay::{InputLog, Replay},
+};
+use serde::Serialize;
+
+use crate::{
+    args::PlayArgs,
+    game_log::{GameLog, GameSummary},
+    io::{GameCommand, InputScript, ShutdownSignals},
+    pointer::steer_towards,
+    settings::Settings,
+};
+
+/// One line of `--output json`: the whole state of the game after a tick,
+/// along with the cells that tick changed. Like the game on screen, it only
+/// shows what the player can see; under fog, hidden food, obstacles and
+/// other snakes are left out.
+#[derive(Serialize)]
+pub struct TickState {
+    tick: u64,
+    score: usize,
+    width: i32,
+    height: i32,
+    snake: Vec<Vector2>,
+    facing: Direction,
+    food: Vec<Vector2>,
+    obstacles: Vec<Vector2>,
+    changes: Vec<(Vector2, Cell)>,
+    paused: bool,
+    result: Option<SimulationResult>,
+
+    /// The body of every other snake, head first. Left out when playing
+    /// alone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    opponents: Vec<Vec<Vector2>>,
+}
+
+impl TickState {
+    /// Describes the game in `view`, with the changes from `previous`, the
+    /// [`SimulationView::frame`] as of the last state described, then brings
+    /// `previous` up to date for the next.
+    pub fn since(previous: &mut FrameBuffer, view: &SimulationView) -> Self {
+        let player = view.player();
+        let frame = view.frame();
+        let changes = frame.changes_since(previous).collect();
+        *previous = frame;
+
+        Self {
+            tick: view.tick(),
+            score: view.score(),
+            width: view.board().width(),
+            height: view.board().height(),
+            snake: player.body().copied().collect(),
+            facing: player.facing(),
+            food: visible(view, view.food()),
+            obstacles: visible(view, view.obstacles()),
+            changes,
+            paused: view.is_paused(),
+            result: view.result().copied(),
+            opponents: view
+                .snakes()
+                .skip(1)
+                .map(|snake| visible(view, snake.body()))
+                .collect(),
+        }
+    }
+}
+
+/// Keeps the `points` the player can see in `view`.
+fn visible<'a>(view: &SimulationView, points: impl Iterator<Item = &'a Vector2>) -> Vec<Vector2> {
+    points
+        .filter(|point| view.is_visible(point))
+        .copied()
+        .collect()
+}
+
+/// Plays a game without touching the terminal, writing the state after every
+/// tick to standard output as one line of JSON. Commands come only from the
+/// input script, if there is one; the game runs at its normal pace until it
+/// ends.
+pub fn play_json(
+    args: PlayArgs,
+    settings: Settings,
+) -> Result<Option<SimulationResult>, Box<dyn Error>> {
+    let mut config = args.game.apply(settings.game);
+    let fixed_seed = config.seed;
+    let seed = *config.seed.get_or_insert_with(rand::random);
+    let mut opponent = args.add_opponent(&mut config, seed);
+    let mut sim = config.build()?;
+
+    let mut script = args
+        .input_script
+        .as_deref()
+        .map(InputScript::load)
+        .transpose()?;
+    let mut inputs = InputLog::new();
+    let log = args.log_games.as_deref().map(GameLog::new);
+
+    let signals = ShutdownSignals::register()?;
+    let mut stdout = stdout().lock();
+    let mut frame = FrameBuffer::new(sim.board());
+    let mut emit = |view: SimulationView| -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut stdout, &TickState::since(&mut frame, &view))?;
+        writeln!(stdout)?;
+        Ok(stdout.flush()?)
+    };
+
+    emit(sim.view())?;
+
+    let mut clock = Clock::new();
+    let mut previous = Instant::now();
+    let mut played = Duration::ZERO;
+    while sim.result().is_none() {
+        while let Some(command) = script
+            .as_mut()
+            .and_then(|script| script.take_due(sim.tick()))
+        {
+            match command {
+                GameCommand::ChangeDirection(direction) => {
+                    inputs.record(sim.tick(), direction);
+                    sim.change_player_move_direction(direction);
+                }
+                GameCommand::PointAt(target) => {
+                    if let Some(direction) = steer_towards(&sim.view().player(), target) {
+                        inputs.record(sim.tick(), direction);
+                        sim.change_player_move_direction(direction);
+                    }
+                }
+                GameCommand::Quit => sim.quit(QuitReason::UserQuit),
+                GameCommand::Pause => sim.set_paused(!sim.is_paused()),
+                GameCommand::Restart => {
+                    if let Some(log) = &log {
+                        log.append(&GameSummary::new(&config, &sim, played))?;
+                    }
+
+                    played = Duration::ZERO;
+                    config.seed = Some(fixed_seed.unwrap_or_else(rand::random));
+                    sim = config.build()?;
+                    inputs = InputLog::new();
+                    clock = Clock::new();
+                    emit(sim.view())?;
+                }
+                GameCommand::SpeedUp => sim.set_speed(sim.speed().scaled(80)),
+                GameCommand::SpeedDown => sim.set_speed(sim.speed().scaled(125)),
+
+                // These only matter to someone watching the game
+                GameCommand::ToggleHud
+                | GameCommand::Screenshot
+                | GameCommand::Boost
+                | GameCommand::StopBoost => {}
+            }
+        }
+
+        if let Some(reason) = signals.received() {
+            sim.quit(reason);
+        }
+
+        let now = Instant::now();
+        clock.elapse(now - previous);
+        if !sim.is_paused() {
+            played += now - previous;
+        }
+        previous = now;
+
+        if let Some(bot) = opponent.as_mut() {
+            bot.steer(&mut sim, 1);
+        }
+
+        // One step at a time, so every tick gets its own line and the script
+        // is checked before each of them. Quitting ends the game without a
+        // step, but still needs a line.
+        let stepped = sim.run(&mut StopAt::new(&mut clock, sim.tick() + 1)) > 0;
+        if stepped || sim.result().is_some() {
+            emit(sim.view())?;
+        }
+
+        if !stepped && sim.result().is_none() {
+            sleep(sim.tick_interval().saturating_sub(clock.pending()));
+        }
+    }
+
+    let summary = GameSummary::new(&config, &sim, played);
+    if args.quiet {
+        eprintln!("{}", summary.to_json());
+    }
+
+    if let Some(log) = &log {
+        log.append(&summary)?;
+    }
+
+    if let Some(path) = args.save_replay {
+        let replay = Replay {
+            config,
+            inputs,
+            ticks: sim.tick(),
+            result: sim.result().copied(),
+        };
+
+        fs::write(path, serde_json::to_string(&replay)?)?;
+    }
+
+    Ok(sim.result().copied())
+}
@@ -0,0 +1,119 @@
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use constrictor_core::{config::GameConfig, models::SnakeSimulation};
+use serde::{Deserialize, Serialize};
+
+use crate::{game_log, settings};
+
+/// How many of the best scores are kept.
+const KEPT: usize = 10;
+
+/// The best scores played on this machine, best first, shown on the title
+/// menu. Kept in `high_scores.toml`, next to the settings file.
+#[derive(Default)]
+pub struct HighScores {
+    path: Option<PathBuf>,
+    table: Table,
+}
+
+/// The file's contents: one `[[score]]` table for each [`HighScore`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Table {
+    #[serde(default, rename = "score")]
+    scores: Vec<HighScore>,
+}
+
+/// One game good enough to be among the [`HighScores`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: usize,
+    pub width: i32,
+    pub height: i32,
+
+    /// The kind of game played, named as in a game log.
+    pub mode: String,
+}
+
+impl HighScores {
+    /// Loads the scores saved next to the settings file at `settings_path`,
+    /// or at the default location if no path is given. Nothing saved yet is
+    /// not an error.
+    pub fn load(settings_path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let path = settings_path
+            .map(Path::to_owned)
+            .or_else(settings::default_path)
+            .map(|path| path.with_file_name("high_scores.toml"));
+
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let table = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("invalid high scores in {}:\n{e}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Table::default(),
+            Err(e) => return Err(format!("could not read {}: {e}", path.display()).into()),
+        };
+
+        Ok(Self {
+            path: Some(path),
+            table,
+        })
+    }
+
+    /// The scores, best first.
+    pub fn scores(&self) -> &[HighScore] {
+        &self.table.scores
+    }
+
+    /// Adds the game `sim`, set up by `config`, if it scored well enough.
+    /// Among equal scores the earlier game stays ahead. Returns whether it
+    /// made the table.
+    pub fn record(&mut self, config: &GameConfig, sim: &SnakeSimulation) -> bool {
+        let score = sim.score();
+        if score == 0 {
+            return false;
+        }
+
+        let scores = &mut self.table.scores;
+        let rank = scores.partition_point(|kept| kept.score >= score);
+        if rank >= KEPT {
+            return false;
+        }
+
+        scores.insert(
+            rank,
+            HighScore {
+                score,
+                width: sim.board().width(),
+                height: sim.board().height(),
+                mode: game_log::mode(config),
+            },
+        );
+        scores.truncate(KEPT);
+        true
+    }
+
+    /// Writes the scores back out. There is nowhere to write them when the
+    /// platform has no configuration directory, or for the
+    /// [default](Default) empty table, so they are only kept until the
+    /// program ends.
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let failed =
+            |e: io::Error| format!("could not save high scores to {}: {e}", path.display());
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(failed)?;
+        }
+
+        fs::write(path, toml::to_string(&self.table)?).map_err(failed)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::{self, Write, stdout};
+
+use crossterm::{cursor, execute, queue, style::Print, terminal};
+
+/// Counts cursor positions from the top of the scrolling region, rather than
+/// the top of the screen.
+const ORIGIN_MODE_ON: &str = "\x1b[?6h";
+
+/// Undoes [`ORIGIN_MODE_ON`], and scrolls the whole screen again.
+const WHOLE_SCREEN: &str = "\x1b[?6l\x1b[r";
+
+/// Keeps the game in the normal screen buffer, in the rows under the prompt,
+/// rather than taking over the alternate screen, for `--inline`. While it is
+/// alive, the terminal addresses those rows as if they were the whole
+/// screen, so the game draws from the top left as usual without disturbing
+/// what is above. The last frame is left behind when it is dropped, with the
+/// cursor underneath.
+pub struct InlineScreen {
+    /// The row of the screen the game starts on.
+    top: u16,
+    rows: u16,
+}
+
+impl InlineScreen {
+    /// Makes room for `rows` rows from the cursor down, scrolling earlier
+    /// output up if there isn't enough below, and confines drawing to them.
+    /// The terminal must be in raw mode.
+    pub fn reserve<W: Write>(rows: u16, stream: &mut W) -> io::Result<Self> {
+        let (_, height) = terminal::size()?;
+        let rows = rows.clamp(1, height.max(1));
+
+        queue!(stream, Print("\r\n".repeat((rows - 1).into())))?;
+        stream.flush()?;
+
+        let (_, bottom) = cursor::position()?;
+        let top = (bottom + 1).saturating_sub(rows);
+
+        // Only those rows scroll, counted from 1
+        let margins = format!("\x1b[{};{}r", top + 1, top + rows);
+        execute!(stream, Print(margins), Print(ORIGIN_MODE_ON))?;
+
+        Ok(Self { top, rows })
+    }
+
+    /// Gets the row of the screen the game starts on, to place what the
+    /// mouse points at.
+    pub const fn top(&self) -> u16 {
+        self.top
+    }
+}
+
+impl Drop for InlineScreen {
+    fn drop(&mut self) {
+        // Addressing the whole screen again sends the cursor to the top, so
+        // it has to be put back under the game by hand
+        _ = execute!(
+            stdout(),
+            Print(WHOLE_SCREEN),
+            cursor::MoveTo(0, self.top + self.rows - 1),
+            Print("\r\n")
+        );
+    }
+}
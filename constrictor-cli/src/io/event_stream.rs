@@ -1,27 +1,47 @@
-use crossterm::event::{Event, read};
-use std::{io, sync::mpsc, thread};
+use crossterm::event::{Event, poll, read};
+use std::{
+    io,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
-/// Provides non-blocking access to a stream of [`Event`]s by creating a
-/// background thread that passes [`Event`]s through an [`mpsc::channel`].
+/// Set while an [`EventStream`] is reading from the terminal. Two readers
+/// would each get an arbitrary share of the events, so only one may exist at
+/// a time.
+static READING: AtomicBool = AtomicBool::new(false);
+
+/// Provides non-blocking access to a stream of [`Event`]s by polling the
+/// terminal. Iterating yields the events that are ready now; [`wait`] sleeps
+/// until there is one, so a loop can react to input without waiting out the
+/// rest of its frame.
+///
+/// After the first error reading from the terminal, the stream ends, since
+/// every further read would fail the same way.
+///
+/// [`wait`]: EventStream::wait
 pub struct EventStream {
-    recv: mpsc::Receiver<Result<Event, io::Error>>,
+    failed: bool,
 }
 
 impl EventStream {
-    // FIXME: this should really be singleton pattern
-    pub fn new() -> Self {
-        let (send, recv) = mpsc::channel();
-
-        thread::spawn(move || {
-            loop {
-                if send.send(read()).is_err() {
-                    // other side of channel has hung up! Oh noes!
-                    return;
-                }
-            }
-        });
-
-        Self { recv }
+    /// Starts reading events from the terminal. Fails if another stream is
+    /// still reading; drop it first.
+    pub fn new() -> io::Result<Self> {
+        if READING.swap(true, Ordering::AcqRel) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "another EventStream is already reading terminal events",
+            ));
+        }
+
+        Ok(Self { failed: false })
+    }
+
+    /// Blocks until an event is ready or `timeout` has passed, and returns
+    /// whether there is an event to take. Errors are left for the iterator to
+    /// report, so this returns `true` for them too.
+    pub fn wait(&mut self, timeout: Duration) -> bool {
+        !self.failed && poll(timeout).unwrap_or(true)
     }
 }
 
@@ -29,6 +49,23 @@ impl Iterator for EventStream {
     type Item = Result<Event, io::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.recv.try_iter().next()
+        if self.failed {
+            return None;
+        }
+
+        let event = match poll(Duration::ZERO) {
+            Ok(false) => return None,
+            Ok(true) => read(),
+            Err(e) => Err(e),
+        };
+
+        self.failed = event.is_err();
+        Some(event)
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        READING.store(false, Ordering::Release);
     }
 }
@@ -1,5 +1,9 @@
-use constrictor_core::math::Direction;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use constrictor_core::math::{Direction, Vector2};
 
 /// The types of commands a user (or automated system) can input into the game.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -7,40 +11,100 @@ pub enum GameCommand {
     /// Command to change the direction of the snake.
     ChangeDirection(Direction),
 
+    /// Command to turn the snake towards a point on the board, such as one
+    /// clicked with the mouse.
+    PointAt(Vector2),
+
     /// Command to quit the game.
     Quit,
-}
 
-impl TryFrom<Event> for GameCommand {
-    type Error = Event;
+    /// Command to pause the game, or resume it if it is paused.
+    Pause,
+
+    /// Command to abandon the current game and start a new one.
+    Restart,
+
+    /// Command to make the game run faster.
+    SpeedUp,
+
+    /// Command to make the game run slower.
+    SpeedDown,
+
+    /// Command to show or hide the status line.
+    ToggleHud,
+
+    /// Command to save a picture of the board as it is now.
+    Screenshot,
+
+    /// Command to run the game faster for as long as the key is held. On
+    /// terminals that can't report key releases, each press toggles it
+    /// instead.
+    Boost,
 
-    /// Parses the [`Event`] into either [`Ok<GameCommand>`] if the event maps
-    /// to a [`GameCommand`], otherwise to [`Err<Event>`] to allow for
-    /// further parsing of the original event.
-    fn try_from(value: Event) -> Result<Self, Self::Error> {
-        match value {
-            Event::Key(e) => e.try_into().map_err(|_| Event::Key(e)),
-            e => Err(e),
+    /// Command to stop boosting, issued when the boost key is released.
+    /// Never bound to a key directly.
+    StopBoost,
+}
+
+/// Writes the command as it appears in an input script: the direction's name
+/// for a turn, `PointAt x,y` for a point, or the variant's name otherwise.
+impl Display for GameCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChangeDirection(direction) => write!(f, "{direction:?}"),
+            Self::PointAt(Vector2 { x, y }) => write!(f, "PointAt {x},{y}"),
+            Self::Quit => f.write_str("Quit"),
+            Self::Pause => f.write_str("Pause"),
+            Self::Restart => f.write_str("Restart"),
+            Self::SpeedUp => f.write_str("SpeedUp"),
+            Self::SpeedDown => f.write_str("SpeedDown"),
+            Self::ToggleHud => f.write_str("ToggleHud"),
+            Self::Screenshot => f.write_str("Screenshot"),
+            Self::Boost => f.write_str("Boost"),
+            Self::StopBoost => f.write_str("StopBoost"),
         }
     }
 }
 
-impl TryFrom<KeyEvent> for GameCommand {
-    type Error = KeyEvent;
-
-    /// Parses the [`KeyEvent`] into either [`Ok<GameCommand>`] if event maps to
-    /// a [`GameCommand`], otherwise to [`Err<KeyEvent>`] to allow for
-    /// further parsing of the original key.
-    fn try_from(value: KeyEvent) -> Result<Self, Self::Error> {
-        match value.code {
-            KeyCode::Char('w') | KeyCode::Up => Ok(GameCommand::ChangeDirection(Direction::Up)),
-            KeyCode::Char('a') | KeyCode::Left => Ok(GameCommand::ChangeDirection(Direction::Left)),
-            KeyCode::Char('s') | KeyCode::Down => Ok(GameCommand::ChangeDirection(Direction::Down)),
-            KeyCode::Char('d') | KeyCode::Right => {
-                Ok(GameCommand::ChangeDirection(Direction::Right))
-            }
-            KeyCode::Char('q') => Ok(GameCommand::Quit),
-            _ => Err(value),
+/// Reads a command written by [`Display`], ignoring case.
+impl FromStr for GameCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+
+        if name.eq_ignore_ascii_case("PointAt") {
+            let point = argument
+                .split_once(',')
+                .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+
+            return match point {
+                Some((x, y)) => Ok(Self::PointAt(Vector2 { x, y })),
+                None => Err(format!("expected `PointAt x,y`, found `{s}`")),
+            };
         }
+
+        if !argument.is_empty() {
+            return Err(format!("`{name}` takes no argument"));
+        }
+
+        let command = match name.to_ascii_lowercase().as_str() {
+            "up" => Self::ChangeDirection(Direction::Up),
+            "down" => Self::ChangeDirection(Direction::Down),
+            "left" => Self::ChangeDirection(Direction::Left),
+            "right" => Self::ChangeDirection(Direction::Right),
+            "quit" => Self::Quit,
+            "pause" => Self::Pause,
+            "restart" => Self::Restart,
+            "speedup" => Self::SpeedUp,
+            "speeddown" => Self::SpeedDown,
+            "togglehud" => Self::ToggleHud,
+            "screenshot" => Self::Screenshot,
+            "boost" => Self::Boost,
+            "stopboost" => Self::StopBoost,
+            _ => return Err(format!("unknown command `{name}`")),
+        };
+
+        Ok(command)
     }
 }
@@ -0,0 +1,54 @@
+use crossterm::event::Event;
+
+use super::{GameCommand, KeyMap};
+
+/// Identifies which player a command belongs to. Seats are numbered from 0 in
+/// the order the players' snakes are added to the game.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Seat(pub usize);
+
+/// A [`GameCommand`] tagged with the [`Seat`] it came from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SeatedCommand {
+    pub seat: Seat,
+    pub command: GameCommand,
+}
+
+/// Routes raw input [`Event`]s to the [`Seat`] they control. Keyboard
+/// [`KeyMap`]s are the only source for now, but gamepads and network peers
+/// slot in here as further sources.
+#[derive(Debug, Default)]
+pub struct InputRouter {
+    keyboard: Vec<(KeyMap, Seat)>,
+}
+
+impl InputRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hands the keys in `keys` to `seat`. Where maps overlap, the map bound
+    /// first wins.
+    pub fn bind_keys(mut self, keys: KeyMap, seat: Seat) -> Self {
+        self.keyboard.push((keys, seat));
+        self
+    }
+
+    /// Works out which [`Seat`] `event` is meant for. Returns [`Err<Event>`]
+    /// with the original event if it does not map to any seat's controls.
+    pub fn route(&self, event: Event) -> Result<SeatedCommand, Event> {
+        let Event::Key(key) = event else {
+            return Err(event);
+        };
+
+        self.keyboard
+            .iter()
+            .find_map(|(keys, seat)| {
+                Some(SeatedCommand {
+                    seat: *seat,
+                    command: keys.command(key)?,
+                })
+            })
+            .ok_or(event)
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use super::GameCommand;
+
+/// A list of [`GameCommand`]s to issue on given ticks, read from a file with
+/// one command per line:
+///
+/// ```text
+/// # Blank lines and lines starting with `#` are skipped
+/// tick 0: Up
+/// tick 12: Left
+/// tick 12: SpeedUp
+/// tick 40: PointAt 3,7
+/// ```
+///
+/// Commands are issued in the order they are written, each before its tick
+/// is played. A command whose tick has already passed is issued straight
+/// away. After a `Restart`, ticks count from zero again, so the commands for
+/// the new game follow it with their own tick numbers.
+///
+/// Scripts can also be recorded with [`InputScript::record`], and written
+/// out in the same format with [`Display`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputScript {
+    commands: VecDeque<(u64, GameCommand)>,
+}
+
+/// Describes why an input script could not be loaded.
+#[derive(Debug)]
+pub enum ScriptError {
+    Read(PathBuf, io::Error),
+
+    /// A line that isn't a tick and a command, with its line number counted
+    /// from 1.
+    Parse(PathBuf, usize, String),
+}
+
+impl Error for ScriptError {}
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            Self::Parse(path, line, e) => write!(f, "{}:{line}: {e}", path.display()),
+        }
+    }
+}
+
+impl InputScript {
+    /// Reads the script in the file at `path`, or from standard input if the
+    /// path is `-`.
+    pub fn load(path: &Path) -> Result<Self, ScriptError> {
+        let read_error = |e| ScriptError::Read(path.to_owned(), e);
+
+        let text = if path == Path::new("-") {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(read_error)?;
+            text
+        } else {
+            fs::read_to_string(path).map_err(read_error)?
+        };
+
+        Self::parse(&text)
+            .map_err(|(line, message)| ScriptError::Parse(path.to_owned(), line, message))
+    }
+
+    /// Parses the script in `text`. On failure, gives the number of the
+    /// offending line and what is wrong with it.
+    pub fn parse(text: &str) -> Result<Self, (usize, String)> {
+        let mut commands = VecDeque::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = line
+                .strip_prefix("tick")
+                .and_then(|rest| rest.split_once(':'))
+                .ok_or_else(|| format!("expected `tick <number>: <command>`, found `{line}`"))
+                .and_then(|(tick, command)| {
+                    let tick = tick
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("`{}` is not a tick number", tick.trim()))?;
+                    Ok((tick, command.parse()?))
+                });
+
+            commands.push_back(entry.map_err(|message| (index + 1, message))?);
+        }
+
+        Ok(Self { commands })
+    }
+
+    /// Adds `command` to the end of the script, to be issued on `tick`.
+    pub fn record(&mut self, tick: u64, command: GameCommand) {
+        self.commands.push_back((tick, command));
+    }
+
+    /// The tick the next command is to be issued on, if any are left.
+    pub fn next_tick(&self) -> Option<u64> {
+        self.commands.front().map(|(tick, _)| *tick)
+    }
+
+    /// Takes the next command if it is due by `tick`.
+    pub fn take_due(&mut self, tick: u64) -> Option<GameCommand> {
+        self.commands
+            .pop_front_if(|(due, _)| *due <= tick)
+            .map(|(_, command)| command)
+    }
+}
+
+impl Display for InputScript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (tick, command) in &self.commands {
+            writeln!(f, "tick {tick}: {command}")?;
+        }
+
+        Ok(())
+    }
+}
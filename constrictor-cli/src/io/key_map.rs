@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use constrictor_core::math::Direction;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use serde::Deserialize;
+
+use super::GameCommand;
+
+/// A built-in set of keys that together make up one player's controls, so
+/// several players can share a keyboard. Every preset also has the same
+/// game controls: `q` quits, `p` pauses, `r` restarts, `+` and `-` change the
+/// speed, tab toggles the status line, `c` saves a screenshot, and holding
+/// space boosts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyZone {
+    /// Both the arrow keys and WASD.
+    #[default]
+    All,
+
+    /// The arrow keys only.
+    Arrows,
+
+    /// W, A, S and D only.
+    Wasd,
+
+    /// H, J, K and L, as in vim.
+    Vim,
+}
+
+/// What a key does when bound in the settings file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Quit,
+    Pause,
+    Restart,
+    SpeedUp,
+    SpeedDown,
+    ToggleHud,
+    Screenshot,
+    Boost,
+
+    /// Nothing; removes the key from the preset it was in.
+    None,
+}
+
+impl KeyAction {
+    const fn command(self) -> Option<GameCommand> {
+        match self {
+            Self::Up => Some(GameCommand::ChangeDirection(Direction::Up)),
+            Self::Down => Some(GameCommand::ChangeDirection(Direction::Down)),
+            Self::Left => Some(GameCommand::ChangeDirection(Direction::Left)),
+            Self::Right => Some(GameCommand::ChangeDirection(Direction::Right)),
+            Self::Quit => Some(GameCommand::Quit),
+            Self::Pause => Some(GameCommand::Pause),
+            Self::Restart => Some(GameCommand::Restart),
+            Self::SpeedUp => Some(GameCommand::SpeedUp),
+            Self::SpeedDown => Some(GameCommand::SpeedDown),
+            Self::ToggleHud => Some(GameCommand::ToggleHud),
+            Self::Screenshot => Some(GameCommand::Screenshot),
+            Self::Boost => Some(GameCommand::Boost),
+            Self::None => None,
+        }
+    }
+}
+
+/// A key named in the settings file: a single character such as `"k"`, or
+/// one of `up`, `down`, `left`, `right`, `esc`, `enter`, `space`, `tab` and
+/// `backspace`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeyName(KeyCode);
+
+impl TryFrom<String> for KeyName {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut chars = value.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Self(KeyCode::Char(c)));
+        }
+
+        let code = match value.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            _ => return Err(format!("unknown key `{value}`")),
+        };
+
+        Ok(Self(code))
+    }
+}
+
+/// Maps keys to the [`GameCommand`]s they issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: HashMap<KeyCode, GameCommand>,
+}
+
+impl KeyMap {
+    /// Creates the [`KeyMap`] for a built-in [`KeyZone`].
+    pub fn preset(zone: KeyZone) -> Self {
+        use Direction::*;
+
+        let arrows = [
+            (KeyCode::Up, Up),
+            (KeyCode::Down, Down),
+            (KeyCode::Left, Left),
+            (KeyCode::Right, Right),
+        ];
+        let wasd = [
+            (KeyCode::Char('w'), Up),
+            (KeyCode::Char('s'), Down),
+            (KeyCode::Char('a'), Left),
+            (KeyCode::Char('d'), Right),
+        ];
+        let vim = [
+            (KeyCode::Char('k'), Up),
+            (KeyCode::Char('j'), Down),
+            (KeyCode::Char('h'), Left),
+            (KeyCode::Char('l'), Right),
+        ];
+
+        let directions: Vec<_> = match zone {
+            KeyZone::All => arrows.into_iter().chain(wasd).collect(),
+            KeyZone::Arrows => arrows.into(),
+            KeyZone::Wasd => wasd.into(),
+            KeyZone::Vim => vim.into(),
+        };
+
+        let controls = [
+            (KeyCode::Char('q'), GameCommand::Quit),
+            (KeyCode::Char('p'), GameCommand::Pause),
+            (KeyCode::Char('r'), GameCommand::Restart),
+            (KeyCode::Char('+'), GameCommand::SpeedUp),
+            (KeyCode::Char('='), GameCommand::SpeedUp),
+            (KeyCode::Char('-'), GameCommand::SpeedDown),
+            (KeyCode::Tab, GameCommand::ToggleHud),
+            (KeyCode::Char('c'), GameCommand::Screenshot),
+            (KeyCode::Char(' '), GameCommand::Boost),
+        ];
+
+        let bindings = directions
+            .into_iter()
+            .map(|(key, direction)| (key, GameCommand::ChangeDirection(direction)))
+            .chain(controls)
+            .collect();
+
+        Self { bindings }
+    }
+
+    /// Applies bindings from the settings file on top of this map.
+    pub fn with_overrides<'a>(
+        mut self,
+        overrides: impl IntoIterator<Item = (&'a KeyName, &'a KeyAction)>,
+    ) -> Self {
+        for (KeyName(key), action) in overrides {
+            match action.command() {
+                Some(command) => self.bindings.insert(*key, command),
+                None => self.bindings.remove(key),
+            };
+        }
+
+        self
+    }
+
+    /// Lists the names of every key bound to `command`, in alphabetical
+    /// order.
+    pub fn keys_for(&self, command: GameCommand) -> Vec<String> {
+        let mut keys: Vec<_> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| **bound == command)
+            .map(|(key, _)| key.to_string())
+            .collect();
+
+        keys.sort();
+        keys
+    }
+
+    /// Looks up the [`GameCommand`] that `key` issues, if any. Only
+    /// terminals with keyboard enhancements report repeats and releases;
+    /// elsewhere, every event counts as a press.
+    pub fn command(&self, key: KeyEvent) -> Option<GameCommand> {
+        let command = self.bindings.get(&key.code).copied()?;
+
+        match (key.kind, command) {
+            (KeyEventKind::Press, _) => Some(command),
+
+            // Holding a key down doesn't turn or boost any more than
+            // pressing it did
+            (KeyEventKind::Repeat, GameCommand::ChangeDirection(_) | GameCommand::Boost) => None,
+            (KeyEventKind::Repeat, _) => Some(command),
+
+            (KeyEventKind::Release, GameCommand::Boost) => Some(GameCommand::StopBoost),
+            (KeyEventKind::Release, _) => None,
+        }
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::preset(KeyZone::default())
+    }
+}
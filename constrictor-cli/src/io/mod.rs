@@ -1,5 +1,19 @@
 pub mod event_stream;
 pub mod game_command;
+pub mod input_router;
+pub mod input_script;
+pub mod key_map;
+pub mod repeat_filter;
+pub mod shutdown_signals;
+#[cfg(feature = "twitch")]
+pub mod twitch_chat;
 
 pub use event_stream::*;
 pub use game_command::*;
+pub use input_router::*;
+pub use input_script::*;
+pub use key_map::*;
+pub use repeat_filter::*;
+pub use shutdown_signals::*;
+#[cfg(feature = "twitch")]
+pub use twitch_chat::*;
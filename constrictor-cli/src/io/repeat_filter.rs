@@ -0,0 +1,44 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::math::Direction;
+
+use super::{GameCommand, Seat, SeatedCommand};
+
+/// Drops direction changes that repeat the previous one from the same
+/// [`Seat`] within a short window. Some terminals deliver a held or bouncy key
+/// as a burst of identical presses, which would otherwise be read as several
+/// deliberate turns. Other commands always pass through.
+#[derive(Debug)]
+pub struct RepeatFilter {
+    window: Duration,
+    last: HashMap<Seat, (Direction, Instant)>,
+}
+
+impl RepeatFilter {
+    /// Creates a filter that ignores repeats within `window` of each other. A
+    /// zero window lets everything through.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last: HashMap::new(),
+        }
+    }
+
+    /// Decides whether `command`, which arrived at `now`, should be acted on.
+    pub fn allows(&mut self, command: &SeatedCommand, now: Instant) -> bool {
+        let GameCommand::ChangeDirection(direction) = command.command else {
+            return true;
+        };
+
+        // Measured from the last press rather than the last accepted one, so a
+        // burst is dropped for as long as it keeps coming.
+        let previous = self.last.insert(command.seat, (direction, now));
+        !matches!(
+            previous,
+            Some((last, at)) if last == direction && now.duration_since(at) < self.window
+        )
+    }
+}
@@ -0,0 +1,42 @@
+use constrictor_core::models::QuitReason;
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// Watches for the process signals that should end a game early, so the main
+/// loop can quit with an appropriate [`QuitReason`] and still restore the
+/// terminal on the way out.
+pub struct ShutdownSignals {
+    terminate: Arc<AtomicBool>,
+    hangup: Arc<AtomicBool>,
+}
+
+impl ShutdownSignals {
+    pub fn register() -> io::Result<Self> {
+        let terminate = Arc::new(AtomicBool::new(false));
+        let hangup = Arc::new(AtomicBool::new(false));
+
+        signal_hook::flag::register(signal_hook::consts::SIGTERM, Arc::clone(&terminate))?;
+
+        #[cfg(unix)]
+        signal_hook::flag::register(signal_hook::consts::SIGHUP, Arc::clone(&hangup))?;
+
+        Ok(Self { terminate, hangup })
+    }
+
+    /// Returns the [`QuitReason`] matching a signal received since
+    /// registration, if any.
+    pub fn received(&self) -> Option<QuitReason> {
+        if self.hangup.load(Ordering::Relaxed) {
+            Some(QuitReason::ConnectionLost)
+        } else if self.terminate.load(Ordering::Relaxed) {
+            Some(QuitReason::HostShutdown)
+        } else {
+            None
+        }
+    }
+}
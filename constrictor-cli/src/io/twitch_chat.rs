@@ -0,0 +1,148 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread,
+};
+
+use constrictor_core::{math::Direction, models::TurnBatch};
+
+use super::GameCommand;
+
+const SERVER: (&str, u16) = ("irc.chat.twitch.tv", 6667);
+
+/// Lets a Twitch channel's chat steer the snake together. Viewers vote with
+/// `!up`, `!down`, `!left` or `!right`, and once every window of ticks the
+/// direction with the most votes is issued as a [`GameCommand`], as if typed.
+/// Each viewer has one vote per window, and voting again changes it. A tie
+/// goes to whichever of the tied directions was voted for first.
+///
+/// Chat is read on a thread of its own, logged in anonymously, so nothing
+/// but the channel name is needed. Losing the connection leaves the game to
+/// the keyboard, and the error is kept for [`TwitchChat::error`].
+pub struct TwitchChat {
+    messages: Receiver<ChatMessage>,
+
+    /// How many ticks votes are gathered for before one is issued.
+    window: u64,
+
+    /// The tick the current window opened on.
+    opened: u64,
+
+    /// The votes cast in the current window, by viewer name.
+    ballots: TurnBatch<String>,
+
+    error: Option<io::Error>,
+}
+
+/// What the chat thread has to tell the game.
+enum ChatMessage {
+    Vote(String, Direction),
+    Lost(io::Error),
+}
+
+impl TwitchChat {
+    /// Starts reading the chat of `channel`, issuing the winning vote every
+    /// `window` ticks. A window of 0 is treated as 1.
+    pub fn join(channel: &str, window: u64) -> Self {
+        let channel = channel.trim_start_matches('#').to_ascii_lowercase();
+        let (sender, messages) = mpsc::channel();
+
+        thread::spawn(move || {
+            if let Err(e) = read_chat(&channel, &sender) {
+                _ = sender.send(ChatMessage::Lost(e));
+            }
+        });
+
+        Self {
+            messages,
+            window: window.max(1),
+            opened: 0,
+            ballots: TurnBatch::new(),
+            error: None,
+        }
+    }
+
+    /// Takes the winning vote if the window has closed by `tick`, opening the
+    /// next one. Gives nothing if nobody voted. Ticks going backwards, as on
+    /// restarting, open a new window straight away.
+    pub fn take_due(&mut self, tick: u64) -> Option<GameCommand> {
+        self.receive();
+
+        if tick < self.opened {
+            self.opened = tick;
+        }
+        if tick < self.opened + self.window {
+            return None;
+        }
+
+        self.opened = tick;
+        self.ballots.take().map(GameCommand::ChangeDirection)
+    }
+
+    /// Gets why the chat stopped being read, if it has.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Counts the votes that have arrived since last time.
+    fn receive(&mut self) {
+        loop {
+            match self.messages.try_recv() {
+                Ok(ChatMessage::Vote(voter, direction)) => self.ballots.vote(voter, direction),
+                Ok(ChatMessage::Lost(e)) => self.error = Some(e),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+    }
+}
+
+/// Reads the chat of `channel` until the connection drops or the game stops
+/// listening, sending on each vote.
+fn read_chat(channel: &str, sender: &Sender<ChatMessage>) -> io::Result<()> {
+    let mut stream = TcpStream::connect(SERVER)?;
+
+    // Twitch lets anyone read chat as a `justinfan` with any password
+    let nick = format!("justinfan{}", rand::random::<u32>() % 100_000);
+    write!(
+        stream,
+        "PASS SCHMOOPIIE\r\nNICK {nick}\r\nJOIN #{channel}\r\n"
+    )?;
+
+    let reader = BufReader::new(stream.try_clone()?);
+    for line in reader.lines() {
+        let line = line?;
+
+        if let Some(server) = line.strip_prefix("PING ") {
+            write!(stream, "PONG {server}\r\n")?;
+            continue;
+        }
+
+        let Some((voter, direction)) = parse_vote(&line) else {
+            continue;
+        };
+
+        if sender.send(ChatMessage::Vote(voter, direction)).is_err() {
+            return Ok(());
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "chat server closed the connection",
+    ))
+}
+
+/// Picks the voter and their vote out of a chat message such as
+/// `:viewer!viewer@viewer.tmi.twitch.tv PRIVMSG #channel :!left`.
+fn parse_vote(line: &str) -> Option<(String, Direction)> {
+    let (prefix, rest) = line.strip_prefix(':')?.split_once(' ')?;
+    let (_, text) = rest.strip_prefix("PRIVMSG ")?.split_once(" :")?;
+    let voter = prefix.split_once('!').map_or(prefix, |(nick, _)| nick);
+
+    let word = text.trim().strip_prefix('!')?;
+    match word.parse().ok()? {
+        GameCommand::ChangeDirection(direction) => Some((voter.to_string(), direction)),
+        _ => None,
+    }
+}
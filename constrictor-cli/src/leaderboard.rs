@@ -0,0 +1,359 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    str::FromStr,
+    time::Duration,
+};
+
+use constrictor_core::{
+    config::GameConfig,
+    models::{SimulationResult, SnakeSimulation},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game_log;
+
+/// How long the leaderboard gets to answer when no timeout is configured.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many of the best scores are shown after a game.
+const TOP_COUNT: usize = 10;
+
+/// The most of a response that is read, so a misbehaving server can't fill
+/// memory.
+const MAX_RESPONSE_BYTES: u64 = 64 * 1024;
+
+/// The `[leaderboard]` section of the settings file. Nothing is sent
+/// anywhere unless an endpoint is set.
+///
+/// ```toml
+/// [leaderboard]
+/// endpoint = "http://scores.example.com/constrictor"
+/// name = "ada"
+/// timeout_ms = 2000
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaderboardSettings {
+    /// Where scores are sent. Only plain `http://` is spoken.
+    pub endpoint: Option<Endpoint>,
+
+    /// The name scores are submitted under, or the user's login name if
+    /// there isn't one.
+    pub name: Option<String>,
+
+    /// How long the leaderboard gets to answer each request, in
+    /// milliseconds.
+    pub timeout_ms: Option<u64>,
+}
+
+/// The address of a leaderboard server, from an `http://host[:port]/path`
+/// URL.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Endpoint {
+    /// The host and port as written, for the `Host` header.
+    authority: String,
+    host: String,
+    port: u16,
+
+    /// The path scores live under, without a trailing slash.
+    path: String,
+}
+
+/// Describes why an endpoint URL could not be used.
+#[derive(Debug)]
+pub enum EndpointError {
+    /// The URL isn't `http://`, the only scheme spoken.
+    Scheme,
+    Port,
+    Host,
+}
+
+impl Error for EndpointError {}
+impl Display for EndpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scheme => f.write_str("leaderboard endpoints must start with http://"),
+            Self::Port => f.write_str("leaderboard endpoint has an invalid port"),
+            Self::Host => f.write_str("leaderboard endpoint has no host"),
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = EndpointError;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        let rest = url.strip_prefix("http://").ok_or(EndpointError::Scheme)?;
+        let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        // An IPv6 address has colons of its own, so only a colon after its
+        // closing bracket starts a port
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+                (host, port.parse().map_err(|_| EndpointError::Port)?)
+            }
+            _ => (authority, 80),
+        };
+
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(EndpointError::Host);
+        }
+
+        Ok(Self {
+            authority: authority.to_string(),
+            host: host.to_string(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for Endpoint {
+    type Error = EndpointError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Describes why the leaderboard couldn't be reached or understood.
+#[derive(Debug)]
+pub enum LeaderboardError {
+    Io(io::Error),
+
+    /// The server answered with something other than success.
+    Status(u16),
+
+    /// The answer wasn't HTTP, or its body wasn't the JSON expected.
+    Response,
+}
+
+impl Error for LeaderboardError {}
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::Status(status) => write!(f, "the server answered with status {status}"),
+            Self::Response => f.write_str("the server's answer could not be understood"),
+        }
+    }
+}
+
+impl From<io::Error> for LeaderboardError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// A finished game, as sent to the leaderboard.
+#[derive(Debug, Serialize)]
+struct Submission<'a> {
+    name: &'a str,
+    seed: Option<u64>,
+    mode: String,
+    score: usize,
+    length: usize,
+    ticks: u64,
+
+    /// The simulation's final [`SnakeSimulation::state_hash`], which playing
+    /// the seed and inputs back must arrive at, so a server can check the
+    /// game against its replay.
+    replay_hash: String,
+}
+
+/// One of the best scores, as the leaderboard lists them.
+#[derive(Debug, Deserialize)]
+struct Entry {
+    name: String,
+    score: usize,
+}
+
+/// A client for an online leaderboard, speaking JSON over HTTP:
+///
+/// - `POST <endpoint>/scores` submits a finished game.
+/// - `GET <endpoint>/scores?mode=<mode>&limit=10` lists the best scores for
+///   a kind of game, best first, as an array of `{"name", "score"}`.
+pub struct Leaderboard {
+    endpoint: Endpoint,
+    name: String,
+    timeout: Duration,
+}
+
+impl Leaderboard {
+    /// Creates a client as `settings` describe, or `None` if they don't
+    /// name an endpoint.
+    pub fn new(settings: &LeaderboardSettings) -> Option<Self> {
+        let endpoint = settings.endpoint.clone()?;
+        let name = settings.name.clone().unwrap_or_else(|| {
+            std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "anonymous".to_string())
+        });
+
+        Some(Self {
+            endpoint,
+            name,
+            timeout: settings
+                .timeout_ms
+                .map_or(DEFAULT_TIMEOUT, Duration::from_millis),
+        })
+    }
+
+    /// Submits the game `sim`, set up by `config`, and prints the best scores
+    /// for its kind of game. Being offline only earns a note; it never fails
+    /// the game. Games that were quit aren't submitted. When `quiet`, the
+    /// game is still submitted, but nothing is printed.
+    pub fn report(&self, config: &GameConfig, sim: &SnakeSimulation, quiet: bool) {
+        if matches!(
+            sim.result(),
+            None | Some(SimulationResult::ManuallyTerminated(_))
+        ) {
+            return;
+        }
+
+        let mode = game_log::mode(config);
+        let submission = Submission {
+            name: &self.name,
+            seed: config.seed,
+            mode: mode.clone(),
+            score: sim.score(),
+            length: sim.snake().len(),
+            ticks: sim.tick(),
+            replay_hash: format!("{:016x}", sim.state_hash()),
+        };
+
+        if quiet {
+            _ = self.submit(&submission);
+            return;
+        }
+
+        let top = self.submit(&submission).and_then(|()| self.top(&mode));
+        match top {
+            Ok(entries) if entries.is_empty() => {}
+            Ok(entries) => {
+                println!("Leaderboard ({mode}):");
+                for (rank, entry) in (1..).zip(&entries) {
+                    println!("{rank:>3}. {:<20} {:>6}", entry.name, entry.score);
+                }
+            }
+            Err(e) => eprintln!("Leaderboard unavailable: {e}"),
+        }
+    }
+
+    fn submit(&self, submission: &Submission) -> Result<(), LeaderboardError> {
+        let body = serde_json::to_string(submission).map_err(|_| LeaderboardError::Response)?;
+        self.request(
+            "POST",
+            &format!("{}/scores", self.endpoint.path),
+            Some(&body),
+        )?;
+        Ok(())
+    }
+
+    fn top(&self, mode: &str) -> Result<Vec<Entry>, LeaderboardError> {
+        let path = format!(
+            "{}/scores?mode={}&limit={TOP_COUNT}",
+            self.endpoint.path,
+            percent_encode(mode)
+        );
+
+        let body = self.request("GET", &path, None)?;
+        let mut entries: Vec<Entry> =
+            serde_json::from_str(&body).map_err(|_| LeaderboardError::Response)?;
+        entries.truncate(TOP_COUNT);
+
+        Ok(entries)
+    }
+
+    /// Makes one HTTP/1.0 request and gives back the body of a successful
+    /// answer. HTTP/1.0 keeps the answer simple: never chunked, and the end
+    /// of it is the end of the connection.
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> Result<String, LeaderboardError> {
+        let Endpoint {
+            authority,
+            host,
+            port,
+            ..
+        } = &self.endpoint;
+
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "could not resolve host");
+        let mut stream = None;
+        for address in (host.as_str(), *port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(connected) => {
+                    stream = Some(connected);
+                    break;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        let mut stream = stream.ok_or(last_error)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+        let mut request = format!(
+            "{method} {path} HTTP/1.0\r\nHost: {authority}\r\nUser-Agent: constrictor/{}\r\n\
+             Accept: application/json\r\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        if let Some(body) = body {
+            request += &format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n",
+                body.len()
+            );
+        }
+        request += "\r\n";
+        request += body.unwrap_or_default();
+        stream.write_all(request.as_bytes())?;
+
+        let mut response = String::new();
+        stream
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_string(&mut response)?;
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or(LeaderboardError::Response)?;
+        let status: u16 = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or(LeaderboardError::Response)?;
+
+        if !(200..300).contains(&status) {
+            return Err(LeaderboardError::Status(status));
+        }
+
+        Ok(body.to_string())
+    }
+}
+
+/// Escapes `text` for a URL's query string.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded += &format!("%{byte:02X}"),
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+/// How much the renderer sends to the terminal each tick, from most to least.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Quality {
+    /// Every frame, in colour.
+    Full,
+
+    /// Every frame, without colour changes.
+    Monochrome,
+
+    /// One frame in every `every` ticks, without colour changes. The
+    /// simulation still steps on every tick; only drawing is skipped.
+    Reduced { every: u32 },
+}
+
+impl Quality {
+    /// The most ticks that may pass between drawn frames. Any fewer frames
+    /// than this and the game is no longer playable anyway.
+    const MAX_FRAME_INTERVAL: u32 = 8;
+
+    const fn lower(self) -> Self {
+        match self {
+            Self::Full => Self::Monochrome,
+            Self::Monochrome => Self::Reduced { every: 2 },
+            Self::Reduced { every } if every * 2 > Self::MAX_FRAME_INTERVAL => self,
+            Self::Reduced { every } => Self::Reduced { every: every * 2 },
+        }
+    }
+
+    const fn higher(self) -> Self {
+        match self {
+            Self::Full | Self::Monochrome => Self::Full,
+            Self::Reduced { every: ..=2 } => Self::Monochrome,
+            Self::Reduced { every } => Self::Reduced { every: every / 2 },
+        }
+    }
+
+    /// Whether frames are drawn in colour.
+    pub const fn color(self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    /// How many ticks pass between drawn frames.
+    pub const fn frame_interval(self) -> u32 {
+        match self {
+            Self::Full | Self::Monochrome => 1,
+            Self::Reduced { every } => every,
+        }
+    }
+
+    /// Whether a frame should be drawn at `tick`, given the last one was
+    /// drawn at `last_drawn`. Going by the ticks since the last frame, rather
+    /// than the tick itself, means frames aren't lost when several ticks are
+    /// stepped at once.
+    pub const fn draws(self, tick: u64, last_drawn: u64) -> bool {
+        tick.saturating_sub(last_drawn) >= self.frame_interval() as u64
+    }
+}
+
+/// Watches how long frames take to reach the terminal and adjusts the
+/// [`Quality`] to match. Over a slow link, such as SSH on a poor connection,
+/// writes block once the link's buffers fill up; that backpressure is what
+/// is measured.
+///
+/// Changes need several frames in a row to agree, so a single slow frame
+/// does not cause flickering between levels.
+#[derive(Debug)]
+pub struct LinkMonitor {
+    quality: Quality,
+
+    /// Recent write times as a fraction of the tick interval, smoothed.
+    load: f64,
+
+    /// Consecutive frames that argued for the same change.
+    streak: i32,
+}
+
+impl LinkMonitor {
+    /// Above this load, quality is lowered.
+    const CONGESTED: f64 = 0.5;
+
+    /// Below this load, quality is raised.
+    const CLEAR: f64 = 0.1;
+
+    /// How many frames in a row must agree before the quality is lowered.
+    const DEGRADE_AFTER: i32 = 5;
+
+    /// How many frames in a row must agree before the quality is raised.
+    const RECOVER_AFTER: i32 = 50;
+
+    /// Weight of the newest frame in the smoothed load.
+    const SMOOTHING: f64 = 0.2;
+
+    pub fn new() -> Self {
+        Self {
+            quality: Quality::Full,
+            load: 0.0,
+            streak: 0,
+        }
+    }
+
+    pub const fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Records that writing a frame took `write_time` when the game allows
+    /// `tick_interval` per tick, and returns the quality to use from now on.
+    /// A frame is allowed as many ticks as pass between frames at the current
+    /// quality.
+    pub fn observe(&mut self, write_time: Duration, tick_interval: Duration) -> Quality {
+        let budget = tick_interval * self.quality.frame_interval();
+        let sample = write_time.as_secs_f64() / budget.as_secs_f64().max(f64::EPSILON);
+        self.load += Self::SMOOTHING * (sample - self.load);
+
+        self.streak = match self.load {
+            load if load > Self::CONGESTED => self.streak.max(0) + 1,
+            load if load < Self::CLEAR => self.streak.min(0) - 1,
+            _ => 0,
+        };
+
+        let previous = self.quality;
+        if self.streak >= Self::DEGRADE_AFTER {
+            self.quality = self.quality.lower();
+            self.streak = 0;
+        } else if self.streak <= -Self::RECOVER_AFTER {
+            self.quality = self.quality.higher();
+            self.streak = 0;
+        }
+
+        // The load is relative to the time allowed per frame, which changes
+        // with the frame interval
+        self.load *=
+            f64::from(previous.frame_interval()) / f64::from(self.quality.frame_interval());
+
+        self.quality
+    }
+}
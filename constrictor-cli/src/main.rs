@@ -1,93 +1,736 @@
+#[cfg(feature = "gif")]
+mod animation;
+mod args;
+mod asciicast;
+mod bench;
+mod campaign;
+mod cli_error;
+mod crash_dump;
+#[cfg(unix)]
+mod daemon;
+mod death_animation;
+mod doctor;
+mod edit;
+mod exit_code;
+mod game_log;
+mod headless;
+mod high_scores;
+mod inline_screen;
 mod io;
+mod leaderboard;
+mod link_quality;
+mod menu;
+mod pointer;
 mod rendering;
 mod scope_guard;
+mod screenshot;
+#[cfg(feature = "serve")]
+mod serve;
+mod session;
+mod settings;
+mod skin;
+mod spectate;
+mod theme;
+mod trail;
+mod watch;
 
+use args::{Cli, Command, DisplayArgs, OutputFormat, PlayArgs, TournamentArgs};
+use asciicast::{Asciicast, TeeWriter};
+use bench::bench_render;
+use clap::Parser;
+use cli_error::CliError;
 use constrictor_core::{
-    math::{Direction, Vector2},
-    models::{Board, SimulationParameterError, Snake, SnakeSimulation},
+    bots::{AStarBot, FloodFillBot, GreedyBot, RandomBot, Tournament, TournamentSettings},
+    models::{Clock, QuitReason, SimulationResult, StopAt},
+    replay::{InputLog, Replay},
 };
+use crash_dump::Crash;
 use crossterm::{
-    cursor, execute, queue,
-    terminal::{self, ClearType},
+    cursor,
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute, style, terminal,
 };
-use io::{EventStream, GameCommand};
+use death_animation::play_death;
+use game_log::{GameLog, GameSummary};
+use high_scores::HighScores;
+use inline_screen::InlineScreen;
+#[cfg(feature = "twitch")]
+use io::TwitchChat;
+use io::{
+    EventStream, GameCommand, InputRouter, InputScript, RepeatFilter, Seat, SeatedCommand,
+    ShutdownSignals,
+};
+use leaderboard::Leaderboard;
+use link_quality::LinkMonitor;
+use menu::{MenuChoice, TitleMenu};
+use pointer::{pointer_command, steer_towards};
+use screenshot::save_screenshot;
+#[cfg(feature = "serve")]
+use serve::LiveServer;
+use session::Session;
+use settings::Settings;
+use skin::Skin;
+#[cfg(feature = "serve")]
+use std::net::{Ipv4Addr, SocketAddr};
 use std::{
-    error::Error,
+    fs,
     io::{Write, stdout},
-    thread::sleep,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::ExitCode,
     time::{Duration, Instant},
 };
 
-use crate::{rendering::Renderable, scope_guard::ScopeGuard};
+use crate::{
+    rendering::{FrameRenderer, Hud, RenderError, RenderMode, render_banner},
+    scope_guard::ScopeGuard,
+    theme::{Theme, ThemeName},
+};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    run(cli).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        exit_code::for_error(&e)
+    })
+}
+
+/// Runs the command given on the command line, giving the code to exit with.
+fn run(cli: Cli) -> Result<ExitCode, CliError> {
+    let default = Command::Play(PlayArgs {
+        menu: true,
+        ..Default::default()
+    });
+
+    if theme::color_disabled(cli.display.no_color) {
+        // Keeps the status line and menus plain too
+        style::force_color_output(false);
+    }
+
+    // Only the commands that draw a board need the settings file for a theme
+    let theme = || -> Result<Theme, CliError> {
+        let settings = Settings::load(cli.config.as_deref())?;
+        Ok(pick_theme(&cli.display, &settings))
+    };
+
+    match cli.command.unwrap_or(default) {
+        Command::Play(args) => {
+            let settings = Settings::load(cli.config.as_deref())?;
+            let theme = pick_theme(&cli.display, &settings);
+            let result = match args.output {
+                OutputFormat::Terminal => play(args, cli.config.as_deref(), settings, theme)?,
+                OutputFormat::Json => headless::play_json(args, settings)?,
+            };
+
+            return Ok(exit_code::for_result(result.as_ref()));
+        }
+        Command::Tournament(args) => tournament(args)?,
+        Command::BenchRender(args) => bench_render(args, theme()?)?,
+        Command::Trail(args) => trail::export_trail(args)?,
+        #[cfg(feature = "gif")]
+        Command::Gif(args) => animation::export_gif(args)?,
+        #[cfg(not(feature = "gif"))]
+        Command::Gif(_) => {
+            return Err(CliError::Other(
+                "GIF export needs constrictor-cli built with the `gif` feature".into(),
+            ));
+        }
+        Command::Watch(args) => watch::watch(args, theme()?)?,
+        Command::Spectate(args) => spectate::spectate(args, theme()?)?,
+        #[cfg(unix)]
+        Command::Daemon(args) => daemon::daemon(args)?,
+        #[cfg(not(unix))]
+        Command::Daemon(_) => {
+            return Err(CliError::Other(
+                "daemon mode needs Unix domain sockets".into(),
+            ));
+        }
+        Command::Campaign(args) => campaign::campaign(args, cli.config.as_deref(), theme()?)?,
+        Command::Edit(args) => edit::edit(args, theme()?)?,
+        Command::Doctor(args) => {
+            if !doctor::doctor(cli.config.as_deref(), args)? {
+                return Ok(ExitCode::from(exit_code::ERROR));
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Picks the theme to draw with: the one named on the command line, else the
+/// one in `settings`, else the classic one. Without colour, the monochrome
+/// theme stands in for the classic one, and any other is drawn in the
+/// terminal's own colours. Where only ASCII can be shown, any other glyphs
+/// are swapped for it. A checkerboard is only added to a theme in colour.
+fn pick_theme(display: &DisplayArgs, settings: &Settings) -> Theme {
+    let chosen = display
+        .theme
+        .map(ThemeName::theme)
+        .or_else(|| settings.theme.clone());
+
+    let theme = match (chosen, theme::color_disabled(display.no_color)) {
+        (Some(theme), true) => theme.without_color(),
+        (Some(theme), false) => theme,
+        (None, true) => ThemeName::Monochrome.theme(),
+        (None, false) => Theme::default(),
+    };
+
+    let theme = if display.checkerboard && theme.has_color() {
+        theme.with_checkerboard()
+    } else {
+        theme
+    };
+
+    if theme::ascii_only(display.ascii) {
+        theme.ascii_only()
+    } else {
+        theme
+    }
+}
+
+/// The most steps the game loop will take at once to catch up after a stall.
+const MAX_CATCH_UP: u32 = 4;
+
+/// How many times faster the game runs while boosting.
+const BOOST_FACTOR: u32 = 2;
+
+fn play(
+    args: PlayArgs,
+    settings_path: Option<&Path>,
+    settings: Settings,
+    theme: Theme,
+) -> Result<Option<SimulationResult>, CliError> {
+    let mut config = args.game.apply(settings.game.clone());
+    if let Some(skin) = args.skin.or(settings.skin) {
+        config.player.skin = skin.id();
+    }
+
+    // Pin the seed down now, so the game can be reproduced from the config.
+    // Restarting picks a new one, unless the player asked for this one.
+    let fixed_seed = config.seed;
+    let seed = *config.seed.get_or_insert_with(rand::random);
+
+    let mut opponent = args.add_opponent(&mut config, seed);
+    let mut sim = config.build()?;
+
+    // Read the script before taking over the terminal, which it may be
+    // piped in alongside
+    let mut script = args
+        .input_script
+        .as_deref()
+        .map(InputScript::load)
+        .transpose()?;
+
+    // Likewise, a port that's taken should be reported on the way in
+    #[cfg(feature = "serve")]
+    let mut live = args
+        .serve
+        .map(|port| {
+            let address = SocketAddr::new(
+                args.serve_address.unwrap_or(Ipv4Addr::LOCALHOST.into()),
+                port,
+            );
+            LiveServer::bind(address).map_err(|e| CliError::Serve(address, e))
+        })
+        .transpose()?;
+
+    // Loaded before the terminal is taken over, so a warning can be read. A
+    // corrupt file is left alone rather than overwritten by the next score
+    let mut high_scores = HighScores::load(settings_path).unwrap_or_else(|e| {
+        eprintln!("{e}\nHigh scores won't be kept this time.");
+        HighScores::default()
+    });
 
-fn main() -> Result<(), Box<dyn Error>> {
     // Try and be a polite neighbour to the user. We're about to mess with their
     // terminal so we better at least try to clean up our own mess.
-    let _restore_terminal = ScopeGuard::new(|| {
-        // clean up with best effort
-        _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
-        _ = terminal::disable_raw_mode();
-    });
+    let leave: fn() = if args.inline {
+        || {
+            // clean up with best effort
+            _ = execute!(stdout(), cursor::Show);
+            _ = terminal::disable_raw_mode();
+        }
+    } else {
+        || {
+            // clean up with best effort
+            _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+            _ = terminal::disable_raw_mode();
+        }
+    };
+    let restore_terminal = ScopeGuard::new(leave);
+
+    // Grab handle to stdout and prepare the rendering environment, starting
+    // the recording first so it sees the screen being set up too
+    let cast = match &args.record_cast {
+        Some(path) => {
+            let size = terminal::size().map_err(CliError::Terminal)?;
+            let cast =
+                Asciicast::create(path, size).map_err(|e| CliError::Save(path.clone(), e))?;
+            Some(cast)
+        }
+        None => None,
+    };
+    let mut stdout = TeeWriter::new(stdout(), cast);
+    terminal::enable_raw_mode().map_err(CliError::Terminal)?;
+    if !args.inline {
+        execute!(stdout, terminal::EnterAlternateScreen).map_err(CliError::Terminal)?;
+    }
+    execute!(stdout, cursor::Hide).map_err(CliError::Terminal)?;
+
+    // Holding a key to boost needs to know when it's let go, which only
+    // terminals with keyboard enhancements (such as kitty's) report. The
+    // flags belong to the alternate screen, so they must be popped before
+    // leaving it.
+    let key_releases = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if key_releases {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )
+        .map_err(CliError::Terminal)?;
+    }
+    let restore_keyboard = key_releases
+        .then(|| ScopeGuard::new(|| _ = execute!(std::io::stdout(), PopKeyboardEnhancementFlags)));
+
+    // Braille packs several cells into each character, so a click can't
+    // pick one out
+    let mouse = (args.mouse || settings.mouse) && !args.braille;
+    let mode = if args.braille {
+        RenderMode::Braille
+    } else if args.half_block {
+        RenderMode::HalfBlock
+    } else {
+        RenderMode::Diff
+    };
+    if mouse {
+        execute!(stdout, EnableMouseCapture).map_err(CliError::Terminal)?;
+    }
+    let restore_mouse =
+        mouse.then(|| ScopeGuard::new(|| _ = execute!(std::io::stdout(), DisableMouseCapture)));
+
+    // Room for the walls, the status line and the buttons under it
+    let inline = if args.inline {
+        let rows = mode
+            .screen_size(sim.board())
+            .map_or(u16::MAX, |size| size.y)
+            .saturating_add(1 + u16::from(mouse));
+        Some(InlineScreen::reserve(rows, &mut stdout).map_err(CliError::Terminal)?)
+    } else {
+        None
+    };
+    let top = inline.as_ref().map_or(0, InlineScreen::top);
+
+    let signals = ShutdownSignals::register().map_err(CliError::Io)?;
+    let mut events = EventStream::new().map_err(CliError::Terminal)?;
+    let keys = settings.key_map(args.keys);
 
-    // Grab handle to stdout and prepare the rendering environment
-    let mut stdout = stdout();
-    terminal::enable_raw_mode()?;
-    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    if args.menu {
+        let mut menu = TitleMenu::new(&keys, config.board, config.speed)
+            .with_puzzle(config.scenario.as_ref())
+            .with_skin(Skin::from_id(config.player.skin))
+            .with_high_scores(high_scores.scores());
+        let attract_after = args.attract_after.unwrap_or(30);
+        if attract_after > 0 {
+            let idle = Duration::from_secs(attract_after.into());
+            menu = menu.with_demo(&config, idle, theme.clone());
+        }
+
+        if menu.run(&mut events, &mut stdout)? == MenuChoice::Quit {
+            return Ok(Some(SimulationResult::ManuallyTerminated(
+                QuitReason::UserQuit,
+            )));
+        }
+
+        config.board = menu.board();
+        config.speed = menu.speed();
+        config.scenario = menu.scenario();
+        config.player.skin = menu.skin().id();
+        sim = config.build()?;
+    }
+
+    let router = InputRouter::new().bind_keys(keys, Seat(0));
+    let mut repeats = RepeatFilter::new(Duration::from_millis(
+        args.repeat_filter_ms.unwrap_or(settings.repeat_filter_ms),
+    ));
+    let mut inputs = InputLog::new();
+    let mut recording = InputScript::default();
+    #[cfg(feature = "twitch")]
+    let mut chat = args
+        .twitch
+        .as_deref()
+        .map(|channel| TwitchChat::join(channel, args.vote_window.unwrap_or(5)));
 
-    let mut command_iter = EventStream::new()
-        .filter_map(|e| e.ok())
-        .filter_map(|e| GameCommand::try_from(e).ok());
+    // The seed of the game the recording starts in
+    let mut recording_seed = seed;
+
+    let mut renderer = FrameRenderer::new(mode, theme.clone());
+    let mut hud = Hud::new();
+    hud.buttons = mouse;
+    if let Ok(code) = config.challenge_code() {
+        hud.notify(format!("Challenge {code}"));
+    }
+    let mut link = LinkMonitor::new();
+    let mut session = Session::new();
+    let mut new_high_score = false;
+    let log = args.log_games.as_deref().map(GameLog::new);
+    renderer.draw(&sim.view(), &mut stdout)?;
+    hud.draw(&sim.view(), sim.tick_interval(), &renderer, &mut stdout)?;
+    stdout.flush().map_err(RenderError::Io)?;
 
-    let mut sim = create_game(32, 32)?;
+    // Give the player a moment to find the keys before the snake sets off
+    let countdown = Duration::from_secs(args.countdown.unwrap_or(3).into());
+    let screenshot_size = args.screenshot_cell_size.unwrap_or(8);
+    let mut starts_at = Instant::now() + countdown;
+    let mut banner_shown = false;
 
-    sim.render(&mut stdout)?;
+    // Steps are taken whenever enough time has built up on the clock, however
+    // long each frame takes to draw. A slow frame means several steps before
+    // the next one is drawn, rather than a slower game.
+    let mut clock = Clock::new();
 
+    let mut previous_frame_start = Instant::now();
+    let mut last_drawn_tick = sim.tick();
+    let mut lost_terminal = None;
+    let mut boosting = false;
     while sim.result().is_none() {
         let frame_start = Instant::now();
+        let counting_down = frame_start < starts_at;
+
+        // The banner isn't part of the frame, so only a full redraw clears it
+        if banner_shown && !counting_down {
+            renderer = FrameRenderer::new(mode, theme.clone());
+            banner_shown = false;
+        }
+
+        // Don't try to catch up on a long stall, like the process being
+        // suspended; the snake would cover half the board in one frame
+        let elapsed = (frame_start - previous_frame_start).min(sim.tick_interval() * MAX_CATCH_UP);
+        previous_frame_start = frame_start;
+
+        // Boosting runs the clock faster rather than changing the speed, so
+        // the game goes back to its own pace when the boost ends
+        if !sim.is_paused() && !counting_down {
+            hud.elapse(elapsed);
+            clock.elapse(elapsed * if boosting { BOOST_FACTOR } else { 1 });
+        }
 
         // Process input that has happened since last tick
-        for command in command_iter.by_ref() {
+        let mut typed = Vec::new();
+        for event in events.by_ref() {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    // We've lost our terminal, so there's nobody left to play
+                    sim.quit(QuitReason::ConnectionLost);
+                    lost_terminal = Some(e);
+                    break;
+                }
+            };
+
+            // There is only one snake for now, so everything is routed to the
+            // first seat.
+            let seated = match router.route(event) {
+                Ok(seated) => seated,
+                Err(Event::Mouse(mut event)) if mouse => {
+                    // Inline, the game starts part way down the screen
+                    let Some(row) = event.row.checked_sub(top) else {
+                        continue;
+                    };
+                    event.row = row;
+
+                    let Some(command) = pointer_command(event, &hud, &renderer, sim.board()) else {
+                        continue;
+                    };
+
+                    SeatedCommand {
+                        seat: Seat(0),
+                        command,
+                    }
+                }
+                Err(_) => continue,
+            };
+
+            if repeats.allows(&seated, Instant::now()) {
+                typed.push(seated.command);
+            }
+        }
+
+        // Scripted commands come after the player's own, as if typed last,
+        // and chat's vote after those. They are taken one at a time, since a
+        // restart changes which are due.
+        let mut typed = typed.into_iter();
+        while let Some(command) = typed
+            .next()
+            .or_else(|| script.as_mut()?.take_due(sim.tick()))
+            .or_else(|| {
+                #[cfg(feature = "twitch")]
+                let vote = chat.as_mut()?.take_due(sim.tick());
+                #[cfg(not(feature = "twitch"))]
+                let vote = None;
+                vote
+            })
+        {
+            recording.record(sim.tick(), command);
+
             match command {
-                GameCommand::Quit => sim.quit(),
+                GameCommand::Quit => sim.quit(QuitReason::UserQuit),
                 GameCommand::ChangeDirection(direction) => {
+                    inputs.record(sim.tick(), direction);
                     sim.change_player_move_direction(direction)
                 }
+                GameCommand::PointAt(target) => {
+                    if let Some(direction) = steer_towards(&sim.view().player(), target) {
+                        inputs.record(sim.tick(), direction);
+                        sim.change_player_move_direction(direction)
+                    }
+                }
+                GameCommand::Pause => sim.set_paused(!sim.is_paused()),
+                GameCommand::Restart => {
+                    session.record(sim.score());
+                    new_high_score |= high_scores.record(&config, &sim);
+                    if let Some(log) = &log {
+                        log.append(&GameSummary::new(&config, &sim, hud.played()))?;
+                    }
+
+                    let seed = fixed_seed.unwrap_or_else(rand::random);
+                    config.seed = Some(seed);
+                    sim = config.build()?;
+                    inputs = InputLog::new();
+
+                    // A new random seed can't be played back into from the
+                    // game before, so the recording starts over with it
+                    if fixed_seed.is_none() {
+                        recording = InputScript::default();
+                        recording_seed = seed;
+                    }
+
+                    renderer = FrameRenderer::new(mode, theme.clone());
+                    hud.reset();
+                    if let Ok(code) = config.challenge_code() {
+                        hud.notify(format!("Challenge {code}"));
+                    }
+                    clock = Clock::new();
+                    boosting = false;
+                    last_drawn_tick = sim.tick();
+                    starts_at = Instant::now() + countdown;
+
+                    #[cfg(feature = "serve")]
+                    if let Some(live) = live.as_mut() {
+                        live.restart(&sim.view());
+                    }
+                }
+                GameCommand::SpeedUp => sim.set_speed(sim.speed().scaled(80)),
+                GameCommand::SpeedDown => sim.set_speed(sim.speed().scaled(125)),
+                GameCommand::ToggleHud => hud.visible = !hud.visible,
+                GameCommand::Screenshot => {
+                    match save_screenshot(&sim.view(), renderer.theme(), screenshot_size) {
+                        Ok(path) => hud.notify(format!("Saved {}", path.display())),
+                        Err(e) => hud.notify(format!("Screenshot failed: {e}")),
+                    }
+                }
+                GameCommand::Boost => boosting = key_releases || !boosting,
+                GameCommand::StopBoost => boosting = false,
+            }
+        }
+
+        if let Some(reason) = signals.received() {
+            sim.quit(reason);
+        }
+
+        #[cfg(feature = "serve")]
+        if let Some(live) = live.as_mut() {
+            live.poll(&sim.view());
+        }
+
+        // Step simulation forward, holding at the next scripted tick so its
+        // commands are issued before it is played. Anything going wrong in
+        // here is a bug, so leave behind enough for someone to reproduce it.
+        let stop = script.as_ref().and_then(InputScript::next_tick);
+        let advance = || {
+            // One step at a time, so the opponent can look before each one
+            while sim.result().is_none() {
+                if let Some(bot) = opponent.as_mut() {
+                    bot.steer(&mut sim, 1);
+                }
+
+                let next = (sim.tick() + 1).min(stop.unwrap_or(u64::MAX));
+                if sim.run(&mut StopAt::new(&mut clock, next)) == 0 {
+                    break;
+                }
+
+                #[cfg(feature = "serve")]
+                if let Some(live) = live.as_mut() {
+                    live.step(&sim.view());
+                }
+            }
+        };
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(advance)) {
+            return Err(Crash::from_panic(payload, &config, &inputs, &sim).into());
+        }
+
+        if let Err(violation) = sim.check_invariants() {
+            return Err(Crash::report(violation.to_string(), &config, &inputs, &sim).into());
+        }
+
+        // Re-render, backing off if the terminal can't keep up. Always draw
+        // the final frame so the game doesn't end on a stale one, and keep
+        // drawing while nothing is moving so the screen still responds.
+        let tick_interval = sim.tick_interval();
+        let idle = sim.is_paused() || counting_down;
+        if link.quality().draws(sim.tick(), last_drawn_tick) || idle || sim.result().is_some() {
+            let write_start = Instant::now();
+            last_drawn_tick = sim.tick();
+            hud.boosting = boosting;
+            let drawn = (|| -> Result<(), RenderError> {
+                renderer.draw(&sim.view(), &mut stdout)?;
+                hud.draw(&sim.view(), tick_interval, &renderer, &mut stdout)?;
+
+                if counting_down {
+                    let left = (starts_at - frame_start).as_secs() + 1;
+                    render_banner(&format!(" {left} "), sim.board(), &renderer, &mut stdout)?;
+                    banner_shown = true;
+                }
+
+                Ok(stdout.flush()?)
+            })();
+
+            // A frame that couldn't be drawn is skipped, and the next one
+            // drawn from scratch in case this one was left half done
+            match drawn {
+                Ok(()) => {
+                    let quality = link.observe(write_start.elapsed(), tick_interval);
+                    renderer.set_color(quality.color());
+                    hud.set_frame_interval(quality.frame_interval());
+                }
+                Err(e) if e.is_recoverable() => renderer = FrameRenderer::new(mode, theme.clone()),
+                Err(e) => return Err(e.into()),
             }
         }
 
-        // Step simulation forward
-        sim.advance();
+        // Wake up in time for the next step, or as soon as there is input to
+        // handle. The clock will count this whole frame on the next pass, so
+        // the time spent drawing it is part of the wait, and waking early
+        // doesn't change when the next step is taken.
+        let frame_duration = frame_start.elapsed();
+        let boost = if boosting { BOOST_FACTOR } else { 1 };
+        let wait_time =
+            (tick_interval.saturating_sub(clock.pending()) / boost).saturating_sub(frame_duration);
+
+        if wait_time > Duration::ZERO {
+            events.wait(wait_time);
+        }
+    }
+
+    // Let the player see what went wrong before the game screen goes
+    let died = matches!(sim.result(), Some(SimulationResult::Died(_)));
+    if died && lost_terminal.is_none() && !args.no_death_animation {
+        play_death(&sim.view(), &mut renderer, &mut events, &mut stdout)?;
+    }
+
+    // Quitting ends the game without a step, so viewers still need telling.
+    // The last step's changes come along again, but were already applied.
+    #[cfg(feature = "serve")]
+    if let Some(live) = live.as_mut() {
+        live.step(&sim.view());
+    }
 
-        // Re-render
-        queue!(
-            &mut stdout,
-            terminal::Clear(ClearType::All),
-            cursor::MoveTo(0, 0)
-        )?;
-        sim.render(&mut stdout)?;
-        stdout.flush()?;
+    // Put the terminal back first, so the summary outlives the game screen
+    stdout.finish().map_err(RenderError::Io)?;
+    session.record(sim.score());
+    new_high_score |= high_scores.record(&config, &sim);
+    drop(restore_mouse);
+    drop(restore_keyboard);
+    drop(inline);
+    drop(restore_terminal);
 
-        let frame_end = Instant::now();
-        let frame_duration = frame_end - frame_start;
-        let sleep_time = Duration::from_millis(75) - frame_duration;
+    let summary = GameSummary::new(&config, &sim, hud.played());
+    if args.quiet {
+        eprintln!("{}", summary.to_json());
+    } else {
+        println!("{session}");
 
-        if sleep_time > Duration::ZERO {
-            sleep(sleep_time);
+        // Each game's code was shown as it started, but only the last one's
+        // is left to copy
+        if let Ok(code) = config.challenge_code() {
+            println!("Challenge a friend to that game with --challenge {code}");
         }
     }
 
-    Ok(())
+    if let Some(leaderboard) = Leaderboard::new(&settings.leaderboard) {
+        leaderboard.report(&config, &sim, args.quiet);
+    }
+
+    if let Some(log) = &log {
+        log.append(&summary)?;
+    }
+
+    if new_high_score {
+        high_scores.save().map_err(CliError::Other)?;
+    }
+
+    if let Some(e) = lost_terminal {
+        eprintln!("Lost the terminal: {e}");
+    }
+
+    #[cfg(feature = "twitch")]
+    if let Some(e) = chat.as_ref().and_then(TwitchChat::error) {
+        eprintln!("Lost Twitch chat: {e}");
+    }
+
+    if let Some(path) = args.record_input {
+        // The seed is needed to play the script back into the same game
+        fs::write(&path, format!("# seed {recording_seed}\n{recording}"))
+            .map_err(|e| CliError::Save(path, e))?;
+    }
+
+    if let Some(path) = args.save_replay {
+        let replay = Replay {
+            config,
+            inputs,
+            ticks: sim.tick(),
+            result: sim.result().copied(),
+        };
+
+        serde_json::to_string(&replay)
+            .map_err(std::io::Error::from)
+            .and_then(|json| fs::write(&path, json))
+            .map_err(|e| CliError::Save(path, e))?;
+    }
+
+    Ok(sim.result().copied())
 }
 
-fn create_game(width: u16, height: u16) -> Result<SnakeSimulation, SimulationParameterError> {
-    let w_i32: i32 = width.into();
-    let h_i32: i32 = height.into();
+fn tournament(args: TournamentArgs) -> Result<(), CliError> {
+    let seed = args.game.seed.unwrap_or_else(rand::random);
+    let game = args.game.apply(TournamentSettings::default().game);
+    let board = game.board;
+
+    let mut tournament = Tournament::new(TournamentSettings {
+        games: args.games,
+        seed,
+        game,
+        max_ticks: args.max_ticks,
+    });
+
+    tournament.register(GreedyBot);
+    tournament.register(RandomBot::new(seed));
+    tournament.register(AStarBot);
+    tournament.register(FloodFillBot);
 
-    let center = Vector2 { x: w_i32, y: h_i32 } / 2;
+    let standings = tournament.run()?;
 
-    SnakeSimulation::new(
-        Board::new((1, w_i32 + 1), (1, h_i32 + 1)),
-        Snake::new(center.neighbour(Direction::Left, 3), Direction::Right),
-        center.neighbour(Direction::Right, 3),
-    )
+    println!(
+        "{} games on a {}x{} board (seed {seed})",
+        args.games, board.width, board.height
+    );
+    println!();
+    print!("{standings}");
+
+    Ok(())
 }
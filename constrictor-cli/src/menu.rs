@@ -0,0 +1,523 @@
+use std::{
+    error::Error,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{
+    bots::{Controller, FloodFillBot},
+    config::{BoardSize, Difficulty, GameConfig, Scenario, SpeedCurve},
+    math::Direction,
+    models::{Appearance, SnakeSimulation},
+};
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyEventKind},
+    queue,
+    style::{self, Attribute, Stylize},
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    high_scores::HighScore,
+    io::{EventStream, GameCommand, KeyMap},
+    rendering::{FrameRenderer, RenderError, RenderMode, render_banner},
+    skin::Skin,
+    theme::Theme,
+};
+
+/// What the player picked on the title screen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MenuChoice {
+    Play,
+    Quit,
+}
+
+/// Board sizes offered on the title screen, smallest first.
+const BOARD_SIZES: [BoardSize; 3] = [
+    BoardSize {
+        width: 16,
+        height: 16,
+    },
+    BoardSize {
+        width: 32,
+        height: 32,
+    },
+    BoardSize {
+        width: 48,
+        height: 32,
+    },
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Item {
+    NewGame,
+    Board,
+    Difficulty,
+    Puzzle,
+    Skin,
+    HighScores,
+    Controls,
+    Quit,
+}
+
+const ITEMS: [Item; 8] = [
+    Item::NewGame,
+    Item::Board,
+    Item::Difficulty,
+    Item::Puzzle,
+    Item::Skin,
+    Item::HighScores,
+    Item::Controls,
+    Item::Quit,
+];
+
+/// What the title screen shows below its name.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Page {
+    Items,
+    HighScores,
+    Controls,
+}
+
+/// The title screen, shown before a game. The player moves through it with
+/// their own direction keys, and picks an entry with enter or space.
+pub struct TitleMenu<'a> {
+    keys: &'a KeyMap,
+    selected: usize,
+    board: BoardSize,
+    speed: SpeedCurve,
+
+    /// The puzzles on offer, and which of them is picked, if any.
+    puzzles: Vec<Scenario>,
+    puzzle: Option<usize>,
+
+    skin: Skin,
+    high_scores: Vec<HighScore>,
+    page: Page,
+
+    /// The game a [`Demo`] plays, and how long the keys must sit idle before
+    /// it starts. No demo is played if this is [`None`].
+    attract: Option<(GameConfig, Duration, Theme)>,
+}
+
+impl<'a> TitleMenu<'a> {
+    pub fn new(keys: &'a KeyMap, board: BoardSize, speed: SpeedCurve) -> Self {
+        Self {
+            keys,
+            selected: 0,
+            board,
+            speed,
+            puzzles: Scenario::built_in(),
+            puzzle: None,
+            skin: Skin::default(),
+            high_scores: Vec::new(),
+            page: Page::Items,
+            attract: None,
+        }
+    }
+
+    /// Plays a [`Demo`] of `config` behind the menu once no key has been
+    /// pressed for `idle`, using whichever board size and speed are picked,
+    /// drawn in `theme`.
+    pub fn with_demo(mut self, config: &GameConfig, idle: Duration, theme: Theme) -> Self {
+        let config = GameConfig {
+            opponents: Vec::new(),
+            scenario: None,
+            ..config.clone()
+        };
+
+        self.attract = Some((config, idle, theme));
+        self
+    }
+
+    /// Starts out with `scenario` picked, offering it alongside the built-in
+    /// puzzles if it isn't one of them.
+    pub fn with_puzzle(mut self, scenario: Option<&Scenario>) -> Self {
+        self.puzzle = scenario.map(|scenario| {
+            self.puzzles
+                .iter()
+                .position(|puzzle| puzzle == scenario)
+                .unwrap_or_else(|| {
+                    self.puzzles.push(scenario.clone());
+                    self.puzzles.len() - 1
+                })
+        });
+
+        self
+    }
+
+    /// Starts out with `skin` picked for the player's snake.
+    pub fn with_skin(mut self, skin: Skin) -> Self {
+        self.skin = skin;
+        self
+    }
+
+    /// Lists `scores`, best first, under the high scores entry.
+    pub fn with_high_scores(mut self, scores: &[HighScore]) -> Self {
+        self.high_scores = scores.to_vec();
+        self
+    }
+
+    /// The board size the player settled on.
+    pub const fn board(&self) -> BoardSize {
+        self.board
+    }
+
+    /// The speed the player settled on.
+    pub const fn speed(&self) -> SpeedCurve {
+        self.speed
+    }
+
+    /// The puzzle the player settled on, if they want to play one.
+    pub fn scenario(&self) -> Option<Scenario> {
+        self.puzzle.map(|index| self.puzzles[index].clone())
+    }
+
+    /// The skin the player settled on for their snake.
+    pub const fn skin(&self) -> Skin {
+        self.skin
+    }
+
+    /// Shows the menu until the player picks something. Any key stops a
+    /// demo that is playing, and does nothing else.
+    pub fn run<W: Write>(
+        &mut self,
+        events: &mut EventStream,
+        stream: &mut W,
+    ) -> Result<MenuChoice, Box<dyn Error>> {
+        let mut demo: Option<Demo> = None;
+        let mut last_key = Instant::now();
+        self.draw(None, stream)?;
+
+        loop {
+            for event in events.by_ref() {
+                let event = event?;
+                if let Event::Key(key) = event
+                    && key.kind != KeyEventKind::Release
+                {
+                    last_key = Instant::now();
+                    if demo.take().is_some() {
+                        self.draw(None, stream)?;
+                        continue;
+                    }
+                }
+
+                if let Some(choice) = self.handle(&event) {
+                    return Ok(choice);
+                }
+
+                self.draw(demo.as_mut(), stream)?;
+            }
+
+            if let Some((config, idle, theme)) = &self.attract
+                && demo.is_none()
+                && last_key.elapsed() >= *idle
+            {
+                let config = GameConfig {
+                    board: self.board,
+                    speed: self.speed,
+                    player: Appearance {
+                        skin: self.skin.id(),
+                        ..config.player.clone()
+                    },
+                    ..config.clone()
+                };
+
+                // A board too small to play on just goes without a demo
+                match Demo::new(config, theme.clone()) {
+                    Ok(started) => demo = Some(started),
+                    Err(_) => self.attract = None,
+                }
+            }
+
+            // Without a demo nothing is animated, so there is nothing to do
+            // until a key is pressed or it is time to start one
+            let wake_at = match (&mut demo, &self.attract) {
+                (Some(demo), _) => {
+                    demo.step();
+                    self.draw(Some(demo), stream)?;
+                    demo.next_step
+                }
+                (None, Some((_, idle, _))) => last_key + *idle,
+                (None, None) => Instant::now() + Duration::from_secs(1),
+            };
+
+            events.wait(wake_at.saturating_duration_since(Instant::now()));
+        }
+    }
+
+    /// Reacts to one input event. Returns the player's choice once they have
+    /// made one.
+    fn handle(&mut self, event: &Event) -> Option<MenuChoice> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        if self.page != Page::Items {
+            self.page = Page::Items;
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char(' ') => return self.activate(),
+            KeyCode::Esc => return Some(MenuChoice::Quit),
+            _ => {}
+        }
+
+        match self.keys.command(*key)? {
+            GameCommand::ChangeDirection(Direction::Up) => {
+                self.selected = (self.selected + ITEMS.len() - 1) % ITEMS.len();
+            }
+            GameCommand::ChangeDirection(Direction::Down) => {
+                self.selected = (self.selected + 1) % ITEMS.len();
+            }
+            GameCommand::ChangeDirection(direction) => {
+                self.cycle(ITEMS[self.selected], direction == Direction::Right);
+            }
+            GameCommand::Quit => return Some(MenuChoice::Quit),
+            _ => {}
+        }
+
+        None
+    }
+
+    fn activate(&mut self) -> Option<MenuChoice> {
+        match ITEMS[self.selected] {
+            Item::NewGame => return Some(MenuChoice::Play),
+            Item::Board | Item::Difficulty | Item::Puzzle | Item::Skin => {
+                self.cycle(ITEMS[self.selected], true);
+            }
+            Item::HighScores => self.page = Page::HighScores,
+            Item::Controls => self.page = Page::Controls,
+            Item::Quit => return Some(MenuChoice::Quit),
+        }
+
+        None
+    }
+
+    /// Moves the setting under `item` on to the next choice, or back to the
+    /// previous one. Items without a setting are left alone.
+    fn cycle(&mut self, item: Item, forward: bool) {
+        match item {
+            Item::Board => self.board = next(&BOARD_SIZES, self.board, forward),
+            Item::Difficulty => {
+                let speeds = Difficulty::ALL.map(Difficulty::speed);
+                self.speed = next(&speeds, self.speed, forward);
+            }
+            Item::Puzzle => {
+                let puzzles: Vec<_> = std::iter::once(None)
+                    .chain((0..self.puzzles.len()).map(Some))
+                    .collect();
+                self.puzzle = next(&puzzles, self.puzzle, forward);
+            }
+            Item::Skin => self.skin = next(&Skin::ALL, self.skin, forward),
+            Item::NewGame | Item::HighScores | Item::Controls | Item::Quit => {}
+        }
+    }
+
+    /// Draws the menu, on top of `demo` if one is playing.
+    fn draw<W: Write>(
+        &self,
+        demo: Option<&mut Demo>,
+        stream: &mut W,
+    ) -> Result<(), Box<dyn Error>> {
+        match demo {
+            Some(demo) => demo.draw(stream)?,
+            None => queue!(stream, terminal::Clear(ClearType::All))?,
+        }
+
+        queue!(
+            stream,
+            style::ResetColor,
+            cursor::MoveTo(2, 1),
+            style::PrintStyledContent("C O N S T R I C T O R".green().bold())
+        )?;
+
+        let lines = match self.page {
+            Page::Items => self.items(),
+            Page::HighScores => self.high_scores(),
+            Page::Controls => self.controls(),
+        };
+
+        for (row, line) in (3..).zip(lines) {
+            queue!(stream, cursor::MoveTo(2, row), style::Print(line))?;
+        }
+
+        stream.flush()?;
+        Ok(())
+    }
+
+    fn items(&self) -> Vec<String> {
+        ITEMS
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let marker = if i == self.selected { '>' } else { ' ' };
+                let label = match item {
+                    Item::NewGame => "New game".to_string(),
+                    Item::Board => {
+                        format!("Board   < {}x{} >", self.board.width, self.board.height)
+                    }
+                    Item::Difficulty => format!("Speed   < {} >", self.difficulty_name()),
+                    Item::Puzzle => {
+                        let name = self.puzzle.map_or("Off", |index| &self.puzzles[index].name);
+                        format!("Puzzle  < {name} >")
+                    }
+                    Item::Skin => format!("Skin    < {} >", self.skin),
+                    Item::HighScores => "High scores".to_string(),
+                    Item::Controls => "Controls".to_string(),
+                    Item::Quit => "Quit".to_string(),
+                };
+
+                format!("{marker} {label}")
+            })
+            .collect()
+    }
+
+    /// Names the difficulty whose speed is picked, if it is one of them.
+    fn difficulty_name(&self) -> String {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.speed() == self.speed)
+            .map_or("Custom".to_string(), |difficulty| difficulty.to_string())
+    }
+
+    fn high_scores(&self) -> Vec<String> {
+        let mut lines: Vec<_> = (1..)
+            .zip(&self.high_scores)
+            .map(|(rank, high)| {
+                let board = format!("{}x{}", high.width, high.height);
+                format!("{rank:>2}. {:>6}  {board:<7} {}", high.score, high.mode)
+            })
+            .collect();
+
+        if lines.is_empty() {
+            lines.push("No scores yet".to_string());
+        }
+
+        lines.push(String::new());
+        lines.push("Press any key to go back".to_string());
+        lines
+    }
+
+    fn controls(&self) -> Vec<String> {
+        let commands = [
+            ("Up", GameCommand::ChangeDirection(Direction::Up)),
+            ("Down", GameCommand::ChangeDirection(Direction::Down)),
+            ("Left", GameCommand::ChangeDirection(Direction::Left)),
+            ("Right", GameCommand::ChangeDirection(Direction::Right)),
+            ("Pause", GameCommand::Pause),
+            ("Restart", GameCommand::Restart),
+            ("Faster", GameCommand::SpeedUp),
+            ("Slower", GameCommand::SpeedDown),
+            ("Boost", GameCommand::Boost),
+            ("Status line", GameCommand::ToggleHud),
+            ("Screenshot", GameCommand::Screenshot),
+            ("Quit", GameCommand::Quit),
+        ];
+
+        let mut lines: Vec<_> = commands
+            .into_iter()
+            .map(|(label, command)| {
+                let keys = self.keys.keys_for(command);
+                let keys = if keys.is_empty() {
+                    "unbound".to_string()
+                } else {
+                    keys.join(", ")
+                };
+
+                format!("{label:<12} {keys}")
+            })
+            .collect();
+
+        lines.push(String::new());
+        lines.push("Press any key to go back".to_string());
+        lines
+    }
+}
+
+/// A game played by a bot behind the title menu, attract mode style, while
+/// nobody is at the keys. A new game starts whenever one ends.
+struct Demo {
+    config: GameConfig,
+    sim: SnakeSimulation,
+    bot: FloodFillBot,
+    renderer: FrameRenderer,
+    theme: Theme,
+
+    /// When the game is next due to take a step.
+    next_step: Instant,
+}
+
+impl Demo {
+    fn new(config: GameConfig, theme: Theme) -> Result<Self, Box<dyn Error>> {
+        let sim = Self::build(&config)?;
+        Ok(Self {
+            next_step: Instant::now() + sim.tick_interval(),
+            config,
+            sim,
+            bot: FloodFillBot,
+            renderer: FrameRenderer::new(RenderMode::Diff, theme.clone()),
+            theme,
+        })
+    }
+
+    /// Builds a game of `config` with a fresh seed, so each demo differs.
+    fn build(config: &GameConfig) -> Result<SnakeSimulation, Box<dyn Error>> {
+        let config = GameConfig {
+            seed: Some(rand::random()),
+            ..config.clone()
+        };
+
+        Ok(config.build()?)
+    }
+
+    /// Takes a step if one is due, or starts over once the game has ended.
+    fn step(&mut self) {
+        if Instant::now() < self.next_step {
+            return;
+        }
+
+        if self.sim.result().is_some() {
+            // The config built fine before, so only its seed has changed
+            if let Ok(sim) = Self::build(&self.config) {
+                self.sim = sim;
+                self.renderer = FrameRenderer::new(RenderMode::Diff, self.theme.clone());
+            }
+        } else {
+            self.bot.steer(&mut self.sim, 0);
+            self.sim.advance();
+        }
+
+        self.next_step = Instant::now() + self.sim.tick_interval();
+    }
+
+    /// Queues the game, dimmed so it stays in the background.
+    fn draw<W: Write>(&mut self, stream: &mut W) -> Result<(), RenderError> {
+        queue!(stream, style::SetAttribute(Attribute::Dim))?;
+        self.renderer.draw(&self.sim.view(), stream)?;
+        queue!(stream, style::SetAttribute(Attribute::Reset))?;
+        render_banner(" DEMO ", self.sim.board(), &self.renderer, stream)
+    }
+}
+
+/// Picks the choice after `current` in `choices`, or the one before it,
+/// wrapping around at either end. A `current` value that isn't one of the
+/// choices, such as one from the settings file, is left for the first or
+/// last choice.
+fn next<T: PartialEq + Copy>(choices: &[T], current: T, forward: bool) -> T {
+    let count = choices.len();
+    let index = match choices.iter().position(|choice| *choice == current) {
+        Some(index) if forward => (index + 1) % count,
+        Some(index) => (index + count - 1) % count,
+        None if forward => 0,
+        None => count - 1,
+    };
+
+    choices[index]
+}
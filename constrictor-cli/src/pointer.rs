@@ -0,0 +1,66 @@
+use constrictor_core::{
+    math::{Direction, Vector2},
+    models::{Board, SnakeView},
+};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use crate::{
+    io::GameCommand,
+    rendering::{FrameRenderer, Hud, board_point_at},
+};
+
+/// Works out what a mouse event on the game screen, as `renderer` draws it,
+/// asks for. Clicking a [`Hud`] button issues its command; clicking or
+/// dragging anywhere else points the snake at that spot.
+pub fn pointer_command(
+    mouse: MouseEvent,
+    hud: &Hud,
+    renderer: &FrameRenderer,
+    board: &Board,
+) -> Option<GameCommand> {
+    let point = board_point_at(mouse.column, mouse.row, board, renderer.mode());
+    let point = GameCommand::PointAt(point);
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => Some(
+            hud.button_at(mouse.column, mouse.row, board, renderer)
+                .unwrap_or(point),
+        ),
+        MouseEventKind::Drag(MouseButton::Left) => Some(point),
+        _ => None,
+    }
+}
+
+/// Picks the direction that turns `snake` towards `target`, along whichever
+/// axis it is further away on. A target behind the snake turns it to the
+/// side instead, since it can't double back on itself. Returns [`None`] if
+/// the snake is already heading that way, or the target is its head.
+pub fn steer_towards(snake: &SnakeView, target: Vector2) -> Option<Direction> {
+    let head = snake.head();
+    let (dx, dy) = (target.x - head.x, target.y - head.y);
+
+    let horizontal = (dx != 0).then_some(if dx > 0 {
+        Direction::Right
+    } else {
+        Direction::Left
+    });
+    let vertical = (dy != 0).then_some(if dy > 0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    });
+
+    let (major, minor) = if dx.abs() >= dy.abs() {
+        (horizontal, vertical)
+    } else {
+        (vertical, horizontal)
+    };
+
+    let heading = snake.pending_facing().unwrap_or(snake.facing());
+    let direction = match major {
+        Some(direction) if direction == heading.flip() => minor,
+        major => major,
+    }?;
+
+    (direction != heading).then_some(direction)
+}
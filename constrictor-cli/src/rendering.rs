@@ -1,118 +1,1144 @@
-use std::{error::Error, io::Write, iter, num::TryFromIntError};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 
+use clap::ValueEnum;
 use constrictor_core::{
-    math::Vector2,
-    models::{Board, Snake, SnakeSimulation},
+    config::{Map, Tile},
+    math::{Direction, Vector2},
+    models::{Board, SimulationView},
+    render::{Annotations, BrailleChar, BrailleFrame, Cell, FrameBuffer, Tint},
 };
 use crossterm::{
     cursor, queue,
-    style::{self, Color},
+    style::{self, Color, Stylize},
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    io::GameCommand,
+    skin::SegmentStyles,
+    theme::{BorderChars, Theme},
 };
 
+/// Describes why something couldn't be drawn on the terminal.
+#[derive(Debug)]
+pub enum RenderError {
+    /// Writing to the terminal failed.
+    Io(io::Error),
+
+    /// A point on the board lies beyond the coordinates a terminal can
+    /// address, as happens on a board far too big to draw.
+    OffScreen(Vector2),
+}
+
+impl RenderError {
+    /// Whether the terminal is still usable, so that the frame can be
+    /// skipped and drawing tried again next time rather than giving up.
+    /// Writes that were only interrupted can be retried. A point off screen
+    /// is off screen every frame, so drawing again would never get further.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Io(e) => matches!(
+                e.kind(),
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+            ),
+            Self::OffScreen(_) => false,
+        }
+    }
+}
+
+impl Error for RenderError {}
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not draw on the terminal: {e}"),
+            Self::OffScreen(Vector2 { x, y }) => write!(
+                f,
+                "the cell at ({x}, {y}) is too far out to draw; try a smaller board"
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for RenderError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
 trait TryToScreen<S, E> {
-    fn try_to_screen(&self) -> Result<S, E>;
+    /// Maps a point on `board` to the screen as `mode` draws it, where the
+    /// board's border is drawn with its top-left corner at the origin.
+    fn try_to_screen(&self, board: &Board, mode: RenderMode) -> Result<S, E>;
 }
 
-impl TryToScreen<Vector2<u16>, TryFromIntError> for Vector2 {
-    fn try_to_screen(&self) -> Result<Vector2<u16>, TryFromIntError> {
-        let x: u16 = (self.x * 2 - 1).try_into()?;
-        let y: u16 = (self.y).try_into()?;
+impl TryToScreen<Vector2<u16>, RenderError> for Vector2 {
+    fn try_to_screen(&self, board: &Board, mode: RenderMode) -> Result<Vector2<u16>, RenderError> {
+        let off_screen = |_| RenderError::OffScreen(*self);
+        let x = i64::from(self.x - board.x_range().start);
+        let y = i64::from(self.y - board.y_range().start);
 
-        Ok(Vector2 { x, y })
+        let (x, y) = match mode {
+            RenderMode::Full | RenderMode::Diff => (x * 2, y),
+            RenderMode::HalfBlock => (x, y.div_euclid(2)),
+            RenderMode::Braille => (x.div_euclid(2), y.div_euclid(4)),
+        };
+
+        Ok(Vector2 {
+            x: (x + 1).try_into().map_err(off_screen)?,
+            y: (y + 1).try_into().map_err(off_screen)?,
+        })
     }
 }
 
-pub trait Renderable {
-    fn render<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>>;
+/// Maps a position on the screen back to the point on `board` drawn there
+/// by `mode`, undoing [`TryToScreen`]. Where a character holds several
+/// cells, this is the top left of them. Positions off the board give points
+/// outside it.
+pub fn board_point_at(column: u16, row: u16, board: &Board, mode: RenderMode) -> Vector2 {
+    let (x, y) = (i32::from(column) - 1, i32::from(row) - 1);
+
+    let (x, y) = match mode {
+        RenderMode::Full | RenderMode::Diff => (x.div_euclid(2), y),
+        RenderMode::HalfBlock => (x, y * 2),
+        RenderMode::Braille => (x * 2, y * 4),
+    };
+
+    Vector2 {
+        x: x + board.x_range().start,
+        y: y + board.y_range().start,
+    }
 }
 
-impl Renderable for SnakeSimulation {
-    fn render<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>> {
-        const FOOD: &str = "╺╸";
+/// The ways a whole frame can be drawn to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RenderMode {
+    /// Clear the screen and redraw everything, every frame.
+    Full,
+
+    /// Only redraw the cells that changed since the previous frame.
+    Diff,
+
+    /// Draw two rows of cells in each row of characters, as the top and
+    /// bottom halves of a block, and each cell one column wide, so that
+    /// boards twice the size fit. Cells show only as their colour, so
+    /// without colour each half is just filled or not. Only the characters
+    /// that changed are redrawn.
+    HalfBlock,
+
+    /// Pack 2 by 4 cells into each character as braille dots, so that boards
+    /// too big for the terminal fit, redrawing only the characters that
+    /// changed. Terrain, fog and checkerboards don't show, and each character
+    /// takes the colour of the most important thing in it.
+    Braille,
+}
+
+impl RenderMode {
+    /// Gets how many columns and rows `board` takes up on the screen, walls
+    /// included, as this mode draws it.
+    pub fn screen_size(self, board: &Board) -> Result<Vector2<u16>, RenderError> {
+        // The last cell, from which the walls are one step further out
+        let last = Vector2 {
+            x: board.x_range().end - 1,
+            y: board.y_range().end - 1,
+        };
+        let last_on_screen = last.try_to_screen(board, self)?;
+        let cell_width = match self {
+            Self::Full | Self::Diff => 2,
+            Self::HalfBlock | Self::Braille => 1,
+        };
+
+        let size =
+            |end: u16, after: u16| end.checked_add(after).ok_or(RenderError::OffScreen(last));
+        Ok(Vector2 {
+            x: size(last_on_screen.x, cell_width + 1)?,
+            y: size(last_on_screen.y, 2)?,
+        })
+    }
+}
+
+/// Draws successive frames of a game using a particular [`RenderMode`], in
+/// the colours and glyphs of a [`Theme`].
+pub struct FrameRenderer {
+    mode: RenderMode,
+    theme: Theme,
+
+    /// The last frame drawn, for [`RenderMode::Diff`] to compare against.
+    previous: Option<FrameBuffer>,
+
+    /// A spare buffer to draw the next frame into, so that diffing does not
+    /// allocate every frame.
+    spare: Option<FrameBuffer>,
+
+    /// The last frame drawn, as [`RenderMode::Braille`] packed it.
+    previous_braille: Option<BrailleFrame>,
+
+    /// How the snakes' skins styled the last frame drawn, for
+    /// [`RenderMode::Diff`] to compare against.
+    previous_styles: SegmentStyles,
+
+    /// Colours to draw particular cells in, over the theme's.
+    highlights: HashMap<Vector2, Color>,
+
+    /// Whether cells are drawn in colour.
+    color: bool,
+}
+
+impl FrameRenderer {
+    pub fn new(mode: RenderMode, theme: Theme) -> Self {
+        Self {
+            mode,
+            previous: None,
+            spare: None,
+            previous_braille: None,
+            previous_styles: SegmentStyles::default(),
+            highlights: HashMap::new(),
+            color: theme.has_color(),
+            theme,
+        }
+    }
+
+    /// Gets the theme frames are drawn in, for drawing anything else to
+    /// match.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Gets the way frames are drawn, for working out what is where on the
+    /// screen.
+    pub const fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    /// Gets how many columns and rows `board` takes up on the screen, for
+    /// drawing anything else around it.
+    pub fn screen_size(&self, board: &Board) -> Result<Vector2<u16>, RenderError> {
+        self.mode.screen_size(board)
+    }
+
+    /// Draws the cells at the points in `highlights` in those colours from
+    /// now on, keeping their glyphs, for effects like the death animation.
+    /// Empty cells stay empty. Braille shows none of them.
+    pub fn set_highlights(&mut self, highlights: HashMap<Vector2, Color>) {
+        self.highlights = highlights;
+    }
+
+    /// Turns colour on or off for cells drawn from now on. Cells already on
+    /// screen keep their colour until they change; new ones use the
+    /// terminal's default. A theme without colour stays without.
+    pub fn set_color(&mut self, color: bool) {
+        self.color = color && self.theme.has_color();
+    }
+
+    /// Queues everything needed to bring the screen up to date with `view`.
+    /// The caller is responsible for flushing `stream`.
+    pub fn draw<W: Write>(
+        &mut self,
+        view: &SimulationView,
+        stream: &mut W,
+    ) -> Result<(), RenderError> {
+        if self.color {
+            queue!(stream, style::SetBackgroundColor(self.theme.background))?;
+        } else {
+            queue!(stream, style::ResetColor)?;
+        }
+
+        match self.mode {
+            RenderMode::Full => {
+                queue!(
+                    stream,
+                    cursor::MoveTo(0, 0),
+                    terminal::Clear(ClearType::FromCursorDown)
+                )?;
+                let frame = view.frame();
+                let styles = self.styles(view, &frame);
+                self.board_and_frame(&frame, &styles, view.board(), stream)
+            }
+            RenderMode::Diff => {
+                let board = view.board();
+                let mut frame = self.spare.take().unwrap_or_else(|| FrameBuffer::new(board));
+                view.draw(&mut frame);
+                let styles = self.styles(view, &frame);
+
+                match &self.previous {
+                    Some(previous) if previous.fits(board) => {
+                        // A skin can restyle a segment without the cell changing
+                        let changed = frame
+                            .changes_since(previous)
+                            .map(|(point, _)| point)
+                            .chain(styles.changes_since(&self.previous_styles));
+                        render_cells(
+                            changed,
+                            &frame,
+                            &styles,
+                            board,
+                            &self.theme,
+                            self.color,
+                            stream,
+                        )?
+                    }
+                    _ => {
+                        queue!(
+                            stream,
+                            cursor::MoveTo(0, 0),
+                            terminal::Clear(ClearType::FromCursorDown)
+                        )?;
+                        self.board_and_frame(&frame, &styles, board, stream)?;
+                    }
+                }
+
+                self.spare = self.previous.replace(frame);
+                self.previous_styles = styles;
+                Ok(())
+            }
+            RenderMode::HalfBlock => {
+                let board = view.board();
+                let mut frame = self.spare.take().unwrap_or_else(|| FrameBuffer::new(board));
+                view.draw(&mut frame);
+                let styles = self.styles(view, &frame);
+
+                match &self.previous {
+                    Some(previous) if previous.fits(board) => render_half_blocks(
+                        frame
+                            .changes_since(previous)
+                            .map(|(point, _)| point)
+                            .chain(styles.changes_since(&self.previous_styles)),
+                        &frame,
+                        &styles,
+                        board,
+                        &self.theme,
+                        self.color,
+                        stream,
+                    )?,
+                    _ => {
+                        queue!(
+                            stream,
+                            cursor::MoveTo(0, 0),
+                            terminal::Clear(ClearType::FromCursorDown)
+                        )?;
+                        render_plain_walls(board, self.mode, &self.theme, stream)?;
 
-        self.board().render(stream)?;
-        self.snake().render(stream)?;
+                        // Each character holds a pair of rows, drawn from the top one
+                        let tops = frame
+                            .cells()
+                            .map(|(point, _)| point)
+                            .filter(|point| (point.y - board.y_range().start) % 2 == 0);
+                        render_half_blocks(
+                            tops,
+                            &frame,
+                            &styles,
+                            board,
+                            &self.theme,
+                            self.color,
+                            stream,
+                        )?;
+                    }
+                }
 
-        let food_pos = self.food_position().try_to_screen()?;
+                self.spare = self.previous.replace(frame);
+                self.previous_styles = styles;
+                Ok(())
+            }
+            RenderMode::Braille => {
+                let frame = BrailleFrame::new(view.board(), &view.frame());
+
+                match &self.previous_braille {
+                    Some(previous)
+                        if (previous.width(), previous.height())
+                            == (frame.width(), frame.height()) =>
+                    {
+                        render_braille(
+                            frame.changes_since(previous),
+                            &self.theme,
+                            self.color,
+                            stream,
+                        )?
+                    }
+                    _ => {
+                        queue!(
+                            stream,
+                            cursor::MoveTo(0, 0),
+                            terminal::Clear(ClearType::FromCursorDown)
+                        )?;
+                        render_plain_walls(view.board(), self.mode, &self.theme, stream)?;
+                        render_braille(frame.chars(), &self.theme, self.color, stream)?;
+                    }
+                }
+
+                self.previous_braille = Some(frame);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FrameRenderer {
+    /// Styles the snakes in `view` as their skins have them, with the
+    /// highlights over the top.
+    fn styles(&self, view: &SimulationView, frame: &FrameBuffer) -> SegmentStyles {
+        let mut styles = SegmentStyles::new(view, frame, &self.theme);
+        styles.recolor(&self.highlights, frame, &self.theme);
+        styles
+    }
+
+    fn board_and_frame<W: Write>(
+        &self,
+        frame: &FrameBuffer,
+        styles: &SegmentStyles,
+        board: &Board,
+        stream: &mut W,
+    ) -> Result<(), RenderError> {
+        board.render(&self.theme, stream)?;
+        render_frame(frame, styles, board, &self.theme, self.color, stream)
+    }
+}
+
+/// A status line drawn underneath the board, showing the score, the
+/// snake's length, how long the game has been played and how fast it runs.
+/// With the mouse in use, a row of buttons goes underneath it.
+pub struct Hud {
+    pub visible: bool,
+
+    /// Whether to draw the [`Hud::BUTTONS`]. They stay put when the status
+    /// line is hidden, since they are controls rather than status.
+    pub buttons: bool,
+
+    /// Whether the game is running faster while the boost key is held.
+    pub boosting: bool,
+
+    /// Time spent playing, not counting pauses.
+    played: Duration,
+
+    /// How many ticks pass between drawn frames, when the terminal can't keep
+    /// up with every one.
+    frame_interval: u32,
+
+    /// A message for the player, such as where a screenshot was saved, and
+    /// when it stops being shown.
+    notice: Option<(String, Instant)>,
+}
+
+impl Hud {
+    /// The buttons that can be clicked, with the command each one issues.
+    const BUTTONS: [(&str, GameCommand); 2] = [
+        ("[ Pause ]", GameCommand::Pause),
+        ("[ Quit ]", GameCommand::Quit),
+    ];
+
+    /// How long a notice stays on the status line.
+    const NOTICE_TIME: Duration = Duration::from_secs(3);
+
+    pub fn new() -> Self {
+        Self {
+            visible: true,
+            buttons: false,
+            boosting: false,
+            played: Duration::ZERO,
+            frame_interval: 1,
+            notice: None,
+        }
+    }
+
+    /// Shows `message` at the end of the status line for a few seconds,
+    /// replacing any message already there.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.notice = Some((message.into(), Instant::now() + Self::NOTICE_TIME));
+    }
+
+    /// Counts `time` towards the game clock.
+    pub fn elapse(&mut self, time: Duration) {
+        self.played += time;
+    }
+
+    /// Notes that only one frame in every `frame_interval` ticks is being
+    /// drawn, so the player knows why the game looks choppy.
+    pub fn set_frame_interval(&mut self, frame_interval: u32) {
+        self.frame_interval = frame_interval;
+    }
+
+    /// Starts the game clock again from zero, for a new game.
+    pub fn reset(&mut self) {
+        self.played = Duration::ZERO;
+    }
+
+    /// How long the game has been played, not counting pauses.
+    pub const fn played(&self) -> Duration {
+        self.played
+    }
+
+    /// Finds the command of the button drawn at `column` and `row` under
+    /// `board` as `renderer` draws it, if there is one.
+    pub fn button_at(
+        &self,
+        column: u16,
+        row: u16,
+        board: &Board,
+        renderer: &FrameRenderer,
+    ) -> Option<GameCommand> {
+        let buttons_row = renderer.screen_size(board).ok()?.y.checked_add(1)?;
+        if !self.buttons || row != buttons_row {
+            return None;
+        }
+
+        Self::button_columns()
+            .find(|(start, label, _)| (*start..start + label.len()).contains(&column.into()))
+            .map(|(_, _, command)| command)
+    }
+
+    /// Lays the buttons out left to right, one space apart, giving the
+    /// column each one starts at.
+    fn button_columns() -> impl Iterator<Item = (usize, &'static str, GameCommand)> {
+        Self::BUTTONS
+            .into_iter()
+            .scan(0, |start, (label, command)| {
+                let column = *start;
+                *start += label.len() + 1;
+                Some((column, label, command))
+            })
+    }
+
+    /// Queues the status line for `view`, which runs at `tick_interval`,
+    /// underneath the board as `renderer` draws it and in the colour of its
+    /// theme. When hidden, the line is cleared instead.
+    pub fn draw<W: Write>(
+        &self,
+        view: &SimulationView,
+        tick_interval: Duration,
+        renderer: &FrameRenderer,
+        stream: &mut W,
+    ) -> Result<(), RenderError> {
+        // The first row after the bottom wall
+        let row = renderer.screen_size(view.board())?.y;
 
         queue!(
             stream,
-            cursor::MoveTo(food_pos.x, food_pos.y),
-            style::SetForegroundColor(Color::Red),
-            style::Print(FOOD)
+            cursor::MoveTo(0, row),
+            style::ResetColor,
+            terminal::Clear(ClearType::CurrentLine)
         )?;
 
+        if self.buttons {
+            for (start, label, _) in Self::button_columns() {
+                let column = u16::try_from(start).unwrap_or(u16::MAX);
+                queue!(
+                    stream,
+                    cursor::MoveTo(column, row + 1),
+                    style::PrintStyledContent(label.reverse())
+                )?;
+            }
+            queue!(stream, cursor::MoveTo(0, row))?;
+        }
+
+        if !self.visible {
+            return Ok(());
+        }
+
+        let seconds = self.played.as_secs();
+        let mut status = format!(
+            "Score {}  Length {}  Time {}:{:02}  {:.1} ticks/s",
+            view.score(),
+            view.player().len(),
+            seconds / 60,
+            seconds % 60,
+            1.0 / tick_interval.as_secs_f64()
+        );
+
+        if let Some(remaining) = view.remaining_food() {
+            status.push_str(&format!("  {remaining} to go"));
+        }
+
+        match view.remaining_moves() {
+            Some(1) => status.push_str("  1 move left"),
+            Some(moves) => status.push_str(&format!("  {moves} moves left")),
+            None => {}
+        }
+
+        // The clock counts steps, so this is how long they take at the
+        // current speed
+        if let Some(steps) = view.remaining_time() {
+            let left = tick_interval.saturating_mul(steps.try_into().unwrap_or(u32::MAX));
+            let seconds = left.as_secs_f64().ceil() as u64;
+            status.push_str(&format!("  {}:{:02} left", seconds / 60, seconds % 60));
+        }
+
+        let combo = view.player().combo_multiplier();
+        if combo > 1 {
+            status.push_str(&format!("  Combo x{combo}"));
+        }
+
+        if view.rules().lives > 1 {
+            status.push_str(&format!("  Lives {}", view.player().lives_left()));
+            if view.player().is_invulnerable() {
+                status.push_str(" (safe)");
+            }
+        }
+
+        for opponent in view.snakes().skip(1) {
+            status.push_str(&format!("  Opponent {}", opponent.score()));
+            if opponent.result().is_some() {
+                status.push_str(" (out)");
+            }
+        }
+
+        if self.boosting {
+            status.push_str("  BOOST");
+        }
+
+        if self.frame_interval > 1 {
+            status.push_str(&format!("  1/{} frames", self.frame_interval));
+        }
+
+        if view.player().is_shielded() {
+            status.push_str("  SHIELD");
+        }
+
+        if view.player().is_mirrored() {
+            status.push_str("  CONTROLS MIRRORED");
+        }
+
+        if view.is_paused() {
+            status.push_str("  PAUSED");
+        }
+
+        if let Some((message, until)) = &self.notice
+            && Instant::now() < *until
+        {
+            status.push_str(&format!("  {message}"));
+        }
+
+        queue!(
+            stream,
+            style::SetForegroundColor(renderer.theme().hud),
+            style::Print(status),
+            style::ResetColor
+        )?;
         Ok(())
     }
 }
 
+pub trait Renderable {
+    fn render<W: Write>(&self, theme: &Theme, stream: &mut W) -> Result<(), RenderError>;
+}
+
+impl Renderable for SimulationView<'_> {
+    fn render<W: Write>(&self, theme: &Theme, stream: &mut W) -> Result<(), RenderError> {
+        let frame = self.frame();
+        let styles = SegmentStyles::new(self, &frame, theme);
+
+        self.board().render(theme, stream)?;
+        render_frame(&frame, &styles, self.board(), theme, true, stream)
+    }
+}
+
 impl Renderable for Board {
-    fn render<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>> {
-        const TOP_LEFT_CORNER: char = '╔';
-        const TOP_RIGHT_CORNER: char = '╗';
-        const BOTTOM_LEFT_CORNER: char = '╚';
-        const BOTTOM_RIGHT_CORNER: char = '╝';
-        const VERTICAL_WALL: char = '║';
-        const HORIZONTAL_WALL: char = '═';
+    fn render<W: Write>(&self, theme: &Theme, stream: &mut W) -> Result<(), RenderError> {
+        const DOORWAY: &str = "  ";
 
-        let w_u16: u16 = (self.width() * 2).try_into()?;
-        let horizontal_bars = iter::repeat_n(HORIZONTAL_WALL, w_u16 as usize).collect::<String>();
+        let chars = theme.border.chars();
+        let horizontal_wall = String::from_iter([chars.horizontal; 2]);
+
+        let (top, bottom) = (self.y_range().start, self.y_range().end - 1);
+        let (left, right) = (self.x_range().start, self.x_range().end - 1);
+
+        // The right wall is where the column after the last would be
+        let right_wall = Vector2 {
+            x: right + 1,
+            y: top,
+        }
+        .try_to_screen(self, RenderMode::Full)?;
+        let w_u16 = right_wall.x - 1;
+
+        // Doorways leave a gap in the wall next to each cell they span
+        let horizontal_bars = |y: i32, side: Direction| -> String {
+            self.x_range()
+                .map(|x| match self.doorway(&Vector2 { x, y }, side) {
+                    Some(_) => DOORWAY,
+                    None => &horizontal_wall,
+                })
+                .collect()
+        };
 
         queue!(
             stream,
-            style::SetForegroundColor(Color::DarkGrey),
-            style::Print(&TOP_LEFT_CORNER),
-            style::Print(&horizontal_bars),
-            style::Print(&TOP_RIGHT_CORNER),
+            style::SetForegroundColor(theme.walls),
+            style::Print(chars.top_left),
+            style::Print(horizontal_bars(top, Direction::Up)),
+            style::Print(chars.top_right),
             cursor::MoveToNextLine(1)
         )?;
 
-        for _ in 0..self.height() {
+        for y in self.y_range() {
+            let vertical_wall =
+                |x: i32, side: Direction| match self.doorway(&Vector2 { x, y }, side) {
+                    Some(_) => ' ',
+                    None => chars.vertical,
+                };
+
+            queue!(stream, style::Print(vertical_wall(left, Direction::Left)))?;
+
+            // A background of its own has to be painted in, where the
+            // terminal's can be left as it was cleared
+            if theme.background == Color::Reset {
+                queue!(stream, cursor::MoveRight(w_u16))?;
+            } else {
+                queue!(stream, style::Print(" ".repeat(w_u16.into())))?;
+            }
+
             queue!(
                 stream,
-                style::Print(VERTICAL_WALL),
-                cursor::MoveRight(w_u16),
-                style::Print(VERTICAL_WALL),
+                style::Print(vertical_wall(right, Direction::Right)),
                 cursor::MoveToNextLine(1)
             )?;
         }
 
         queue!(
             stream,
-            style::Print(&BOTTOM_LEFT_CORNER),
-            style::Print(&horizontal_bars),
-            style::Print(&BOTTOM_RIGHT_CORNER)
+            style::Print(chars.bottom_left),
+            style::Print(horizontal_bars(bottom, Direction::Down)),
+            style::Print(chars.bottom_right)
         )?;
 
-        Ok(())
+        render_inner_walls(self, &chars, stream)
+    }
+}
+
+/// Renders the walls a mask leaves inside `board`: every unplayable cell
+/// next to a playable one, diagonals included. Each joins up with whichever
+/// of its neighbours are walls too.
+fn render_inner_walls<W: Write>(
+    board: &Board,
+    chars: &BorderChars,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    if board.mask().is_none() {
+        return Ok(());
+    }
+
+    let is_wall = |cell: &Vector2| {
+        board.in_bounds(cell)
+            && !board.contains(cell)
+            && (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| Vector2 { x: dx, y: dy }))
+                .any(|offset| board.contains(&(*cell + offset)))
+    };
+
+    for y in board.y_range() {
+        for x in board.x_range() {
+            let cell = Vector2 { x, y };
+            if !is_wall(&cell) {
+                continue;
+            }
+
+            let [up, down, left, right] = [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ]
+            .map(|direction| is_wall(&cell.neighbour(direction, 1)));
+
+            let screen_pos = cell.try_to_screen(board, RenderMode::Full)?;
+            queue!(
+                stream,
+                cursor::MoveTo(screen_pos.x, screen_pos.y),
+                style::Print(wall_glyph(up, down, left, right, chars))
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Picks the two characters drawn for a wall cell, given which of its
+/// neighbours it joins up with, out of `chars`. Vertical strokes sit in the
+/// first character, so that they line up from row to row.
+fn wall_glyph(up: bool, down: bool, left: bool, right: bool, chars: &BorderChars) -> String {
+    let first = match (up, down, left, right) {
+        (true, true, true, true) => chars.cross,
+        (true, true, true, false) => chars.tee_left,
+        (true, true, false, true) => chars.tee_right,
+        (true, false, true, true) => chars.tee_up,
+        (false, true, true, true) => chars.tee_down,
+        (true, false, true, false) => chars.bottom_right,
+        (true, false, false, true) => chars.bottom_left,
+        (false, true, true, false) => chars.top_right,
+        (false, true, false, true) => chars.top_left,
+        (true, _, false, false) | (_, true, false, false) => chars.vertical,
+        (false, false, _, _) => chars.horizontal,
+    };
+
+    // A wall reaching nowhere vertically is drawn as a bar all the same
+    let second = if right || !(up || down) {
+        chars.horizontal
+    } else {
+        ' '
+    };
+    [first, second].into_iter().collect()
+}
+
+/// Renders every non-empty cell of `frame`, positioned relative to the
+/// `board` it was drawn from, with the snakes' segments as `styles` has
+/// them.
+fn render_frame<W: Write>(
+    frame: &FrameBuffer,
+    styles: &SegmentStyles,
+    board: &Board,
+    theme: &Theme,
+    color: bool,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    // A checkerboard shows through the empty cells, so those are drawn too
+    let checker = color && theme.checker.is_some();
+    let drawn = frame
+        .cells()
+        .filter(|(point, cell)| *cell != Cell::Empty || (checker && board.contains(point)))
+        .map(|(point, _)| point);
+
+    render_cells(drawn, frame, styles, board, theme, color, stream)
+}
+
+/// Renders the cells of `frame` at each of `points`, blanking any that are
+/// empty, with the snakes' segments as `styles` has them and the theme's
+/// checkerboard behind them.
+fn render_cells<W: Write>(
+    points: impl Iterator<Item = Vector2>,
+    frame: &FrameBuffer,
+    styles: &SegmentStyles,
+    board: &Board,
+    theme: &Theme,
+    color: bool,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    let checker = color && theme.checker.is_some();
+
+    for point in points {
+        let cell = frame.get(&point).unwrap_or_default();
+        let glyph = styles.glyph(&point, cell, theme);
+        let background = checker.then(|| theme.background_at(&point));
+        render_cell(point, glyph, background, board, color, stream)?;
     }
+
+    // Whatever is drawn next goes back to the plain background
+    if checker {
+        queue!(stream, style::SetBackgroundColor(theme.background))?;
+    }
+
+    Ok(())
 }
 
-impl Renderable for Snake {
-    fn render<W: Write>(&self, stream: &mut W) -> Result<(), Box<dyn Error>> {
-        const SNAKE_HEAD: &str = "██";
-        const SNAKE_BODY: &str = "░░";
+/// Renders a single cell at `point` on `board` with `glyph`, as
+/// [`Theme::glyph`] gives it, over `background` if one is given. Colours are
+/// left alone unless `color` is set.
+fn render_cell<W: Write>(
+    point: Vector2,
+    glyph: Option<(&str, Color)>,
+    background: Option<Color>,
+    board: &Board,
+    color: bool,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    const BLANK: &str = "  ";
 
-        let head_pos = self.head().try_to_screen()?;
+    let screen_pos = point.try_to_screen(board, RenderMode::Full)?;
+    queue!(stream, cursor::MoveTo(screen_pos.x, screen_pos.y))?;
 
+    if let Some(background) = background
+        && color
+    {
+        queue!(stream, style::SetBackgroundColor(background))?;
+    }
+
+    match glyph {
+        Some((glyph, fg)) if color => {
+            queue!(stream, style::SetForegroundColor(fg), style::Print(glyph))?
+        }
+        Some((glyph, _)) => queue!(stream, style::Print(glyph))?,
+        None => queue!(stream, style::Print(BLANK))?,
+    }
+
+    Ok(())
+}
+
+/// Renders `annotations` over the cells of `frame`. A label replaces the
+/// cell's glyph, keeping only its first two characters; a tint colours the
+/// cell's background. Annotations outside `board` are skipped.
+pub fn render_annotations<W: Write>(
+    annotations: &Annotations,
+    frame: &FrameBuffer,
+    board: &Board,
+    theme: &Theme,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    for (point, annotation) in annotations.iter() {
+        let Some(cell) = frame.get(point) else {
+            continue;
+        };
+
+        let background = match annotation.tint {
+            Some(Tint::Good) => theme.tints.good,
+            Some(Tint::Bad) => theme.tints.bad,
+            Some(Tint::Info) => theme.tints.info,
+            None => theme.background_at(point),
+        };
+
+        let text = match &annotation.label {
+            Some(label) => format!("{:>2}", label.chars().take(2).collect::<String>()),
+            None => theme
+                .glyph(cell)
+                .map_or("  ", |(glyph, _)| glyph)
+                .to_string(),
+        };
+
+        let screen_pos = point.try_to_screen(board, RenderMode::Full)?;
         queue!(
             stream,
-            cursor::MoveTo(head_pos.x, head_pos.y),
-            style::SetForegroundColor(Color::Green),
-            style::Print(SNAKE_HEAD)
+            cursor::MoveTo(screen_pos.x, screen_pos.y),
+            style::SetForegroundColor(theme.tints.label),
+            style::SetBackgroundColor(background),
+            style::Print(text),
+            style::ResetColor
         )?;
+    }
 
-        for segment in self.body_iter().skip(1) {
-            let body_pos = segment.try_to_screen()?;
+    Ok(())
+}
 
-            queue!(
+/// Renders every tile of `map` inside a wall of its own, with the tile at
+/// `selected` shown in reverse. Each tile looks like what it turns into in a
+/// game, except that portal ends show their letter so pairs can be told
+/// apart. Returns the [`Board`] the map was drawn as, to position anything
+/// else drawn with it.
+pub fn render_map<W: Write>(
+    map: &Map,
+    selected: Vector2,
+    theme: &Theme,
+    stream: &mut W,
+) -> Result<Board, RenderError> {
+    // A map too big to address is caught drawing its far corner
+    let size = |cells: usize| i32::try_from(cells).unwrap_or(i32::MAX);
+    let board = Board::new((0, size(map.width())), (0, size(map.height())));
+    board.render(theme, stream)?;
+
+    for (point, tile) in map.cells() {
+        let cell = match tile {
+            Tile::Wall => Cell::Obstacle,
+            Tile::Floor => Cell::Empty,
+            Tile::Spawn => Cell::SnakeHead {
+                snake: 0,
+                facing: Direction::Right,
+            },
+            Tile::Food => Cell::Food,
+            Tile::Portal(letter) => Cell::Portal {
+                pair: (letter as usize).saturating_sub('a' as usize),
+            },
+        };
+
+        let (text, fg) = match (tile, theme.glyph(cell)) {
+            (Tile::Portal(letter), Some((_, fg))) => (format!("{letter} "), fg),
+            (_, Some((text, fg))) => (text.to_string(), fg),
+            (_, None) => ("  ".to_string(), Color::Reset),
+        };
+
+        let text = if point == selected {
+            text.reverse()
+        } else {
+            text.stylize()
+        };
+
+        let screen_pos = point.try_to_screen(&board, RenderMode::Full)?;
+        queue!(
+            stream,
+            cursor::MoveTo(screen_pos.x, screen_pos.y),
+            style::SetForegroundColor(fg),
+            style::PrintStyledContent(text)
+        )?;
+    }
+
+    Ok(board)
+}
+
+/// Writes `text` across the middle of `board` as `renderer` draws it, over
+/// whatever is drawn there.
+pub fn render_banner<W: Write>(
+    text: &str,
+    board: &Board,
+    renderer: &FrameRenderer,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    let center = board.center().try_to_screen(board, renderer.mode)?;
+    let half_width = u16::try_from(text.chars().count() / 2).unwrap_or(u16::MAX);
+
+    queue!(
+        stream,
+        cursor::MoveTo(center.x.saturating_sub(half_width), center.y),
+        style::PrintStyledContent(text.bold().reverse())
+    )?;
+
+    Ok(())
+}
+
+/// Renders the walls around `board` for `mode` as a plain box, without the
+/// doorways or the walls around a mask, which are too fine to show when
+/// several cells share a character.
+fn render_plain_walls<W: Write>(
+    board: &Board,
+    mode: RenderMode,
+    theme: &Theme,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    let chars = theme.border.chars();
+    let size = mode.screen_size(board)?;
+    let inside = usize::from(size.x - 2);
+    let horizontal = String::from_iter(vec![chars.horizontal; inside]);
+
+    queue!(
+        stream,
+        style::SetForegroundColor(theme.walls),
+        cursor::MoveTo(0, 0),
+        style::Print(chars.top_left),
+        style::Print(&horizontal),
+        style::Print(chars.top_right)
+    )?;
+
+    for row in 1..size.y - 1 {
+        queue!(stream, cursor::MoveTo(0, row), style::Print(chars.vertical))?;
+
+        // A background of its own has to be painted in, as for the cells
+        if theme.background != Color::Reset {
+            queue!(stream, style::Print(" ".repeat(inside)))?;
+        }
+
+        queue!(
+            stream,
+            cursor::MoveTo(size.x - 1, row),
+            style::Print(chars.vertical)
+        )?;
+    }
+
+    queue!(
+        stream,
+        cursor::MoveTo(0, size.y - 1),
+        style::Print(chars.bottom_left),
+        style::Print(&horizontal),
+        style::Print(chars.bottom_right)
+    )?;
+
+    Ok(())
+}
+
+/// Renders each of `chars` of a [`BrailleFrame`], given by column and row,
+/// in the colour `theme` gives its most important cell. Characters without
+/// any dots are blanked.
+fn render_braille<W: Write>(
+    chars: impl Iterator<Item = (usize, usize, BrailleChar)>,
+    theme: &Theme,
+    color: bool,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    for (column, row, char) in chars {
+        // The walls and status line are checked to fit, so the frame does
+        let column = u16::try_from(column + 1).unwrap_or(u16::MAX);
+        let row = u16::try_from(row + 1).unwrap_or(u16::MAX);
+        queue!(stream, cursor::MoveTo(column, row))?;
+
+        match theme.glyph(char.cell) {
+            Some((_, fg)) if color => queue!(
                 stream,
-                cursor::MoveTo(body_pos.x, body_pos.y),
-                style::SetForegroundColor(Color::Green),
-                style::Print(SNAKE_BODY)
-            )?;
+                style::SetForegroundColor(fg),
+                style::Print(char.to_char())
+            )?,
+            Some(_) => queue!(stream, style::Print(char.to_char()))?,
+            None => queue!(stream, style::Print(' '))?,
         }
+    }
 
-        Ok(())
+    Ok(())
+}
+
+/// Renders the characters of [`RenderMode::HalfBlock`] that hold each of
+/// `points` in `frame`, the upper cell as the foreground of the top half
+/// and the lower one as the background, with the snakes' segments in the
+/// colours `styles` gives them. Without `color`, each half is filled if its
+/// cell is drawn at all. Cells a mask leaves unplayable are filled in the
+/// colour of the walls, and empty ones in the theme's checkerboard, if it
+/// has one.
+fn render_half_blocks<W: Write>(
+    points: impl Iterator<Item = Vector2>,
+    frame: &FrameBuffer,
+    styles: &SegmentStyles,
+    board: &Board,
+    theme: &Theme,
+    color: bool,
+    stream: &mut W,
+) -> Result<(), RenderError> {
+    const UPPER: char = '▀';
+    const LOWER: char = '▄';
+    const FULL: char = '█';
+
+    // The colour of the cell at `point`, or `None` if it isn't drawn. Only
+    // the shaded squares of a checkerboard are, since the others are just
+    // background.
+    let checker = |point: &Vector2| {
+        theme
+            .checker
+            .filter(|_| color && theme.background_at(point) != theme.background)
+    };
+    let fill = |point: Vector2| {
+        if !board.y_range().contains(&point.y) {
+            None
+        } else if !board.contains(&point) {
+            Some(theme.walls)
+        } else {
+            let cell = frame.get(&point)?;
+            styles
+                .glyph(&point, cell, theme)
+                .map(|(_, color)| color)
+                .or_else(|| checker(&point))
+        }
+    };
+
+    for point in points {
+        let top = Vector2 {
+            y: point.y - (point.y - board.y_range().start) % 2,
+            ..point
+        };
+        let bottom = Vector2 {
+            y: top.y + 1,
+            ..top
+        };
+
+        let screen_pos = top.try_to_screen(board, RenderMode::HalfBlock)?;
+        queue!(stream, cursor::MoveTo(screen_pos.x, screen_pos.y))?;
+
+        match (fill(top), fill(bottom)) {
+            (Some(upper), Some(lower)) if color => queue!(
+                stream,
+                style::SetForegroundColor(upper),
+                style::SetBackgroundColor(lower),
+                style::Print(UPPER),
+                style::SetBackgroundColor(theme.background)
+            )?,
+            (Some(upper), None) if color => queue!(
+                stream,
+                style::SetForegroundColor(upper),
+                style::Print(UPPER)
+            )?,
+            (None, Some(lower)) if color => queue!(
+                stream,
+                style::SetForegroundColor(lower),
+                style::Print(LOWER)
+            )?,
+            (Some(_), Some(_)) => queue!(stream, style::Print(FULL))?,
+            (Some(_), None) => queue!(stream, style::Print(UPPER))?,
+            (None, Some(_)) => queue!(stream, style::Print(LOWER))?,
+            (None, None) => queue!(stream, style::Print(' '))?,
+        }
     }
+
+    Ok(())
 }
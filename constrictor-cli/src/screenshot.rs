@@ -0,0 +1,147 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use constrictor_core::{
+    math::Direction,
+    models::{PowerUp, SimulationView, Terrain},
+    render::{Cell, PALETTE, Raster},
+};
+use crossterm::style::Color;
+use png::{BitDepth, ColorType, Encoder};
+
+use crate::theme::Theme;
+
+/// Draws the board in `view` as a PNG in the working directory, each cell
+/// `cell_size` pixels square and in the colours of `theme`. Files are named
+/// after when they were taken, with a number added when that name is taken
+/// already, so screenshots never replace one another. Gives the path written.
+pub fn save_screenshot(
+    view: &SimulationView,
+    theme: &Theme,
+    cell_size: u32,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let raster = Raster::new(view.board(), &view.frame(), cell_size);
+
+    let taken = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let name = format!("constrictor-{taken}-tick{}", view.tick());
+    let (path, file) = (1..)
+        .map(|copy| match copy {
+            1 => PathBuf::from(format!("{name}.png")),
+            _ => PathBuf::from(format!("{name}-{copy}.png")),
+        })
+        .find_map(|path| match File::create_new(&path) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => None,
+            file => Some(file.map(|file| (path, file))),
+        })
+        .expect("some copy number is free")?;
+
+    let palette = palette(theme);
+    let mut encoder = Encoder::new(BufWriter::new(file), raster.width(), raster.height());
+    encoder.set_color(ColorType::Indexed);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_palette(palette.as_flattened());
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(raster.pixels())?;
+    writer.finish()?;
+
+    Ok(path)
+}
+
+/// The [`Raster`] palette in the colours of `theme`. Anything the theme
+/// leaves in the terminal's own colour keeps the [`PALETTE`] one.
+fn palette(theme: &Theme) -> [[u8; 3]; 15] {
+    let cell = |cell| theme.glyph(cell).map_or(Color::Reset, |(_, color)| color);
+    let colors = [
+        theme.background,
+        theme.walls,
+        cell(Cell::Food),
+        cell(Cell::PracticeFood),
+        cell(Cell::Obstacle),
+        cell(Cell::PowerUp(PowerUp::Shield)),
+        cell(Cell::Portal { pair: 0 }),
+        cell(Cell::Hazard),
+        cell(Cell::Terrain(Terrain::Sticky)),
+        cell(Cell::Terrain(Terrain::Boost)),
+        cell(Cell::Hidden),
+        cell(Cell::SnakeHead {
+            snake: 0,
+            facing: Direction::Right,
+        }),
+        cell(Cell::SnakeBody { snake: 0 }),
+        cell(Cell::SnakeHead {
+            snake: 1,
+            facing: Direction::Right,
+        }),
+        cell(Cell::SnakeBody { snake: 1 }),
+    ];
+
+    let mut palette = PALETTE;
+    for (shade, color) in palette.iter_mut().zip(colors) {
+        if let Some(rgb) = rgb(color) {
+            *shade = rgb;
+        }
+    }
+    palette
+}
+
+/// `color` as xterm draws it, or [`None`] for [`Color::Reset`].
+fn rgb(color: Color) -> Option<[u8; 3]> {
+    let ansi = match color {
+        Color::Reset => return None,
+        Color::Rgb { r, g, b } => return Some([r, g, b]),
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15,
+        Color::AnsiValue(value) => value,
+    };
+
+    const SYSTEM: [[u8; 3]; 16] = [
+        [0x00, 0x00, 0x00],
+        [0xcd, 0x00, 0x00],
+        [0x00, 0xcd, 0x00],
+        [0xcd, 0xcd, 0x00],
+        [0x00, 0x00, 0xee],
+        [0xcd, 0x00, 0xcd],
+        [0x00, 0xcd, 0xcd],
+        [0xe5, 0xe5, 0xe5],
+        [0x7f, 0x7f, 0x7f],
+        [0xff, 0x00, 0x00],
+        [0x00, 0xff, 0x00],
+        [0xff, 0xff, 0x00],
+        [0x5c, 0x5c, 0xff],
+        [0xff, 0x00, 0xff],
+        [0x00, 0xff, 0xff],
+        [0xff, 0xff, 0xff],
+    ];
+    let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+
+    Some(match ansi {
+        0..16 => SYSTEM[usize::from(ansi)],
+        16..232 => {
+            let cube = ansi - 16;
+            [level(cube / 36), level(cube / 6 % 6), level(cube % 6)]
+        }
+        _ => [8 + (ansi - 232) * 10; 3],
+    })
+}
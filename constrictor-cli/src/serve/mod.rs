@@ -0,0 +1,201 @@
+use std::{
+    error::Error,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+};
+
+use constrictor_core::{
+    models::SimulationView,
+    net::{Delta, Snapshot},
+    render::FrameBuffer,
+};
+use serde::Serialize;
+
+/// The page that draws the game, served at `/`.
+const PAGE: &str = include_str!("index.html");
+
+/// The most a browser may send before its request is considered complete.
+const MAX_REQUEST: usize = 8 * 1024;
+
+/// Lets a game played in the terminal be watched live in a browser. The
+/// server answers `/` with a page that draws the board, and `/events` with a
+/// stream of server-sent events the page listens to: a `snapshot` of the
+/// whole game when a viewer arrives or a new game starts, then a `delta`
+/// after every step. Both carry the same JSON as the network protocol's
+/// [`Snapshot`] and [`Delta`].
+///
+/// Nothing here blocks. The game calls [`LiveServer::poll`] once a frame to
+/// take new viewers, and a viewer that can't keep up is dropped rather than
+/// holding up the game; its browser reconnects and starts from a snapshot.
+pub struct LiveServer {
+    listener: TcpListener,
+
+    /// Browsers that haven't finished sending their request yet.
+    pending: Vec<Request>,
+
+    /// Browsers listening for events.
+    viewers: Vec<Viewer>,
+}
+
+/// A browser listening for events, and the game as it was last told it
+/// looks.
+struct Viewer {
+    stream: TcpStream,
+    frame: FrameBuffer,
+}
+
+/// A browser's connection, and whatever it has sent of its request so far.
+struct Request {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+}
+
+impl LiveServer {
+    /// Listens for browsers on `address`.
+    pub fn bind(address: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            pending: Vec::new(),
+            viewers: Vec::new(),
+        })
+    }
+
+    /// Takes new connections and answers whichever requests are complete.
+    /// New viewers are sent a snapshot of `view` to start from.
+    pub fn poll(&mut self, view: &SimulationView) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_ok() {
+                        self.pending.push(Request {
+                            stream,
+                            buffer: Vec::new(),
+                        });
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+
+        let mut pending = Vec::new();
+        for mut request in self.pending.drain(..) {
+            match request.read() {
+                Ok(Some(path)) => {
+                    if let Some(viewer) = answer(request.stream, &path, view) {
+                        self.viewers.push(viewer);
+                    }
+                }
+                Ok(None) => pending.push(request),
+                Err(_) => {}
+            }
+        }
+        self.pending = pending;
+    }
+
+    /// Tells every viewer how the game in `view` has changed since they were
+    /// last told.
+    pub fn step(&mut self, view: &SimulationView) {
+        self.viewers.retain_mut(|viewer| {
+            let delta = Delta::since(&mut viewer.frame, view);
+            viewer.send("delta", &delta)
+        });
+    }
+
+    /// Tells every viewer that a new game has started, as shown in `view`.
+    pub fn restart(&mut self, view: &SimulationView) {
+        self.viewers.retain_mut(|viewer| {
+            viewer.frame = view.frame();
+            viewer.send("snapshot", &Snapshot::from(view))
+        });
+    }
+}
+
+impl Viewer {
+    /// Sends the viewer `data` as the event named `event`, returning `false`
+    /// if they have gone.
+    fn send(&mut self, event: &str, data: &impl Serialize) -> bool {
+        encode(event, data).is_ok_and(|message| self.stream.write_all(message.as_bytes()).is_ok())
+    }
+}
+
+impl Request {
+    /// Reads whatever the browser has sent, giving the path it asked for
+    /// once the request is complete. Fails if the browser hung up or sent
+    /// too much.
+    fn read(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        let mut chunk = [0; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err("browser hung up".into()),
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if !self.buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            if self.buffer.len() > MAX_REQUEST {
+                return Err("request too large".into());
+            }
+            return Ok(None);
+        }
+
+        // Only the path of the request line matters: `GET /events HTTP/1.1`
+        let request = String::from_utf8_lossy(&self.buffer);
+        let path = request.split_whitespace().nth(1).unwrap_or("/");
+        Ok(Some(path.to_string()))
+    }
+}
+
+/// Answers a request for `path`, giving back the viewer to keep sending
+/// events to, if it is one.
+fn answer(mut stream: TcpStream, path: &str, view: &SimulationView) -> Option<Viewer> {
+    // Any query is only there to get past caches
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+
+    match path {
+        "/" | "/index.html" => {
+            _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{PAGE}",
+                PAGE.len()
+            );
+            None
+        }
+        "/events" => {
+            let snapshot = encode("snapshot", &Snapshot::from(view)).ok()?;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+                 Cache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n{snapshot}"
+            )
+            .ok()?;
+            Some(Viewer {
+                stream,
+                frame: view.frame(),
+            })
+        }
+        _ => {
+            _ = write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+            None
+        }
+    }
+}
+
+/// Writes `data` as a server-sent event named `event`. JSON has no line
+/// breaks of its own, so it fits on the one `data` line.
+fn encode(event: &str, data: &impl Serialize) -> serde_json::Result<String> {
+    Ok(format!(
+        "event: {event}\ndata: {}\n\n",
+        serde_json::to_string(data)?
+    ))
+}
@@ -0,0 +1,50 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// Running totals for every game played since the program started.
+pub struct Session {
+    started: Instant,
+    games: u32,
+    best_score: usize,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            games: 0,
+            best_score: 0,
+        }
+    }
+
+    /// Counts a game that ended, or was abandoned, with `score`.
+    pub fn record(&mut self, score: usize) {
+        self.games += 1;
+        self.best_score = self.best_score.max(score);
+    }
+
+    /// How long the session has been going.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+impl Display for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seconds = self.elapsed().as_secs();
+        let games = match self.games {
+            1 => "1 game".to_string(),
+            n => format!("{n} games"),
+        };
+
+        write!(
+            f,
+            "Played {games} in {}m {:02}s. Best score: {}",
+            seconds / 60,
+            seconds % 60,
+            self.best_score
+        )
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use constrictor_core::config::GameConfig;
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::{
+    io::{KeyAction, KeyMap, KeyName, KeyZone},
+    leaderboard::LeaderboardSettings,
+    skin::Skin,
+    theme::{self, Theme},
+};
+
+/// The version of the settings file format understood by this release. Files
+/// written for older versions are migrated as they are loaded.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Steps that each bring a settings table up by one version, starting from
+/// version 0.
+const MIGRATIONS: [fn(&mut Table); SETTINGS_VERSION as usize] = [
+    // 0 -> 1: files from before versioning only lack the version itself
+    |_| {},
+];
+
+/// Defaults loaded from the settings file, which command line flags override.
+///
+/// ```toml
+/// version = 1
+/// keys = "wasd"
+/// repeat_filter_ms = 30
+/// mouse = true
+/// theme = "amber"
+/// skin = "rainbow"
+///
+/// [bindings]
+/// k = "up"
+/// esc = "quit"
+/// q = "none"
+///
+/// [game]
+/// walls = "Wrap"
+///
+/// [game.board]
+/// width = 48
+/// height = 24
+///
+/// [game.speed]
+/// initial_interval_ms = 100
+/// minimum_interval_ms = 50
+/// speedup_per_food_ms = 2
+///
+/// [leaderboard]
+/// endpoint = "http://scores.example.com/constrictor"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// The [`SETTINGS_VERSION`] the file was written for. Missing in files
+    /// from before versioning, which count as version 0.
+    pub version: u32,
+
+    /// The preset keys steer the snake with.
+    pub keys: KeyZone,
+
+    /// Changes to the preset: keys to add, rebind, or unbind with `"none"`.
+    pub bindings: HashMap<KeyName, KeyAction>,
+
+    /// How long after a direction key the same key is ignored, in
+    /// milliseconds. Zero turns the filter off.
+    pub repeat_filter_ms: u64,
+
+    /// Whether the mouse steers the snake, as with `--mouse`.
+    pub mouse: bool,
+
+    /// The game played by `play`.
+    pub game: GameConfig,
+
+    /// The online leaderboard finished games are sent to, if any.
+    pub leaderboard: LeaderboardSettings,
+
+    /// How the board looks, as with `--theme`, or changes to one of the
+    /// built-in themes.
+    #[serde(deserialize_with = "theme::deserialize_setting")]
+    pub theme: Option<Theme>,
+
+    /// How the player's snake is drawn, as with `--skin`.
+    pub skin: Option<Skin>,
+}
+
+/// Describes why the settings file could not be loaded.
+#[derive(Debug)]
+pub enum SettingsError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+
+    /// The file was written by a newer release, which this one can't migrate
+    /// back from.
+    TooNew(PathBuf, u32),
+}
+
+impl Error for SettingsError {}
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            Self::Parse(path, e) => write!(f, "invalid settings in {}:\n{e}", path.display()),
+            Self::TooNew(path, version) => write!(
+                f,
+                "{} is for settings version {version}, but only versions up to \
+                 {SETTINGS_VERSION} are understood",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Settings {
+    /// Builds the [`KeyMap`] for `zone`, or for [`Self::keys`] if no zone is
+    /// given, with [`Self::bindings`] applied on top.
+    pub fn key_map(&self, zone: Option<KeyZone>) -> KeyMap {
+        KeyMap::preset(zone.unwrap_or(self.keys)).with_overrides(&self.bindings)
+    }
+
+    /// Loads the settings from `path`, or from the default location if no
+    /// path is given. A missing file at the default location is not an error
+    /// and gives the default settings.
+    pub fn load(path: Option<&Path>) -> Result<Self, SettingsError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_owned(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(SettingsError::Read(path, e)),
+        };
+
+        Self::migrate(&path, &text).map(|migrated| migrated.settings)
+    }
+
+    /// Parses the settings in `text`, read from `path`, migrating them to
+    /// [`SETTINGS_VERSION`] first if they are older.
+    pub fn migrate(path: &Path, text: &str) -> Result<Migrated, SettingsError> {
+        let parse_error = |e| SettingsError::Parse(path.to_owned(), e);
+        let mut table: Table = toml::from_str(text).map_err(parse_error)?;
+
+        // A version that isn't a number is left for deserializing to reject
+        let from_version = match table.get("version").map(Value::as_integer) {
+            None => 0,
+            Some(version) => version
+                .and_then(|version| u32::try_from(version).ok())
+                .unwrap_or(SETTINGS_VERSION),
+        };
+
+        if from_version > SETTINGS_VERSION {
+            return Err(SettingsError::TooNew(path.to_owned(), from_version));
+        }
+
+        for migration in &MIGRATIONS[from_version as usize..] {
+            migration(&mut table);
+        }
+
+        // Parsed from text rather than the table, so errors say where they
+        // are. A migrated file is written out again first, and the lines
+        // given are those of what it was migrated to.
+        let settings = if from_version < SETTINGS_VERSION {
+            table.insert(
+                "version".to_string(),
+                Value::Integer(SETTINGS_VERSION.into()),
+            );
+            toml::from_str(&table.to_string())
+        } else {
+            toml::from_str(text)
+        }
+        .map_err(parse_error)?;
+
+        Ok(Migrated {
+            settings,
+            table,
+            from_version,
+        })
+    }
+}
+
+/// Settings brought up to [`SETTINGS_VERSION`] by [`Settings::migrate`].
+pub struct Migrated {
+    pub settings: Settings,
+
+    /// The migrated file, ready to be written back out.
+    pub table: Table,
+
+    /// The version the file was written for before migrating.
+    pub from_version: u32,
+}
+
+/// Where the settings file lives when none is given: `constrictor/config.toml`
+/// inside the platform's configuration directory.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    config_dir.map(|dir| dir.join("constrictor").join("config.toml"))
+}
@@ -0,0 +1,220 @@
+use std::{collections::HashMap, fmt::Display};
+
+use clap::ValueEnum;
+use constrictor_core::{
+    math::Vector2,
+    models::{Segment, SimulationView},
+    render::{Cell, FrameBuffer},
+};
+use crossterm::style::Color;
+use serde::Deserialize;
+
+use crate::theme::Theme;
+
+/// The ways a snake can be drawn, to pick with `--skin`, from the settings
+/// file or on the title menu. The skin is kept in the snake's
+/// [`Appearance`](constrictor_core::models::Appearance), so replays and
+/// spectators draw it the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Skin {
+    /// The theme's own glyphs.
+    #[default]
+    Classic,
+
+    /// A solid block for every segment.
+    Solid,
+
+    /// Solid at the head, fading into lighter shades towards the tail.
+    Gradient,
+
+    /// A snake emoji for the head and coloured squares for the body.
+    Emoji,
+
+    /// Stripes of every colour, which stay with each segment as the snake
+    /// moves.
+    Rainbow,
+}
+
+impl Skin {
+    /// Every skin, in the order of their identifiers.
+    pub const ALL: [Self; 5] = [
+        Self::Classic,
+        Self::Solid,
+        Self::Gradient,
+        Self::Emoji,
+        Self::Rainbow,
+    ];
+
+    /// Gets the identifier kept in an
+    /// [`Appearance`](constrictor_core::models::Appearance) for this skin.
+    pub const fn id(self) -> u16 {
+        self as u16
+    }
+
+    /// Gets the skin `id` identifies, or [`Skin::Classic`] for one this
+    /// release doesn't know.
+    pub fn from_id(id: u16) -> Self {
+        Self::ALL.get(usize::from(id)).copied().unwrap_or_default()
+    }
+
+    /// Styles `segment` of the snake at `snake`, which is `len` long and
+    /// drawn with `glyph` in `color` by the theme. Returns [`None`] where the
+    /// theme's glyph is left as it is.
+    fn style(
+        self,
+        segment: Segment,
+        len: usize,
+        snake: usize,
+        glyph: &str,
+        color: Color,
+    ) -> Option<(String, Color)> {
+        const SHADES: [&str; 3] = ["▓▓", "▒▒", "░░"];
+        const SQUARES: [&str; 5] = ["🟩", "🟪", "🟦", "🟨", "🟧"];
+        const RAINBOW: [Color; 6] = [
+            Color::Red,
+            Color::Yellow,
+            Color::Green,
+            Color::Cyan,
+            Color::Blue,
+            Color::Magenta,
+        ];
+
+        let head = segment.index == 0;
+        let styled = match self {
+            Self::Classic => return None,
+            Self::Solid => ("██".to_string(), color),
+            Self::Gradient if head => ("██".to_string(), color),
+            Self::Gradient => {
+                let shade = segment.index * SHADES.len() / len.max(1);
+                (SHADES[shade.min(SHADES.len() - 1)].to_string(), color)
+            }
+            Self::Emoji if head => ("🐍".to_string(), color),
+            Self::Emoji => (SQUARES[snake % SQUARES.len()].to_string(), color),
+            Self::Rainbow if head => return None,
+            Self::Rainbow => {
+                let stripe = segment.laid_at % RAINBOW.len() as u64;
+                (glyph.to_string(), RAINBOW[stripe as usize])
+            }
+        };
+
+        Some(styled)
+    }
+}
+
+impl Display for Skin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Classic => "Classic",
+            Self::Solid => "Solid",
+            Self::Gradient => "Gradient",
+            Self::Emoji => "Emoji",
+            Self::Rainbow => "Rainbow",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The glyph and colour of every snake segment that its [`Skin`] draws
+/// differently from the theme, and of any cell recoloured on top, by where
+/// it is on the board.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentStyles(HashMap<Vector2, (String, Color)>);
+
+impl SegmentStyles {
+    /// Styles the segments of the snakes in `view` as their skins draw them
+    /// over `theme`, wherever `frame` shows them. A theme drawn only in ASCII
+    /// keeps its own glyphs, and takes just the colours.
+    pub fn new(view: &SimulationView, frame: &FrameBuffer, theme: &Theme) -> Self {
+        let ascii = theme.is_ascii();
+        let mut styles = HashMap::new();
+
+        for (index, snake) in view.snakes().enumerate() {
+            let skin = Skin::from_id(snake.appearance().skin);
+            if skin == Skin::Classic {
+                continue;
+            }
+
+            for segment in snake.segments() {
+                // Something else may be drawn over the segment, like another
+                // snake's head, or fog
+                let shown = match frame.get(&segment.position) {
+                    Some(cell @ Cell::SnakeHead { snake, .. }) if snake == index => cell,
+                    Some(cell @ Cell::SnakeBody { snake }) if snake == index => cell,
+                    _ => continue,
+                };
+                let Some((glyph, color)) = theme.glyph(shown) else {
+                    continue;
+                };
+                let Some((mut styled, color)) =
+                    skin.style(segment, snake.len(), index, glyph, color)
+                else {
+                    continue;
+                };
+
+                if ascii && !styled.is_ascii() {
+                    styled = glyph.to_string();
+                }
+
+                // Where the snake crosses itself, the segment nearest the
+                // head is the one on top
+                styles.entry(segment.position).or_insert((styled, color));
+            }
+        }
+
+        Self(styles)
+    }
+
+    /// Draws the cells of `frame` at the points in `colors` in those colours
+    /// instead, keeping their glyphs. Empty cells are left empty.
+    pub fn recolor(
+        &mut self,
+        colors: &HashMap<Vector2, Color>,
+        frame: &FrameBuffer,
+        theme: &Theme,
+    ) {
+        for (point, color) in colors {
+            let Some(cell) = frame.get(point) else {
+                continue;
+            };
+
+            if let Some((glyph, _)) = self.glyph(point, cell, theme) {
+                let glyph = glyph.to_string();
+                self.0.insert(*point, (glyph, *color));
+            }
+        }
+    }
+
+    /// Gets the glyph and colour to draw `cell` at `point` with, or [`None`]
+    /// if it isn't drawn, as [`Theme::glyph`] does.
+    pub fn glyph<'a>(
+        &'a self,
+        point: &Vector2,
+        cell: Cell,
+        theme: &'a Theme,
+    ) -> Option<(&'a str, Color)> {
+        match self.0.get(point) {
+            Some((glyph, color)) => Some((glyph, *color)),
+            None => theme.glyph(cell),
+        }
+    }
+
+    /// Returns an [`Iterator`] over the points styled differently to how
+    /// they were in `previous`, which need drawing again even where the cell
+    /// there is unchanged.
+    pub fn changes_since<'a>(&'a self, previous: &'a Self) -> impl Iterator<Item = Vector2> + 'a {
+        let restyled = self
+            .0
+            .iter()
+            .filter(|(point, style)| previous.0.get(point) != Some(style))
+            .map(|(point, _)| *point);
+        let unstyled = previous
+            .0
+            .keys()
+            .filter(|point| !self.0.contains_key(point))
+            .copied();
+
+        restyled.chain(unstyled)
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    error::Error,
+    io::{Write, stdout},
+    time::{Duration, Instant},
+};
+
+use constrictor_core::{
+    bots::Controller,
+    config::GameConfig,
+    models::{Appearance, SimulationResult, SnakeSimulation},
+};
+use crossterm::{cursor, event::Event, execute, terminal};
+
+use crate::{
+    args::SpectateArgs,
+    io::{EventStream, GameCommand, KeyMap},
+    rendering::{FrameRenderer, Hud, RenderMode, render_banner},
+    scope_guard::ScopeGuard,
+    theme::Theme,
+};
+
+/// How long the result of a match stays up before the next one starts.
+const MATCH_BREAK: Duration = Duration::from_secs(3);
+
+/// Pits two built-in bots against each other on one board, and draws the
+/// match for someone to watch. The usual keys pause, change the speed, hide
+/// the status line or skip to the next match. With `--loop`, matches carry on
+/// with fresh seeds until the viewer quits.
+pub fn spectate(args: SpectateArgs, theme: Theme) -> Result<(), Box<dyn Error>> {
+    let mut config = args.game.apply(GameConfig::default());
+
+    let _restore_terminal = ScopeGuard::new(|| {
+        _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        _ = terminal::disable_raw_mode();
+    });
+
+    let mut stdout = stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut events = EventStream::new()?;
+    let keys = KeyMap::default();
+    let mut hud = Hud::new();
+
+    loop {
+        let seed = *config.seed.get_or_insert_with(rand::random);
+        let mut bots = [
+            args.bot.build(seed),
+            args.opponent.build(seed.wrapping_add(1)),
+        ];
+
+        config.player.name = Some(bots[0].name().to_string());
+        config.opponents = vec![Appearance {
+            name: Some(bots[1].name().to_string()),
+            color: 1,
+            ..Default::default()
+        }];
+
+        let mut sim = config.build()?;
+        let mut renderer = FrameRenderer::new(RenderMode::Diff, theme.clone());
+        hud.reset();
+
+        let mut next_step = Instant::now() + sim.tick_interval();
+        while sim.result().is_none() {
+            if Instant::now() >= next_step {
+                if !sim.is_paused() {
+                    hud.elapse(sim.tick_interval());
+                }
+
+                for (snake, bot) in bots.iter_mut().enumerate() {
+                    bot.steer(&mut sim, snake);
+                }
+
+                sim.advance();
+                next_step = Instant::now() + sim.tick_interval();
+            }
+
+            renderer.draw(&sim.view(), &mut stdout)?;
+            hud.draw(&sim.view(), sim.tick_interval(), &renderer, &mut stdout)?;
+            stdout.flush()?;
+
+            match next_command(&mut events, &keys, next_step)? {
+                Some(GameCommand::Quit) => return Ok(()),
+                Some(GameCommand::Restart) => break,
+                Some(GameCommand::Pause) => sim.set_paused(!sim.is_paused()),
+                Some(GameCommand::SpeedUp) => sim.set_speed(sim.speed().scaled(80)),
+                Some(GameCommand::SpeedDown) => sim.set_speed(sim.speed().scaled(125)),
+                Some(GameCommand::ToggleHud) => hud.visible = !hud.visible,
+                _ => {}
+            }
+        }
+
+        // A skipped match goes straight on to the next one. Otherwise, without
+        // `--loop`, the result stays up until the viewer quits or skips on.
+        if sim.result().is_some() {
+            renderer.draw(&sim.view(), &mut stdout)?;
+            hud.draw(&sim.view(), sim.tick_interval(), &renderer, &mut stdout)?;
+            render_banner(&outcome(&sim, &bots), sim.board(), &renderer, &mut stdout)?;
+            stdout.flush()?;
+
+            loop {
+                match next_command(&mut events, &keys, Instant::now() + MATCH_BREAK)? {
+                    Some(GameCommand::Quit) => return Ok(()),
+                    Some(GameCommand::Restart) => break,
+                    None if args.repeat => break,
+                    _ => {}
+                }
+            }
+        }
+
+        // Keep whatever speed the viewer settled on for the next match
+        config.speed = *sim.speed();
+        config.seed = Some(rand::random());
+    }
+}
+
+/// Waits for the viewer to issue a command, giving up at `deadline`.
+fn next_command(
+    events: &mut EventStream,
+    keys: &KeyMap,
+    deadline: Instant,
+) -> Result<Option<GameCommand>, Box<dyn Error>> {
+    while let Some(timeout) = deadline.checked_duration_since(Instant::now())
+        && events.wait(timeout)
+    {
+        for event in events.by_ref() {
+            if let Event::Key(key) = event?
+                && let Some(command) = keys.command(key)
+            {
+                return Ok(Some(command));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Describes how a finished match between `bots` ended, naming the winner if
+/// there was one.
+fn outcome(sim: &SnakeSimulation, bots: &[Box<dyn Controller>]) -> String {
+    let winner =
+        (0..bots.len()).find(|&snake| sim.result_for(snake) == Some(&SimulationResult::Won));
+
+    match winner {
+        Some(snake) => format!(" {} wins! ", bots[snake].name()),
+        None => " Draw ".to_string(),
+    }
+}
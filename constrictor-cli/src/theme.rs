@@ -0,0 +1,707 @@
+use std::env;
+
+use clap::ValueEnum;
+use constrictor_core::{
+    math::Vector2,
+    models::{PowerUp, Terrain},
+    render::Cell,
+};
+use crossterm::style::Color;
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
+use toml::{Table, Value};
+
+/// The themes that come built in, to pick with `--theme` or from the
+/// settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// Block glyphs, with each kind of cell in its own colour.
+    #[default]
+    Classic,
+
+    /// The classic colours, but with plain ASCII characters for terminals
+    /// whose fonts lack box drawing and block glyphs.
+    Ascii,
+
+    /// Glyphs that differ by their characters alone, such as `@@` for the
+    /// player's head and `**` for food, with no colour at all.
+    Monochrome,
+
+    /// Shades of amber on black, like an old monitor.
+    Amber,
+
+    /// Blues, oranges and yellows that stay apart for players who can't
+    /// tell red from green, with a different shape for every kind of cell.
+    Deuteranopia,
+
+    /// Like [`ThemeName::Deuteranopia`], but with nothing in red, which
+    /// looks dark to players without red cones.
+    Protanopia,
+
+    /// Reds, teals and greys that stay apart for players who can't tell
+    /// blue from green or yellow from violet, with a different shape for
+    /// every kind of cell.
+    Tritanopia,
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        match self {
+            Self::Classic => Theme::default(),
+            Self::Ascii => Theme::ascii(),
+            Self::Monochrome => Theme::monochrome(),
+            Self::Amber => Theme::amber(),
+            Self::Deuteranopia => Theme::distinct(&Palette::DEUTERANOPIA),
+            Self::Protanopia => Theme::distinct(&Palette::PROTANOPIA),
+            Self::Tritanopia => Theme::distinct(&Palette::TRITANOPIA),
+        }
+    }
+}
+
+/// The colours of a theme that doesn't lean on colour alone, by what they
+/// mark. They come from the Okabe-Ito palette, picked so that each kind of
+/// colour blindness still sees them as different.
+struct Palette {
+    snake: Color,
+    opponents: [Color; 3],
+    food: Color,
+    danger: Color,
+    power_up: Color,
+    terrain: Color,
+    portal: Color,
+    good: Color,
+    bad: Color,
+}
+
+impl Palette {
+    const ORANGE: Color = Color::Rgb {
+        r: 230,
+        g: 159,
+        b: 0,
+    };
+    const SKY_BLUE: Color = Color::Rgb {
+        r: 86,
+        g: 180,
+        b: 233,
+    };
+    const BLUISH_GREEN: Color = Color::Rgb {
+        r: 0,
+        g: 158,
+        b: 115,
+    };
+    const YELLOW: Color = Color::Rgb {
+        r: 240,
+        g: 228,
+        b: 66,
+    };
+    const BLUE: Color = Color::Rgb {
+        r: 0,
+        g: 114,
+        b: 178,
+    };
+    const VERMILION: Color = Color::Rgb {
+        r: 213,
+        g: 94,
+        b: 0,
+    };
+    const REDDISH_PURPLE: Color = Color::Rgb {
+        r: 204,
+        g: 121,
+        b: 167,
+    };
+
+    const DEUTERANOPIA: Self = Self {
+        snake: Self::SKY_BLUE,
+        opponents: [Self::YELLOW, Self::REDDISH_PURPLE, Self::BLUE],
+        food: Self::ORANGE,
+        danger: Self::VERMILION,
+        power_up: Color::White,
+        terrain: Color::Grey,
+        portal: Self::BLUISH_GREEN,
+        good: Self::BLUE,
+        bad: Self::VERMILION,
+    };
+
+    const PROTANOPIA: Self = Self {
+        opponents: [Self::REDDISH_PURPLE, Self::BLUE, Self::BLUISH_GREEN],
+        food: Self::YELLOW,
+        danger: Self::ORANGE,
+        portal: Color::White,
+        bad: Self::ORANGE,
+        ..Self::DEUTERANOPIA
+    };
+
+    const TRITANOPIA: Self = Self {
+        snake: Self::BLUISH_GREEN,
+        opponents: [Self::ORANGE, Self::SKY_BLUE, Self::YELLOW],
+        food: Self::VERMILION,
+        danger: Self::REDDISH_PURPLE,
+        power_up: Color::White,
+        terrain: Color::Grey,
+        portal: Self::BLUE,
+        good: Self::BLUISH_GREEN,
+        bad: Self::VERMILION,
+    };
+}
+
+/// How one kind of cell looks: the two characters filling the two columns a
+/// cell takes up, and their colour.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Glyph {
+    #[serde(deserialize_with = "two_characters")]
+    pub text: String,
+    pub color: Color,
+}
+
+impl Glyph {
+    fn new(text: &str, color: Color) -> Self {
+        Self {
+            text: text.to_string(),
+            color,
+        }
+    }
+}
+
+/// The characters walls are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Border {
+    /// Double box drawing lines, like `╔══╗`.
+    #[default]
+    Double,
+
+    /// Single box drawing lines, like `┌──┐`.
+    Single,
+
+    /// Plain ASCII, like `+--+`.
+    Ascii,
+}
+
+/// The characters of a [`Border`], named for the wall they draw. A tee is
+/// named for the side its stem points to.
+pub struct BorderChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+    pub cross: char,
+    pub tee_left: char,
+    pub tee_right: char,
+    pub tee_up: char,
+    pub tee_down: char,
+}
+
+impl Border {
+    pub const fn chars(self) -> BorderChars {
+        match self {
+            Self::Double => BorderChars {
+                top_left: '╔',
+                top_right: '╗',
+                bottom_left: '╚',
+                bottom_right: '╝',
+                horizontal: '═',
+                vertical: '║',
+                cross: '╬',
+                tee_left: '╣',
+                tee_right: '╠',
+                tee_up: '╩',
+                tee_down: '╦',
+            },
+            Self::Single => BorderChars {
+                top_left: '┌',
+                top_right: '┐',
+                bottom_left: '└',
+                bottom_right: '┘',
+                horizontal: '─',
+                vertical: '│',
+                cross: '┼',
+                tee_left: '┤',
+                tee_right: '├',
+                tee_up: '┴',
+                tee_down: '┬',
+            },
+            Self::Ascii => BorderChars {
+                top_left: '+',
+                top_right: '+',
+                bottom_left: '+',
+                bottom_right: '+',
+                horizontal: '-',
+                vertical: '|',
+                cross: '+',
+                tee_left: '+',
+                tee_right: '+',
+                tee_up: '+',
+                tee_down: '+',
+            },
+        }
+    }
+}
+
+/// The background colours a bot's annotations tint cells with, and the
+/// colour of their labels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tints {
+    pub good: Color,
+    pub bad: Color,
+    pub info: Color,
+    pub label: Color,
+}
+
+impl Default for Tints {
+    fn default() -> Self {
+        Self {
+            good: Color::DarkGreen,
+            bad: Color::DarkRed,
+            info: Color::DarkBlue,
+            label: Color::White,
+        }
+    }
+}
+
+/// Everything about how a game looks on the terminal: the glyph for each
+/// kind of cell, the walls, and the colours of the board and status line.
+///
+/// The settings file picks a built-in theme by name, or changes some parts of
+/// one, named as `base`:
+///
+/// ```toml
+/// [theme]
+/// base = "ascii"
+/// border = "single"
+/// head = { text = "@>", color = "yellow" }
+/// opponent_colors = ["cyan", "#ff8800"]
+/// checker = "#1c1c1c"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// The player's snake.
+    pub head: Glyph,
+    pub body: Glyph,
+
+    /// The other snakes, each in the next of `opponent_colors`, starting over
+    /// when they run out.
+    #[serde(deserialize_with = "two_characters")]
+    pub opponent_head: String,
+    #[serde(deserialize_with = "two_characters")]
+    pub opponent_body: String,
+    #[serde(deserialize_with = "not_empty")]
+    pub opponent_colors: Vec<Color>,
+
+    pub food: Glyph,
+    pub practice_food: Glyph,
+    pub obstacle: Glyph,
+    pub shield: Glyph,
+    pub mirror: Glyph,
+    pub hazard: Glyph,
+    pub sticky: Glyph,
+    pub boost: Glyph,
+    pub fog: Glyph,
+
+    /// The ends of each pair of portals, in the same way as
+    /// `opponent_colors`.
+    #[serde(deserialize_with = "not_empty")]
+    pub portals: Vec<Glyph>,
+
+    pub border: Border,
+    pub walls: Color,
+
+    /// Behind the board. [`Color::Reset`] leaves the terminal's own.
+    pub background: Color,
+
+    /// Behind every other cell of the board, in a checkerboard with
+    /// `background`, or [`None`] for the same background everywhere.
+    pub checker: Option<Color>,
+
+    /// The status line under the board.
+    pub hud: Color,
+
+    pub tints: Tints,
+}
+
+impl Default for Theme {
+    /// The [`ThemeName::Classic`] theme.
+    fn default() -> Self {
+        Self {
+            head: Glyph::new("██", Color::Green),
+            body: Glyph::new("░░", Color::Green),
+            opponent_head: "██".to_string(),
+            opponent_body: "░░".to_string(),
+            opponent_colors: vec![Color::Magenta, Color::Cyan, Color::Yellow, Color::Blue],
+            food: Glyph::new("╺╸", Color::Red),
+            practice_food: Glyph::new("╺╸", Color::Grey),
+            obstacle: Glyph::new("▓▓", Color::DarkGrey),
+            shield: Glyph::new("<>", Color::White),
+            mirror: Glyph::new("><", Color::DarkMagenta),
+            hazard: Glyph::new("▒▒", Color::DarkRed),
+            sticky: Glyph::new("::", Color::DarkYellow),
+            boost: Glyph::new("»»", Color::DarkCyan),
+            fog: Glyph::new("··", Color::DarkGrey),
+            portals: vec![
+                Glyph::new("()", Color::Cyan),
+                Glyph::new("()", Color::Yellow),
+                Glyph::new("()", Color::Magenta),
+            ],
+            border: Border::Double,
+            walls: Color::DarkGrey,
+            background: Color::Reset,
+            checker: None,
+            hud: Color::Reset,
+            tints: Tints::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// The [`ThemeName::Monochrome`] theme.
+    fn monochrome() -> Self {
+        let plain = |text| Glyph::new(text, Color::Reset);
+
+        Self {
+            head: plain("@@"),
+            body: plain("oo"),
+            opponent_head: "&&".to_string(),
+            opponent_body: "xx".to_string(),
+            opponent_colors: vec![Color::Reset],
+            food: plain("**"),
+            practice_food: plain("++"),
+            obstacle: plain("##"),
+            shield: plain("<>"),
+            mirror: plain("><"),
+            hazard: plain("~~"),
+            sticky: plain("::"),
+            boost: plain(">>"),
+            fog: plain(".."),
+            portals: vec![plain("()"), plain("[]"), plain("{}")],
+            ..Self::default()
+        }
+        .without_color()
+    }
+
+    /// The [`ThemeName::Amber`] theme.
+    fn amber() -> Self {
+        let classic = Self::default();
+        let bright = |glyph: Glyph| Glyph::new(&glyph.text, Color::Yellow);
+        let dim = |glyph: Glyph| Glyph::new(&glyph.text, Color::DarkYellow);
+
+        Self {
+            head: bright(classic.head),
+            body: dim(classic.body),
+            opponent_colors: vec![Color::DarkYellow],
+            food: bright(classic.food),
+            practice_food: dim(classic.practice_food),
+            obstacle: dim(classic.obstacle),
+            shield: bright(classic.shield),
+            mirror: bright(classic.mirror),
+            hazard: bright(classic.hazard),
+            sticky: dim(classic.sticky),
+            boost: dim(classic.boost),
+            fog: dim(classic.fog),
+            portals: classic.portals.into_iter().map(bright).collect(),
+            border: Border::Single,
+            walls: Color::DarkYellow,
+            background: Color::Black,
+            hud: Color::Yellow,
+            tints: Tints {
+                label: Color::Black,
+                ..Tints::default()
+            },
+            ..classic
+        }
+    }
+
+    /// A theme in the colours of `palette` where every kind of cell also has
+    /// a shape of its own, so that nothing is told apart by colour alone:
+    /// opponents, practice food, hazards and each pair of portals all look
+    /// different from what they share a colour with in the classic theme.
+    fn distinct(palette: &Palette) -> Self {
+        Self {
+            head: Glyph::new("██", palette.snake),
+            body: Glyph::new("░░", palette.snake),
+            opponent_head: "▐▌".to_string(),
+            opponent_body: "╍╍".to_string(),
+            opponent_colors: palette.opponents.to_vec(),
+            food: Glyph::new("╺╸", palette.food),
+            practice_food: Glyph::new("╶╴", Color::Grey),
+            hazard: Glyph::new("╳╳", palette.danger),
+            shield: Glyph::new("<>", palette.power_up),
+            mirror: Glyph::new("><", palette.power_up),
+            sticky: Glyph::new("::", palette.terrain),
+            boost: Glyph::new("»»", palette.terrain),
+            portals: ["()", "[]", "{}"]
+                .into_iter()
+                .map(|text| Glyph::new(text, palette.portal))
+                .collect(),
+            tints: Tints {
+                good: palette.good,
+                bad: palette.bad,
+                info: Color::DarkGrey,
+                ..Tints::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    /// The [`ThemeName::Ascii`] theme.
+    fn ascii() -> Self {
+        let mut theme = Self::default();
+        let mut plain = Self::monochrome();
+
+        for (glyph, text) in theme.glyphs_mut().zip(plain.glyphs_mut()) {
+            glyph.text = text.text.clone();
+        }
+
+        Self {
+            opponent_head: plain.opponent_head,
+            opponent_body: plain.opponent_body,
+            border: Border::Ascii,
+            ..theme
+        }
+    }
+
+    /// Every glyph that has a colour of its own.
+    fn glyphs_mut(&mut self) -> impl Iterator<Item = &mut Glyph> {
+        [
+            &mut self.head,
+            &mut self.body,
+            &mut self.food,
+            &mut self.practice_food,
+            &mut self.obstacle,
+            &mut self.shield,
+            &mut self.mirror,
+            &mut self.hazard,
+            &mut self.sticky,
+            &mut self.boost,
+            &mut self.fog,
+        ]
+        .into_iter()
+        .chain(&mut self.portals)
+    }
+
+    /// Swaps every glyph that isn't plain ASCII for the one in its place in
+    /// the monochrome theme, and the walls for `+-|`, keeping the colours,
+    /// for terminals that can't draw anything else.
+    pub fn ascii_only(mut self) -> Self {
+        let mut plain = Self::monochrome();
+        plain.portals = plain
+            .portals
+            .iter()
+            .cycle()
+            .take(self.portals.len())
+            .cloned()
+            .collect();
+
+        for (glyph, fallback) in self.glyphs_mut().zip(plain.glyphs_mut()) {
+            if !glyph.text.is_ascii() {
+                glyph.text = fallback.text.clone();
+            }
+        }
+
+        let ascii = |text: String, fallback: String| if text.is_ascii() { text } else { fallback };
+        Self {
+            opponent_head: ascii(self.opponent_head, plain.opponent_head),
+            opponent_body: ascii(self.opponent_body, plain.opponent_body),
+            border: Border::Ascii,
+            ..self
+        }
+    }
+
+    /// Draws everything in the terminal's own colours, keeping the
+    /// characters.
+    pub fn without_color(mut self) -> Self {
+        for glyph in self.glyphs_mut() {
+            glyph.color = Color::Reset;
+        }
+
+        Self {
+            opponent_colors: vec![Color::Reset],
+            walls: Color::Reset,
+            background: Color::Reset,
+            checker: None,
+            hud: Color::Reset,
+            tints: Tints {
+                good: Color::Reset,
+                bad: Color::Reset,
+                info: Color::Reset,
+                label: Color::Reset,
+            },
+            ..self
+        }
+    }
+
+    /// Shades every other cell of the board, for `--checkerboard`, in a tone
+    /// just off the background unless the theme already picks one.
+    pub fn with_checkerboard(self) -> Self {
+        // Nudged towards the middle, so it shows on light backgrounds too
+        let nudge = |channel: u8| {
+            if channel < 128 {
+                channel.saturating_add(18)
+            } else {
+                channel - 18
+            }
+        };
+        let checker = match self.background {
+            Color::Rgb { r, g, b } => Color::Rgb {
+                r: nudge(r),
+                g: nudge(g),
+                b: nudge(b),
+            },
+            _ => Color::AnsiValue(235),
+        };
+
+        Self {
+            checker: self.checker.or(Some(checker)),
+            ..self
+        }
+    }
+
+    /// Gets the colour behind the cell at `point`, which alternates with
+    /// [`Self::checker`] if there is one.
+    pub fn background_at(&self, point: &Vector2) -> Color {
+        match self.checker {
+            Some(checker) if (point.x + point.y).rem_euclid(2) == 1 => checker,
+            _ => self.background,
+        }
+    }
+
+    /// Whether everything is drawn in plain ASCII, as after
+    /// [`Self::ascii_only`].
+    pub fn is_ascii(&self) -> bool {
+        let mut theme = self.clone();
+        let snakes = [&theme.opponent_head, &theme.opponent_body];
+
+        theme.border == Border::Ascii
+            && snakes.iter().all(|text| text.is_ascii())
+            && theme.glyphs_mut().all(|glyph| glyph.text.is_ascii())
+    }
+
+    /// Whether anything is drawn in a colour of its own, rather than the
+    /// terminal's.
+    pub fn has_color(&self) -> bool {
+        *self != self.clone().without_color()
+    }
+
+    /// How a [`Cell`] looks, or [`None`] if it is left blank.
+    pub fn glyph(&self, cell: Cell) -> Option<(&str, Color)> {
+        let glyph = match cell {
+            Cell::Empty => return None,
+            Cell::Food => &self.food,
+            Cell::PracticeFood => &self.practice_food,
+            Cell::Obstacle => &self.obstacle,
+            Cell::PowerUp(PowerUp::Shield) => &self.shield,
+            Cell::PowerUp(PowerUp::Mirror { .. }) => &self.mirror,
+            Cell::Hazard => &self.hazard,
+            Cell::Terrain(Terrain::Sticky) => &self.sticky,
+            Cell::Terrain(Terrain::Boost) => &self.boost,
+            Cell::Hidden => &self.fog,
+            Cell::Portal { pair } => &self.portals[pair % self.portals.len()],
+            Cell::SnakeHead { snake: 0, .. } => &self.head,
+            Cell::SnakeBody { snake: 0 } => &self.body,
+            Cell::SnakeHead { snake, .. } => {
+                return Some((&self.opponent_head, self.opponent_color(snake)));
+            }
+            Cell::SnakeBody { snake } => {
+                return Some((&self.opponent_body, self.opponent_color(snake)));
+            }
+        };
+
+        Some((&glyph.text, glyph.color))
+    }
+
+    /// The colour of the snake at `index`, counting the player's as 0.
+    fn opponent_color(&self, index: usize) -> Color {
+        self.opponent_colors[(index - 1) % self.opponent_colors.len()]
+    }
+}
+
+/// Whether to draw without colour: when `no_color` is set, or the `NO_COLOR`
+/// environment variable is set to anything but an empty string, as
+/// <https://no-color.org> asks.
+pub fn color_disabled(no_color: bool) -> bool {
+    no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Whether to draw in plain ASCII: when `ascii` is set, or the terminal
+/// looks unable to show anything else. That is a guess, made from a `TERM`
+/// that predates Unicode, or a locale that doesn't use UTF-8. A terminal
+/// with no locale set at all is taken to manage, as most do.
+pub fn ascii_only(ascii: bool) -> bool {
+    const ASCII_TERMINALS: [&str; 6] = ["dumb", "vt52", "vt100", "vt102", "vt220", "ansi"];
+
+    let term = env::var("TERM").unwrap_or_default();
+    if ascii || ASCII_TERMINALS.contains(&term.as_str()) {
+        return true;
+    }
+
+    // Windows consoles take Unicode whatever the locale
+    if cfg!(windows) {
+        return false;
+    }
+
+    // The first of these that is set decides the character set
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+
+    locale.is_some_and(|locale| {
+        let locale = locale.to_ascii_lowercase();
+        !(locale.contains("utf-8") || locale.contains("utf8"))
+    })
+}
+
+/// Reads the `theme` from the settings file: either the name of a built-in
+/// theme, or a table of changes to the one named as its `base`, which
+/// defaults to [`ThemeName::Classic`].
+pub fn deserialize_setting<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Theme>, D::Error> {
+    let mut changes = match Value::deserialize(deserializer)? {
+        Value::Table(table) => table,
+        name => {
+            let name = ThemeName::deserialize(name).map_err(D::Error::custom)?;
+            return Ok(Some(name.theme()));
+        }
+    };
+
+    let base = match changes.remove("base") {
+        Some(name) => ThemeName::deserialize(name).map_err(D::Error::custom)?,
+        None => ThemeName::default(),
+    };
+
+    let mut theme = Table::try_from(base.theme()).map_err(D::Error::custom)?;
+    theme.extend(changes);
+
+    Theme::deserialize(Value::Table(theme))
+        .map(Some)
+        .map_err(D::Error::custom)
+}
+
+/// Rejects glyphs that wouldn't fill the two columns each cell takes up,
+/// which would throw the rest of the row out of line.
+fn two_characters<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    if text.chars().count() != 2 {
+        return Err(D::Error::custom(format!(
+            "glyph {text:?} must be exactly two characters"
+        )));
+    }
+
+    Ok(text)
+}
+
+/// Rejects empty lists of colours or glyphs to take turns with.
+fn not_empty<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Vec<T>, D::Error> {
+    let items = Vec::deserialize(deserializer)?;
+    if items.is_empty() {
+        return Err(D::Error::custom("needs at least one entry"));
+    }
+
+    Ok(items)
+}
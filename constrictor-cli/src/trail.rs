@@ -0,0 +1,114 @@
+use std::{error::Error, fmt::Write, fs};
+
+use constrictor_core::{math::Vector2, models::Board, replay::Replay};
+
+use crate::args::TrailArgs;
+
+const BACKGROUND: &str = "#1b1b1b";
+const WALL: &str = "#555555";
+
+/// Plays back a replay and writes the path its snake's head traced as an SVG
+/// image, shaded from blue at the start of the game to red at the end.
+pub fn export_trail(args: TrailArgs) -> Result<(), Box<dyn Error>> {
+    let replay: Replay = serde_json::from_str(&fs::read_to_string(&args.replay)?)?;
+    let mut playback = replay.play()?;
+
+    let mut path = vec![*playback.simulation().snake().head()];
+    while playback.step() {
+        let head = *playback.simulation().snake().head();
+
+        // Dying doesn't move the snake, so the last step may repeat a cell
+        if path.last() != Some(&head) {
+            path.push(head);
+        }
+    }
+
+    let svg = render_svg(playback.simulation().board(), &path, args.cell_size)?;
+    let output = args
+        .output
+        .unwrap_or_else(|| args.replay.with_extension("svg"));
+    fs::write(&output, svg)?;
+
+    println!("Wrote {} steps to {}", path.len() - 1, output.display());
+    Ok(())
+}
+
+/// Draws `path` over `board`, leaving a one-cell margin for the wall.
+fn render_svg(board: &Board, path: &[Vector2], cell_size: u32) -> Result<String, std::fmt::Error> {
+    let cell = f64::from(cell_size);
+    let width = f64::from(board.width() + 2) * cell;
+    let height = f64::from(board.height() + 2) * cell;
+
+    // Centre of a board cell, in image coordinates
+    let center = |point: &Vector2| {
+        (
+            (f64::from(point.x - board.x_range().start) + 1.5) * cell,
+            (f64::from(point.y - board.y_range().start) + 1.5) * cell,
+        )
+    };
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )?;
+    writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{BACKGROUND}"/>"#
+    )?;
+    writeln!(
+        svg,
+        r#"<rect x="{half}" y="{half}" width="{w}" height="{h}" fill="none" stroke="{WALL}" stroke-width="{cell}"/>"#,
+        half = cell / 2.0,
+        w = width - cell,
+        h = height - cell,
+    )?;
+
+    // Cells a mask leaves unplayable are part of the wall
+    for y in board.y_range() {
+        for x in board.x_range() {
+            let point = Vector2 { x, y };
+            if !board.contains(&point) {
+                let (x, y) = center(&point);
+                writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{cell}" height="{cell}" fill="{WALL}"/>"#,
+                    x = x - cell / 2.0,
+                    y = y - cell / 2.0,
+                )?;
+            }
+        }
+    }
+
+    let steps = path.len().saturating_sub(1).max(1) as f64;
+    for (index, pair) in path.windows(2).enumerate() {
+        let (from, to) = (&pair[0], &pair[1]);
+
+        // Wrapping, doorways and portals move the head across the board in one step;
+        // leave a gap rather than drawing a line through everything.
+        if from.manhattan_distance(*to) != 1 {
+            continue;
+        }
+
+        let hue = 240.0 * (1.0 - index as f64 / steps);
+        let ((x1, y1), (x2, y2)) = (center(from), center(to));
+
+        writeln!(
+            svg,
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="hsl({hue:.1},80%,55%)" stroke-width="{w}" stroke-linecap="round"/>"#,
+            w = cell * 0.6,
+        )?;
+    }
+
+    if let Some(head) = path.last() {
+        let (x, y) = center(head);
+        writeln!(
+            svg,
+            r#"<circle cx="{x}" cy="{y}" r="{r}" fill="white"/>"#,
+            r = cell * 0.4
+        )?;
+    }
+
+    writeln!(svg, "</svg>")?;
+    Ok(svg)
+}
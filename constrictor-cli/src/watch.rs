@@ -0,0 +1,75 @@
+use std::{
+    error::Error,
+    io::{Write, stdout},
+    time::Instant,
+};
+
+use constrictor_core::config::GameConfig;
+use crossterm::{cursor, event::Event, execute, terminal};
+
+use crate::{
+    args::WatchArgs,
+    io::{EventStream, GameCommand, KeyMap},
+    rendering::{FrameRenderer, RenderMode, render_annotations},
+    scope_guard::ScopeGuard,
+    theme::Theme,
+};
+
+/// Plays a game with a built-in bot at normal speed, drawing what the bot
+/// [explains](constrictor_core::bots::Controller::explain) about each step over the board. Quits
+/// early with the usual quit key.
+pub fn watch(args: WatchArgs, theme: Theme) -> Result<(), Box<dyn Error>> {
+    let config = args.game.apply(GameConfig::default());
+    let mut sim = config.build()?;
+
+    let mut bot = args.bot.build(config.seed.unwrap_or_else(rand::random));
+
+    let _restore_terminal = ScopeGuard::new(|| {
+        _ = execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        _ = terminal::disable_raw_mode();
+    });
+
+    let mut stdout = stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut events = EventStream::new()?;
+    let keys = KeyMap::default();
+
+    // Annotations sit on top of cells that may not change between frames,
+    // so every frame is drawn from scratch
+    let mut renderer = FrameRenderer::new(RenderMode::Full, theme);
+
+    while sim.result().is_none() {
+        let view = sim.view();
+        renderer.draw(&view, &mut stdout)?;
+        render_annotations(
+            &bot.explain(&view),
+            &view.frame(),
+            view.board(),
+            renderer.theme(),
+            &mut stdout,
+        )?;
+        stdout.flush()?;
+
+        bot.steer(&mut sim, 0);
+
+        sim.advance();
+
+        // Wait for the next step, but quit as soon as the player asks to
+        let next_step = Instant::now() + sim.tick_interval();
+        while let Some(timeout) = next_step.checked_duration_since(Instant::now())
+            && events.wait(timeout)
+        {
+            for event in events.by_ref() {
+                if let Event::Key(key) = event?
+                    && keys.command(key) == Some(GameCommand::Quit)
+                {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    error::Error,
+    fmt::Display,
+};
+
+use crate::{
+    math::{Direction, Vector2},
+    models::{Board, BoardStep, Occupancy, SnakeSimulation},
+};
+
+/// Thresholds a level must meet to pass [`check_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LevelRequirements {
+    /// The fewest cells that must be free of walls and obstacles.
+    pub min_free_cells: usize,
+}
+
+impl Default for LevelRequirements {
+    fn default() -> Self {
+        Self { min_free_cells: 16 }
+    }
+}
+
+/// Describes something that makes a level unplayable or unfair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelIssue {
+    /// Some free cells can never be reached from where the snake starts.
+    Unreachable { cells: usize },
+
+    /// Doing nothing kills the snake on the very first step.
+    FatalSpawn,
+
+    /// There are fewer free cells than [`LevelRequirements::min_free_cells`].
+    TooSmall { free: usize, required: usize },
+}
+
+impl Error for LevelIssue {}
+impl Display for LevelIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unreachable { cells } => {
+                write!(f, "{cells} free cells cannot be reached from the spawn")
+            }
+            Self::FatalSpawn => f.write_str("the snake dies on its first step unless it turns"),
+            Self::TooSmall { free, required } => {
+                write!(f, "only {free} free cells, at least {required} are needed")
+            }
+        }
+    }
+}
+
+/// Finds every cell of `board` the snake could reach from `from` without
+/// passing through a cell that is `blocked`, following wrapping edges,
+/// portals and doorways that lead back onto the same board. `from` itself is always
+/// included.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+///
+/// use constrictor_core::analysis::reachable_cells;
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::models::Board;
+///
+/// // A wall down the middle of a 5x3 board cuts it in two
+/// let board = Board::new((0, 5), (0, 3));
+/// let wall: HashSet<_> = (0..3).map(|y| Vector2 { x: 2, y }).collect();
+///
+/// let left = reachable_cells(&board, Vector2 { x: 0, y: 0 }, &wall);
+/// assert_eq!(left.len(), 6);
+/// assert!(!left.contains(&Vector2 { x: 4, y: 0 }));
+/// ```
+pub fn reachable_cells<O: Occupancy>(
+    board: &Board,
+    from: Vector2,
+    blocked: &O,
+) -> HashSet<Vector2> {
+    let mut seen = HashSet::from([from]);
+    let mut frontier = VecDeque::from([from]);
+
+    while let Some(cell) = frontier.pop_front() {
+        for direction in Direction::ALL {
+            let BoardStep::To(next) = board.step(&cell, direction) else {
+                continue;
+            };
+
+            if !blocked.is_occupied(&next) && seen.insert(next) {
+                frontier.push_back(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Checks that the level a [`SnakeSimulation`] starts on is playable: every
+/// free cell is reachable from the spawn, the snake survives its first step
+/// without input, and there is enough room to play in. Returns every issue
+/// found.
+///
+/// # Example
+/// ```
+/// use constrictor_core::analysis::{LevelIssue, LevelRequirements, check_level};
+/// use constrictor_core::config::{BoardSize, GameConfig};
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let sim = config.build().unwrap();
+/// assert_eq!(check_level(&sim, &LevelRequirements::default()), Ok(()));
+///
+/// let cramped = GameConfig {
+///     board: BoardSize { width: 7, height: 1 },
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     check_level(&cramped.build().unwrap(), &LevelRequirements::default()),
+///     Err(vec![LevelIssue::TooSmall { free: 7, required: 16 }])
+/// );
+/// ```
+pub fn check_level(
+    sim: &SnakeSimulation,
+    requirements: &LevelRequirements,
+) -> Result<(), Vec<LevelIssue>> {
+    let mut issues = Vec::new();
+
+    let obstacles = sim.obstacles();
+    let free = sim.board().free_cells().excluding(obstacles).count();
+
+    // The snake's own body is not in the way for long, so it counts as floor
+    let reachable = reachable_cells(sim.board(), *sim.snake().head(), obstacles).len();
+    if reachable < free {
+        issues.push(LevelIssue::Unreachable {
+            cells: free - reachable,
+        });
+    }
+
+    if sim.is_fatal_move(sim.snake().facing()) {
+        issues.push(LevelIssue::FatalSpawn);
+    }
+
+    if free < requirements.min_free_cells {
+        issues.push(LevelIssue::TooSmall {
+            free,
+            required: requirements.min_free_cells,
+        });
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::{
+    bots::{
+        Controller,
+        controller::{blocked_cells, safe_directions},
+    },
+    math::{Direction, Vector2},
+    models::{BoardStep, SimulationView},
+    render::{Annotation, Annotations, Tint},
+};
+
+/// A [`Controller`] that follows the shortest path to the food, found with A*
+/// search, treating every snake and obstacle as a wall. When no food can be
+/// reached it takes any safe step, preferring to keep going straight.
+///
+/// # Example
+/// ```
+/// use constrictor_core::bots::{AStarBot, Controller};
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+///
+/// let board = Board::new((0, 5), (0, 5));
+/// let snake = Snake::new(Vector2 { x: 0, y: 4 }, Direction::Right);
+/// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 0, y: 0 }, 0).unwrap();
+///
+/// assert_eq!(AStarBot.next_direction(&sim.view()), Some(Direction::Up));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AStarBot;
+
+impl Controller for AStarBot {
+    fn name(&self) -> &str {
+        "a-star"
+    }
+
+    fn next_direction(&mut self, view: &SimulationView) -> Option<Direction> {
+        if let Some(&(direction, _)) = shortest_path(view).first() {
+            return Some(direction);
+        }
+
+        let facing = view.player().facing();
+        safe_directions(view).min_by_key(|direction| *direction != facing)
+    }
+
+    /// Highlights the path it is following, if it found one.
+    fn explain(&self, view: &SimulationView) -> Annotations {
+        shortest_path(view)
+            .into_iter()
+            .map(|(_, cell)| (cell, Annotation::tint(Tint::Info)))
+            .collect()
+    }
+}
+
+/// Finds the shortest path from the player's head to the nearest food, as
+/// the direction of each step and the cell it leads to. Empty if there is no
+/// such path.
+fn shortest_path(view: &SimulationView) -> Vec<(Direction, Vector2)> {
+    let board = view.board();
+    let head = *view.player().head();
+    let blocked = blocked_cells(view);
+    let food: Vec<Vector2> = view.food().copied().collect();
+
+    // Distances as the crow flies are only a lower bound without doorways,
+    // which can be shortcuts. Without one, this is a plain breadth-first search.
+    let estimate = |cell: Vector2| match board.doorways() {
+        [] => food
+            .iter()
+            .map(|food| cell.manhattan_distance(*food))
+            .min()
+            .unwrap_or(0),
+        _ => 0,
+    };
+
+    let mut came_from: HashMap<Vector2, (Direction, Vector2)> = HashMap::new();
+    let mut cost = HashMap::from([(head, 0)]);
+    let mut open = BinaryHeap::from([Reverse((estimate(head), head.y, head.x))]);
+
+    while let Some(Reverse((_, y, x))) = open.pop() {
+        let cell = Vector2 { x, y };
+        if food.contains(&cell) {
+            let mut path = vec![];
+            let mut at = cell;
+            while let Some(&(direction, from)) = came_from.get(&at) {
+                path.push((direction, at));
+                at = from;
+            }
+
+            path.reverse();
+            return path;
+        }
+
+        // The first step is limited to where the snake can actually turn
+        let directions: Vec<Direction> = if cell == head {
+            safe_directions(view).collect()
+        } else {
+            Direction::ALL.to_vec()
+        };
+
+        for direction in directions {
+            let BoardStep::To(next) = board.step(&cell, direction) else {
+                continue;
+            };
+
+            let next_cost = cost[&cell] + 1;
+            let better = cost.get(&next).is_none_or(|known| next_cost < *known);
+            if (cell == head || !blocked.contains(&next)) && better {
+                cost.insert(next, next_cost);
+                came_from.insert(next, (direction, cell));
+                open.push(Reverse((next_cost + estimate(next), next.y, next.x)));
+            }
+        }
+    }
+
+    Vec::new()
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use crate::{
+    math::{Direction, Vector2},
+    models::{SimulationView, SnakeSimulation},
+    render::Annotations,
+};
+
+/// Something that steers a snake in a [`SnakeSimulation`], such as a bot.
+/// It always sees the game as the player of the snake it steers, so the same
+/// [`Controller`] works for any snake.
+pub trait Controller {
+    /// A short, human-readable name for this [`Controller`], used when
+    /// reporting results.
+    fn name(&self) -> &str;
+
+    /// Decides which [`Direction`] to steer in before the simulation next
+    /// advances. Returning [`None`] keeps the current facing.
+    fn next_direction(&mut self, view: &SimulationView) -> Option<Direction>;
+
+    /// Describes how this [`Controller`] sees the board right now, for
+    /// frontends to overlay while debugging it. Has no effect on play, and
+    /// gives no [`Annotations`] unless overridden.
+    fn explain(&self, view: &SimulationView) -> Annotations {
+        _ = view;
+        Annotations::new()
+    }
+
+    /// Steers the snake with index `snake` in `sim` wherever
+    /// [`Self::next_direction`] decides, seeing the game from that snake's
+    /// point of view.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::bots::{Controller, GreedyBot};
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snakes = vec![
+    ///     Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right),
+    ///     Snake::new(Vector2 { x: 7, y: 7 }, Direction::Left),
+    /// ];
+    /// let mut sim =
+    ///     SnakeSimulation::seeded_with_snakes(board, snakes, Vector2 { x: 7, y: 2 }, 3).unwrap();
+    ///
+    /// // Food is straight up from the second snake
+    /// GreedyBot.steer(&mut sim, 1);
+    /// assert_eq!(sim.snake_at(1).unwrap().facing(), Direction::Up);
+    /// assert_eq!(sim.snake().facing(), Direction::Right);
+    /// ```
+    fn steer(&mut self, sim: &mut SnakeSimulation, snake: usize) {
+        if let Some(direction) = self.next_direction(&sim.view_as(snake)) {
+            // Bots know which way they mean to go, so see through mirrored
+            // controls
+            let direction = if sim.is_mirrored_for(snake) {
+                direction.flip()
+            } else {
+                direction
+            };
+
+            sim.change_move_direction(snake, direction);
+        }
+    }
+}
+
+/// Returns an [`Iterator`] over the directions the player's snake can turn to
+/// without reversing or dying on the next step.
+pub(crate) fn safe_directions<'a>(
+    view: &'a SimulationView,
+) -> impl Iterator<Item = Direction> + use<'a> {
+    let reverse = view.player().last_move_direction().flip();
+
+    Direction::ALL
+        .into_iter()
+        .filter(move |direction| *direction != reverse && !view.is_fatal_move(*direction))
+}
+
+/// Finds whichever food is nearest the player's head right now.
+pub(crate) fn nearest_food(view: &SimulationView) -> Option<Vector2> {
+    let head = view.player().head();
+    view.food()
+        .min_by_key(|food| head.manhattan_distance(**food))
+        .copied()
+}
+
+/// Gets every cell covered by a snake, an obstacle or the hazard zone. Tails that are about to
+/// move out of the way still count, so plans made around these are cautious.
+pub(crate) fn blocked_cells(view: &SimulationView) -> HashSet<Vector2> {
+    view.snakes()
+        .flat_map(|snake| snake.body())
+        .chain(view.obstacles())
+        .chain(view.hazard())
+        .copied()
+        .collect()
+}
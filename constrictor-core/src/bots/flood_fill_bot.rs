@@ -0,0 +1,93 @@
+use crate::{
+    analysis::reachable_cells,
+    bots::{
+        Controller,
+        controller::{blocked_cells, nearest_food, safe_directions},
+    },
+    math::{Direction, Vector2},
+    models::{BoardStep, SimulationView},
+    render::{Annotation, Annotations, Tint},
+};
+
+/// A [`Controller`] that heads for the food, but only along steps that leave
+/// room to move afterwards. Each step is judged by flood filling the board
+/// from where it leads; if none leave room for the whole snake, it takes
+/// whichever leaves the most.
+///
+/// # Example
+/// ```
+/// use constrictor_core::bots::{Controller, FloodFillBot};
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+///
+/// let board = Board::new((0, 5), (0, 5));
+/// let snake = Snake::new(Vector2 { x: 2, y: 2 }, Direction::Right);
+/// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 2, y: 0 }, 0).unwrap();
+///
+/// assert_eq!(FloodFillBot.next_direction(&sim.view()), Some(Direction::Up));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct FloodFillBot;
+
+impl Controller for FloodFillBot {
+    fn name(&self) -> &str {
+        "flood-fill"
+    }
+
+    fn next_direction(&mut self, view: &SimulationView) -> Option<Direction> {
+        let player = view.player();
+        let facing = player.facing();
+        let food = nearest_food(view);
+        let options = room_after_each_step(view);
+
+        let roomy = options.iter().filter(|(_, _, room)| *room >= player.len());
+        let towards_food = roomy.min_by_key(|(direction, cell, _)| {
+            let distance = food.map(|food| cell.manhattan_distance(food));
+            (distance, *direction != facing)
+        });
+
+        towards_food
+            .or_else(|| options.iter().max_by_key(|(_, _, room)| *room))
+            .map(|(direction, _, _)| *direction)
+    }
+
+    /// Writes how much room each safe step leaves, highlighting the one it
+    /// would take.
+    fn explain(&self, view: &SimulationView) -> Annotations {
+        let choice = Self.next_direction(view);
+
+        room_after_each_step(view)
+            .into_iter()
+            .map(|(direction, cell, room)| {
+                let annotation = Annotation::score(room as i64);
+                if Some(direction) == choice {
+                    (cell, annotation.with_tint(Tint::Good))
+                } else {
+                    (cell, annotation)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Gets every safe step the player can take on the board, where it leads,
+/// and how many cells can be reached from there.
+fn room_after_each_step(view: &SimulationView) -> Vec<(Direction, Vector2, usize)> {
+    let board = view.board();
+    let head = view.player().head();
+    let blocked = blocked_cells(view);
+
+    safe_directions(view)
+        .filter_map(|direction| {
+            let BoardStep::To(cell) = board.step(head, direction) else {
+                return None;
+            };
+
+            Some((
+                direction,
+                cell,
+                reachable_cells(board, cell, &blocked).len(),
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+use crate::{
+    bots::{
+        Controller,
+        controller::{nearest_food, safe_directions},
+    },
+    math::Direction,
+    models::SimulationView,
+    render::{Annotation, Annotations, Tint},
+};
+
+/// A [`Controller`] that always takes the safe step that brings it closest to
+/// the food, without planning any further ahead than that.
+///
+/// # Example
+/// ```
+/// use constrictor_core::bots::{Controller, GreedyBot};
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+///
+/// let board = Board::new((0, 5), (0, 5));
+/// let snake = Snake::new(Vector2 { x: 2, y: 2 }, Direction::Right);
+/// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 2, y: 0 }, 0).unwrap();
+///
+/// assert_eq!(GreedyBot.next_direction(&sim.view()), Some(Direction::Up));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct GreedyBot;
+
+impl Controller for GreedyBot {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn next_direction(&mut self, view: &SimulationView) -> Option<Direction> {
+        let player = view.player();
+        let head = *player.head();
+        let facing = player.facing();
+        let food = nearest_food(view)?;
+
+        // Prefer to keep going straight when several moves are equally good
+        safe_directions(view).min_by_key(|direction| {
+            let distance = head.neighbour(*direction, 1).manhattan_distance(food);
+            (distance, *direction != facing)
+        })
+    }
+
+    /// Scores each safe step by its distance to the food, highlighting the
+    /// one it would take, and crosses out the fatal ones.
+    fn explain(&self, view: &SimulationView) -> Annotations {
+        let head = *view.player().head();
+        let Some(food) = nearest_food(view) else {
+            return Annotations::new();
+        };
+
+        let choice = Self.next_direction(view);
+        let reverse = view.player().last_move_direction().flip();
+
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| *direction != reverse)
+            .map(|direction| {
+                let cell = head.neighbour(direction, 1);
+                let annotation = if view.is_fatal_move(direction) {
+                    Annotation::label("x").with_tint(Tint::Bad)
+                } else if Some(direction) == choice {
+                    Annotation::score(cell.manhattan_distance(food).into()).with_tint(Tint::Good)
+                } else {
+                    Annotation::score(cell.manhattan_distance(food).into())
+                };
+
+                (cell, annotation)
+            })
+            .collect()
+    }
+}
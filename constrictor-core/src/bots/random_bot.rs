@@ -0,0 +1,34 @@
+use rand::{SeedableRng, rngs::StdRng, seq::IteratorRandom};
+
+use crate::{
+    bots::{Controller, controller::safe_directions},
+    math::Direction,
+    models::SimulationView,
+};
+
+/// A [`Controller`] that wanders aimlessly, picking uniformly between the
+/// moves that won't immediately kill it. Mostly useful as a baseline to
+/// measure other bots against.
+#[derive(Debug, Clone)]
+pub struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    /// Creates a [`RandomBot`] whose choices are derived from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Controller for RandomBot {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn next_direction(&mut self, view: &SimulationView) -> Option<Direction> {
+        safe_directions(view).choose(&mut self.rng)
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt::Display;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    bots::Controller,
+    config::{BoardSize, ConfigError, GameConfig},
+    models::SimulationResult,
+};
+
+/// Parameters shared by every game played in a [`Tournament`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentSettings {
+    /// The number of seeded games each bot plays.
+    pub games: usize,
+
+    /// The seed from which the seed of every game is derived.
+    pub seed: u64,
+
+    /// The setup of every game. Its [`GameConfig::seed`] is ignored in favour
+    /// of the per-game seeds derived from [`Self::seed`].
+    pub game: GameConfig,
+
+    /// The number of steps after which a game is cut short, so that bots
+    /// which loop forever without dying can't stall the tournament.
+    pub max_ticks: usize,
+}
+
+impl Default for TournamentSettings {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            seed: 0,
+            game: GameConfig {
+                board: BoardSize {
+                    width: 16,
+                    height: 16,
+                },
+                ..Default::default()
+            },
+            max_ticks: 10_000,
+        }
+    }
+}
+
+/// Pits a set of registered [`Controller`]s against each other. Every bot
+/// plays the exact same sequence of seeded games, and the bot(s) reaching the
+/// greatest length in a game are credited with winning it.
+///
+/// # Example
+/// ```
+/// use constrictor_core::bots::{GreedyBot, RandomBot, Tournament, TournamentSettings};
+///
+/// let mut tournament = Tournament::new(TournamentSettings {
+///     games: 5,
+///     ..Default::default()
+/// });
+/// tournament.register(GreedyBot);
+/// tournament.register(RandomBot::new(7));
+///
+/// let standings = tournament.run().unwrap();
+/// assert_eq!(standings.rows().len(), 2);
+/// assert_eq!(standings.rows()[0].name, "greedy");
+/// ```
+pub struct Tournament {
+    settings: TournamentSettings,
+    entrants: Vec<Box<dyn Controller>>,
+}
+
+impl Tournament {
+    /// Creates a [`Tournament`] with no entrants.
+    pub fn new(settings: TournamentSettings) -> Self {
+        Self {
+            settings,
+            entrants: Vec::new(),
+        }
+    }
+
+    /// Enters `bot` into the [`Tournament`].
+    pub fn register<C: Controller + 'static>(&mut self, bot: C) {
+        self.entrants.push(Box::new(bot));
+    }
+
+    /// Plays every game with every entrant and tallies the results. Fails if
+    /// [`TournamentSettings::game`] is invalid.
+    pub fn run(mut self) -> Result<Standings, ConfigError> {
+        let mut rows: Vec<_> = self
+            .entrants
+            .iter()
+            .map(|bot| Standing::new(bot.name()))
+            .collect();
+
+        let mut seeds = StdRng::seed_from_u64(self.settings.seed);
+
+        for _ in 0..self.settings.games {
+            let seed = seeds.random();
+
+            let outcomes = self
+                .entrants
+                .iter_mut()
+                .map(|bot| play_game(&self.settings, seed, bot.as_mut()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let best_length = outcomes.iter().map(|o| o.length).max().unwrap_or(0);
+
+            for (row, outcome) in rows.iter_mut().zip(&outcomes) {
+                row.record(outcome, outcome.length == best_length);
+            }
+        }
+
+        rows.sort_by(|a, b| {
+            b.games_won
+                .cmp(&a.games_won)
+                .then(b.total_length.cmp(&a.total_length))
+        });
+
+        Ok(Standings { rows })
+    }
+}
+
+/// How a single bot fared in a single game.
+struct GameOutcome {
+    length: usize,
+    result: Option<SimulationResult>,
+}
+
+/// Plays one game to completion (or until [`TournamentSettings::max_ticks`]).
+fn play_game(
+    settings: &TournamentSettings,
+    seed: u64,
+    bot: &mut dyn Controller,
+) -> Result<GameOutcome, ConfigError> {
+    let mut sim = GameConfig {
+        seed: Some(seed),
+        ..settings.game.clone()
+    }
+    .build()?;
+
+    for _ in 0..settings.max_ticks {
+        bot.steer(&mut sim, 0);
+
+        if sim.advance().is_some() {
+            break;
+        }
+    }
+
+    Ok(GameOutcome {
+        length: sim.snake().len(),
+        result: sim.result().copied(),
+    })
+}
+
+/// One bot's row in the [`Standings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    /// The [`Controller::name`] of the bot.
+    pub name: String,
+
+    /// The number of games played.
+    pub games_played: usize,
+
+    /// The number of games in which this bot reached the greatest length of
+    /// all entrants. Ties credit every tied bot.
+    pub games_won: usize,
+
+    /// The number of games in which this bot filled the entire board.
+    pub boards_cleared: usize,
+
+    /// The number of games in which this bot died.
+    pub deaths: usize,
+
+    /// The sum of this bot's final length across all games.
+    pub total_length: usize,
+
+    /// The greatest final length this bot reached in any game.
+    pub best_length: usize,
+}
+
+impl Standing {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            games_played: 0,
+            games_won: 0,
+            boards_cleared: 0,
+            deaths: 0,
+            total_length: 0,
+            best_length: 0,
+        }
+    }
+
+    fn record(&mut self, outcome: &GameOutcome, won: bool) {
+        self.games_played += 1;
+        self.games_won += usize::from(won);
+        self.total_length += outcome.length;
+        self.best_length = self.best_length.max(outcome.length);
+
+        match outcome.result {
+            Some(SimulationResult::Won) => self.boards_cleared += 1,
+            Some(SimulationResult::Died(_)) => self.deaths += 1,
+            _ => {}
+        }
+    }
+
+    /// The mean final length across all games played.
+    pub fn average_length(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.total_length as f64 / self.games_played as f64
+        }
+    }
+}
+
+/// The final results of a [`Tournament`], ordered from best to worst.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standings {
+    rows: Vec<Standing>,
+}
+
+impl Standings {
+    /// Gets the rows of the standings, ordered from best to worst.
+    pub fn rows(&self) -> &[Standing] {
+        &self.rows
+    }
+}
+
+impl Display for Standings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|row| row.name.len())
+            .chain(["bot".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:>4}  {:<name_width$}  {:>6}  {:>5}  {:>7}  {:>6}  {:>8}  {:>4}",
+            "#", "bot", "played", "won", "cleared", "deaths", "avg len", "best"
+        )?;
+
+        for (rank, row) in self.rows.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<name_width$}  {:>6}  {:>5}  {:>7}  {:>6}  {:>8.2}  {:>4}",
+                rank + 1,
+                row.name,
+                row.games_played,
+                row.games_won,
+                row.boards_cleared,
+                row.deaths,
+                row.average_length(),
+                row.best_length,
+            )?;
+        }
+
+        Ok(())
+    }
+}
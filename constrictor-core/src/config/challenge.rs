@@ -0,0 +1,407 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    config::{BoardShape, BoardSize, GameConfig, SpeedCurve, WallStyle},
+    models::{EventSchedule, Rules, Shrink, SpawnStrategy, TimeLimit},
+};
+
+/// The version of the encoding written into every challenge code. Bump it
+/// whenever the layout below changes, and keep reading the older ones.
+const VERSION: u8 = 1;
+
+/// Crockford's base32 alphabet, which leaves out letters easily mistaken
+/// for digits.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// How many characters of a code go between each dash.
+const GROUP: usize = 5;
+
+/// Describes why a [`GameConfig`] could not be turned into a challenge code,
+/// or a code back into a [`GameConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    /// The config has a part that codes don't carry, such as a map. Holds
+    /// what it is.
+    Unshareable(&'static str),
+
+    /// The config has no seed, so the same game can't be played again.
+    NoSeed,
+
+    /// The code has a character that isn't part of any code.
+    InvalidCharacter(char),
+
+    /// The code was cut short or mistyped.
+    Mistyped,
+
+    /// The code is from a newer version of the game, which uses an encoding
+    /// this one can't read.
+    UnknownVersion(u8),
+}
+
+impl Error for ChallengeError {}
+impl Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unshareable(what) => write!(f, "challenge codes can't include {what}"),
+            Self::NoSeed => f.write_str("challenge codes need a fixed seed"),
+            Self::InvalidCharacter(c) => write!(f, "{c:?} is never part of a challenge code"),
+            Self::Mistyped => f.write_str("challenge code is incomplete or mistyped"),
+            Self::UnknownVersion(version) => write!(
+                f,
+                "challenge code uses version {version} of the encoding, which is newer than this \
+                 game understands"
+            ),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Writes the board size and shape, walls, rules, speed, food placement
+    /// and seed of this config as a short code, which
+    /// [`Self::from_challenge_code`] turns back into the same game. How the
+    /// snakes look isn't part of it.
+    ///
+    /// Only games made from those settings can be shared this way, so a
+    /// config with a map, a puzzle, portals, terrain, doorways, opponents or
+    /// events has no code.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::{ChallengeError, GameConfig};
+    ///
+    /// let mut config = GameConfig::default();
+    /// assert_eq!(config.challenge_code(), Err(ChallengeError::NoSeed));
+    ///
+    /// config.seed = Some(1234);
+    /// config.rules.lives = 3;
+    ///
+    /// let code = config.challenge_code().unwrap();
+    /// assert_eq!(GameConfig::from_challenge_code(&code), Ok(config));
+    ///
+    /// // Codes can be typed back in without much care
+    /// let typed = code.to_lowercase().replace('0', "o");
+    /// assert!(GameConfig::from_challenge_code(&typed).is_ok());
+    /// ```
+    pub fn challenge_code(&self) -> Result<String, ChallengeError> {
+        let unshareable = [
+            (self.map.is_some(), "maps"),
+            (self.scenario.is_some(), "puzzles"),
+            (!self.portals.is_empty(), "portals"),
+            (!self.terrain.is_empty(), "terrain"),
+            (matches!(self.walls, WallStyle::Doorways(_)), "doorways"),
+            (!self.opponents.is_empty(), "opponents"),
+            (self.events != EventSchedule::default(), "events"),
+        ];
+
+        if let Some((_, what)) = unshareable.into_iter().find(|(found, _)| *found) {
+            return Err(ChallengeError::Unshareable(what));
+        }
+
+        let seed = self.seed.ok_or(ChallengeError::NoSeed)?;
+
+        let mut bytes = vec![VERSION];
+        write_varint(&mut bytes, self.board.width.into());
+        write_varint(&mut bytes, self.board.height.into());
+
+        let shape = BoardShape::ALL
+            .iter()
+            .position(|shape| *shape == self.shape)
+            .unwrap_or(0) as u8;
+        let wrap = u8::from(self.walls == WallStyle::Wrap) << 4;
+        bytes.push(shape | wrap);
+
+        write_varint(&mut bytes, seed);
+        write_varint(&mut bytes, self.speed.initial_interval_ms);
+        write_varint(&mut bytes, self.speed.minimum_interval_ms);
+        write_varint(&mut bytes, self.speed.speedup_per_food_ms);
+        write_varint(
+            &mut bytes,
+            match self.spawn {
+                SpawnStrategy::Uniform => 0,
+                SpawnStrategy::AwayFromHead { min_distance } => u64::from(min_distance) + 1,
+            },
+        );
+
+        write_rules(&mut bytes, &self.rules);
+        bytes.push(checksum(&bytes));
+
+        Ok(encode(&bytes))
+    }
+
+    /// Reads a code written by [`Self::challenge_code`]. Letters can be in
+    /// either case, and dashes are ignored. Everything the code doesn't
+    /// carry is left as the default.
+    pub fn from_challenge_code(code: &str) -> Result<Self, ChallengeError> {
+        let bytes = decode(code)?;
+        let (&sum, body) = bytes.split_last().ok_or(ChallengeError::Mistyped)?;
+        if checksum(body) != sum {
+            return Err(ChallengeError::Mistyped);
+        }
+
+        let mut reader = Reader(body);
+        match reader.byte()? {
+            VERSION => {}
+            version => return Err(ChallengeError::UnknownVersion(version)),
+        }
+
+        let board = BoardSize {
+            width: reader.narrow()?,
+            height: reader.narrow()?,
+        };
+
+        let layout = reader.byte()?;
+        let shape = *BoardShape::ALL
+            .get(usize::from(layout & 0x0f))
+            .ok_or(ChallengeError::Mistyped)?;
+        let walls = match layout >> 4 {
+            0 => WallStyle::Solid,
+            1 => WallStyle::Wrap,
+            _ => return Err(ChallengeError::Mistyped),
+        };
+
+        let seed = reader.varint()?;
+        let speed = SpeedCurve {
+            initial_interval_ms: reader.varint()?,
+            minimum_interval_ms: reader.varint()?,
+            speedup_per_food_ms: reader.varint()?,
+        };
+        let spawn = match reader.varint()? {
+            0 => SpawnStrategy::Uniform,
+            n => SpawnStrategy::AwayFromHead {
+                min_distance: (n - 1).try_into().map_err(|_| ChallengeError::Mistyped)?,
+            },
+        };
+
+        let rules = read_rules(&mut reader)?;
+        if !reader.0.is_empty() {
+            return Err(ChallengeError::Mistyped);
+        }
+
+        Ok(Self {
+            board,
+            shape,
+            walls,
+            rules,
+            speed,
+            spawn,
+            seed: Some(seed),
+            ..Default::default()
+        })
+    }
+}
+
+// Which of the rules are switched on or given, as bits of a single number
+// written ahead of the rules themselves
+const ONE_TURN_PER_TICK: u64 = 1 << 0;
+const ASSIST: u64 = 1 << 1;
+const FOOD_GOAL: u64 = 1 << 2;
+const MOVE_LIMIT: u64 = 1 << 3;
+const TIME_LIMIT: u64 = 1 << 4;
+const COMBO_WINDOW: u64 = 1 << 5;
+const SHRINK: u64 = 1 << 6;
+const FLEEING_FOOD: u64 = 1 << 7;
+const FOG: u64 = 1 << 8;
+
+fn write_rules(bytes: &mut Vec<u8>, rules: &Rules) {
+    let flags = [
+        (rules.one_turn_per_tick, ONE_TURN_PER_TICK),
+        (rules.assist, ASSIST),
+        (rules.food_goal.is_some(), FOOD_GOAL),
+        (rules.move_limit.is_some(), MOVE_LIMIT),
+        (rules.time_limit.is_some(), TIME_LIMIT),
+        (rules.combo_window.is_some(), COMBO_WINDOW),
+        (rules.shrink.is_some(), SHRINK),
+        (rules.fleeing_food.is_some(), FLEEING_FOOD),
+        (rules.fog.is_some(), FOG),
+    ];
+
+    let flags = flags
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag);
+
+    write_varint(bytes, flags);
+    write_varint(bytes, rules.growth_per_food.into());
+    write_varint(bytes, rules.lives.into());
+    write_varint(bytes, rules.respawn_grace.into());
+
+    let optional = [
+        rules.food_goal,
+        rules.move_limit,
+        rules.combo_window,
+        rules.fleeing_food,
+        rules.fog,
+    ];
+    for value in optional.into_iter().flatten() {
+        write_varint(bytes, value.into());
+    }
+
+    if let Some(limit) = rules.time_limit {
+        write_varint(bytes, limit.steps);
+        write_varint(bytes, limit.bonus_per_food);
+    }
+
+    if let Some(shrink) = rules.shrink {
+        write_varint(bytes, shrink.after);
+        write_varint(bytes, shrink.every);
+    }
+}
+
+/// Reads the rules back in the order [`write_rules`] wrote them.
+fn read_rules(reader: &mut Reader) -> Result<Rules, ChallengeError> {
+    let flags = reader.varint()?;
+    if flags >> 9 != 0 {
+        return Err(ChallengeError::Mistyped);
+    }
+
+    let mut rules = Rules {
+        one_turn_per_tick: flags & ONE_TURN_PER_TICK != 0,
+        assist: flags & ASSIST != 0,
+        growth_per_food: reader.narrow()?,
+        lives: reader.narrow()?,
+        respawn_grace: reader.narrow()?,
+        ..Default::default()
+    };
+
+    let mut optional = |flag: u64| -> Result<Option<u32>, ChallengeError> {
+        (flags & flag != 0).then(|| reader.narrow()).transpose()
+    };
+
+    rules.food_goal = optional(FOOD_GOAL)?;
+    rules.move_limit = optional(MOVE_LIMIT)?;
+    rules.combo_window = optional(COMBO_WINDOW)?;
+    rules.fleeing_food = optional(FLEEING_FOOD)?;
+    rules.fog = optional(FOG)?;
+
+    if flags & TIME_LIMIT != 0 {
+        rules.time_limit = Some(TimeLimit {
+            steps: reader.varint()?,
+            bonus_per_food: reader.varint()?,
+        });
+    }
+
+    if flags & SHRINK != 0 {
+        rules.shrink = Some(Shrink {
+            after: reader.varint()?,
+            every: reader.varint()?,
+        });
+    }
+
+    Ok(rules)
+}
+
+/// Writes `value` in as few bytes as it needs, seven bits to a byte, with
+/// the high bit set on every byte but the last.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+/// The bytes of a code not yet read.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, ChallengeError> {
+        let (&byte, rest) = self.0.split_first().ok_or(ChallengeError::Mistyped)?;
+        self.0 = rest;
+        Ok(byte)
+    }
+
+    /// Reads a number written by [`write_varint`].
+    fn varint(&mut self) -> Result<u64, ChallengeError> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ChallengeError::Mistyped)
+    }
+
+    /// Reads a number written by [`write_varint`] that has to fit in `T`.
+    fn narrow<T: TryFrom<u64>>(&mut self) -> Result<T, ChallengeError> {
+        self.varint()?
+            .try_into()
+            .map_err(|_| ChallengeError::Mistyped)
+    }
+}
+
+/// Catches most typos, so that a mistyped code is turned away rather than
+/// starting some other game. This is CRC-8, with polynomial `0x07`.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Writes `bytes` in base32, in groups of [`GROUP`] characters split by
+/// dashes.
+fn encode(bytes: &[u8]) -> String {
+    let mut digits = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            digits.push(ALPHABET[(buffer >> bits) as usize & 0x1f]);
+        }
+    }
+
+    if bits > 0 {
+        digits.push(ALPHABET[(buffer << (5 - bits)) as usize & 0x1f]);
+    }
+
+    digits
+        .chunks(GROUP)
+        .map(|group| String::from_utf8_lossy(group))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Reads base32 written by [`encode`], forgiving the mix-ups Crockford's
+/// alphabet is designed around: `O` for `0`, and `I` or `L` for `1`.
+fn decode(code: &str) -> Result<Vec<u8>, ChallengeError> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in code.chars().filter(|c| *c != '-' && !c.is_whitespace()) {
+        let digit = match c.to_ascii_uppercase() {
+            'O' => 0,
+            'I' | 'L' => 1,
+            upper => ALPHABET
+                .iter()
+                .position(|&letter| char::from(letter) == upper)
+                .ok_or(ChallengeError::InvalidCharacter(c))?,
+        };
+
+        buffer = (buffer << 5) | digit as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
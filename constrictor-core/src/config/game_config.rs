@@ -0,0 +1,703 @@
+use std::{collections::HashSet, error::Error, fmt::Display, time::Duration};
+
+use crate::{
+    config::{Map, Scenario},
+    math::{Direction, Vector2},
+    models::{
+        Appearance, Board, Doorway, EventSchedule, Portal, Rules, SimulationParameterError, Snake,
+        SnakeSimulation, SpawnStrategy, Terrain,
+    },
+};
+
+/// The single description of a game setup, shared by every frontend. A
+/// [`GameConfig`] fully determines the [`SnakeSimulation`] it builds, up to the
+/// inputs it is given.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+///
+/// let config = GameConfig {
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+///
+/// let sim = config.build().unwrap();
+/// assert_eq!(sim.board().width(), 32);
+/// assert_eq!(sim.snake().len(), 1);
+/// ```
+///
+/// Anything left out of a serialized config takes its default, but a key
+/// that isn't recognised, at any depth, is an error rather than ignored:
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use constrictor_core::config::GameConfig;
+///
+/// let config: GameConfig = serde_json::from_str(r#"{"walls": "Wrap"}"#).unwrap();
+/// assert_eq!(config.board, GameConfig::default().board);
+///
+/// let typo = serde_json::from_str::<GameConfig>(r#"{"wals": "Wrap"}"#);
+/// assert!(typo.unwrap_err().to_string().contains("unknown field `wals`"));
+///
+/// let nested = serde_json::from_str::<GameConfig>(r#"{"rules": {"asist": true}}"#);
+/// assert!(nested.is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct GameConfig {
+    /// The dimensions of the board.
+    pub board: BoardSize,
+
+    /// Which cells within those dimensions are playable.
+    pub shape: BoardShape,
+
+    /// A hand-drawn level to play on instead. When given, it decides the
+    /// board entirely, along with where the snakes start and where food
+    /// appears, and [`Self::board`] and [`Self::shape`] are ignored.
+    pub map: Option<Map>,
+
+    /// A puzzle to play instead. When given, it decides the board, the snake
+    /// and the food, overriding [`Self::map`] as well, along with the
+    /// [`Rules::food_goal`] and [`Rules::move_limit`].
+    pub scenario: Option<Scenario>,
+
+    /// Which parts of the board's outer wall are open.
+    pub walls: WallStyle,
+
+    /// Pairs of cells linked by a [`Portal`], which the snakes can travel
+    /// between.
+    pub portals: Vec<Portal>,
+
+    /// Cells with [`Terrain`] that changes how the snakes move over them.
+    pub terrain: Vec<(Vector2, Terrain)>,
+
+    /// The rules the game is played under.
+    pub rules: Rules,
+
+    /// How quickly the game runs, and how that changes as it goes on.
+    pub speed: SpeedCurve,
+
+    /// How new food is placed.
+    pub spawn: SpawnStrategy,
+
+    /// The seed all randomness is derived from. A random seed is chosen when
+    /// building if this is [`None`], provided the `os-rng` feature is enabled.
+    pub seed: Option<u64>,
+
+    /// How the player's snake looks.
+    pub player: Appearance,
+
+    /// How each other snake sharing the board looks. Whatever steers them,
+    /// such as a bot, is up to the frontend.
+    pub opponents: Vec<Appearance>,
+
+    /// Events that happen as the game goes on, such as obstacles appearing.
+    pub events: EventSchedule,
+}
+
+/// The dimensions of a [`Board`], in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct BoardSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        Self {
+            width: 32,
+            height: 32,
+        }
+    }
+}
+
+/// The shape of the playable area of a [`Board`], fitted to its
+/// [`BoardSize`]. Cells outside the shape are walls.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::{BoardShape, BoardSize};
+/// use constrictor_core::math::Vector2;
+///
+/// let size = BoardSize { width: 9, height: 9 };
+/// let corner = Vector2 { x: 0, y: 0 };
+/// let middle = Vector2 { x: 4, y: 4 };
+///
+/// assert!(BoardShape::Rectangle.contains(size, &corner));
+/// assert!(!BoardShape::Circle.contains(size, &corner));
+/// assert!(!BoardShape::Cross.contains(size, &corner));
+/// assert!(BoardShape::Cross.contains(size, &middle));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardShape {
+    /// Every cell is playable.
+    #[default]
+    Rectangle,
+
+    /// The largest ellipse that fits.
+    Circle,
+
+    /// Two bars, each a third as thick as the board, crossing in the middle.
+    Cross,
+}
+
+impl BoardShape {
+    /// Every shape, starting with the default.
+    pub const ALL: [Self; 3] = [Self::Rectangle, Self::Circle, Self::Cross];
+
+    /// Determines whether `cell` is playable on a board of `size` with this
+    /// shape, with its top-left cell at the origin.
+    pub fn contains(self, size: BoardSize, cell: &Vector2) -> bool {
+        let (width, height) = (i64::from(size.width), i64::from(size.height));
+        if !(0..width).contains(&cell.x.into()) || !(0..height).contains(&cell.y.into()) {
+            return false;
+        }
+
+        // Offsets from the middle of the board, in half cells
+        let dx = 2 * i64::from(cell.x) + 1 - width;
+        let dy = 2 * i64::from(cell.y) + 1 - height;
+
+        match self {
+            Self::Rectangle => true,
+            Self::Circle => {
+                dx * dx * height * height + dy * dy * width * width <= (width * height).pow(2)
+            }
+            Self::Cross => dx.abs() <= width / 3 || dy.abs() <= height / 3,
+        }
+    }
+}
+
+impl Display for BoardShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Rectangle => "Rectangle",
+            Self::Circle => "Circle",
+            Self::Cross => "Cross",
+        })
+    }
+}
+
+/// Describes which parts of the outer wall of a [`Board`] are open.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WallStyle {
+    /// The wall is solid all the way around, as in Classic Snake.
+    #[default]
+    Solid,
+
+    /// There is no wall; the snake wraps around to the opposite side.
+    Wrap,
+
+    /// The wall is solid except for the given [`Doorway`]s.
+    Doorways(Vec<Doorway>),
+}
+
+/// Describes how long each step of the simulation lasts. The interval starts
+/// at [`Self::initial_interval_ms`] and shrinks by
+/// [`Self::speedup_per_food_ms`] for each piece of food eaten, down to
+/// [`Self::minimum_interval_ms`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct SpeedCurve {
+    pub initial_interval_ms: u64,
+    pub minimum_interval_ms: u64,
+    pub speedup_per_food_ms: u64,
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        Difficulty::default().speed()
+    }
+}
+
+impl SpeedCurve {
+    /// Gets the duration of a step once `food_eaten` pieces of food have been
+    /// eaten.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use constrictor_core::config::SpeedCurve;
+    ///
+    /// let curve = SpeedCurve {
+    ///     initial_interval_ms: 100,
+    ///     minimum_interval_ms: 60,
+    ///     speedup_per_food_ms: 10,
+    /// };
+    ///
+    /// assert_eq!(curve.tick_interval(0), Duration::from_millis(100));
+    /// assert_eq!(curve.tick_interval(2), Duration::from_millis(80));
+    /// assert_eq!(curve.tick_interval(50), Duration::from_millis(60));
+    /// ```
+    pub fn tick_interval(&self, food_eaten: usize) -> Duration {
+        let speedup = self.speedup_per_food_ms.saturating_mul(food_eaten as u64);
+
+        let interval = self
+            .initial_interval_ms
+            .saturating_sub(speedup)
+            .max(self.minimum_interval_ms);
+
+        Duration::from_millis(interval)
+    }
+
+    /// Gets this curve with every interval scaled to `percent`% of its
+    /// length, so lower percentages run faster. Intervals never drop below
+    /// 1 ms.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::SpeedCurve;
+    ///
+    /// let curve = SpeedCurve {
+    ///     initial_interval_ms: 100,
+    ///     minimum_interval_ms: 60,
+    ///     speedup_per_food_ms: 10,
+    /// };
+    ///
+    /// let faster = curve.scaled(50);
+    /// assert_eq!(faster.initial_interval_ms, 50);
+    /// assert_eq!(faster.minimum_interval_ms, 30);
+    /// assert_eq!(faster.speedup_per_food_ms, 5);
+    /// ```
+    pub fn scaled(&self, percent: u64) -> Self {
+        let scale = |ms: u64| ms.saturating_mul(percent) / 100;
+
+        Self {
+            initial_interval_ms: scale(self.initial_interval_ms).max(1),
+            minimum_interval_ms: scale(self.minimum_interval_ms).max(1),
+            speedup_per_food_ms: scale(self.speedup_per_food_ms),
+        }
+    }
+}
+
+/// Preset [`SpeedCurve`]s for players to pick between. Each starts at a
+/// different pace and speeds up as food is eaten. [`Difficulty::Normal`] is
+/// the default [`SpeedCurve`].
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::Difficulty;
+///
+/// let (easy, hard) = (Difficulty::Easy.speed(), Difficulty::Hard.speed());
+/// assert!(easy.tick_interval(0) > hard.tick_interval(0));
+/// assert!(hard.tick_interval(10) < hard.tick_interval(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Every difficulty, from easiest to hardest.
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// Gets the [`SpeedCurve`] played at this difficulty.
+    pub const fn speed(self) -> SpeedCurve {
+        let (initial_interval_ms, minimum_interval_ms, speedup_per_food_ms) = match self {
+            Self::Easy => (110, 70, 2),
+            Self::Normal => (75, 45, 1),
+            Self::Hard => (60, 30, 2),
+        };
+
+        SpeedCurve {
+            initial_interval_ms,
+            minimum_interval_ms,
+            speedup_per_food_ms,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        })
+    }
+}
+
+/// Describes why a [`GameConfig`] is unusable.
+#[derive(PartialEq, Eq, Debug)]
+pub enum ConfigError {
+    /// The board is too small to fit the starting layout, or its
+    /// [`BoardShape`] leaves part of the layout off it.
+    BoardTooSmall,
+
+    /// [`Rules::growth_per_food`] is zero, so the game could never be won.
+    NoGrowth,
+
+    /// The [`SpeedCurve`] has a zero interval, or a minimum greater than its
+    /// initial interval.
+    InvalidSpeedCurve,
+
+    /// No seed was given, and the `os-rng` feature needed to pick one is
+    /// disabled.
+    MissingSeed,
+
+    /// A [`Portal`] has an end off the board, both ends in the same cell, or
+    /// an end in a cell another portal already uses.
+    InvalidPortal,
+
+    /// Some [`Terrain`] is off the board.
+    TerrainOffBoard,
+
+    /// The [`Map`] has fewer spawn points than there are snakes, or no room
+    /// left over for the food.
+    NotEnoughSpawns,
+
+    /// A [`Scenario`] only has the player's snake, so it can't be played
+    /// with opponents.
+    OpponentsInScenario,
+
+    /// The simulation rejected the starting layout.
+    Simulation(SimulationParameterError),
+}
+
+impl Error for ConfigError {}
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BoardTooSmall => write!(
+                f,
+                "board must be at least {}x{} cells",
+                GameConfig::MIN_WIDTH,
+                GameConfig::MIN_HEIGHT
+            ),
+            Self::NoGrowth => f.write_str("snake must grow by at least one segment per food"),
+            Self::InvalidSpeedCurve => f.write_str(
+                "tick intervals must be non-zero, and the minimum must not exceed the initial",
+            ),
+            Self::MissingSeed => f.write_str("a seed is required when os-rng is disabled"),
+            Self::InvalidPortal => {
+                f.write_str("portals must link two different cells on the board, each used once")
+            }
+            Self::TerrainOffBoard => f.write_str("terrain must be on the board"),
+            Self::NotEnoughSpawns => f.write_str(
+                "map must have a spawn point for every snake, and floor left over for the food",
+            ),
+            Self::OpponentsInScenario => f.write_str("scenarios can't be played with opponents"),
+            Self::Simulation(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<SimulationParameterError> for ConfigError {
+    fn from(value: SimulationParameterError) -> Self {
+        Self::Simulation(value)
+    }
+}
+
+impl GameConfig {
+    /// The narrowest board that fits the starting layout.
+    pub const MIN_WIDTH: u16 = 7;
+
+    /// The shortest board that fits the starting layout.
+    pub const MIN_HEIGHT: u16 = 1;
+
+    /// Checks that the [`GameConfig`] describes a playable game.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::{BoardSize, ConfigError, GameConfig};
+    /// use constrictor_core::math::Vector2;
+    /// use constrictor_core::models::Portal;
+    ///
+    /// assert_eq!(GameConfig::default().validate(), Ok(()));
+    ///
+    /// let tiny = GameConfig {
+    ///     board: BoardSize { width: 3, height: 3 },
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(tiny.validate(), Err(ConfigError::BoardTooSmall));
+    ///
+    /// // A cell can only be the end of one portal
+    /// let (a, b, c) = (Vector2 { x: 1, y: 1 }, Vector2 { x: 5, y: 5 }, Vector2 { x: 9, y: 9 });
+    /// let forked = GameConfig {
+    ///     portals: vec![Portal { a, b }, Portal { a: b, b: c }],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(forked.validate(), Err(ConfigError::InvalidPortal));
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let too_small = self.board.width < Self::MIN_WIDTH || self.board.height < Self::MIN_HEIGHT;
+        if self.map.is_none() && self.scenario.is_none() && too_small {
+            return Err(ConfigError::BoardTooSmall);
+        }
+
+        let bare = self.bare_board();
+        let on_board = |cell: &Vector2| bare.contains(cell);
+        if self
+            .portals
+            .iter()
+            .any(|portal| !on_board(&portal.a) || !on_board(&portal.b) || portal.a == portal.b)
+        {
+            return Err(ConfigError::InvalidPortal);
+        }
+
+        // Including any the map already has
+        let mut ends = HashSet::new();
+        let shared = bare
+            .portals()
+            .iter()
+            .chain(&self.portals)
+            .any(|portal| !ends.insert(portal.a) || !ends.insert(portal.b));
+        if shared {
+            return Err(ConfigError::InvalidPortal);
+        }
+
+        if self.terrain.iter().any(|(cell, _)| !on_board(cell)) {
+            return Err(ConfigError::TerrainOffBoard);
+        }
+
+        let board = self.board();
+        if self.scenario.is_some() {
+            if !self.opponents.is_empty() {
+                return Err(ConfigError::OpponentsInScenario);
+            }
+        } else if let Some(map) = &self.map {
+            if map.spawns().len() <= self.opponents.len() || self.food_start(&board).is_none() {
+                return Err(ConfigError::NotEnoughSpawns);
+            }
+        } else if !self
+            .starting_cells(&board)
+            .all(|cell| board.contains(&cell))
+        {
+            return Err(ConfigError::BoardTooSmall);
+        }
+
+        if self.rules.growth_per_food == 0 {
+            return Err(ConfigError::NoGrowth);
+        }
+
+        let speed = &self.speed;
+        if speed.minimum_interval_ms == 0 || speed.minimum_interval_ms > speed.initial_interval_ms {
+            return Err(ConfigError::InvalidSpeedCurve);
+        }
+
+        Ok(())
+    }
+
+    /// Creates the [`Board`] described by this [`GameConfig`], with its
+    /// top-left cell at the origin.
+    ///
+    /// Panics if any of [`Self::portals`] or [`Self::terrain`] is invalid,
+    /// which [`Self::validate`] checks for.
+    pub fn board(&self) -> Board {
+        let board = self.bare_board();
+        let board = self.portals.iter().copied().fold(board, Board::with_portal);
+        let board = self.terrain.iter().fold(board, |board, &(cell, terrain)| {
+            board.with_terrain(cell, terrain)
+        });
+
+        match &self.walls {
+            WallStyle::Solid => board,
+            WallStyle::Wrap => board.wrapping(),
+            WallStyle::Doorways(doorways) => {
+                doorways.iter().cloned().fold(board, Board::with_doorway)
+            }
+        }
+    }
+
+    /// Creates the [`Board`] described by [`Self::scenario`] or
+    /// [`Self::map`], or by [`Self::board`] and [`Self::shape`] without
+    /// either, before any [`Self::portals`], [`Self::terrain`] or
+    /// [`Self::walls`] are added.
+    fn bare_board(&self) -> Board {
+        if let Some(scenario) = &self.scenario {
+            return scenario.layout.map().board();
+        }
+
+        if let Some(map) = &self.map {
+            return map.board();
+        }
+
+        let board = Board::new((0, self.board.width.into()), (0, self.board.height.into()));
+        match self.shape {
+            BoardShape::Rectangle => board,
+            shape => {
+                let cells: Vec<_> = board
+                    .cell_iter()
+                    .filter(|cell| shape.contains(self.board, cell))
+                    .collect();
+                board.with_mask(cells)
+            }
+        }
+    }
+
+    /// Gets each snake on `board` facing the way it starts, starting with
+    /// the player's. See [`Self::build`].
+    fn starting_snakes(&self, board: &Board) -> Vec<(Vector2, Direction)> {
+        let Some(map) = &self.map else {
+            return self
+                .starting_cells(board)
+                .map(|head| (head, Direction::Right))
+                .collect();
+        };
+
+        // The straightest run of open cells gives the most time to react
+        let run = |head: Vector2, direction: Direction| {
+            (1..)
+                .take_while(|distance| board.contains(&head.neighbour(direction, *distance)))
+                .count()
+        };
+
+        map.spawns()
+            .into_iter()
+            .take(1 + self.opponents.len())
+            .map(|head| {
+                // Reversed, so that ties go to whichever comes first
+                let facing = Direction::ALL
+                    .into_iter()
+                    .rev()
+                    .max_by_key(|direction| run(head, *direction))
+                    .unwrap_or(Direction::Right);
+
+                (head, facing)
+            })
+            .collect()
+    }
+
+    /// Gets where the food starts on `board` when playing on [`Self::map`]:
+    /// the free cell of the food region closest to the middle of the board,
+    /// or of the whole board if the region is empty. [`None`] if there is no
+    /// such cell.
+    fn food_start(&self, board: &Board) -> Option<Vector2> {
+        let map = self.map.as_ref()?;
+        let region = map.food_region();
+        let candidates = if region.is_empty() {
+            board.free_cells().iter().collect()
+        } else {
+            region
+        };
+
+        let center = board.center();
+        candidates
+            .into_iter()
+            .filter(|cell| !map.spawns().contains(cell))
+            .min_by_key(|cell| cell.manhattan_distance(center))
+    }
+
+    /// Gets where each snake's head starts on `board`, starting with the
+    /// player's. Opponents line up two rows apart, alternately above and
+    /// below the player.
+    fn starting_cells(&self, board: &Board) -> impl Iterator<Item = Vector2> {
+        let player = board.center().neighbour(Direction::Left, 3);
+        let opponents = (0..self.opponents.len()).map(move |index| {
+            let side = if index % 2 == 0 {
+                Direction::Up
+            } else {
+                Direction::Down
+            };
+
+            player.neighbour(side, 2 * (index as i32 / 2 + 1))
+        });
+
+        std::iter::once(player).chain(opponents)
+    }
+
+    /// Gets [`Self::seed`], or a random seed if there isn't one.
+    fn seed_or_random(&self) -> Result<u64, ConfigError> {
+        #[cfg(feature = "os-rng")]
+        return Ok(self.seed.unwrap_or_else(rand::random));
+
+        #[cfg(not(feature = "os-rng"))]
+        return self.seed.ok_or(ConfigError::MissingSeed);
+    }
+
+    /// Validates this [`GameConfig`] and builds the [`SnakeSimulation`] it
+    /// describes. The snake starts three cells left of centre facing right,
+    /// with the food three cells right of centre. Any opponents start
+    /// alongside the player, facing the same way.
+    ///
+    /// On a [`Map`], each snake starts on a spawn point instead, facing
+    /// along the longest straight run of open cells from it. The food starts
+    /// as close to the middle of the board as it can, within the map's food
+    /// region if it has one, and only ever appears there afterwards.
+    ///
+    /// A [`Scenario`] lays out the snake and the food itself, and the game
+    /// is won by eating all of its food within its moves.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::models::Appearance;
+    ///
+    /// let config = GameConfig {
+    ///     opponents: vec![Appearance::default(); 2],
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(config.build().unwrap().snakes().len(), 3);
+    /// ```
+    pub fn build(&self) -> Result<SnakeSimulation, ConfigError> {
+        self.validate()?;
+
+        let board = self.board();
+        if let Some(scenario) = &self.scenario {
+            return self.build_scenario(scenario, board);
+        }
+
+        let food = self
+            .food_start(&board)
+            .unwrap_or_else(|| board.center().neighbour(Direction::Right, 3));
+        let appearances = std::iter::once(&self.player).chain(&self.opponents);
+        let snakes = self
+            .starting_snakes(&board)
+            .into_iter()
+            .zip(appearances)
+            .map(|((head, facing), appearance)| {
+                Snake::new(head, facing).with_appearance(appearance.clone())
+            })
+            .collect();
+
+        let sim = SnakeSimulation::seeded_with_snakes(board, snakes, food, self.seed_or_random()?)?;
+        let sim = match &self.map {
+            Some(map) if !map.food_region().is_empty() => sim.with_food_region(map.food_region()),
+            _ => sim,
+        };
+
+        Ok(self.finish(sim, self.rules.clone()))
+    }
+
+    /// Builds the [`SnakeSimulation`] for `scenario`, played on `board`.
+    fn build_scenario(
+        &self,
+        scenario: &Scenario,
+        board: Board,
+    ) -> Result<SnakeSimulation, ConfigError> {
+        let food = scenario.layout.food();
+        let snake = scenario.layout.snake().with_appearance(self.player.clone());
+
+        let sim = SnakeSimulation::seeded(board, snake, food[0], self.seed_or_random()?)?
+            .with_food_sequence(food[1..].iter().copied());
+
+        let rules = Rules {
+            food_goal: Some(food.len() as u32),
+            move_limit: Some(scenario.moves),
+            ..self.rules.clone()
+        };
+
+        Ok(self.finish(sim, rules))
+    }
+
+    /// Applies everything but the board, snakes and food to `sim`, which is
+    /// played under `rules`.
+    fn finish(&self, sim: SnakeSimulation, rules: Rules) -> SnakeSimulation {
+        sim.with_rules(rules)
+            .with_spawn_strategy(self.spawn)
+            .with_speed(self.speed)
+            .with_events(self.events.clone())
+    }
+}
@@ -0,0 +1,414 @@
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use crate::{
+    math::Vector2,
+    models::{Board, Portal},
+};
+
+/// A hand-drawn level, written as text with one character per cell:
+///
+/// ```text
+/// ##########
+/// #S.......#
+/// #..a..FF.#
+/// #S....FF.#
+/// #..###..a#
+/// ##########
+/// ```
+///
+/// - `#` or a space is wall.
+/// - `.` is floor.
+/// - `S` is floor a snake can start on. The player starts on the first, in
+///   reading order, and any opponents on the ones after it.
+/// - `F` is floor food can appear on. Without any, food can appear anywhere.
+/// - A lowercase letter is one end of a [`Portal`], linked to the only other
+///   cell with the same letter.
+///
+/// Rows shorter than the longest are padded out with wall. The cell at the
+/// start of the first row is the origin, whatever the board's bounds are.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::{Map, MapError};
+/// use constrictor_core::math::Vector2;
+///
+/// let map: Map = "#####\n#S.F#\n#####".parse().unwrap();
+/// assert_eq!(map.spawns(), [Vector2 { x: 1, y: 1 }]);
+/// assert_eq!(map.food_region(), [Vector2 { x: 3, y: 1 }]);
+///
+/// // The surrounding wall becomes the edge of the board
+/// let board = map.board();
+/// assert_eq!((board.x_range(), board.y_range()), (1..4, 1..2));
+///
+/// assert_eq!(
+///     "#S.?#".parse::<Map>(),
+///     Err(MapError::UnknownTile { line: 1, column: 4, found: '?' })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Map {
+    width: usize,
+
+    /// Every cell, in reading order.
+    tiles: Vec<Tile>,
+}
+
+/// What a single cell of a [`Map`] holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Wall,
+    Floor,
+    Spawn,
+    Food,
+
+    /// One end of the [`Portal`] with this lowercase letter.
+    Portal(char),
+}
+
+impl Tile {
+    /// Gets the character this [`Tile`] is written as.
+    pub const fn glyph(self) -> char {
+        match self {
+            Self::Wall => '#',
+            Self::Floor => '.',
+            Self::Spawn => 'S',
+            Self::Food => 'F',
+            Self::Portal(letter) => letter,
+        }
+    }
+
+    /// Gets the [`Tile`] written as `glyph`, if any.
+    pub const fn from_glyph(glyph: char) -> Option<Self> {
+        Some(match glyph {
+            '#' | ' ' => Self::Wall,
+            '.' => Self::Floor,
+            'S' => Self::Spawn,
+            'F' => Self::Food,
+            'a'..='z' => Self::Portal(glyph),
+            _ => return None,
+        })
+    }
+
+    /// Returns whether snakes can move onto this [`Tile`].
+    pub const fn is_playable(self) -> bool {
+        !matches!(self, Self::Wall)
+    }
+}
+
+/// Describes why text could not be read as a [`Map`]. Lines and columns are
+/// counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    /// A character that isn't written for any [`Tile`].
+    UnknownTile {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+
+    /// A portal letter used for a single cell, with nothing to link it to.
+    UnpairedPortal {
+        line: usize,
+        column: usize,
+        letter: char,
+    },
+
+    /// A portal letter used again after already linking two cells.
+    ExtraPortalEnd {
+        line: usize,
+        column: usize,
+        letter: char,
+    },
+
+    /// There are no playable cells at all.
+    NoFloor,
+}
+
+impl Error for MapError {}
+impl Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTile {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {line}, column {column}: `{found}` is not a tile; expected one of `#`, `.`, \
+                 `S`, `F`, a space or a lowercase portal letter"
+            ),
+            Self::UnpairedPortal {
+                line,
+                column,
+                letter,
+            } => write!(
+                f,
+                "line {line}, column {column}: portal `{letter}` has no other end"
+            ),
+            Self::ExtraPortalEnd {
+                line,
+                column,
+                letter,
+            } => write!(
+                f,
+                "line {line}, column {column}: portal `{letter}` already has two ends"
+            ),
+            Self::NoFloor => f.write_str("map has no floor to play on"),
+        }
+    }
+}
+
+impl FromStr for Map {
+    type Err = MapError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut rows: Vec<Vec<Tile>> = Vec::new();
+        let mut portal_ends: HashMap<char, (usize, usize, usize)> = HashMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let mut row = Vec::new();
+
+            for (column, glyph) in line.chars().enumerate() {
+                let (line, column) = (index + 1, column + 1);
+                let tile = Tile::from_glyph(glyph).ok_or(MapError::UnknownTile {
+                    line,
+                    column,
+                    found: glyph,
+                })?;
+
+                if let Tile::Portal(letter) = tile {
+                    let (count, ..) = portal_ends.entry(letter).or_insert((0, line, column));
+                    *count += 1;
+
+                    if *count > 2 {
+                        return Err(MapError::ExtraPortalEnd {
+                            line,
+                            column,
+                            letter,
+                        });
+                    }
+                }
+
+                row.push(tile);
+            }
+
+            rows.push(row);
+        }
+
+        // Blank lines at the end are just where the file stops
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        // Report the earliest lone end, so the same map always gives the same error
+        if let Some((letter, (_, line, column))) = portal_ends
+            .into_iter()
+            .filter(|(_, (count, ..))| *count == 1)
+            .min_by_key(|(_, (_, line, column))| (*line, *column))
+        {
+            return Err(MapError::UnpairedPortal {
+                line,
+                column,
+                letter,
+            });
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let tiles: Vec<_> = rows
+            .into_iter()
+            .flat_map(|row| {
+                let padding = width - row.len();
+                row.into_iter()
+                    .chain(std::iter::repeat_n(Tile::Wall, padding))
+            })
+            .collect();
+
+        if !tiles.iter().any(|tile| tile.is_playable()) {
+            return Err(MapError::NoFloor);
+        }
+
+        Ok(Self { width, tiles })
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.tiles.chunks(self.width) {
+            let row: String = row.iter().map(|tile| tile.glyph()).collect();
+            writeln!(f, "{row}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<String> for Map {
+    type Error = MapError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Map> for String {
+    fn from(value: Map) -> Self {
+        value.to_string()
+    }
+}
+
+impl Map {
+    /// Creates a [`Map`] of nothing but floor, `width` cells across and
+    /// `height` cells down.
+    ///
+    /// Panics if either is zero.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0);
+
+        Self {
+            width,
+            tiles: vec![Tile::Floor; width * height],
+        }
+    }
+
+    /// Puts `tile` on `cell`. Nothing checks that the [`Map`] still makes
+    /// sense afterwards, so a portal can be left with a single end while it
+    /// is being drawn; reading the map back with [`str::parse`] does.
+    ///
+    /// Panics if `cell` is off the map.
+    ///
+    /// ```
+    /// use constrictor_core::config::{Map, MapError, Tile};
+    /// use constrictor_core::math::Vector2;
+    ///
+    /// let mut map = Map::new(3, 1);
+    /// map.set_tile(&Vector2 { x: 1, y: 0 }, Tile::Portal('a'));
+    /// assert_eq!(map.to_string(), ".a.\n");
+    ///
+    /// let error = map.to_string().parse::<Map>().unwrap_err();
+    /// assert!(matches!(error, MapError::UnpairedPortal { line: 1, column: 2, .. }));
+    /// ```
+    pub fn set_tile(&mut self, cell: &Vector2, tile: Tile) {
+        let index = usize::try_from(cell.x)
+            .ok()
+            .filter(|x| *x < self.width)
+            .zip(usize::try_from(cell.y).ok())
+            .map(|(x, y)| y * self.width + x)
+            .filter(|index| *index < self.tiles.len())
+            .expect("cell should be on the map");
+
+        self.tiles[index] = tile;
+    }
+
+    /// Gets the number of columns in this [`Map`].
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the number of rows in this [`Map`].
+    pub const fn height(&self) -> usize {
+        self.tiles.len() / self.width
+    }
+
+    /// Gets the [`Tile`] at `cell`. Everything off the map is wall.
+    pub fn tile(&self, cell: &Vector2) -> Tile {
+        let (Ok(x), Ok(y)) = (usize::try_from(cell.x), usize::try_from(cell.y)) else {
+            return Tile::Wall;
+        };
+
+        if x >= self.width {
+            return Tile::Wall;
+        }
+
+        self.tiles
+            .get(y * self.width + x)
+            .copied()
+            .unwrap_or(Tile::Wall)
+    }
+
+    /// Returns an [`Iterator`] over every cell and its [`Tile`], in reading
+    /// order.
+    pub fn cells(&self) -> impl Iterator<Item = (Vector2, Tile)> + '_ {
+        (0..)
+            .zip(self.tiles.chunks(self.width))
+            .flat_map(|(y, row)| {
+                (0..)
+                    .zip(row)
+                    .map(move |(x, tile)| (Vector2 { x, y }, *tile))
+            })
+    }
+
+    /// Gets the cells holding `tile`, in reading order.
+    fn cells_with(&self, tile: Tile) -> Vec<Vector2> {
+        self.cells()
+            .filter(|(_, found)| *found == tile)
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Gets the cells snakes can start on, in reading order.
+    pub fn spawns(&self) -> Vec<Vector2> {
+        self.cells_with(Tile::Spawn)
+    }
+
+    /// Gets the cells food can appear on, in reading order. Food can appear
+    /// anywhere if this is empty.
+    pub fn food_region(&self) -> Vec<Vector2> {
+        self.cells_with(Tile::Food)
+    }
+
+    /// Gets the [`Portal`]s linking cells of this [`Map`], in the reading
+    /// order of their first ends.
+    pub fn portals(&self) -> Vec<Portal> {
+        let mut first_ends: Vec<(char, Vector2)> = Vec::new();
+        let mut portals = Vec::new();
+
+        for (cell, tile) in self.cells() {
+            let Tile::Portal(letter) = tile else {
+                continue;
+            };
+
+            match first_ends.iter().position(|(seen, _)| *seen == letter) {
+                Some(index) => portals.push((
+                    index,
+                    Portal {
+                        a: first_ends[index].1,
+                        b: cell,
+                    },
+                )),
+                None => first_ends.push((letter, cell)),
+            }
+        }
+
+        portals.sort_by_key(|(index, _)| *index);
+        portals.into_iter().map(|(_, portal)| portal).collect()
+    }
+
+    /// Creates the [`Board`] this [`Map`] describes, bounded as tightly as
+    /// possible around its playable cells and masked down to them. Wall
+    /// around the edges is left for the board's own wall.
+    pub fn board(&self) -> Board {
+        let playable: Vec<_> = self
+            .cells()
+            .filter(|(_, tile)| tile.is_playable())
+            .map(|(cell, _)| cell)
+            .collect();
+
+        let xs = playable.iter().map(|cell| cell.x);
+        let ys = playable.iter().map(|cell| cell.y);
+        let (min_x, max_x) = (xs.clone().min(), xs.max());
+        let (min_y, max_y) = (ys.clone().min(), ys.max());
+
+        // There is always a playable cell, which parsing makes sure of
+        let board = Board::new(
+            (min_x.unwrap_or(0), max_x.unwrap_or(0) + 1),
+            (min_y.unwrap_or(0), max_y.unwrap_or(0) + 1),
+        )
+        .with_mask(playable);
+
+        self.portals().into_iter().fold(board, Board::with_portal)
+    }
+}
@@ -0,0 +1,356 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{
+    config::{Map, MapError, Tile},
+    math::{Direction, Vector2},
+    models::Snake,
+};
+
+/// The puzzles that come with the game, easiest first, as their name, move
+/// limit and [`Layout`].
+const BUILT_IN: [(&str, u32, &str); 5] = [
+    (
+        "First Steps",
+        16,
+        "\
+##########
+#........#
+#.***@.1.#
+#........#
+#.2....3.#
+##########",
+    ),
+    (
+        "Hairpin",
+        12,
+        "\
+###########
+#.........#
+#.#######.#
+#@******#1#
+###########",
+    ),
+    (
+        "Coiled",
+        15,
+        "\
+########
+#.....3#
+#.****.#
+#.*1.*.#
+#.*.@*.#
+#2.....#
+########",
+    ),
+    (
+        "Looking Glass",
+        10,
+        "\
+##########
+#a..#....#
+#...#.3..#
+#.1.#..a.#
+#...#.2..#
+#*@.#....#
+##########",
+    ),
+    (
+        "Spiral",
+        32,
+        "\
+#########
+#1......#
+#.#####.#
+#.#3..#.#
+#.#.#.#.#
+#.#.#2#.#
+#.....#.#
+#######.#
+#*@.....#
+#########",
+    ),
+];
+
+/// A puzzle: a fixed board, snake and food, and only so many moves to eat
+/// all of the food in. Played by giving it as [`GameConfig::scenario`].
+///
+/// [`GameConfig::scenario`]: crate::config::GameConfig::scenario
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Scenario {
+    pub name: String,
+
+    /// How many moves there are to eat all of the food in, a move being one
+    /// cell travelled.
+    pub moves: u32,
+
+    /// Where everything starts.
+    pub layout: Layout,
+}
+
+impl Scenario {
+    /// Gets the puzzles that come with the game, easiest first.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::{GameConfig, Scenario};
+    ///
+    /// for scenario in Scenario::built_in() {
+    ///     let config = GameConfig {
+    ///         scenario: Some(scenario),
+    ///         seed: Some(1),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     assert!(config.build().is_ok());
+    /// }
+    /// ```
+    pub fn built_in() -> Vec<Self> {
+        BUILT_IN
+            .into_iter()
+            .map(|(name, moves, layout)| Self {
+                name: name.to_string(),
+                moves,
+                layout: layout.parse().expect("built-in scenarios should be valid"),
+            })
+            .collect()
+    }
+}
+
+/// The board, snake and food of a [`Scenario`], drawn as text in the same
+/// way as a [`Map`], with a few more characters:
+///
+/// ```text
+/// ##########
+/// #.***@...#
+/// #......1.#
+/// #.2......#
+/// ##########
+/// ```
+///
+/// - `@` is the snake's head, and each `*` another segment of its body,
+///   which runs in a single line from the head. The snake faces away from
+///   the segment behind its head, or right if there isn't one.
+/// - `1` to `9` are the food, eaten in that order. Only one piece is on the
+///   board at a time, and the next appears once it is eaten.
+///
+/// Everything else is read as a [`Map`], except that spawn points and food
+/// regions are plain floor.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::{Layout, ScenarioError};
+/// use constrictor_core::math::{Direction, Vector2};
+///
+/// let layout: Layout = "#######\n#*@.1.#\n#######".parse().unwrap();
+/// assert_eq!(layout.snake().len(), 2);
+/// assert_eq!(layout.snake().facing(), Direction::Right);
+/// assert_eq!(layout.food(), [Vector2 { x: 4, y: 1 }]);
+///
+/// assert_eq!(
+///     "#*.@.1#".parse::<Layout>(),
+///     Err(ScenarioError::Body { line: 1, column: 2 })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Layout {
+    map: Map,
+
+    /// The snake's body, from head to tail.
+    snake: Vec<Vector2>,
+
+    /// The food, in the order it is eaten. Never empty.
+    food: Vec<Vector2>,
+}
+
+/// Describes why text could not be read as a [`Layout`]. Lines and columns
+/// are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioError {
+    /// The board around everything else isn't a valid [`Map`].
+    Map(MapError),
+
+    /// There isn't exactly one `@` for the snake's head.
+    Head,
+
+    /// A segment of the snake's body isn't part of a single line running
+    /// from the head.
+    Body { line: usize, column: usize },
+
+    /// The food isn't numbered from 1 up, with each number used once.
+    Food,
+}
+
+impl Error for ScenarioError {}
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Map(e) => e.fmt(f),
+            Self::Head => f.write_str("layout must have exactly one `@` for the snake's head"),
+            Self::Body { line, column } => write!(
+                f,
+                "line {line}, column {column}: the snake's body must run in a single line from \
+                 its head"
+            ),
+            Self::Food => {
+                f.write_str("food must be numbered from 1 up, with each number used once")
+            }
+        }
+    }
+}
+
+impl From<MapError> for ScenarioError {
+    fn from(value: MapError) -> Self {
+        Self::Map(value)
+    }
+}
+
+impl FromStr for Layout {
+    type Err = ScenarioError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut head = None;
+        let mut body = Vec::new();
+        let mut food = Vec::new();
+        let mut board = String::with_capacity(text.len());
+
+        for (y, line) in (0..).zip(text.lines()) {
+            for (x, glyph) in (0..).zip(line.chars()) {
+                let cell = Vector2 { x, y };
+                match glyph {
+                    '@' if head.replace(cell).is_some() => return Err(ScenarioError::Head),
+                    '@' => {}
+                    '*' => body.push(cell),
+                    '1'..='9' => food.push((glyph, cell)),
+                    _ => {
+                        board.push(glyph);
+                        continue;
+                    }
+                }
+
+                // Whatever is on it, the cell itself is floor
+                board.push(Tile::Floor.glyph());
+            }
+
+            board.push('\n');
+        }
+
+        let map: Map = board.parse()?;
+        let snake = trace_body(head.ok_or(ScenarioError::Head)?, body)?;
+
+        food.sort_by_key(|(glyph, _)| *glyph);
+        let numbered = food
+            .iter()
+            .zip('1'..='9')
+            .all(|((glyph, _), expected)| *glyph == expected);
+        if food.is_empty() || !numbered {
+            return Err(ScenarioError::Food);
+        }
+
+        Ok(Self {
+            map,
+            snake,
+            food: food.into_iter().map(|(_, cell)| cell).collect(),
+        })
+    }
+}
+
+/// Orders `body` into a line running from `head`, each segment next to the
+/// one before it. Fails at the first segment, in reading order, left out of
+/// the line, or if the line splits in two.
+fn trace_body(head: Vector2, mut body: Vec<Vector2>) -> Result<Vec<Vector2>, ScenarioError> {
+    let mut snake = vec![head];
+
+    while !body.is_empty() {
+        let last = snake[snake.len() - 1];
+        let next: Vec<usize> = (0..body.len())
+            .filter(|&i| body[i].manhattan_distance(last) == 1)
+            .collect();
+
+        match next[..] {
+            [index] => snake.push(body.swap_remove(index)),
+            _ => {
+                let stray = body.iter().min_by_key(|cell| (cell.y, cell.x));
+                let stray = stray.copied().unwrap_or(last);
+
+                return Err(ScenarioError::Body {
+                    line: stray.y as usize + 1,
+                    column: stray.x as usize + 1,
+                });
+            }
+        }
+    }
+
+    Ok(snake)
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut row = 0;
+
+        for (cell, tile) in self.map.cells() {
+            if cell.y != row {
+                writeln!(f)?;
+                row = cell.y;
+            }
+
+            let glyph = if cell == self.snake[0] {
+                '@'
+            } else if self.snake.contains(&cell) {
+                '*'
+            } else if let Some(index) = self.food.iter().position(|food| *food == cell) {
+                char::from_digit(index as u32 + 1, 10).unwrap_or('.')
+            } else {
+                tile.glyph()
+            };
+
+            write!(f, "{glyph}")?;
+        }
+
+        writeln!(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<String> for Layout {
+    type Error = ScenarioError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Layout> for String {
+    fn from(value: Layout) -> Self {
+        value.to_string()
+    }
+}
+
+impl Layout {
+    /// Gets the [`Map`] of the board, with nothing on it.
+    pub const fn map(&self) -> &Map {
+        &self.map
+    }
+
+    /// Creates the [`Snake`] as it starts.
+    pub fn snake(&self) -> Snake {
+        let facing = match self.snake.get(1) {
+            Some(neck) => Direction::ALL
+                .into_iter()
+                .find(|direction| neck.neighbour(*direction, 1) == self.snake[0])
+                .unwrap_or(Direction::Right),
+            None => Direction::Right,
+        };
+
+        Snake::from_body(self.snake.iter().copied(), facing).expect("a snake always has a head")
+    }
+
+    /// Gets the cells the food appears on, in the order it is eaten.
+    pub fn food(&self) -> &[Vector2] {
+        &self.food
+    }
+}
@@ -1,3 +1,57 @@
+//! Frontend-agnostic rules engine for Classic Snake.
+//!
+//! # Feature flags
+//! Everything beyond the simulation itself is opt-in, so the minimal build is
+//! simply `cargo build -p constrictor-core --no-default-features`.
+//!
+//! - `os-rng` (default): seed simulations from the operating system when no
+//!   seed is given. Disable this for targets without an entropy source, such
+//!   as `wasm32-unknown-unknown`.
+//! - `serde`: `Serialize`/`Deserialize` impls for the math and model types,
+//!   and the messages of the network protocol.
+//! - `arbitrary`: `Arbitrary` impls for the math types and rules, used by the
+//!   fuzz targets in `fuzz/`.
+//! - `proto`: Protocol Buffers encodings of the network protocol, in
+//!   `net::proto`, for clients not written in Rust. The schema is
+//!   `proto/constrictor.proto`.
+//!
+//! Every combination of features is expected to build; `scripts/feature-matrix.sh`
+//! checks this.
+
+pub mod analysis {
+    pub mod level_check;
+
+    pub use level_check::*;
+}
+
+pub mod bots {
+    pub mod a_star_bot;
+    pub mod controller;
+    pub mod flood_fill_bot;
+    pub mod greedy_bot;
+    pub mod random_bot;
+    pub mod tournament;
+
+    pub use a_star_bot::*;
+    pub use controller::Controller;
+    pub use flood_fill_bot::*;
+    pub use greedy_bot::*;
+    pub use random_bot::*;
+    pub use tournament::*;
+}
+
+pub mod config {
+    pub mod challenge;
+    pub mod game_config;
+    pub mod map;
+    pub mod scenario;
+
+    pub use challenge::*;
+    pub use game_config::*;
+    pub use map::*;
+    pub use scenario::*;
+}
+
 pub mod math {
     pub mod direction;
     pub mod vector2;
@@ -7,11 +61,71 @@ pub mod math {
 }
 
 pub mod models {
+    pub mod appearance;
     pub mod board;
+    pub mod doorway;
+    pub mod events;
+    pub mod lockstep;
+    pub mod occupancy;
+    pub mod portal;
+    pub mod power_up;
+    pub mod rollback;
+    pub mod rules;
+    pub mod simulation_view;
     pub mod snake;
     pub mod snake_simulation;
+    pub mod terrain;
+    pub mod tick_source;
+    pub mod turn_batch;
 
+    pub use appearance::*;
     pub use board::*;
+    pub use doorway::*;
+    pub use events::*;
+    pub use lockstep::*;
+    pub use occupancy::*;
+    pub use portal::*;
+    pub use power_up::*;
+    pub use rollback::*;
+    pub use rules::*;
+    pub use simulation_view::*;
     pub use snake::*;
     pub use snake_simulation::*;
+    pub use terrain::*;
+    pub use tick_source::*;
+    pub use turn_batch::*;
+}
+
+pub mod net {
+    #[cfg(feature = "proto")]
+    pub mod proto;
+    pub mod protocol;
+
+    pub use protocol::*;
+}
+
+pub mod render {
+    pub mod annotations;
+    pub mod braille;
+    pub mod frame_buffer;
+    pub mod raster;
+    pub mod text_frame;
+
+    pub use annotations::*;
+    pub use braille::*;
+    pub use frame_buffer::*;
+    pub use raster::*;
+    pub use text_frame::*;
+}
+
+pub mod replay {
+    pub mod determinism;
+    pub mod golden;
+    pub mod input_log;
+    pub mod playback;
+
+    pub use determinism::*;
+    pub use golden::*;
+    pub use input_log::*;
+    pub use playback::*;
 }
@@ -1,4 +1,6 @@
 #[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Direction {
     Up,
     Right,
@@ -7,6 +9,14 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Every [`Direction`], in clockwise order starting from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
     /// Get the [`Direction`] that is 90 degrees counter-clockwise from `self`.
     ///
     /// # Example
@@ -3,6 +3,8 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 use crate::math::Direction;
 
 #[derive(Hash, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Vector2<T = i32> {
     pub x: T,
     pub y: T,
@@ -44,6 +46,24 @@ impl<T: Add<Output = T> + Sub<Output = T>> Vector2<T> {
     }
 }
 
+impl Vector2 {
+    /// Gets the Manhattan (taxicab) distance between `self` and `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::Vector2;
+    ///
+    /// let a = Vector2 { x: 1, y: -2 };
+    /// let b = Vector2 { x: -3, y: 4 };
+    ///
+    /// assert_eq!(a.manhattan_distance(b), 10);
+    /// assert_eq!(b.manhattan_distance(a), 10);
+    /// ```
+    pub const fn manhattan_distance(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
 impl<T: AddAssign + SubAssign> Vector2<T> {
     /// Moves `self` by `magnitude` in `direction`.
     ///
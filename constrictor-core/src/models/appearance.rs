@@ -0,0 +1,35 @@
+/// Cosmetic details of a [`Snake`](crate::models::Snake), so every renderer
+/// and spectator can label and style it the same way. Appearance never
+/// affects how the game plays.
+///
+/// Colours and skins are identifiers rather than concrete styles. Each
+/// frontend maps them onto whatever it can draw, and `0` is always the
+/// frontend's default.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Appearance, Snake};
+///
+/// let snek = Snake::new(Vector2 { x: 4, y: 2 }, Direction::Right).with_appearance(Appearance {
+///     name: Some("Monty".into()),
+///     color: 3,
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(snek.appearance().name.as_deref(), Some("Monty"));
+/// assert_eq!(snek.appearance().skin, 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Appearance {
+    /// The name shown alongside the snake, if it has one.
+    pub name: Option<String>,
+
+    /// Identifies the colour the snake is drawn in.
+    pub color: u16,
+
+    /// Identifies the glyphs or sprites the snake is drawn with.
+    pub skin: u16,
+}
@@ -1,12 +1,32 @@
-use std::ops::Range;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
-use crate::math::Vector2;
+use rand::{Rng, seq::IteratorRandom};
 
+use crate::{
+    math::{Direction, Vector2},
+    models::{BoardStep, Doorway, DoorwayTarget, Occupancy, Portal, Terrain},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "BoardBounds", into = "BoardBounds")
+)]
 pub struct Board {
     min_x: i32,
     min_y: i32,
     max_x: i32,
     max_y: i32,
+    doorways: Vec<Doorway>,
+    portals: Vec<Portal>,
+    terrain: HashMap<Vector2, Terrain>,
+
+    /// The playable cells, if not every cell within the bounds is playable.
+    mask: Option<HashSet<Vector2>>,
 }
 
 impl Board {
@@ -29,7 +49,219 @@ impl Board {
             max_x: x.0.max(x.1),
             min_y: y.0.min(y.1),
             max_y: y.0.max(y.1),
+            doorways: Vec::new(),
+            portals: Vec::new(),
+            terrain: HashMap::new(),
+            mask: None,
+        }
+    }
+
+    /// Narrows the playable cells of this [`Board`] down to `cells`, giving it
+    /// any shape that fits within its bounds. Every other cell is a wall.
+    /// Replaces any mask already applied.
+    ///
+    /// Panics if any of `cells` is outside the bounds, or if any [`Portal`]
+    /// or [`Terrain`] already on the board would be left off it.
+    ///
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, BoardStep};
+    ///
+    /// // A donut, with a hole in the middle
+    /// let hole = Vector2 { x: 1, y: 1 };
+    /// let board = Board::new((0, 3), (0, 3)).with_mask(
+    ///     Board::new((0, 3), (0, 3)).cell_iter().filter(|cell| *cell != hole),
+    /// );
+    ///
+    /// assert!(!board.contains(&hole));
+    /// assert_eq!(board.cell_iter().count(), 8);
+    ///
+    /// let step = board.step(&Vector2 { x: 0, y: 1 }, Direction::Right);
+    /// assert_eq!(step, BoardStep::Blocked);
+    /// ```
+    pub fn with_mask<I: IntoIterator<Item = Vector2>>(mut self, cells: I) -> Self {
+        let mask: HashSet<_> = cells.into_iter().collect();
+        assert!(mask.iter().all(|cell| self.in_bounds(cell)));
+
+        self.mask = Some(mask);
+        assert!(
+            self.portals
+                .iter()
+                .all(|portal| self.contains(&portal.a) && self.contains(&portal.b))
+        );
+        assert!(self.terrain.keys().all(|cell| self.contains(cell)));
+
+        self
+    }
+
+    /// Opens a [`Doorway`] in the outer wall of this [`Board`].
+    pub fn with_doorway(mut self, doorway: Doorway) -> Self {
+        self.doorways.push(doorway);
+        self
+    }
+
+    /// Links two cells of this [`Board`] with a [`Portal`].
+    ///
+    /// Panics if either end is off the board, both ends are the same cell, or
+    /// either end is already an end of another [`Portal`].
+    pub fn with_portal(mut self, portal: Portal) -> Self {
+        assert!(self.contains(&portal.a) && self.contains(&portal.b));
+        assert_ne!(portal.a, portal.b);
+        assert!(
+            self.portals
+                .iter()
+                .all(|other| !other.is_occupied(&portal.a) && !other.is_occupied(&portal.b))
+        );
+
+        self.portals.push(portal);
+        self
+    }
+
+    /// Lays `terrain` on `cell` of this [`Board`], replacing whatever terrain
+    /// was there.
+    ///
+    /// Panics if `cell` is off the board.
+    pub fn with_terrain(mut self, cell: Vector2, terrain: Terrain) -> Self {
+        assert!(self.contains(&cell));
+
+        self.terrain.insert(cell, terrain);
+        self
+    }
+
+    /// Opens every cell of every wall of this [`Board`], wrapping around to
+    /// the opposite side.
+    ///
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, BoardStep};
+    ///
+    /// let board = Board::new((0, 4), (0, 4)).wrapping();
+    ///
+    /// let step = board.step(&Vector2 { x: 0, y: 1 }, Direction::Left);
+    /// assert_eq!(step, BoardStep::To(Vector2 { x: 3, y: 1 }));
+    /// ```
+    pub fn wrapping(self) -> Self {
+        let (x_range, y_range) = (self.x_range(), self.y_range());
+
+        Direction::ALL.into_iter().fold(self, |board, side| {
+            let span = match side {
+                Direction::Up | Direction::Down => x_range.clone(),
+                Direction::Left | Direction::Right => y_range.clone(),
+            };
+
+            board.with_doorway(Doorway {
+                side,
+                span,
+                leads_to: DoorwayTarget::Wrap,
+            })
+        })
+    }
+
+    /// Returns the [`Doorway`]s in the outer wall of this [`Board`].
+    pub fn doorways(&self) -> &[Doorway] {
+        &self.doorways
+    }
+
+    /// Returns the [`Portal`]s linking cells of this [`Board`].
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    /// Returns the playable cells of this [`Board`], or [`None`] if every
+    /// cell within its bounds is playable.
+    pub const fn mask(&self) -> Option<&HashSet<Vector2>> {
+        self.mask.as_ref()
+    }
+
+    /// Returns the [`Terrain`] of every cell that has any.
+    pub const fn terrain(&self) -> &HashMap<Vector2, Terrain> {
+        &self.terrain
+    }
+
+    /// Returns the [`Terrain`] of `cell`, or [`None`] if it is plain ground.
+    pub fn terrain_at(&self, cell: &Vector2) -> Option<Terrain> {
+        self.terrain.get(cell).copied()
+    }
+
+    /// Returns the [`Doorway`] a snake at `from` would pass through by moving
+    /// towards `side`, if any. This only considers the coordinate of `from`
+    /// along `side`, not whether `from` is actually at the edge.
+    pub fn doorway(&self, from: &Vector2, side: Direction) -> Option<&Doorway> {
+        self.doorways
+            .iter()
+            .find(|doorway| doorway.side == side && doorway.spans(from))
+    }
+
+    /// Determines where moving one cell in `direction` from `from` ends up,
+    /// taking [`Doorway`]s and [`Portal`]s into account.
+    ///
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, BoardStep};
+    ///
+    /// let board = Board::new((0, 4), (0, 4));
+    ///
+    /// let step = board.step(&Vector2 { x: 1, y: 1 }, Direction::Right);
+    /// assert_eq!(step, BoardStep::To(Vector2 { x: 2, y: 1 }));
+    ///
+    /// let step = board.step(&Vector2 { x: 3, y: 1 }, Direction::Right);
+    /// assert_eq!(step, BoardStep::Blocked);
+    /// ```
+    pub fn step(&self, from: &Vector2, direction: Direction) -> BoardStep {
+        let mut from = *from;
+
+        // Portals can lead straight into one another, but never round in circles
+        for _ in 0..=self.portals.len() {
+            let step = self.step_past_walls(&from, direction);
+            let BoardStep::To(cell) = step else {
+                return step;
+            };
+
+            match self.portals.iter().find_map(|portal| portal.twin(&cell)) {
+                Some(twin) => from = twin,
+                None => return step,
+            }
         }
+
+        BoardStep::Blocked
+    }
+
+    /// Like [`Self::step`], but ignoring [`Portal`]s.
+    fn step_past_walls(&self, from: &Vector2, direction: Direction) -> BoardStep {
+        let next = from.neighbour(direction, 1);
+
+        if self.contains(&next) {
+            return BoardStep::To(next);
+        }
+
+        match self.doorway(from, direction).map(|d| d.leads_to) {
+            None => BoardStep::Blocked,
+            // Walls left by the mask are inside the board, so lead nowhere else
+            Some(DoorwayTarget::Board(_)) if self.in_bounds(&next) => BoardStep::Blocked,
+            Some(DoorwayTarget::Board(target)) => BoardStep::Exit(target),
+            Some(DoorwayTarget::Wrap) => self.wrap(from, direction),
+        }
+    }
+
+    /// Finds the next playable cell from `from` in `direction`, carrying on
+    /// around to the opposite side of the bounds and past any gaps in the
+    /// mask, or coming back round to `from` itself if there is no other.
+    fn wrap(&self, from: &Vector2, direction: Direction) -> BoardStep {
+        let span = match direction {
+            Direction::Up | Direction::Down => self.height(),
+            Direction::Left | Direction::Right => self.width(),
+        };
+
+        (1..=span)
+            .map(|distance| {
+                let cell = from.neighbour(direction, distance);
+                Vector2 {
+                    x: (cell.x - self.min_x).rem_euclid(self.width()) + self.min_x,
+                    y: (cell.y - self.min_y).rem_euclid(self.height()) + self.min_y,
+                }
+            })
+            .find(|cell| self.contains(cell))
+            .map_or(BoardStep::Blocked, BoardStep::To)
     }
 
     /// Returns the width of this [`Board`].
@@ -76,13 +308,71 @@ impl Board {
         self.min_y..self.max_y
     }
 
-    /// Returns an [`Iterator<Item = Vector2>`] over the cells in the board.
+    /// Returns the cell at the centre of this [`Board`], rounding towards the
+    /// lower bound on each axis.
+    ///
+    /// ```
+    /// use constrictor_core::models::Board;
+    /// use constrictor_core::math::Vector2;
+    ///
+    /// assert_eq!(Board::new((-10, 10), (-5, 5)).center(), Vector2 { x: 0, y: 0 });
+    /// assert_eq!(Board::new((0, 5), (0, 4)).center(), Vector2 { x: 2, y: 2 });
+    /// ```
+    pub const fn center(&self) -> Vector2 {
+        Vector2 {
+            x: self.min_x + self.width() / 2,
+            y: self.min_y + self.height() / 2,
+        }
+    }
+
+    /// Returns which ring of this [`Board`] `point` lies in, counting inward
+    /// from the edge of its bounds, or [`None`] if `point` is not on the board. The cells
+    /// along the edge make up ring 0, the cells just inside those ring 1, and
+    /// so on.
+    ///
+    /// ```
+    /// use constrictor_core::models::Board;
+    /// use constrictor_core::math::Vector2;
+    ///
+    /// let board = Board::new((0, 5), (0, 4));
+    /// assert_eq!(board.ring(&Vector2 { x: 4, y: 1 }), Some(0));
+    /// assert_eq!(board.ring(&Vector2 { x: 2, y: 2 }), Some(1));
+    /// assert_eq!(board.ring(&Vector2 { x: 5, y: 2 }), None);
+    /// assert_eq!(board.ring_count(), 2);
+    /// ```
+    pub fn ring(&self, point: &Vector2) -> Option<u32> {
+        if !self.contains(point) {
+            return None;
+        }
+
+        let from_edge = (point.x - self.min_x)
+            .min(self.max_x - 1 - point.x)
+            .min(point.y - self.min_y)
+            .min(self.max_y - 1 - point.y);
+
+        Some(from_edge as u32)
+    }
+
+    /// Returns how many rings of cells this [`Board`] has. See [`Self::ring`].
+    pub const fn ring_count(&self) -> u32 {
+        let shortest = if self.width() < self.height() {
+            self.width()
+        } else {
+            self.height()
+        };
+
+        (shortest as u32).div_ceil(2)
+    }
+
+    /// Returns an [`Iterator<Item = Vector2>`] over the playable cells in the
+    /// board, in row-major order.
     pub fn cell_iter(&self) -> impl Iterator<Item = Vector2> {
         self.y_range()
             .flat_map(|y| self.x_range().map(move |x| Vector2 { x, y }))
+            .filter(|cell| self.mask.as_ref().is_none_or(|mask| mask.contains(cell)))
     }
 
-    /// Determines whether or not `point` is contained within the [`Board`].
+    /// Determines whether or not `point` is a playable cell of the [`Board`].
     ///
     /// ```
     /// use constrictor_core::models::Board;
@@ -92,31 +382,164 @@ impl Board {
     /// assert!(!Board::new((-10, 10), (-5, 5)).contains(&Vector2 { x: 10, y: 5 }));
     /// ```
     pub fn contains(&self, point: &Vector2) -> bool {
+        self.in_bounds(point) && self.mask.as_ref().is_none_or(|mask| mask.contains(point))
+    }
+
+    /// Determines whether or not `point` is within the bounds of the
+    /// [`Board`], whether or not it is playable.
+    pub fn in_bounds(&self, point: &Vector2) -> bool {
         self.x_range().contains(&point.x) && self.y_range().contains(&point.y)
     }
 
-    /// Generates a random free cell according to `is_taken`. Returns [`None`]
-    /// if no free cell could be found.
-    ///
-    /// ### Note:
-    /// If, and only if, `taken_cell_count` is guaranteed to be the same number
-    /// of cells as `is_taken` would find when run on the entire board, the
-    /// following additional guarantees hold:
-    /// - if at least one free cell exists, [`None`] will never be returned, and
-    /// - the free cell is chosen with uniformity, as specified by
-    ///   [`rand::distr::Uniform`]
-    pub fn random_free_cell<F: Fn(&Vector2) -> bool>(
-        &self,
-        taken_cell_count: usize,
-        is_taken: F,
-    ) -> Option<Vector2> {
-        let total_cells = (self.width() as usize) * (self.height() as usize);
-        let free_cells = total_cells - taken_cell_count;
-
-        let target_cell = rand::random_range(0..free_cells);
-
-        self.cell_iter()
-            .filter(|cell| !is_taken(cell))
-            .nth(target_cell)
+    /// Returns a [`FreeCells`] query over every cell in this [`Board`] that
+    /// isn't the end of a [`Portal`], which can be narrowed down by excluding
+    /// any number of [`Occupancy`] sources.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Proximity, Snake};
+    /// use rand::{SeedableRng, rngs::StdRng};
+    ///
+    /// let board = Board::new((0, 4), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let obstacles = HashSet::from([Vector2 { x: 3, y: 0 }]);
+    ///
+    /// let free = board.free_cells().excluding(&snake).excluding(&obstacles);
+    /// assert_eq!(free.count(), 2);
+    ///
+    /// let near_head = Proximity { center: *snake.head(), min_distance: 2 };
+    /// let far_from_head = free.excluding(&near_head);
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// assert_eq!(far_from_head.choose(&mut rng), Some(Vector2 { x: 2, y: 0 }));
+    /// ```
+    pub fn free_cells(&self) -> FreeCells<'_> {
+        FreeCells {
+            board: self,
+            exclusions: vec![&self.portals],
+        }
+    }
+}
+
+/// A query over the cells of a [`Board`] that are not covered by any of a set
+/// of excluded [`Occupancy`] sources. Created by [`Board::free_cells`].
+#[derive(Clone)]
+pub struct FreeCells<'a> {
+    board: &'a Board,
+    exclusions: Vec<&'a dyn Occupancy>,
+}
+
+impl<'a> FreeCells<'a> {
+    /// Excludes every cell occupied by `source`.
+    pub fn excluding<O: Occupancy>(mut self, source: &'a O) -> Self {
+        self.exclusions.push(source as &'a dyn Occupancy);
+        self
+    }
+
+    /// Returns whether or not `cell` is on the [`Board`] and not excluded.
+    pub fn contains(&self, cell: &Vector2) -> bool {
+        self.board.contains(cell) && !self.exclusions.iter().any(|o| o.is_occupied(cell))
+    }
+
+    /// Returns an [`Iterator`] over the free cells, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Vector2> {
+        self.board
+            .cell_iter()
+            .filter(|cell| !self.exclusions.iter().any(|o| o.is_occupied(cell)))
+    }
+
+    /// Counts the free cells.
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Picks a free cell uniformly at random using `rng`, in a single pass
+    /// over the [`Board`]. Returns [`None`] if there are no free cells.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Vector2> {
+        self.iter().choose(rng)
+    }
+}
+
+/// Unvalidated mirror of [`Board`] used to reject inverted or empty bounds
+/// during deserialization. Terrain is kept as a list, sorted by cell, since
+/// not every format allows a cell as a map key, and so is the mask.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardBounds {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+    #[serde(default)]
+    doorways: Vec<Doorway>,
+    #[serde(default)]
+    portals: Vec<Portal>,
+    #[serde(default)]
+    terrain: Vec<(Vector2, Terrain)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mask: Option<Vec<Vector2>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardBounds {
+    fn from(value: Board) -> Self {
+        let mut terrain: Vec<_> = value.terrain.into_iter().collect();
+        terrain.sort_by_key(|(cell, _)| (cell.y, cell.x));
+
+        let mask = value.mask.map(|mask| {
+            let mut mask: Vec<_> = mask.into_iter().collect();
+            mask.sort_by_key(|cell| (cell.y, cell.x));
+            mask
+        });
+
+        Self {
+            min_x: value.min_x,
+            min_y: value.min_y,
+            max_x: value.max_x,
+            max_y: value.max_y,
+            doorways: value.doorways,
+            portals: value.portals,
+            terrain,
+            mask,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardBounds> for Board {
+    type Error = &'static str;
+
+    fn try_from(value: BoardBounds) -> Result<Self, Self::Error> {
+        if value.min_x >= value.max_x || value.min_y >= value.max_y {
+            return Err("board bounds must have min < max on each axis");
+        }
+
+        let board = Self::new((value.min_x, value.max_x), (value.min_y, value.max_y));
+        let board = match value.mask {
+            Some(mask) if mask.iter().all(|cell| board.in_bounds(cell)) => board.with_mask(mask),
+            Some(_) => return Err("masked cells must be within the board's bounds"),
+            None => board,
+        };
+
+        let misplaced = |portal: &Portal| {
+            !board.contains(&portal.a) || !board.contains(&portal.b) || portal.a == portal.b
+        };
+
+        if value.portals.iter().any(misplaced) {
+            return Err("portals must link two different cells on the board");
+        }
+
+        if value.terrain.iter().any(|(cell, _)| !board.contains(cell)) {
+            return Err("terrain must be on the board");
+        }
+
+        let board = value.doorways.into_iter().fold(board, Self::with_doorway);
+        let board = value.portals.into_iter().fold(board, Self::with_portal);
+        Ok(value
+            .terrain
+            .into_iter()
+            .fold(board, |board, (cell, terrain)| {
+                board.with_terrain(cell, terrain)
+            }))
     }
 }
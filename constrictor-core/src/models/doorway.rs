@@ -0,0 +1,84 @@
+use std::ops::Range;
+
+use crate::math::{Direction, Vector2};
+
+/// An opening in the outer wall of a [`Board`](crate::models::Board).
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, BoardStep, Doorway, DoorwayTarget};
+///
+/// // A gap in the top wall above x = 2 and x = 3, wrapping to the bottom.
+/// let board = Board::new((0, 6), (0, 6)).with_doorway(Doorway {
+///     side: Direction::Up,
+///     span: 2..4,
+///     leads_to: DoorwayTarget::Wrap,
+/// });
+///
+/// let through = board.step(&Vector2 { x: 2, y: 0 }, Direction::Up);
+/// assert_eq!(through, BoardStep::To(Vector2 { x: 2, y: 5 }));
+///
+/// let blocked = board.step(&Vector2 { x: 4, y: 0 }, Direction::Up);
+/// assert_eq!(blocked, BoardStep::Blocked);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Doorway {
+    /// The side of the board the opening is in. For example,
+    /// [`Direction::Up`] is the top wall.
+    pub side: Direction,
+
+    /// The cells along [`Self::side`] the opening spans. These are
+    /// x-coordinates for the top and bottom walls, and y-coordinates for the
+    /// left and right walls.
+    pub span: Range<i32>,
+
+    /// Where passing through the opening leads.
+    pub leads_to: DoorwayTarget,
+}
+
+impl Doorway {
+    /// Returns whether or not a snake at `from`, moving towards
+    /// [`Self::side`], would pass through this [`Doorway`] if it were at the
+    /// edge of the board.
+    pub fn spans(&self, from: &Vector2) -> bool {
+        self.span.contains(&along_side(self.side, from))
+    }
+}
+
+/// Gets the coordinate of `point` along the wall on `side`.
+const fn along_side(side: Direction, point: &Vector2) -> i32 {
+    match side {
+        Direction::Up | Direction::Down => point.x,
+        Direction::Left | Direction::Right => point.y,
+    }
+}
+
+/// Where a [`Doorway`] leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DoorwayTarget {
+    /// Back onto the same board, from the opposite side.
+    Wrap,
+
+    /// Off this board and onto another board in a larger world, identified by
+    /// an index that is meaningful to whoever owns the world.
+    Board(usize),
+}
+
+/// The outcome of moving one cell from a point on a
+/// [`Board`](crate::models::Board). See
+/// [`Board::step`](crate::models::Board::step).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardStep {
+    /// The move ends on this cell of the board.
+    To(Vector2),
+
+    /// The move runs into a wall.
+    Blocked,
+
+    /// The move leaves through a [`Doorway`] to another board.
+    Exit(usize),
+}
@@ -0,0 +1,109 @@
+use crate::{config::SpeedCurve, models::PowerUp};
+
+/// Decides on which ticks a [`ScheduledEvent`] happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventTrigger {
+    /// Once, when the simulation reaches the given tick.
+    At(u64),
+
+    /// Every `period` ticks, starting at tick `start`.
+    Every { start: u64, period: u64 },
+
+    /// On any tick, with a one in `one_in` chance. The chance is rolled with
+    /// the simulation's seeded randomness, so it is still reproducible.
+    Randomly { one_in: u32 },
+}
+
+/// What happens when a [`ScheduledEvent`] is triggered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventAction {
+    /// Places up to `count` obstacles on random free cells, keeping clear of
+    /// the snake's head. Obstacles kill the snake like a wall does.
+    SpawnObstacles { count: u32 },
+
+    /// Removes every obstacle from the board.
+    ClearObstacles,
+
+    /// Places a [`PowerUp`] on a random free cell, keeping clear of the
+    /// snakes' heads.
+    SpawnPowerUp(PowerUp),
+
+    /// Replaces the [`SpeedCurve`] the game runs at.
+    SetSpeed(SpeedCurve),
+}
+
+/// An [`EventAction`] paired with the [`EventTrigger`] that sets it off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct ScheduledEvent {
+    pub trigger: EventTrigger,
+    pub action: EventAction,
+}
+
+/// The events a game mode declares, checked by the
+/// [`SnakeSimulation`](crate::models::SnakeSimulation) after every step. Events
+/// triggered on the same tick happen in the order they were added.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::models::{EventAction, EventSchedule, EventTrigger, Terrain};
+/// use constrictor_core::render::Cell;
+///
+/// // Sticky ground all along the left edge, well away from the snake
+/// let terrain = (0..32).map(|y| (Vector2 { x: 0, y }, Terrain::Sticky));
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     terrain: terrain.collect(),
+///     events: EventSchedule::new()
+///         .with(EventTrigger::At(2), EventAction::SpawnObstacles { count: 300 })
+///         .with(EventTrigger::At(4), EventAction::ClearObstacles),
+///     ..Default::default()
+/// };
+///
+/// let mut sim = config.build().unwrap();
+/// sim.advance();
+/// sim.advance();
+/// assert_eq!(sim.view().obstacles().count(), 300);
+///
+/// sim.advance();
+/// sim.advance();
+/// assert_eq!(sim.view().obstacles().count(), 0);
+///
+/// // Clearing the obstacles shows whatever they stood on again
+/// let sticky = Cell::Terrain(Terrain::Sticky);
+/// assert!(sim.changes().iter().any(|(_, cell)| *cell == sticky));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct EventSchedule {
+    events: Vec<ScheduledEvent>,
+}
+
+impl EventSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an event to the schedule.
+    pub fn with(mut self, trigger: EventTrigger, action: EventAction) -> Self {
+        self.events.push(ScheduledEvent { trigger, action });
+        self
+    }
+
+    /// Returns an [`Iterator`] over every scheduled event.
+    pub fn iter(&self) -> impl Iterator<Item = &ScheduledEvent> {
+        self.events.iter()
+    }
+
+    /// Returns `true` if nothing is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
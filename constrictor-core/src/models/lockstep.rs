@@ -0,0 +1,168 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    math::Direction,
+    models::{SimulationResult, SnakeSimulation},
+};
+
+/// A [`SnakeSimulation::state_hash`] taken at a particular tick, for peers to
+/// exchange and compare.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    pub tick: u64,
+    pub hash: u64,
+}
+
+/// Describes why a peer's [`Checkpoint`] could not be verified.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Desync {
+    /// Both sides reached `tick`, but disagree about the state of the game.
+    HashMismatch { tick: u64, local: u64, remote: u64 },
+
+    /// There is no local [`Checkpoint`] for the given tick, either because
+    /// the simulation has not reached it yet or because no checkpoint is taken
+    /// on that tick.
+    UnknownTick(u64),
+}
+
+impl Error for Desync {}
+impl Display for Desync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HashMismatch {
+                tick,
+                local,
+                remote,
+            } => write!(
+                f,
+                "state diverged at tick {tick} (local hash {local:016x}, remote hash {remote:016x})"
+            ),
+            Self::UnknownTick(tick) => write!(f, "no local checkpoint for tick {tick}"),
+        }
+    }
+}
+
+/// Drives a [`SnakeSimulation`] in lockstep with identical copies running
+/// elsewhere. Every peer starts from the same parameters and seed, and only
+/// moves are exchanged, one for each snake every tick; each peer applies them
+/// in the same order and periodically compares [`Checkpoint`]s to catch
+/// divergence. How moves and checkpoints travel between peers is up to the
+/// caller.
+///
+/// # Example
+/// ```
+/// use constrictor_core::bots::{Controller, FloodFillBot, GreedyBot};
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Lockstep, Snake, SnakeSimulation};
+///
+/// let make = || {
+///     let board = Board::new((0, 8), (0, 8));
+///     let snakes = vec![
+///         Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right),
+///         Snake::new(Vector2 { x: 7, y: 7 }, Direction::Left),
+///     ];
+///     let sim =
+///         SnakeSimulation::seeded_with_snakes(board, snakes, Vector2 { x: 4, y: 0 }, 7).unwrap();
+///     Lockstep::new(sim, 2)
+/// };
+///
+/// // The host runs a bot for each snake, and sends the peer their moves
+/// let (mut host, mut peer) = (make(), make());
+/// while host.result().is_none() && host.simulation().tick() < 20 {
+///     let moves = [
+///         GreedyBot.next_direction(&host.simulation().view_as(0)),
+///         FloodFillBot.next_direction(&host.simulation().view_as(1)),
+///     ];
+///
+///     let sent = host.step(&moves);
+///     let received = peer.step(&moves);
+///
+///     if let Some(checkpoint) = received {
+///         assert_eq!(sent, Some(checkpoint));
+///         assert!(host.verify(&checkpoint).is_ok());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lockstep {
+    sim: SnakeSimulation,
+    checkpoint_interval: u64,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Lockstep {
+    /// Wraps `sim`, taking a [`Checkpoint`] every `checkpoint_interval`
+    /// ticks. An interval of 0 is treated as 1.
+    pub fn new(sim: SnakeSimulation, checkpoint_interval: u64) -> Self {
+        Self {
+            sim,
+            checkpoint_interval: checkpoint_interval.max(1),
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Get a shared reference to the underlying [`SnakeSimulation`].
+    pub const fn simulation(&self) -> &SnakeSimulation {
+        &self.sim
+    }
+
+    /// Applies this tick's moves and advances the simulation. `moves` holds a
+    /// move for each snake, by index, as it would be given to
+    /// [`SnakeSimulation::change_move_direction`]; snakes with [`None`], or
+    /// past the end of `moves`, keep going the way they face. Returns a
+    /// [`Checkpoint`] to send to the other peers when one is due.
+    pub fn step(&mut self, moves: &[Option<Direction>]) -> Option<Checkpoint> {
+        if self.sim.result().is_some() {
+            return None;
+        }
+
+        for (snake, direction) in moves.iter().enumerate() {
+            if let Some(direction) = direction {
+                self.sim.change_move_direction(snake, *direction);
+            }
+        }
+
+        self.sim.advance();
+
+        let tick = self.sim.tick();
+        let finished = self.sim.result().is_some();
+        if !tick.is_multiple_of(self.checkpoint_interval) && !finished {
+            return None;
+        }
+
+        let checkpoint = Checkpoint {
+            tick,
+            hash: self.sim.state_hash(),
+        };
+
+        self.checkpoints.push(checkpoint);
+        Some(checkpoint)
+    }
+
+    /// Get the final result of the simulation, if it has been determined.
+    pub const fn result(&self) -> Option<&SimulationResult> {
+        self.sim.result()
+    }
+
+    /// Compares a [`Checkpoint`] received from a peer against the local one
+    /// for the same tick.
+    pub fn verify(&self, remote: &Checkpoint) -> Result<(), Desync> {
+        let local = self
+            .checkpoints
+            .iter()
+            .rev()
+            .find(|local| local.tick == remote.tick)
+            .ok_or(Desync::UnknownTick(remote.tick))?;
+
+        if local.hash != remote.hash {
+            return Err(Desync::HashMismatch {
+                tick: remote.tick,
+                local: local.hash,
+                remote: remote.hash,
+            });
+        }
+
+        Ok(())
+    }
+}
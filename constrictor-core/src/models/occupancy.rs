@@ -0,0 +1,70 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{math::Vector2, models::Snake};
+
+/// A source of cells that can't be used, such as a [`Snake`] or a set of
+/// obstacles. Used to exclude cells when sampling with
+/// [`Board::free_cells`](crate::models::Board::free_cells).
+pub trait Occupancy {
+    /// Returns whether or not `cell` is occupied.
+    fn is_occupied(&self, cell: &Vector2) -> bool;
+}
+
+impl Occupancy for Snake {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.contains(cell)
+    }
+}
+
+impl Occupancy for Vector2 {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self == cell
+    }
+}
+
+impl<O: Occupancy> Occupancy for Vec<O> {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.iter().any(|source| source.is_occupied(cell))
+    }
+}
+
+impl Occupancy for HashSet<Vector2> {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.contains(cell)
+    }
+}
+
+impl<V> Occupancy for HashMap<Vector2, V> {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.contains_key(cell)
+    }
+}
+
+/// Occupies every cell closer than [`Self::min_distance`] (by
+/// [`Vector2::manhattan_distance`]) to [`Self::center`]. Useful to keep things
+/// from spawning right under the snake's nose.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::models::{Occupancy, Proximity};
+///
+/// let near_origin = Proximity {
+///     center: Vector2 { x: 0, y: 0 },
+///     min_distance: 2,
+/// };
+///
+/// assert!(near_origin.is_occupied(&Vector2 { x: 1, y: 0 }));
+/// assert!(!near_origin.is_occupied(&Vector2 { x: 1, y: 1 }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proximity {
+    pub center: Vector2,
+    pub min_distance: u32,
+}
+
+impl Occupancy for Proximity {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.center.manhattan_distance(*cell) < self.min_distance
+    }
+}
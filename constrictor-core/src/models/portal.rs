@@ -0,0 +1,52 @@
+use crate::{math::Vector2, models::Occupancy};
+
+/// A pair of linked cells inside a [`Board`](crate::models::Board). A snake
+/// moving into either end comes out of the other, still heading the same way.
+/// Nothing ever occupies the ends themselves.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, BoardStep, Portal};
+///
+/// let board = Board::new((0, 8), (0, 4)).with_portal(Portal {
+///     a: Vector2 { x: 2, y: 1 },
+///     b: Vector2 { x: 6, y: 3 },
+/// });
+///
+/// // Moving into one end leaves from the cell just past the other
+/// let step = board.step(&Vector2 { x: 1, y: 1 }, Direction::Right);
+/// assert_eq!(step, BoardStep::To(Vector2 { x: 7, y: 3 }));
+///
+/// // The same goes for the other way around, and past the edge of the board
+/// let step = board.step(&Vector2 { x: 6, y: 2 }, Direction::Down);
+/// assert_eq!(step, BoardStep::To(Vector2 { x: 2, y: 2 }));
+/// let step = board.step(&Vector2 { x: 2, y: 0 }, Direction::Down);
+/// assert_eq!(step, BoardStep::Blocked);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Portal {
+    pub a: Vector2,
+    pub b: Vector2,
+}
+
+impl Portal {
+    /// Returns the other end of this [`Portal`] if `cell` is one of its ends.
+    pub fn twin(&self, cell: &Vector2) -> Option<Vector2> {
+        if *cell == self.a {
+            Some(self.b)
+        } else if *cell == self.b {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+impl Occupancy for Portal {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        self.twin(cell).is_some()
+    }
+}
@@ -0,0 +1,49 @@
+/// An item lying on the board that gives whichever snake moves onto it an
+/// effect, for better or worse. Power-ups are placed by
+/// [`EventAction::SpawnPowerUp`](crate::models::EventAction::SpawnPowerUp).
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{
+///     Board, DeathReason, EventAction, EventSchedule, EventTrigger, PowerUp, SimulationResult,
+///     Snake, SnakeSimulation,
+/// };
+///
+/// let board = Board::new((0, 8), (0, 1));
+/// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+/// let shield = EventAction::SpawnPowerUp(PowerUp::Shield);
+/// let events = EventSchedule::new().with(EventTrigger::At(1), shield);
+/// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 7, y: 0 }, 3)
+///     .unwrap()
+///     .with_events(events);
+///
+/// // The shield lands ahead of the snake, which picks it up on its way
+/// for _ in 0..7 {
+///     sim.advance();
+/// }
+/// assert!(sim.is_shielded_for(0));
+///
+/// // Reaching the wall uses up the shield rather than killing the snake
+/// assert_eq!(sim.advance(), None);
+/// assert!(!sim.is_shielded_for(0));
+/// assert_eq!(
+///     sim.advance(),
+///     Some(&SimulationResult::Died(DeathReason::HitWall))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerUp {
+    /// Protects the snake from its next collision with a wall, an obstacle
+    /// or itself. Running into a wall or obstacle cancels that move, so the
+    /// snake holds still for a step; running into itself lets it carry on
+    /// over its own body. Either way the shield is used up. A snake holds at
+    /// most one shield.
+    Shield,
+
+    /// Mirrors the snake's controls for the given number of steps, so that
+    /// turning left turns right and turning up turns down. Picking up another
+    /// restarts the count.
+    Mirror { ticks: u32 },
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    error::Error,
+    fmt::Display,
+    mem,
+};
+
+use crate::{math::Direction, models::SnakeSimulation};
+
+/// Describes why an input could not be taken into account.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RollbackError {
+    /// The input was made on `tick`, which is too long ago to rewind to;
+    /// the oldest tick still kept is `oldest`.
+    TooLate { tick: u64, oldest: u64 },
+}
+
+impl Error for RollbackError {}
+impl Display for RollbackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLate { tick, oldest } => write!(
+                f,
+                "input for tick {tick} arrived too late to roll back to (oldest kept is {oldest})"
+            ),
+        }
+    }
+}
+
+/// Drives a [`SnakeSimulation`] that peers play without waiting for each
+/// other. Every step goes ahead with whatever inputs have arrived, and when
+/// one turns up late for an earlier tick, the simulation is rewound to that
+/// tick and played forward again with it. This needs a snapshot of the last
+/// few ticks and every input made during them, which are kept here.
+///
+/// Inputs for the same tick are applied in order of snake, so peers agree
+/// however their inputs happened to arrive.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Rollback, Snake, SnakeSimulation};
+///
+/// let make = || {
+///     let board = Board::new((0, 8), (0, 8));
+///     let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+///     let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 4, y: 0 }, 7).unwrap();
+///     Rollback::new(sim, 8)
+/// };
+///
+/// // One peer has the turn in time, the other only hears of it later on
+/// let (mut prompt, mut late) = (make(), make());
+/// prompt.input(2, 0, Direction::Down).unwrap();
+/// for _ in 0..4 {
+///     prompt.advance();
+///     late.advance();
+/// }
+/// assert_ne!(prompt.simulation().state_hash(), late.simulation().state_hash());
+///
+/// late.input(2, 0, Direction::Down).unwrap();
+/// assert_eq!(late.simulation().tick(), 4);
+/// assert_eq!(prompt.simulation().state_hash(), late.simulation().state_hash());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rollback {
+    sim: SnakeSimulation,
+
+    /// How many ticks back inputs can still arrive for.
+    capacity: usize,
+
+    /// The simulation as it was at the start of each of the last
+    /// [`Self::capacity`] ticks, before that tick's inputs, oldest first.
+    snapshots: VecDeque<SnakeSimulation>,
+
+    /// The inputs made on each tick still kept, as the snake and the
+    /// direction it turned, in the order they arrived.
+    inputs: BTreeMap<u64, Vec<(usize, Direction)>>,
+
+    /// How many steps were asked for after the game ended, which are taken
+    /// after all if a late input undoes the ending.
+    idle_steps: usize,
+}
+
+impl Rollback {
+    /// Wraps `sim`, keeping enough to rewind up to `capacity` ticks. A
+    /// capacity of 0 is treated as 1.
+    pub fn new(sim: SnakeSimulation, capacity: usize) -> Self {
+        Self {
+            sim,
+            capacity: capacity.max(1),
+            snapshots: VecDeque::new(),
+            inputs: BTreeMap::new(),
+            idle_steps: 0,
+        }
+    }
+
+    /// Get a shared reference to the underlying [`SnakeSimulation`].
+    pub const fn simulation(&self) -> &SnakeSimulation {
+        &self.sim
+    }
+
+    /// Gets the oldest tick an input can still arrive for.
+    pub fn oldest_tick(&self) -> u64 {
+        self.snapshots
+            .front()
+            .map_or(self.sim.tick(), SnakeSimulation::tick)
+    }
+
+    /// Records that `snake` turned towards `direction` on `tick`. An input
+    /// for a tick already played rewinds the simulation to that tick and
+    /// plays it forward to where it was, and one for a tick still to come
+    /// is held until then.
+    pub fn input(
+        &mut self,
+        tick: u64,
+        snake: usize,
+        direction: Direction,
+    ) -> Result<(), RollbackError> {
+        let oldest = self.oldest_tick();
+        if tick < oldest {
+            return Err(RollbackError::TooLate { tick, oldest });
+        }
+
+        self.inputs
+            .entry(tick)
+            .or_default()
+            .push((snake, direction));
+
+        // A fatal step doesn't advance the tick, so whether the tick was
+        // played is told by its snapshot rather than the simulation's tick
+        if let Some(index) = self.snapshots.iter().position(|sim| sim.tick() == tick) {
+            self.rewind(index);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the inputs for the current tick and advances the simulation,
+    /// keeping a snapshot to rewind to.
+    pub fn advance(&mut self) {
+        if self.sim.result().is_some() {
+            self.idle_steps += 1;
+            return;
+        }
+
+        self.snapshots.push_back(self.sim.clone());
+        if self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        let tick = self.sim.tick();
+        if let Some(inputs) = self.inputs.get(&tick) {
+            // Sorting is stable, so each snake's turns keep their order
+            let mut inputs = inputs.clone();
+            inputs.sort_by_key(|(snake, _)| *snake);
+
+            for (snake, direction) in inputs {
+                self.sim.change_move_direction(snake, direction);
+            }
+        }
+
+        self.sim.advance();
+
+        // Inputs from before the oldest snapshot can never be replayed
+        let oldest = self.oldest_tick();
+        self.inputs.retain(|tick, _| *tick >= oldest);
+    }
+
+    /// Puts the simulation back as it was in the snapshot at `index`, then
+    /// takes the steps taken since again.
+    fn rewind(&mut self, index: usize) {
+        let steps = self.snapshots.len() - index + mem::take(&mut self.idle_steps);
+        self.sim = self.snapshots[index].clone();
+        self.snapshots.truncate(index);
+
+        for _ in 0..steps {
+            self.advance();
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// The tunable rules a [`SnakeSimulation`](crate::models::SnakeSimulation)
+/// is played under. The [`Default`] rules are those of Classic Snake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Rules {
+    /// How many segments the snake grows by for each piece of food it eats.
+    /// The snake grows by one segment per step until it has grown this much.
+    pub growth_per_food: u32,
+
+    /// When `true`, only the first direction change in each step takes
+    /// effect and later ones are ignored. Otherwise the last one wins.
+    pub one_turn_per_tick: bool,
+
+    /// When `true`, a direction change that would kill the snake on the very
+    /// next step is ignored, so it carries on as it was. Makes the game
+    /// gentler for young or less nimble players.
+    pub assist: bool,
+
+    /// How many pieces of food must be eaten to win. When [`None`], the game
+    /// is only won once the snake fills the board.
+    pub food_goal: Option<u32>,
+
+    /// How many moves each snake gets to win in, a move being one cell
+    /// travelled. A snake that runs out before winning is out of play, with
+    /// [`SimulationResult::OutOfMoves`](crate::models::SimulationResult::OutOfMoves).
+    /// When [`None`], there is no limit.
+    pub move_limit: Option<u32>,
+
+    /// When set, the game is played against the clock: each snake has only
+    /// so many steps to score as much as it can, and is then out of play
+    /// with [`SimulationResult::TimeExpired`](crate::models::SimulationResult::TimeExpired).
+    pub time_limit: Option<TimeLimit>,
+
+    /// How many times each snake can die before it is out of play. After
+    /// every death but the last, the snake respawns somewhere safe at half
+    /// its length. Zero counts as one.
+    pub lives: u32,
+
+    /// How many steps a snake is invulnerable for after respawning. Rather
+    /// than dying, an invulnerable snake holds still until it can move.
+    pub respawn_grace: u32,
+
+    /// How many steps after eating the next food must be eaten to build a
+    /// combo, which multiplies the points each piece of food scores. The
+    /// multiplier drops by one once that long passes without eating, and
+    /// again after every further stretch that long. When [`None`], every
+    /// piece of food scores one point.
+    pub combo_window: Option<u32>,
+
+    /// When set, the board closes in for a sudden-death finish: its rings
+    /// turn into a hazard zone one at a time, from the edge inward, until
+    /// only the innermost is left. A snake caught in the zone dies, and food
+    /// in it moves somewhere safe.
+    pub shrink: Option<Shrink>,
+
+    /// When set, the food runs from the snakes: every this many steps it
+    /// moves one cell further from the nearest head, if it can get there
+    /// without going into a wall, an obstacle or a snake. Zero counts as one.
+    pub fleeing_food: Option<u32>,
+
+    /// When set, the board is covered in fog: each snake only sees the cells
+    /// within this many cells of its head, as the crow flies, and its own
+    /// body. The game plays the same either way, but frontends hide the
+    /// rest. See [`SimulationView::is_visible`](crate::models::SimulationView::is_visible).
+    pub fog: Option<u32>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            growth_per_food: 1,
+            one_turn_per_tick: false,
+            assist: false,
+            food_goal: None,
+            move_limit: None,
+            time_limit: None,
+            lives: 1,
+            respawn_grace: 10,
+            combo_window: None,
+            shrink: None,
+            fleeing_food: None,
+            fog: None,
+        }
+    }
+}
+
+/// When the rings of a shrinking board close. See [`Rules::shrink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Shrink {
+    /// The step on which the outermost ring closes.
+    pub after: u64,
+
+    /// How many steps apart each further ring closes. Zero counts as one.
+    pub every: u64,
+}
+
+/// How long each snake has in a time attack. See [`Rules::time_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct TimeLimit {
+    /// How many steps each snake starts with.
+    pub steps: u64,
+
+    /// How many more steps each piece of food eaten is worth.
+    pub bonus_per_food: u64,
+}
+
+/// Describes how a new piece of food is placed once the previous one is
+/// eaten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SpawnStrategy {
+    /// Every free cell is equally likely.
+    #[default]
+    Uniform,
+
+    /// Every free cell at least `min_distance` away from the snake's head is
+    /// equally likely. Falls back to [`SpawnStrategy::Uniform`] if there is no
+    /// such cell.
+    AwayFromHead { min_distance: u32 },
+}
+
+/// What eating a piece of food does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FoodKind {
+    /// Grows the snake and counts towards the score.
+    #[default]
+    Regular,
+
+    /// Does nothing but make way for the next piece. For warming up or
+    /// learning the controls without affecting the game.
+    Practice,
+}
@@ -0,0 +1,459 @@
+use crate::{
+    math::{Direction, Vector2},
+    models::{
+        Appearance, Board, FoodKind, PowerUp, Rules, Segment, SimulationResult, Snake,
+        SnakeSimulation,
+    },
+    render::{Cell, FrameBuffer},
+};
+
+/// A read-only view of a [`SnakeSimulation`], handed to bots, renderers and
+/// other observers. It exposes only what a consumer needs to see, so the
+/// simulation's internal representation can change without breaking them.
+///
+/// A view sees the game from one snake's point of view, which it calls the
+/// player's. That is usually the actual player's, but
+/// [`SnakeSimulation::view_as`] gives a view from any snake's.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+/// sim.advance();
+///
+/// let view = sim.view();
+/// assert_eq!(view.tick(), 1);
+/// assert_eq!(view.score(), 0);
+/// assert_eq!(view.snakes().count(), 1);
+/// assert_eq!(view.food().count(), 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct SimulationView<'a> {
+    sim: &'a SnakeSimulation,
+
+    /// The index of the snake treated as the player's.
+    perspective: usize,
+}
+
+impl<'a> SimulationView<'a> {
+    pub(crate) const fn new(sim: &'a SnakeSimulation, perspective: usize) -> Self {
+        Self { sim, perspective }
+    }
+
+    /// Gets the [`Board`] the simulation is happening on.
+    pub const fn board(&self) -> &'a Board {
+        self.sim.board()
+    }
+
+    /// Gets the player's snake.
+    pub const fn player(&self) -> SnakeView<'a> {
+        SnakeView {
+            sim: self.sim,
+            index: self.perspective,
+        }
+    }
+
+    /// Returns an [`Iterator`] over every snake in the simulation, starting
+    /// with the actual player's.
+    pub fn snakes(&self) -> impl Iterator<Item = SnakeView<'a>> + use<'a> {
+        let sim = self.sim;
+        (0..sim.snakes().len()).map(move |index| SnakeView { sim, index })
+    }
+
+    /// Returns an [`Iterator`] over the positions of every piece of food.
+    pub fn food(&self) -> impl Iterator<Item = &'a Vector2> + use<'a> {
+        [self.sim.food_position()].into_iter()
+    }
+
+    /// Returns an [`Iterator`] over every cell blocked by an obstacle.
+    pub fn obstacles(&self) -> impl Iterator<Item = &'a Vector2> + use<'a> {
+        self.sim.obstacles().iter()
+    }
+
+    /// Returns an [`Iterator`] over every power-up on the board, and the
+    /// cell it is in.
+    pub fn power_ups(&self) -> impl Iterator<Item = (&'a Vector2, PowerUp)> + use<'a> {
+        self.sim
+            .power_ups()
+            .iter()
+            .map(|(cell, power_up)| (cell, *power_up))
+    }
+
+    /// Returns an [`Iterator`] over every cell in the hazard zone of a
+    /// shrinking board. See [`Rules::shrink`].
+    pub fn hazard(&self) -> impl Iterator<Item = &'a Vector2> + use<'a> {
+        self.sim.hazard().iter()
+    }
+
+    /// Gets what eating the food does.
+    pub const fn food_kind(&self) -> FoodKind {
+        self.sim.food_kind()
+    }
+
+    /// Gets the [`Rules`] the game is played under.
+    pub const fn rules(&self) -> &'a Rules {
+        self.sim.rules()
+    }
+
+    /// Gets the player's score.
+    pub fn score(&self) -> usize {
+        self.player().score()
+    }
+
+    /// Gets how many more pieces of food must be eaten to win, if there is a
+    /// food goal.
+    pub fn remaining_food(&self) -> Option<usize> {
+        self.sim.remaining_food_for(self.perspective)
+    }
+
+    /// Gets how many more moves there are to win in, if there is a move
+    /// limit.
+    pub fn remaining_moves(&self) -> Option<u64> {
+        self.sim.remaining_moves_for(self.perspective)
+    }
+
+    /// Gets how many more steps there are before time runs out, if there is
+    /// a time limit.
+    pub fn remaining_time(&self) -> Option<u64> {
+        self.sim.remaining_time_for(self.perspective)
+    }
+
+    /// Gets how close the player is to winning, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        self.sim.progress_for(self.perspective)
+    }
+
+    /// Gets the number of steps the simulation has advanced.
+    pub const fn tick(&self) -> u64 {
+        self.sim.tick()
+    }
+
+    /// Returns `true` if the simulation is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.sim.is_paused()
+    }
+
+    /// Gets the final result of the simulation, if it has been determined.
+    /// See [`SnakeSimulation::result`]; how it went for each snake is up to
+    /// [`SnakeView::result`].
+    pub const fn result(&self) -> Option<&'a SimulationResult> {
+        self.sim.result()
+    }
+
+    /// Determines whether moving the player's snake in `direction` on the
+    /// next step would kill it. See [`SnakeSimulation::is_fatal_move`].
+    pub fn is_fatal_move(&self, direction: Direction) -> bool {
+        self.sim.is_fatal_move_for(self.perspective, direction)
+    }
+
+    /// Determines whether the player can see `point`. Under [`Rules::fog`],
+    /// that is only the cells near the player's head and those of its body.
+    /// Otherwise every cell is visible.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 16), (0, 16));
+    /// let snake = Snake::new(Vector2 { x: 4, y: 4 }, Direction::Right);
+    /// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 12, y: 12 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         fog: Some(3),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// let view = sim.view();
+    /// assert!(view.is_visible(&Vector2 { x: 6, y: 6 }));
+    /// assert!(!view.is_visible(&Vector2 { x: 7, y: 6 }));
+    /// assert!(!view.is_visible(view.food().next().unwrap()));
+    /// ```
+    pub fn is_visible(&self, point: &Vector2) -> bool {
+        let Some(radius) = self.rules().fog else {
+            return true;
+        };
+
+        let player = self.player();
+        let head = player.head();
+        let dx = i64::from(point.x) - i64::from(head.x);
+        let dy = i64::from(point.y) - i64::from(head.y);
+
+        dx * dx + dy * dy <= i64::from(radius).pow(2) || player.contains(point)
+    }
+
+    /// Gets what currently occupies `point`, as it would appear in a
+    /// [`FrameBuffer`], even if it is hidden from the player. See
+    /// [`Self::is_visible`].
+    pub fn cell(&self, point: &Vector2) -> Cell {
+        let snakes = || self.snakes().enumerate();
+
+        if let Some((index, snake)) = snakes().find(|(_, snake)| snake.head() == point) {
+            return Cell::SnakeHead {
+                snake: index,
+                facing: snake.facing(),
+            };
+        }
+
+        if let Some((index, _)) = snakes().find(|(_, snake)| snake.contains(point)) {
+            return Cell::SnakeBody { snake: index };
+        }
+
+        if self.food().any(|food| food == point) {
+            return self.food_cell();
+        }
+
+        if self.sim.obstacles().contains(point) {
+            return Cell::Obstacle;
+        }
+
+        if let Some(power_up) = self.sim.power_ups().get(point) {
+            return Cell::PowerUp(*power_up);
+        }
+
+        if let Some(pair) = self
+            .board()
+            .portals()
+            .iter()
+            .position(|p| p.twin(point).is_some())
+        {
+            return Cell::Portal { pair };
+        }
+
+        if self.sim.hazard().contains(point) {
+            return Cell::Hazard;
+        }
+
+        if let Some(terrain) = self.board().terrain_at(point) {
+            return Cell::Terrain(terrain);
+        }
+
+        Cell::Empty
+    }
+
+    /// Gets how food is drawn, which depends on what eating it does.
+    fn food_cell(&self) -> Cell {
+        match self.sim.food_kind() {
+            FoodKind::Regular => Cell::Food,
+            FoodKind::Practice => Cell::PracticeFood,
+        }
+    }
+
+    /// Gets the cells changed by the most recent step, and what they changed
+    /// to. See [`SnakeSimulation::changes`].
+    pub fn changes(&self) -> &'a [(Vector2, Cell)] {
+        self.sim.changes()
+    }
+
+    /// Draws the current state of the simulation into a new [`FrameBuffer`],
+    /// as the player sees it. Any cell they can't see is
+    /// [`Cell::Hidden`].
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::render::Cell;
+    ///
+    /// let config = GameConfig {
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let sim = config.build().unwrap();
+    /// let view = sim.view();
+    /// let frame = view.frame();
+    ///
+    /// assert_eq!(frame.get(view.food().next().unwrap()), Some(Cell::Food));
+    /// assert!(matches!(
+    ///     frame.get(view.player().head()),
+    ///     Some(Cell::SnakeHead { snake: 0, .. })
+    /// ));
+    /// ```
+    pub fn frame(&self) -> FrameBuffer {
+        let mut buffer = FrameBuffer::new(self.board());
+        self.draw(&mut buffer);
+        buffer
+    }
+
+    /// Like [`Self::frame`], but reuses `buffer`. The buffer is resized if it
+    /// does not fit the [`Board`].
+    pub fn draw(&self, buffer: &mut FrameBuffer) {
+        if buffer.fits(self.board()) {
+            buffer.clear();
+        } else {
+            *buffer = FrameBuffer::new(self.board());
+        }
+
+        for (cell, terrain) in self.board().terrain() {
+            buffer.set(cell, Cell::Terrain(*terrain));
+        }
+
+        for cell in self.hazard() {
+            buffer.set(cell, Cell::Hazard);
+        }
+
+        for obstacle in self.obstacles() {
+            buffer.set(obstacle, Cell::Obstacle);
+        }
+
+        for (pair, portal) in self.board().portals().iter().enumerate() {
+            buffer.set(&portal.a, Cell::Portal { pair });
+            buffer.set(&portal.b, Cell::Portal { pair });
+        }
+
+        for (cell, power_up) in self.power_ups() {
+            buffer.set(cell, Cell::PowerUp(power_up));
+        }
+
+        for food in self.food() {
+            buffer.set(food, self.food_cell());
+        }
+
+        // Heads go last, so they are visible even when something else shares
+        // their cell, like the food a winning snake finishes on.
+        for (index, snake) in self.snakes().enumerate() {
+            for segment in snake.body().skip(1) {
+                buffer.set(segment, Cell::SnakeBody { snake: index });
+            }
+        }
+
+        for (index, snake) in self.snakes().enumerate() {
+            let head = Cell::SnakeHead {
+                snake: index,
+                facing: snake.facing(),
+            };
+
+            buffer.set(snake.head(), head);
+        }
+
+        if self.rules().fog.is_some() {
+            for cell in self.board().cell_iter() {
+                if !self.is_visible(&cell) {
+                    buffer.set(&cell, Cell::Hidden);
+                }
+            }
+        }
+    }
+}
+
+/// A read-only view of a single snake in a [`SimulationView`].
+#[derive(Clone, Copy)]
+pub struct SnakeView<'a> {
+    sim: &'a SnakeSimulation,
+    index: usize,
+}
+
+impl<'a> SnakeView<'a> {
+    fn snake(&self) -> &'a Snake {
+        self.sim
+            .snake_at(self.index)
+            .expect("snake views are only made for snakes in the simulation")
+    }
+
+    /// Gets the index of the snake, as used by
+    /// [`Cell::SnakeHead`] and [`SnakeSimulation::result_for`]. The player's
+    /// snake is `0`.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets how the game ended for the snake, or [`None`] while it is still
+    /// in play.
+    pub fn result(&self) -> Option<&'a SimulationResult> {
+        self.sim.result_for(self.index)
+    }
+
+    /// Gets the snake's score. See [`SnakeSimulation::score`].
+    pub fn score(&self) -> usize {
+        self.sim.score_for(self.index)
+    }
+
+    /// Gets the snake's current combo multiplier. See
+    /// [`SnakeSimulation::combo_multiplier`].
+    pub fn combo_multiplier(&self) -> u32 {
+        self.sim.combo_multiplier_for(self.index)
+    }
+
+    /// Gets how many lives the snake has left, counting the one it is on.
+    pub fn lives_left(&self) -> u32 {
+        self.sim.lives_left_for(self.index)
+    }
+
+    /// Returns `true` if the snake is carrying a [`PowerUp::Shield`].
+    pub fn is_shielded(&self) -> bool {
+        self.sim.is_shielded_for(self.index)
+    }
+
+    /// Returns `true` if the snake's controls are mirrored by a
+    /// [`PowerUp::Mirror`].
+    pub fn is_mirrored(&self) -> bool {
+        self.sim.is_mirrored_for(self.index)
+    }
+
+    /// Returns `true` if the snake has only just respawned, and can't die
+    /// yet.
+    pub fn is_invulnerable(&self) -> bool {
+        self.sim.is_invulnerable_for(self.index)
+    }
+
+    /// Gets the position of the snake's head.
+    pub fn head(&self) -> &'a Vector2 {
+        self.snake().head()
+    }
+
+    /// Gets the position of the snake's tail.
+    pub fn tail(&self) -> &'a Vector2 {
+        self.snake().tail()
+    }
+
+    /// Returns an [`Iterator`] over the body of the snake, from head to tail.
+    pub fn body(&self) -> impl Iterator<Item = &'a Vector2> + use<'a> {
+        self.snake().body_iter()
+    }
+
+    /// Returns an [`Iterator`] over the [`Segment`]s of the snake, from head
+    /// to tail.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + use<'a> {
+        self.snake().segments()
+    }
+
+    /// Gets the total length of the snake.
+    pub fn len(&self) -> usize {
+        self.snake().len()
+    }
+
+    /// Returns `true` if the snake has no body.
+    pub fn is_empty(&self) -> bool {
+        self.snake().is_empty()
+    }
+
+    /// Gets the direction the snake is facing.
+    pub fn facing(&self) -> Direction {
+        self.snake().facing()
+    }
+
+    /// Gets the direction the snake last moved in, which it cannot turn
+    /// straight back along.
+    pub fn last_move_direction(&self) -> Direction {
+        self.snake().last_move_direction()
+    }
+
+    /// Gets the turn queued for the next step, if any.
+    pub fn pending_facing(&self) -> Option<Direction> {
+        self.snake().pending_facing()
+    }
+
+    /// Gets how the snake should be labelled and styled.
+    pub fn appearance(&self) -> &'a Appearance {
+        self.snake().appearance()
+    }
+
+    /// Returns whether or not the snake's body covers `point`.
+    pub fn contains(&self, point: &Vector2) -> bool {
+        self.snake().contains(point)
+    }
+}
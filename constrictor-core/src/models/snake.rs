@@ -4,7 +4,11 @@ use std::collections::hash_map::Entry;
 
 use crate::math::Direction;
 use crate::math::Vector2;
+use crate::models::Appearance;
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "SnakeState", try_from = "SnakeState"))]
 pub struct Snake {
     /// The direction the snake is currently facing.
     facing: Direction,
@@ -28,6 +32,31 @@ pub struct Snake {
     /// You should avoid manual manipulation of this field because it can lead
     /// to divergence from [`Self::body`].
     body_point_counts: HashMap<Vector2, usize>,
+
+    /// How the snake should be labelled and styled.
+    appearance: Appearance,
+
+    /// How many segments have been laid at the head over the snake's life,
+    /// counting those it started with.
+    laid: u64,
+}
+
+/// One segment of a [`Snake`]'s body, as [`Snake::segments`] gives it, for
+/// frontends that style each segment differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// Where the segment is.
+    pub position: Vector2,
+
+    /// How far the segment is from the head, which is 0. Since a segment is
+    /// laid at the head and stays put, this is also how many moves old it
+    /// is.
+    pub index: usize,
+
+    /// How many segments the snake had laid before this one. Unlike
+    /// [`Self::index`], this stays the same while the segment lasts, so it
+    /// tells segments apart as the snake moves on.
+    pub laid_at: u64,
 }
 
 impl Snake {
@@ -51,6 +80,8 @@ impl Snake {
             body_point_counts: HashMap::new(),
             last_move_direction: facing,
             facing,
+            appearance: Appearance::default(),
+            laid: 0,
         };
 
         snek.push_head(head_position);
@@ -58,6 +89,44 @@ impl Snake {
         snek
     }
 
+    /// Creates a [`Snake`] facing `facing` whose body covers `body`, from
+    /// head to tail. Returns [`None`] if `body` is empty. Nothing checks
+    /// that each segment is next to the one before it.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::Snake;
+    ///
+    /// let body = [Vector2 { x: 4, y: 2 }, Vector2 { x: 3, y: 2 }, Vector2 { x: 3, y: 3 }];
+    /// let snek = Snake::from_body(body, Direction::Right).unwrap();
+    /// assert_eq!(snek.len(), 3);
+    /// assert_eq!(snek.head(), &Vector2 { x: 4, y: 2 });
+    /// assert_eq!(snek.tail(), &Vector2 { x: 3, y: 3 });
+    ///
+    /// assert!(Snake::from_body([], Direction::Right).is_none());
+    /// ```
+    pub fn from_body<I: IntoIterator<Item = Vector2>>(body: I, facing: Direction) -> Option<Self> {
+        let body: Vec<_> = body.into_iter().collect();
+        let mut segments = body.into_iter().rev();
+
+        let mut snek = Self::new(segments.next()?, facing);
+        segments.for_each(|segment| snek.push_head(segment));
+
+        Some(snek)
+    }
+
+    /// Replaces the [`Appearance`] of the [`Snake`].
+    pub fn with_appearance(mut self, appearance: Appearance) -> Self {
+        self.appearance = appearance;
+        self
+    }
+
+    /// Gets the [`Appearance`] of the [`Snake`].
+    pub fn appearance(&self) -> &Appearance {
+        &self.appearance
+    }
+
     /// Gets the direction the [`Snake`] is facing.
     ///
     /// # Example
@@ -72,6 +141,46 @@ impl Snake {
         self.facing
     }
 
+    /// Gets the direction the [`Snake`] last moved in. The [`Snake`] can turn
+    /// any way except straight back along this direction.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::Snake;
+    ///
+    /// let mut snek = Snake::new(Vector2 { x: 4, y: 2 }, Direction::Right);
+    /// snek.try_set_facing(Direction::Up);
+    /// assert_eq!(snek.last_move_direction(), Direction::Right);
+    ///
+    /// snek.advance(false);
+    /// assert_eq!(snek.last_move_direction(), Direction::Up);
+    /// ```
+    pub fn last_move_direction(&self) -> Direction {
+        self.last_move_direction
+    }
+
+    /// Gets the turn queued for the next step, if the [`Snake`] is facing a
+    /// different way than it last moved.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::Snake;
+    ///
+    /// let mut snek = Snake::new(Vector2 { x: 4, y: 2 }, Direction::Right);
+    /// assert_eq!(snek.pending_facing(), None);
+    ///
+    /// snek.try_set_facing(Direction::Down);
+    /// assert_eq!(snek.pending_facing(), Some(Direction::Down));
+    ///
+    /// snek.advance(false);
+    /// assert_eq!(snek.pending_facing(), None);
+    /// ```
+    pub fn pending_facing(&self) -> Option<Direction> {
+        (self.facing != self.last_move_direction).then_some(self.facing)
+    }
+
     /// Gets the total length of the [`Snake`].
     ///
     /// # Example
@@ -145,6 +254,36 @@ impl Snake {
         self.body.iter()
     }
 
+    /// Returns an [`Iterator`] over the [`Segment`]s of the [`Snake`], from
+    /// head to tail.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::Snake;
+    ///
+    /// let mut snek = Snake::new(Vector2 { x: 4, y: 2 }, Direction::Right);
+    /// snek.advance(true);
+    /// snek.advance(false);
+    ///
+    /// let tail = snek.segments().last().unwrap();
+    /// assert_eq!(tail.position, Vector2 { x: 5, y: 2 });
+    /// assert_eq!((tail.index, tail.laid_at), (1, 1));
+    ///
+    /// let head = snek.segments().next().unwrap();
+    /// assert_eq!((head.index, head.laid_at), (0, 2));
+    /// ```
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.body
+            .iter()
+            .enumerate()
+            .map(|(index, position)| Segment {
+                position: *position,
+                index,
+                laid_at: self.laid - 1 - index as u64,
+            })
+    }
+
     /// Gets the position of the [`Snake`]'s tail.
     ///
     /// # Example
@@ -274,6 +413,13 @@ impl Snake {
         // Though it should never be valid, do this first in case len() == 1
         let new_head = self.next_head_position();
 
+        self.advance_to(new_head, consumed_food);
+    }
+
+    /// Like [`Self::advance`], but the head moves to `new_head` rather than
+    /// its neighbour in the direction of `self.facing`. This allows the board
+    /// to redirect the [`Snake`], such as when it passes through a doorway.
+    pub(crate) fn advance_to(&mut self, new_head: Vector2, consumed_food: bool) {
         // Dropping the tail first ensures we can avoid pointless collection growth
         if !consumed_food {
             _ = self.pop_tail();
@@ -283,6 +429,17 @@ impl Snake {
         self.last_move_direction = self.facing
     }
 
+    /// Returns `true` if the [`Snake`] has a body and [`Self::body`] and
+    /// [`Self::body_point_counts`] agree with each other.
+    pub(crate) fn is_consistent(&self) -> bool {
+        let mut expected = HashMap::with_capacity(self.body_point_counts.len());
+        for point in &self.body {
+            *expected.entry(*point).or_insert(0) += 1;
+        }
+
+        !self.body.is_empty() && expected == self.body_point_counts
+    }
+
     /// Push a new head onto the snake.
     ///
     /// # Note
@@ -291,6 +448,7 @@ impl Snake {
     fn push_head(&mut self, head: Vector2) {
         self.body.push_front(head);
         *self.body_point_counts.entry(head).or_insert(0) += 1;
+        self.laid += 1;
     }
 
     /// Pop the tail from the snake.
@@ -312,3 +470,49 @@ impl Snake {
         Some(old_tail)
     }
 }
+
+/// Serialized form of a [`Snake`]. [`Snake::body_point_counts`] is derived
+/// state, so only the body itself is stored and the counts are rebuilt on
+/// deserialization. [`Snake::laid`] is missing from older saves, which count
+/// only the segments in the body.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SnakeState {
+    facing: Direction,
+    last_move_direction: Direction,
+    body: VecDeque<Vector2>,
+    #[serde(default)]
+    appearance: Appearance,
+    #[serde(default)]
+    laid: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<Snake> for SnakeState {
+    fn from(value: Snake) -> Self {
+        Self {
+            facing: value.facing,
+            last_move_direction: value.last_move_direction,
+            body: value.body,
+            appearance: value.appearance,
+            laid: value.laid,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SnakeState> for Snake {
+    type Error = &'static str;
+
+    fn try_from(value: SnakeState) -> Result<Self, Self::Error> {
+        let mut body = value.body.into_iter().rev();
+        let tail = body.next().ok_or("snake body must not be empty")?;
+
+        let mut snek = Snake::new(tail, value.facing).with_appearance(value.appearance);
+        snek.last_move_direction = value.last_move_direction;
+        body.for_each(|segment| snek.push_head(segment));
+        snek.laid = snek.laid.max(value.laid);
+
+        Ok(snek)
+    }
+}
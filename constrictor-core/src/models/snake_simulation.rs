@@ -1,32 +1,98 @@
-use std::{error::Error, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::Display,
+    time::Duration,
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 use crate::{
+    config::SpeedCurve,
     math::{Direction, Vector2},
-    models::{Board, Snake},
+    models::{
+        Board, BoardStep, DoorwayTarget, EventAction, EventSchedule, EventTrigger, FoodKind,
+        FreeCells, Occupancy, PowerUp, Proximity, Rules, SimulationView, Snake, SpawnStrategy,
+        Terrain, TickSource,
+    },
+    render::{Cell, TextFrame},
 };
 
-/// Describes the outcome of a [`SnakeSimulation`].
-#[derive(PartialEq, Eq, Debug)]
+/// Describes the outcome of a [`SnakeSimulation`], or of one [`Snake`] in
+/// it.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SimulationResult {
     /// The snake died for the specified reason.
     Died(DeathReason),
 
-    /// The game was manually terminated.
-    ManuallyTerminated,
+    /// The game was manually terminated for the specified reason.
+    ManuallyTerminated(QuitReason),
 
-    // The simulation is complete. There is no more food to consume.
+    /// The simulation is complete. Either the food goal in the
+    /// [`Rules`] was reached, there is no room left for more food, or every
+    /// other snake is out of play.
     Won,
+
+    /// Another snake won while this one was still in play.
+    Lost,
+
+    /// The snake left the [`Board`] through a
+    /// [`Doorway`](crate::models::Doorway) leading to the
+    /// board identified by the given index. What happens next is up to
+    /// whoever owns that world of boards.
+    LeftBoard(usize),
+
+    /// The snake used up every move the [`Rules::move_limit`] allows without
+    /// winning.
+    OutOfMoves,
+
+    /// The snake's time ran out under the [`Rules::time_limit`]. How well it
+    /// did is down to its score.
+    TimeExpired,
 }
 
 /// Describes the reason a [`SnakeSimulation`] ended with
 /// [`SimulationResult::Died`].
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeathReason {
     /// The [`Snake`] collided with an edge of the [`Board`].
     HitWall,
 
     /// The [`Snake`] collided with itself.
     HitSelf,
+
+    /// The [`Snake`] ran into the body of the snake with the given index.
+    HitSnake(usize),
+
+    /// The [`Snake`] moved into the same cell as the snake with the given
+    /// index, and was not the longer of the two.
+    HeadOn(usize),
+
+    /// The [`Snake`] was caught in the hazard zone of a shrinking [`Board`].
+    /// See [`Rules::shrink`].
+    Hazard,
+}
+
+/// Describes why a [`SnakeSimulation`] ended with
+/// [`SimulationResult::ManuallyTerminated`].
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuitReason {
+    /// The player asked to quit.
+    UserQuit,
+
+    /// The player's connection to the game (such as their terminal or a
+    /// network socket) went away.
+    ConnectionLost,
+
+    /// Whatever is hosting the game is shutting down.
+    HostShutdown,
+
+    /// The game could not continue because of an error outside the
+    /// simulation.
+    Error,
 }
 
 /// Represents a virtual game of Classic Snake. The rules are:
@@ -34,18 +100,183 @@ pub enum DeathReason {
 /// - the [`Snake`] cannot go out of bounds, and does not wrap around when
 ///   hitting an edge
 /// - the [`Snake`] grows in length by 1 when consuming food
+///
+/// Several snakes can share the board, all moving at once. The first is the
+/// player's. A snake dies running into any snake's body, and when two heads
+/// meet only the longer snake survives. Snakes that die stay where they are,
+/// blocking the others, and the last snake left in play wins. A snake with
+/// [`Rules::lives`] to spare respawns instead of dying.
+#[derive(Debug, Clone)]
 pub struct SnakeSimulation {
     /// The board the game is taking place on.
     board: Board,
 
-    /// The player's snake.
-    snake: Snake,
+    /// Every snake in the game, starting with the player's.
+    snakes: Vec<Contender>,
 
     /// The position of the food.
     food_position: Vector2,
 
-    /// Final simulation result.
+    /// What eating the food does. New food is of the same kind.
+    food_kind: FoodKind,
+
+    /// Final simulation result. With several snakes this is the player's
+    /// result, and is only set once the whole game is over.
     simulation_result: Option<SimulationResult>,
+
+    /// The rules the game is played under.
+    rules: Rules,
+
+    /// How new food is placed.
+    spawn_strategy: SpawnStrategy,
+
+    /// The cells new food is placed on while any of them are free, if not
+    /// the whole board.
+    food_region: Option<HashSet<Vector2>>,
+
+    /// The cells the next pieces of food are placed on, in order, before
+    /// placement goes back to the [`SpawnStrategy`].
+    food_sequence: VecDeque<Vector2>,
+
+    /// How quickly the game runs.
+    speed: SpeedCurve,
+
+    /// Events that happen as the game goes on.
+    events: EventSchedule,
+
+    /// Cells inside the board that block the snake like walls do.
+    obstacles: HashSet<Vector2>,
+
+    /// The power-ups lying on the board, waiting to be picked up.
+    power_ups: HashMap<Vector2, PowerUp>,
+
+    /// Cells the board has shrunk away from, which kill any snake in them.
+    /// See [`Rules::shrink`].
+    hazard: HashSet<Vector2>,
+
+    /// How many of the board's rings have closed into the hazard zone.
+    closed_rings: u32,
+
+    /// How many steps the simulation has advanced.
+    tick: u64,
+
+    /// Whether the game is paused, in which case advancing does nothing.
+    paused: bool,
+
+    /// The cells changed by the most recent step.
+    changes: Vec<(Vector2, Cell)>,
+
+    /// Source of randomness for food placement. Seeding this makes the whole
+    /// simulation reproducible.
+    rng: StdRng,
+}
+
+/// A [`Snake`] taking part in a [`SnakeSimulation`], along with everything
+/// tracked for it alone.
+#[derive(Debug, Clone)]
+struct Contender {
+    snake: Snake,
+
+    /// How many more steps the snake will grow for, rather than moving its
+    /// tail along with its head.
+    pending_growth: u32,
+
+    /// How many pieces of food the snake has eaten so far.
+    food_eaten: usize,
+
+    /// How many cells the snake has moved, for [`Rules::move_limit`].
+    moves: u64,
+
+    /// How many steps eating has added to the [`Rules::time_limit`].
+    time_bonus: u64,
+
+    /// The points scored so far, each piece of food being worth the combo
+    /// multiplier at the time it was eaten.
+    score: usize,
+
+    /// The current combo multiplier, never less than 1.
+    combo: u32,
+
+    /// How many steps it has been since the snake last ate, or [`None`] if
+    /// it hasn't yet.
+    since_meal: Option<u32>,
+
+    /// Whether the snake's direction has changed since the last step, for
+    /// [`Rules::one_turn_per_tick`].
+    turned_this_tick: bool,
+
+    /// How many lives the snake has left, counting the one it is on.
+    lives: u32,
+
+    /// How many more steps the snake is invulnerable for, after respawning.
+    grace: u32,
+
+    /// Whether the snake is carrying a [`PowerUp::Shield`].
+    shielded: bool,
+
+    /// How many more steps the snake's controls are mirrored for, after
+    /// picking up a [`PowerUp::Mirror`].
+    mirrored: u32,
+
+    /// Whether [`Terrain::Sticky`] held the snake back on the last step, so
+    /// that it moves on this one.
+    stuck: bool,
+
+    /// How the game ended for the snake, once it is out of play.
+    result: Option<SimulationResult>,
+}
+
+impl Contender {
+    const fn new(snake: Snake) -> Self {
+        Self {
+            snake,
+            pending_growth: 0,
+            food_eaten: 0,
+            moves: 0,
+            time_bonus: 0,
+            score: 0,
+            combo: 1,
+            since_meal: None,
+            turned_this_tick: false,
+            lives: 1,
+            grace: 0,
+            shielded: false,
+            mirrored: 0,
+            stuck: false,
+            result: None,
+        }
+    }
+
+    const fn in_play(&self) -> bool {
+        self.result.is_none()
+    }
+
+    /// Counts a piece of food eaten, building the combo if it came within
+    /// `window` steps of the last one.
+    fn eat(&mut self, window: Option<u32>) {
+        if let (Some(window), Some(since_meal)) = (window, self.since_meal)
+            && since_meal <= window
+        {
+            self.combo += 1;
+        }
+
+        self.food_eaten += 1;
+        self.score += self.combo as usize;
+        self.since_meal = Some(0);
+    }
+
+    /// Counts a step passing, letting the combo decay by one each time
+    /// another `window` steps go by without food.
+    fn wait_for_meal(&mut self, window: Option<u32>) {
+        let (Some(window), Some(since_meal)) = (window, &mut self.since_meal) else {
+            return;
+        };
+
+        *since_meal = since_meal.saturating_add(1);
+        if *since_meal > window && (*since_meal - 1).is_multiple_of(window.max(1)) {
+            self.combo = (self.combo - 1).max(1);
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -60,6 +291,16 @@ pub enum SimulationParameterError {
 
     /// The provided [`Snake`] and [`Vector2`] for the food position overlap.
     SnakeOverlapsFood,
+
+    /// No [`Snake`]s were provided.
+    NoSnakes,
+
+    /// Two of the provided [`Snake`]s overlap.
+    SnakesOverlap,
+
+    /// A provided [`Snake`] or the food is on the end of a
+    /// [`Portal`](crate::models::Portal).
+    OnPortal,
 }
 
 impl Error for SimulationParameterError {}
@@ -69,60 +310,462 @@ impl Display for SimulationParameterError {
             Self::SnakeOutOfBounds => "snake covers out-of-bounds positions",
             Self::FoodOutOfBounds => "given food position outside the bounds of board",
             Self::SnakeOverlapsFood => "given food position covered by snake",
+            Self::NoSnakes => "at least one snake is required",
+            Self::SnakesOverlap => "given snakes overlap",
+            Self::OnPortal => "given snake or food position is on a portal",
         })
     }
 }
 
+/// Describes internal state of a [`SnakeSimulation`] that should be
+/// impossible to reach. Seeing one of these means there is a bug.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum InvariantViolation {
+    /// The [`Snake`]'s body and its bookkeeping no longer agree.
+    SnakeCorrupted,
+
+    /// Part of the [`Snake`] is outside of the [`Board`].
+    SnakeOutOfBounds(Vector2),
+
+    /// The food is outside of the [`Board`].
+    FoodOutOfBounds(Vector2),
+
+    /// The food is underneath a [`Snake`] while the game is still going.
+    FoodUnderSnake(Vector2),
+}
+
+impl Error for InvariantViolation {}
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SnakeCorrupted => f.write_str("snake body and body point counts have diverged"),
+            Self::SnakeOutOfBounds(cell) => {
+                write!(f, "snake covers out-of-bounds position {cell:?}")
+            }
+            Self::FoodOutOfBounds(cell) => write!(f, "food at out-of-bounds position {cell:?}"),
+            Self::FoodUnderSnake(cell) => write!(f, "food at {cell:?} is covered by a snake"),
+        }
+    }
+}
+
 impl SnakeSimulation {
     /// Create a new [`SnakeSimulation`] from a [`Board`] and [`Snake`] with the
     /// food positioned at the position [`Vector2`].
+    #[cfg(feature = "os-rng")]
     pub fn new(
         board: Board,
         snake: Snake,
         food_position: Vector2,
     ) -> Result<Self, SimulationParameterError> {
+        Self::seeded(board, snake, food_position, rand::random())
+    }
+
+    /// Like [`SnakeSimulation::new`], but all randomness is derived from
+    /// `seed`. Two simulations created with the same parameters and seed
+    /// behave identically given the same inputs.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    ///
+    /// let make = || {
+    ///     let board = Board::new((0, 4), (0, 1));
+    ///     let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    ///     SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 42).unwrap()
+    /// };
+    ///
+    /// let (mut a, mut b) = (make(), make());
+    /// a.advance();
+    /// b.advance();
+    ///
+    /// assert_eq!(a.food_position(), b.food_position());
+    /// ```
+    pub fn seeded(
+        board: Board,
+        snake: Snake,
+        food_position: Vector2,
+        seed: u64,
+    ) -> Result<Self, SimulationParameterError> {
+        Self::seeded_with_snakes(board, vec![snake], food_position, seed)
+    }
+
+    /// Like [`SnakeSimulation::seeded`], but with several snakes sharing the
+    /// board. The first is the player's; how the rest are steered is up to
+    /// the caller, such as with a [`Controller`](crate::bots::Controller).
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{
+    ///     Board, DeathReason, SimulationResult, Snake, SnakeSimulation,
+    /// };
+    ///
+    /// let board = Board::new((0, 5), (0, 3));
+    /// let snakes = vec![
+    ///     Snake::new(Vector2 { x: 0, y: 1 }, Direction::Right),
+    ///     Snake::new(Vector2 { x: 4, y: 1 }, Direction::Left),
+    /// ];
+    /// let mut sim =
+    ///     SnakeSimulation::seeded_with_snakes(board, snakes, Vector2 { x: 0, y: 0 }, 1).unwrap();
+    ///
+    /// // Both heads reach the middle at once, and neither is longer
+    /// sim.advance();
+    /// sim.advance();
+    ///
+    /// assert_eq!(
+    ///     sim.result_for(1),
+    ///     Some(&SimulationResult::Died(DeathReason::HeadOn(0)))
+    /// );
+    /// assert_eq!(
+    ///     sim.result(),
+    ///     Some(&SimulationResult::Died(DeathReason::HeadOn(1)))
+    /// );
+    /// ```
+    pub fn seeded_with_snakes(
+        board: Board,
+        snakes: Vec<Snake>,
+        food_position: Vector2,
+        seed: u64,
+    ) -> Result<Self, SimulationParameterError> {
+        if snakes.is_empty() {
+            return Err(SimulationParameterError::NoSnakes);
+        }
+
         if !board.contains(&food_position) {
             return Err(SimulationParameterError::FoodOutOfBounds);
         }
 
-        for cell in snake.body_iter() {
-            if !board.contains(cell) {
-                return Err(SimulationParameterError::SnakeOutOfBounds);
-            }
+        let on_portal = |cell: &Vector2| board.portals().iter().any(|p| p.twin(cell).is_some());
+        if on_portal(&food_position) || snakes.iter().flat_map(Snake::body_iter).any(on_portal) {
+            return Err(SimulationParameterError::OnPortal);
+        }
+
+        for (index, snake) in snakes.iter().enumerate() {
+            for cell in snake.body_iter() {
+                if !board.contains(cell) {
+                    return Err(SimulationParameterError::SnakeOutOfBounds);
+                }
 
-            if &food_position == cell {
-                return Err(SimulationParameterError::SnakeOverlapsFood);
+                if &food_position == cell {
+                    return Err(SimulationParameterError::SnakeOverlapsFood);
+                }
+
+                if snakes[..index].iter().any(|other| other.contains(cell)) {
+                    return Err(SimulationParameterError::SnakesOverlap);
+                }
             }
         }
 
         Ok(Self {
             board,
-            snake,
+            snakes: snakes.into_iter().map(Contender::new).collect(),
             food_position,
+            food_kind: FoodKind::Regular,
             simulation_result: None,
+            rules: Rules::default(),
+            spawn_strategy: SpawnStrategy::default(),
+            food_region: None,
+            food_sequence: VecDeque::new(),
+            speed: SpeedCurve::default(),
+            events: EventSchedule::default(),
+            obstacles: HashSet::new(),
+            power_ups: HashMap::new(),
+            hazard: HashSet::new(),
+            closed_rings: 0,
+            tick: 0,
+            paused: false,
+            changes: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
         })
     }
 
-    /// Hook to request the simulation to be quit. Intended to be called within
-    /// input handling logic.
-    pub fn quit(&mut self) {
-        self.simulation_result = Some(SimulationResult::ManuallyTerminated);
+    /// Replaces the [`Rules`] the [`SnakeSimulation`] is played under.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 10), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         growth_per_food: 3,
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // Eating the food starts the snake growing for the next three steps
+    /// for _ in 0..3 {
+    ///     sim.advance();
+    /// }
+    ///
+    /// assert_eq!(sim.snake().len(), 4);
+    /// ```
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        for contender in &mut self.snakes {
+            contender.lives = rules.lives.max(1);
+        }
+
+        self.rules = rules;
+        self
+    }
+
+    /// Replaces the [`SpawnStrategy`] used to place new food.
+    pub fn with_spawn_strategy(mut self, spawn_strategy: SpawnStrategy) -> Self {
+        self.spawn_strategy = spawn_strategy;
+        self
+    }
+
+    /// Places new food only on `cells`, for as long as any of them are free.
+    /// Once they are all taken, food goes anywhere on the board again.
+    pub fn with_food_region<I: IntoIterator<Item = Vector2>>(mut self, cells: I) -> Self {
+        self.food_region = Some(cells.into_iter().collect());
+        self
+    }
+
+    /// Places each new piece of food on the next of `cells` in turn, rather
+    /// than wherever the [`SpawnStrategy`] picks. If a cell is taken when its
+    /// turn comes, the piece goes on the nearest free cell instead, so that
+    /// the same moves always lead to the same food.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_food_sequence([Vector2 { x: 5, y: 5 }]);
+    ///
+    /// sim.advance();
+    /// assert_eq!(sim.food_position(), &Vector2 { x: 5, y: 5 });
+    /// ```
+    pub fn with_food_sequence<I: IntoIterator<Item = Vector2>>(mut self, cells: I) -> Self {
+        self.food_sequence = cells.into_iter().collect();
+        self
+    }
+
+    /// Replaces the [`SpeedCurve`] the [`SnakeSimulation`] runs at.
+    pub fn with_speed(mut self, speed: SpeedCurve) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Replaces the [`EventSchedule`] of the [`SnakeSimulation`].
+    pub fn with_events(mut self, events: EventSchedule) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Hook to pause or resume the simulation. While paused,
+    /// [`Self::advance`] does nothing. Intended to be called within input
+    /// handling logic.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    ///
+    /// let config = GameConfig {
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let mut sim = config.build().unwrap();
+    /// sim.set_paused(true);
+    /// sim.advance();
+    /// assert_eq!(sim.tick(), 0);
+    ///
+    /// sim.set_paused(false);
+    /// sim.advance();
+    /// assert_eq!(sim.tick(), 1);
+    /// ```
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns `true` if the simulation is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Hook to change what eating food does from now on, including the food
+    /// already on the board. Intended for warm-up and tutorial phases, which
+    /// switch to [`FoodKind::Practice`] and back. The food's cell is added to
+    /// the [changes](Self::changes), as it is drawn differently.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, FoodKind, Snake, SnakeSimulation};
+    /// use constrictor_core::render::Cell;
+    ///
+    /// let board = Board::new((0, 10), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0).unwrap();
+    ///
+    /// sim.set_food_kind(FoodKind::Practice);
+    /// assert_eq!(sim.changes(), [(Vector2 { x: 1, y: 0 }, Cell::PracticeFood)]);
+    ///
+    /// sim.advance();
+    ///
+    /// assert_eq!(sim.food_eaten(), 0);
+    /// assert_eq!(sim.snake().len(), 1);
+    /// ```
+    pub fn set_food_kind(&mut self, kind: FoodKind) {
+        self.food_kind = kind;
+        self.changes
+            .push((self.food_position, self.view().cell(&self.food_position)));
+    }
+
+    /// Hook to change how quickly the game runs from now on. Intended to be
+    /// called within input handling logic.
+    pub fn set_speed(&mut self, speed: SpeedCurve) {
+        self.speed = speed;
+    }
+
+    /// Hook to request the simulation to be quit for the given `reason`.
+    /// Intended to be called within input handling logic.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::models::{QuitReason, SimulationResult};
+    ///
+    /// let config = GameConfig {
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let mut sim = config.build().unwrap();
+    /// sim.quit(QuitReason::HostShutdown);
+    ///
+    /// assert_eq!(
+    ///     sim.result(),
+    ///     Some(&SimulationResult::ManuallyTerminated(QuitReason::HostShutdown))
+    /// );
+    /// ```
+    pub fn quit(&mut self, reason: QuitReason) {
+        let result = SimulationResult::ManuallyTerminated(reason);
+
+        for contender in self.snakes.iter_mut().filter(|c| c.in_play()) {
+            contender.result = Some(result);
+        }
+
+        self.simulation_result = Some(result);
     }
 
     /// Hook to change the player's movement direction. Intended to be called
-    /// within input handling logic.
+    /// within input handling logic. While the snake's controls are mirrored
+    /// by a [`PowerUp::Mirror`], `new_direction` is flipped first. Under
+    /// [`Rules::assist`], turns that would be fatal on the next step are
+    /// rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snake = Snake::new(Vector2 { x: 4, y: 4 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 0, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         one_turn_per_tick: true,
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // Only the first turn in a step is applied
+    /// sim.change_player_move_direction(Direction::Up);
+    /// sim.change_player_move_direction(Direction::Down);
+    /// assert_eq!(sim.snake().facing(), Direction::Up);
+    ///
+    /// sim.advance();
+    /// sim.change_player_move_direction(Direction::Left);
+    /// assert_eq!(sim.snake().facing(), Direction::Left);
+    /// ```
+    ///
+    /// With assist on, the snake won't turn into a wall:
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snake = Snake::new(Vector2 { x: 4, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 0, y: 4 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         assist: true,
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// sim.change_player_move_direction(Direction::Up);
+    /// assert_eq!(sim.snake().facing(), Direction::Right);
+    ///
+    /// sim.change_player_move_direction(Direction::Down);
+    /// assert_eq!(sim.snake().facing(), Direction::Down);
+    /// ```
     pub fn change_player_move_direction(&mut self, new_direction: Direction) {
-        self.snake.try_set_facing(new_direction);
+        self.change_move_direction(0, new_direction);
+    }
+
+    /// Like [`Self::change_player_move_direction`], but for the snake with
+    /// index `snake`. Does nothing if there is no such snake.
+    pub fn change_move_direction(&mut self, snake: usize, new_direction: Direction) {
+        let new_direction = if self.is_mirrored_for(snake) {
+            new_direction.flip()
+        } else {
+            new_direction
+        };
+
+        if self.rules.assist && self.is_fatal_move_for(snake, new_direction) {
+            return;
+        }
+
+        let one_turn_per_tick = self.rules.one_turn_per_tick;
+        let Some(contender) = self.snakes.get_mut(snake) else {
+            return;
+        };
+
+        if one_turn_per_tick && contender.turned_this_tick {
+            return;
+        }
+
+        let previous = contender.snake.facing();
+        if contender.snake.try_set_facing(new_direction) && new_direction != previous {
+            contender.turned_this_tick = true;
+        }
     }
 
     /// Get the final result of the simulation, if it has been determined.
+    /// With several snakes, this is the player's result once the whole game
+    /// is over.
     pub const fn result(&self) -> Option<&SimulationResult> {
         self.simulation_result.as_ref()
     }
 
-    /// Get a shared reference to the [`Snake`] being simulated.
-    pub const fn snake(&self) -> &Snake {
-        &self.snake
+    /// Get how the game ended for the snake with index `snake`, if it is out
+    /// of play.
+    pub fn result_for(&self, snake: usize) -> Option<&SimulationResult> {
+        self.snakes.get(snake)?.result.as_ref()
+    }
+
+    /// Get a shared reference to the player's [`Snake`].
+    pub fn snake(&self) -> &Snake {
+        &self.snakes[0].snake
+    }
+
+    /// Get a shared reference to the [`Snake`] with index `snake`, if there
+    /// is one.
+    pub fn snake_at(&self, snake: usize) -> Option<&Snake> {
+        self.snakes.get(snake).map(|contender| &contender.snake)
+    }
+
+    /// Returns an [`Iterator`] over every [`Snake`] in the simulation,
+    /// starting with the player's.
+    pub fn snakes(&self) -> impl ExactSizeIterator<Item = &Snake> {
+        self.snakes.iter().map(|contender| &contender.snake)
     }
 
     /// Get a shared reference to the [`Board`] the simulation is happening on.
@@ -132,67 +775,1618 @@ impl SnakeSimulation {
 
     /// Get a shared reference to the [`Vector2`] representing the current food
     /// position.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 3, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         fleeing_food: Some(2),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // Every second step, the food moves away from the snake
+    /// sim.advance();
+    /// assert_eq!(sim.food_position(), &Vector2 { x: 3, y: 0 });
+    /// sim.advance();
+    /// assert_eq!(sim.food_position(), &Vector2 { x: 4, y: 0 });
+    /// ```
     pub const fn food_position(&self) -> &Vector2 {
         &self.food_position
     }
 
-    /// Step the simulation forward by one step. The player's [`Snake`] will
-    /// move, possibly consuming food and growing. If the player wins or
-    /// dies, [`Some<SimulationResult>`] is returned accordingly. Otherwise,
-    /// [`None`] is returned.
+    /// Get what eating the food does.
+    pub const fn food_kind(&self) -> FoodKind {
+        self.food_kind
+    }
+
+    /// Get a shared reference to the [`Rules`] the simulation is played under.
+    pub const fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Get the [`SpeedCurve`] the game currently runs at.
+    pub const fn speed(&self) -> &SpeedCurve {
+        &self.speed
+    }
+
+    /// Get how long the current step should last, according to the
+    /// [`SpeedCurve`] and the food eaten so far.
+    pub fn tick_interval(&self) -> Duration {
+        let food_eaten = self.snakes.iter().map(|c| c.food_eaten).sum();
+        self.speed.tick_interval(food_eaten)
+    }
+
+    /// Get the cells currently blocked by obstacles.
+    pub const fn obstacles(&self) -> &HashSet<Vector2> {
+        &self.obstacles
+    }
+
+    /// Get the power-ups lying on the board, by the cell each is in.
+    pub const fn power_ups(&self) -> &HashMap<Vector2, PowerUp> {
+        &self.power_ups
+    }
+
+    /// Get the cells the board has shrunk away from, which kill any
+    /// [`Snake`] in them. See [`Rules::shrink`].
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{
+    ///     Board, DeathReason, Rules, Shrink, SimulationResult, Snake, SnakeSimulation,
+    /// };
+    ///
+    /// let board = Board::new((0, 5), (0, 5));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 2 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 1 }, 1)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         shrink: Some(Shrink { after: 2, every: 2 }),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// sim.advance();
+    /// assert!(sim.hazard().is_empty());
+    ///
+    /// // The outer ring closes while the snake is safely inside it
+    /// sim.advance();
+    /// assert_eq!(sim.hazard().len(), 16);
+    /// assert_eq!(sim.result(), None);
+    ///
+    /// // The next ring closes over the snake, leaving only the centre
+    /// sim.change_player_move_direction(Direction::Up);
+    /// sim.advance();
+    /// sim.change_player_move_direction(Direction::Right);
+    /// assert_eq!(
+    ///     sim.advance(),
+    ///     Some(&SimulationResult::Died(DeathReason::Hazard))
+    /// );
+    /// ```
+    pub const fn hazard(&self) -> &HashSet<Vector2> {
+        &self.hazard
+    }
+
+    /// Get the number of pieces of food the player has eaten so far.
+    pub fn food_eaten(&self) -> usize {
+        self.food_eaten_by(0)
+    }
+
+    /// Get the number of pieces of food the snake with index `snake` has
+    /// eaten so far.
+    pub fn food_eaten_by(&self, snake: usize) -> usize {
+        self.snakes.get(snake).map_or(0, |c| c.food_eaten)
+    }
+
+    /// Get the player's score. Each piece of food eaten is worth the combo
+    /// multiplier at the time, so without [`Rules::combo_window`] this is the
+    /// same as [`Self::food_eaten`].
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 3), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         combo_window: Some(5),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// sim.advance();
+    /// assert_eq!(sim.combo_multiplier(), 1);
+    ///
+    /// // The next food is right in front, so it is eaten straight away
+    /// sim.advance();
+    /// assert_eq!(sim.combo_multiplier(), 2);
+    /// assert_eq!(sim.food_eaten(), 2);
+    /// assert_eq!(sim.score(), 3);
+    /// ```
+    pub fn score(&self) -> usize {
+        self.score_for(0)
+    }
+
+    /// Like [`Self::score`], but for the snake with index `snake`.
+    pub fn score_for(&self, snake: usize) -> usize {
+        self.snakes.get(snake).map_or(0, |c| c.score)
+    }
+
+    /// Get the player's current combo multiplier, which is 1 until they eat
+    /// in quick succession. See [`Rules::combo_window`].
+    pub fn combo_multiplier(&self) -> u32 {
+        self.combo_multiplier_for(0)
+    }
+
+    /// Like [`Self::combo_multiplier`], but for the snake with index `snake`.
+    pub fn combo_multiplier_for(&self, snake: usize) -> u32 {
+        self.snakes.get(snake).map_or(1, |c| c.combo)
+    }
+
+    /// Get how many more pieces of food must be eaten to win, or [`None`] if
+    /// there is no food goal and the game is won by filling the board.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::models::Rules;
+    ///
+    /// let config = GameConfig {
+    ///     rules: Rules {
+    ///         food_goal: Some(5),
+    ///         ..Default::default()
+    ///     },
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(config.build().unwrap().remaining_food(), Some(5));
+    ///
+    /// let endless = GameConfig {
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(endless.build().unwrap().remaining_food(), None);
+    /// ```
+    pub fn remaining_food(&self) -> Option<usize> {
+        self.remaining_food_for(0)
+    }
+
+    /// Like [`Self::remaining_food`], but for the snake with index `snake`.
+    pub fn remaining_food_for(&self, snake: usize) -> Option<usize> {
+        let goal = self.rules.food_goal? as usize;
+        Some(goal.saturating_sub(self.food_eaten_by(snake)))
+    }
+
+    /// Get how many cells the player's [`Snake`] has moved so far.
+    pub fn moves(&self) -> u64 {
+        self.moves_for(0)
+    }
+
+    /// Like [`Self::moves`], but for the snake with index `snake`.
+    pub fn moves_for(&self, snake: usize) -> u64 {
+        self.snakes.get(snake).map_or(0, |c| c.moves)
+    }
+
+    /// Get how many more moves the player has to win in, or [`None`] if
+    /// there is no [`Rules::move_limit`].
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, SimulationResult, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 7, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         food_goal: Some(1),
+    ///         move_limit: Some(3),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// sim.advance();
+    /// assert_eq!(sim.remaining_moves(), Some(2));
+    ///
+    /// // The food is seven cells away, which is too far
+    /// sim.advance();
+    /// assert_eq!(sim.advance(), Some(&SimulationResult::OutOfMoves));
+    /// assert_eq!(sim.remaining_moves(), Some(0));
+    /// ```
+    pub fn remaining_moves(&self) -> Option<u64> {
+        self.remaining_moves_for(0)
+    }
+
+    /// Like [`Self::remaining_moves`], but for the snake with index `snake`.
+    pub fn remaining_moves_for(&self, snake: usize) -> Option<u64> {
+        let limit = u64::from(self.rules.move_limit?);
+        Some(limit.saturating_sub(self.moves_for(snake)))
+    }
+
+    /// Get how many more steps the player has before their time runs out,
+    /// or [`None`] if there is no [`Rules::time_limit`]. Each piece of food
+    /// eaten adds to it.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{
+    ///     Board, Rules, SimulationResult, Snake, SnakeSimulation, TimeLimit,
+    /// };
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0)
+    ///     .unwrap()
+    ///     .with_food_sequence([Vector2 { x: 7, y: 7 }])
+    ///     .with_rules(Rules {
+    ///         time_limit: Some(TimeLimit { steps: 2, bonus_per_food: 3 }),
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // Eating on the first step earns three more
+    /// sim.advance();
+    /// assert_eq!(sim.remaining_time(), Some(4));
+    ///
+    /// for _ in 0..3 {
+    ///     assert_eq!(sim.advance(), None);
+    /// }
+    /// assert_eq!(sim.advance(), Some(&SimulationResult::TimeExpired));
+    /// ```
+    pub fn remaining_time(&self) -> Option<u64> {
+        self.remaining_time_for(0)
+    }
+
+    /// Like [`Self::remaining_time`], but for the snake with index `snake`.
+    pub fn remaining_time_for(&self, snake: usize) -> Option<u64> {
+        let limit = self.rules.time_limit?;
+        let bonus = self.snakes.get(snake).map_or(0, |c| c.time_bonus);
+        Some(limit.steps.saturating_add(bonus).saturating_sub(self.tick))
+    }
+
+    /// Get how many lives the player has left, counting the one they are on.
+    /// Once none are left, the player is out of play.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 8), (0, 8));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 4 }, Direction::Left);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 7, y: 7 }, 0)
+    ///     .unwrap()
+    ///     .with_rules(Rules {
+    ///         lives: 2,
+    ///         ..Default::default()
+    ///     });
+    ///
+    /// // Running into the wall costs a life, and the snake respawns
+    /// sim.advance();
+    /// assert_eq!(sim.result(), None);
+    /// assert_eq!(sim.lives_left(), 1);
+    /// assert!(sim.is_invulnerable_for(0));
+    /// ```
+    pub fn lives_left(&self) -> u32 {
+        self.lives_left_for(0)
+    }
+
+    /// Like [`Self::lives_left`], but for the snake with index `snake`.
+    pub fn lives_left_for(&self, snake: usize) -> u32 {
+        self.snakes.get(snake).map_or(0, |c| c.lives)
+    }
+
+    /// Returns `true` if the snake with index `snake` has respawned recently
+    /// enough to still be invulnerable. See [`Rules::respawn_grace`].
+    pub fn is_invulnerable_for(&self, snake: usize) -> bool {
+        self.snakes.get(snake).is_some_and(|c| c.grace > 0)
+    }
+
+    /// Returns `true` if the snake with index `snake` is carrying a
+    /// [`PowerUp::Shield`].
+    pub fn is_shielded_for(&self, snake: usize) -> bool {
+        self.snakes.get(snake).is_some_and(|c| c.shielded)
+    }
+
+    /// Returns `true` if the controls of the snake with index `snake` are
+    /// mirrored by a [`PowerUp::Mirror`].
+    pub fn is_mirrored_for(&self, snake: usize) -> bool {
+        self.snakes.get(snake).is_some_and(|c| c.mirrored > 0)
+    }
+
+    /// Get how close the player is to winning, from `0.0` to `1.0`. With a
+    /// food goal this is the share of the goal eaten so far, otherwise it is
+    /// the share of the open board the [`Snake`] covers.
+    pub fn progress(&self) -> f32 {
+        self.progress_for(0)
+    }
+
+    /// Like [`Self::progress`], but for the snake with index `snake`.
+    pub fn progress_for(&self, snake: usize) -> f32 {
+        if self.result_for(snake) == Some(&SimulationResult::Won) {
+            return 1.0;
+        }
+
+        let (done, total) = match self.rules.food_goal {
+            Some(goal) => (self.food_eaten_by(snake), goal as usize),
+            None => (
+                self.snake_at(snake).map_or(0, Snake::len),
+                self.board
+                    .free_cells()
+                    .excluding(&self.obstacles)
+                    .excluding(&self.hazard)
+                    .count(),
+            ),
+        };
+
+        if total == 0 {
+            return 1.0;
+        }
+
+        (done as f32 / total as f32).min(1.0)
+    }
+
+    /// Get the number of steps the simulation has advanced.
+    pub const fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Get the cells changed by the most recent call to [`Self::advance`], and
+    /// what each changed to, including the heads of snakes that turned since
+    /// the step before. Applying these to a
+    /// [`FrameBuffer`](crate::render::FrameBuffer) of the previous state
+    /// brings it up to date.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    /// use constrictor_core::render::Cell;
+    ///
+    /// let board = Board::new((0, 4), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 3, y: 0 }, 0).unwrap();
+    ///
+    /// sim.advance();
+    /// assert_eq!(
+    ///     sim.changes(),
+    ///     [
+    ///         (Vector2 { x: 0, y: 0 }, Cell::Empty),
+    ///         (
+    ///             Vector2 { x: 1, y: 0 },
+    ///             Cell::SnakeHead { snake: 0, facing: Direction::Right }
+    ///         ),
+    ///     ]
+    /// );
+    ///
+    /// // Turning into the wall goes nowhere, but the head still faces it
+    /// sim.change_player_move_direction(Direction::Up);
+    /// assert!(sim.advance().is_some());
+    /// assert_eq!(
+    ///     sim.changes(),
+    ///     [(
+    ///         Vector2 { x: 1, y: 0 },
+    ///         Cell::SnakeHead { snake: 0, facing: Direction::Up }
+    ///     )]
+    /// );
+    /// ```
+    pub fn changes(&self) -> &[(Vector2, Cell)] {
+        &self.changes
+    }
+
+    /// Get a read-only [`SimulationView`] of the simulation, from the
+    /// player's point of view.
+    pub const fn view(&self) -> SimulationView<'_> {
+        SimulationView::new(self, 0)
+    }
+
+    /// Like [`Self::view`], but from the point of view of the snake with
+    /// index `snake`, which the view treats as the player's. This is how a
+    /// [`Controller`](crate::bots::Controller) steers a snake other than the
+    /// player's.
+    ///
+    /// # Panics
+    /// Panics if there is no snake with index `snake`.
+    pub fn view_as(&self, snake: usize) -> SimulationView<'_> {
+        assert!(snake < self.snakes.len(), "no snake with index {snake}");
+        SimulationView::new(self, snake)
+    }
+
+    /// Checks that the simulation's internal state is consistent. This is
+    /// cheap enough to run every step, and lets frontends report corruption
+    /// as soon as it happens rather than when it eventually causes a panic.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 4), (0, 1));
+    /// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    /// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 1, y: 0 }, 0).unwrap();
+    ///
+    /// while sim.advance().is_none() {
+    ///     assert_eq!(sim.check_invariants(), Ok(()));
+    /// }
+    /// ```
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        for snake in self.snakes() {
+            if !snake.is_consistent() {
+                return Err(InvariantViolation::SnakeCorrupted);
+            }
+
+            if let Some(cell) = snake.body_iter().find(|cell| !self.board.contains(cell)) {
+                return Err(InvariantViolation::SnakeOutOfBounds(*cell));
+            }
+        }
+
+        if !self.board.contains(&self.food_position) {
+            return Err(InvariantViolation::FoodOutOfBounds(self.food_position));
+        }
+
+        // Winning leaves the food where the head finished, since there is
+        // nowhere left to respawn it.
+        let won = self
+            .snakes
+            .iter()
+            .any(|c| c.result == Some(SimulationResult::Won));
+        if !won
+            && self
+                .snakes()
+                .any(|snake| snake.contains(&self.food_position))
+        {
+            return Err(InvariantViolation::FoodUnderSnake(self.food_position));
+        }
+
+        Ok(())
+    }
+
+    /// Computes a fingerprint of the simulation's state. Two simulations that
+    /// have stayed in sync produce the same hash on every platform, which lets
+    /// peers running the same game cheaply check they agree. Hashes are not
+    /// guaranteed to be stable between versions of this crate.
+    ///
+    /// Everything that decides how the game goes on is covered: the tick;
+    /// the board's bounds, mask, [`Doorway`](crate::models::Doorway)s,
+    /// [`Portal`](crate::models::Portal)s and [`Terrain`]; the [`Rules`],
+    /// [`SpeedCurve`], [`SpawnStrategy`], food region and [`EventSchedule`];
+    /// every snake's body from head to tail; the food; and the random number
+    /// generator, so diverging randomness is caught before it shows on the
+    /// board. Sets are hashed in a fixed order, whatever order they iterate
+    /// in. Whether the game is paused is left out, since each peer pauses its
+    /// own copy, and so is how the snakes look.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Rules, Snake, SnakeSimulation, Terrain};
+    ///
+    /// let make = |board: Board| {
+    ///     let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+    ///     SnakeSimulation::seeded(board, snake, Vector2 { x: 4, y: 0 }, 7).unwrap()
+    /// };
+    /// let board = Board::new((0, 8), (0, 8));
+    ///
+    /// let (mut a, mut b) = (make(board.clone()), make(board.clone()));
+    /// assert_eq!(a.state_hash(), b.state_hash());
+    ///
+    /// // The same board under other rules is another game
+    /// let lives = make(board.clone()).with_rules(Rules {
+    ///     lives: 3,
+    ///     ..Default::default()
+    /// });
+    /// assert_ne!(lives.state_hash(), a.state_hash());
+    ///
+    /// // And so is the board with terrain laid on it
+    /// let sticky = make(board.with_terrain(Vector2 { x: 6, y: 6 }, Terrain::Sticky));
+    /// assert_ne!(sticky.state_hash(), a.state_hash());
+    ///
+    /// a.change_player_move_direction(Direction::Down);
+    /// a.advance();
+    /// b.advance();
+    /// assert_ne!(a.state_hash(), b.state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = StateHasher::default();
+
+        hash.write(self.tick);
+        hash.write_board(&self.board);
+        hash.write_rules(&self.rules);
+        hash.write_speed(&self.speed);
+        hash.write_events(&self.events);
+        hash.write(self.closed_rings.into());
+
+        match self.spawn_strategy {
+            SpawnStrategy::Uniform => hash.write(0),
+            SpawnStrategy::AwayFromHead { min_distance } => {
+                hash.write(1 + (u64::from(min_distance) << 8));
+            }
+        }
+
+        hash.write_option(self.food_region.as_ref().map(|region| region.len() as u64));
+        if let Some(region) = &self.food_region {
+            hash.write_cells(region);
+        }
+
+        // The next number drawn stands in for the generator's whole state,
+        // without disturbing it
+        hash.write(self.rng.clone().random());
+
+        hash.write_point(&self.food_position);
+        hash.write(self.food_kind as u64);
+
+        hash.write(self.food_sequence.len() as u64);
+        for cell in &self.food_sequence {
+            hash.write_point(cell);
+        }
+
+        hash.write_cells(&self.obstacles);
+
+        let mut power_ups: Vec<_> = self.power_ups.iter().collect();
+        power_ups.sort_by_key(|(cell, _)| (cell.y, cell.x));
+        hash.write(power_ups.len() as u64);
+        for (cell, power_up) in power_ups {
+            hash.write_point(cell);
+            hash.write_power_up(power_up);
+        }
+
+        hash.write_cells(&self.hazard);
+
+        hash.write(self.snakes.len() as u64);
+        for contender in &self.snakes {
+            hash.write(contender.food_eaten as u64);
+            hash.write(contender.moves);
+            hash.write(contender.time_bonus);
+            hash.write(contender.score as u64);
+            hash.write(contender.combo.into());
+            hash.write(contender.since_meal.map_or(0, |steps| u64::from(steps) + 1));
+            hash.write(contender.pending_growth.into());
+            hash.write(contender.lives.into());
+            hash.write(contender.grace.into());
+            hash.write(contender.shielded.into());
+            hash.write(contender.mirrored.into());
+            hash.write(contender.stuck.into());
+            hash.write_result(contender.result.as_ref());
+
+            let snake = &contender.snake;
+            hash.write(snake.facing() as u64);
+            hash.write(snake.len() as u64);
+            for cell in snake.body_iter() {
+                hash.write_point(cell);
+            }
+        }
+
+        hash.write_result(self.simulation_result.as_ref());
+        hash.0
+    }
+
+    /// Determines whether moving the player's [`Snake`] one cell in
+    /// `direction` on the next step would kill it. This does not consider
+    /// whether the [`Snake`] is actually allowed to turn that way, nor where
+    /// other snakes are about to move.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::{Direction, Vector2};
+    /// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+    ///
+    /// let board = Board::new((0, 3), (0, 3));
+    /// let snake = Snake::new(Vector2 { x: 2, y: 1 }, Direction::Right);
+    /// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 0, y: 0 }, 0).unwrap();
+    ///
+    /// assert!(sim.is_fatal_move(Direction::Right));
+    /// assert!(!sim.is_fatal_move(Direction::Up));
+    /// ```
+    pub fn is_fatal_move(&self, direction: Direction) -> bool {
+        self.is_fatal_move_for(0, direction)
+    }
+
+    /// Like [`Self::is_fatal_move`], but for the snake with index `snake`.
+    pub fn is_fatal_move_for(&self, snake: usize, direction: Direction) -> bool {
+        let Some(head) = self.snake_at(snake).map(Snake::head) else {
+            return false;
+        };
+
+        match self.board.step(head, direction) {
+            BoardStep::To(cell) => self.collision_at(snake, &cell).is_some(),
+            BoardStep::Blocked => true,
+            BoardStep::Exit(_) => false,
+        }
+    }
+
+    /// Step the simulation forward by one step. Every [`Snake`] still in
+    /// play will move, possibly consuming food and growing. If the game is
+    /// won or lost, [`Some<SimulationResult>`] is returned accordingly.
+    /// Otherwise, [`None`] is returned.
     pub fn advance(&mut self) -> Option<&SimulationResult> {
+        self.changes.clear();
+
+        // Turning changes how a head is drawn straight away, but the snake
+        // may not go anywhere: on a step that ends the game, while it is held
+        // still, or while the game is paused
+        let turned: Vec<usize> = (0..self.snakes.len())
+            .filter(|&index| self.snakes[index].turned_this_tick)
+            .collect();
+
+        self.advance_snakes();
+
+        for index in turned {
+            let head = *self.snakes[index].snake.head();
+            let cell = self.view().cell(&head);
+
+            match self
+                .changes
+                .iter_mut()
+                .find(|(changed, _)| *changed == head)
+            {
+                Some((_, recorded)) => *recorded = cell,
+                None => self.changes.push((head, cell)),
+            }
+        }
+
+        self.result()
+    }
+
+    /// Does the work of [`Self::advance`], other than recording which heads
+    /// have turned.
+    fn advance_snakes(&mut self) -> Option<&SimulationResult> {
         // Short circuit advancement and return the simulation result if it is known
-        if self.result().is_some() {
+        if self.result().is_some() || self.paused {
             return self.result();
         }
 
-        let speculative_head = self.snake.next_head_position();
+        // Sticky terrain holds a snake back every other step, and boost terrain
+        // gives it a second move once everyone has made their first
+        let mut movers = Vec::new();
+        let mut stuck = Vec::new();
+        let mut boosted = Vec::new();
+        for (index, contender) in self.snakes.iter().enumerate() {
+            if !contender.in_play() {
+                continue;
+            }
 
-        if !self.board.contains(&speculative_head) {
-            return self.terminate(SimulationResult::Died(DeathReason::HitWall));
+            match self.board.terrain_at(contender.snake.head()) {
+                Some(Terrain::Sticky) if !contender.stuck => stuck.push(index),
+                Some(Terrain::Boost) => {
+                    movers.push(index);
+                    boosted.push(index);
+                }
+                _ => movers.push(index),
+            }
         }
 
-        // Check if we're about to run into ourselves
-        let snake_will_hit_food = speculative_head == self.food_position;
-        let snake_will_hit_tail = &speculative_head == self.snake.tail();
+        let plan = self.plan_moves(&movers);
 
-        if self.snake.contains(&speculative_head) && (!snake_will_hit_tail || snake_will_hit_food) {
-            return self.terminate(SimulationResult::Died(DeathReason::HitSelf));
+        // Nobody moving means nobody is left in play, so the game is over
+        if plan.moves.is_empty()
+            && plan.held.is_empty()
+            && plan.respawning.is_empty()
+            && stuck.is_empty()
+        {
+            return self.end_if_decided();
         }
 
-        // The snake should advance before we respawn the food, else it is possible for
-        // the food to spawn exactly where the head ends up. This puts us in an invalid
-        // state where the snake is on top of the food.
-        self.snake.advance(snake_will_hit_food);
+        self.tick += 1;
+        for (index, contender) in self.snakes.iter_mut().enumerate() {
+            contender.turned_this_tick = false;
+            contender.stuck = stuck.contains(&index);
+            contender.grace = contender.grace.saturating_sub(1);
+            contender.mirrored = contender.mirrored.saturating_sub(1);
+            contender.wait_for_meal(self.rules.combo_window);
+        }
+
+        let mut respawning = plan.respawning;
+        let Some(mut ate) = self.make_moves(&plan.moves) else {
+            return self.result();
+        };
+
+        boosted.retain(|index| {
+            self.snakes[*index].in_play() && plan.moves.iter().any(|(mover, _)| mover == index)
+        });
+
+        if !boosted.is_empty() {
+            let plan = self.plan_moves(&boosted);
+            respawning.extend(plan.respawning);
+
+            match self.make_moves(&plan.moves) {
+                Some(ate_again) => ate |= ate_again,
+                None => return self.result(),
+            }
+        }
+
+        // Fleeing food stays put on the step it was placed
+        if !ate
+            && let Some(period) = self.rules.fleeing_food
+            && self.tick.is_multiple_of(u64::from(period.max(1)))
+        {
+            self.flee_food();
+        }
+
+        // A closing ring catches anyone in it, and the food moves out of its way
+        if self.ring_due() {
+            respawning.extend(self.close_ring());
+
+            if self.hazard.contains(&self.food_position) {
+                // The snakes fill what is left of the board, so they have won
+                let winners: Vec<usize> = (0..self.snakes.len())
+                    .filter(|&index| self.snakes[index].in_play())
+                    .collect();
+                return self.win(&winners);
+            }
+        }
+
+        // With nowhere safe to respawn, that life was the last
+        for (index, result) in respawning {
+            if !self.respawn(index) {
+                self.snakes[index].result = Some(result);
+            }
+        }
+
+        // Eating the last food wins before this, even on the very last move
+        if let Some(limit) = self.rules.move_limit {
+            for contender in &mut self.snakes {
+                if contender.in_play() && contender.moves >= u64::from(limit) {
+                    contender.result = Some(SimulationResult::OutOfMoves);
+                }
+            }
+        }
+
+        if let Some(limit) = self.rules.time_limit {
+            for contender in &mut self.snakes {
+                let deadline = limit.steps.saturating_add(contender.time_bonus);
+                if contender.in_play() && self.tick >= deadline {
+                    contender.result = Some(SimulationResult::TimeExpired);
+                }
+            }
+        }
+
+        if self.end_if_decided().is_some() {
+            return self.result();
+        }
+
+        self.run_events();
+        None
+    }
+
+    /// Works out where each of the `movers` is headed, and which of them won't
+    /// get there. Those that die are taken out of play, unless they have
+    /// lives to spare. Nothing moves yet.
+    fn plan_moves(&mut self, movers: &[usize]) -> Plan {
+        // Invulnerable snakes hold still instead of dying. A shield stops the
+        // snake short of a wall, or lets it carry on over its own body.
+        let mut moves = Vec::new();
+        let mut out = Vec::new();
+        let mut held = Vec::new();
+        let mut shields_used = Vec::new();
+        for &index in movers {
+            let contender = &self.snakes[index];
+            let snake = &contender.snake;
+            let shielded = contender.shielded && contender.grace == 0;
+            match self.board.step(snake.head(), snake.facing()) {
+                BoardStep::To(cell) => match self.collision_at(index, &cell) {
+                    Some(_) if contender.grace > 0 => held.push(index),
+                    Some(DeathReason::HitWall) if shielded => {
+                        shields_used.push(index);
+                        held.push(index);
+                    }
+                    Some(DeathReason::HitSelf) if shielded => {
+                        shields_used.push(index);
+                        moves.push((index, cell));
+                    }
+                    Some(reason) => out.push((index, SimulationResult::Died(reason))),
+                    None => moves.push((index, cell)),
+                },
+                BoardStep::Blocked if contender.grace > 0 => held.push(index),
+                BoardStep::Blocked if shielded => {
+                    shields_used.push(index);
+                    held.push(index);
+                }
+                BoardStep::Blocked => {
+                    out.push((index, SimulationResult::Died(DeathReason::HitWall)));
+                }
+                BoardStep::Exit(target) => out.push((index, SimulationResult::LeftBoard(target))),
+            }
+        }
+
+        for index in shields_used {
+            self.snakes[index].shielded = false;
+        }
+
+        // Invulnerable snakes also give way rather than meeting another head on
+        let giving_way: Vec<usize> = moves
+            .iter()
+            .filter(|&&(index, cell)| {
+                self.snakes[index].grace > 0
+                    && moves
+                        .iter()
+                        .any(|&(other, other_cell)| other != index && other_cell == cell)
+            })
+            .map(|&(index, _)| index)
+            .collect();
+        moves.retain(|(index, _)| !giving_way.contains(index));
+        held.extend(giving_way);
+
+        // When heads meet, only a snake longer than all the others survives
+        let length = |index: usize| self.snakes[index].snake.len();
+        let head_on: Vec<_> = moves
+            .iter()
+            .filter_map(|&(index, cell)| {
+                let rival = moves.iter().find(|&&(other, other_cell)| {
+                    other != index && other_cell == cell && length(other) >= length(index)
+                })?;
+
+                Some((index, SimulationResult::Died(DeathReason::HeadOn(rival.0))))
+            })
+            .collect();
 
-        if !snake_will_hit_food {
+        moves.retain(|(index, _)| !head_on.iter().any(|(dead, _)| dead == index));
+
+        // A snake with lives to spare respawns once everyone else has moved
+        let mut respawning = Vec::new();
+        for (index, result) in out.into_iter().chain(head_on) {
+            let contender = &mut self.snakes[index];
+            if let SimulationResult::Died(_) = result {
+                contender.lives = contender.lives.saturating_sub(1);
+                if contender.lives > 0 {
+                    respawning.push((index, result));
+                    continue;
+                }
+            }
+
+            contender.result = Some(result);
+        }
+
+        Plan {
+            moves,
+            held,
+            respawning,
+        }
+    }
+
+    /// Moves each snake in `moves` on to its cell, picking up whatever is
+    /// there, and replaces the food if it was eaten. Returns whether the food
+    /// was eaten, or [`None`] if that ended the game.
+    fn make_moves(&mut self, moves: &[(usize, Vector2)]) -> Option<bool> {
+        let touched: Vec<Vector2> = moves
+            .iter()
+            .flat_map(|&(index, cell)| {
+                let snake = &self.snakes[index].snake;
+                [*snake.head(), *snake.tail(), cell]
+            })
+            .collect();
+        let before: Vec<Cell> = touched.iter().map(|cell| self.view().cell(cell)).collect();
+
+        let mut eater = None;
+        for &(index, cell) in moves {
+            let contender = &mut self.snakes[index];
+            let snake_will_hit_food = cell == self.food_position;
+
+            if snake_will_hit_food {
+                eater = Some(index);
+            }
+
+            if let Some(power_up) = self.power_ups.remove(&cell) {
+                match power_up {
+                    PowerUp::Shield => contender.shielded = true,
+                    PowerUp::Mirror { ticks } => contender.mirrored = ticks,
+                }
+            }
+
+            if snake_will_hit_food && self.food_kind == FoodKind::Regular {
+                contender.eat(self.rules.combo_window);
+                if let Some(limit) = self.rules.time_limit {
+                    contender.time_bonus =
+                        contender.time_bonus.saturating_add(limit.bonus_per_food);
+                }
+
+                contender.pending_growth = contender
+                    .pending_growth
+                    .saturating_add(self.rules.growth_per_food);
+            }
+
+            // The snake should advance before we respawn the food, else it is possible for
+            // the food to spawn exactly where the head ends up. This puts us in an invalid
+            // state where the snake is on top of the food.
+            let snake_will_grow = contender.pending_growth > 0;
+            contender.pending_growth = contender.pending_growth.saturating_sub(1);
+            contender.snake.advance_to(cell, snake_will_grow);
+            contender.moves += 1;
+        }
+
+        self.record_changes(&touched, &before);
+
+        let Some(eater) = eater else {
+            return Some(false);
+        };
+
+        if self.remaining_food_for(eater) == Some(0) {
+            _ = self.win(&[eater]);
+            return None;
+        }
+
+        let Some(position) = self.next_food_position() else {
+            // Failed to spawn food, can only happen when the snakes fill the entire
+            // board. So if we get here, everyone still in play has actually won.
+            let winners: Vec<usize> = moves.iter().map(|&(index, _)| index).collect();
+            _ = self.win(&winners);
             return None;
+        };
+
+        self.food_position = position;
+        self.changes.push((position, self.view().cell(&position)));
+        Some(true)
+    }
+
+    /// Steps the simulation for as long as `source` says a step is due,
+    /// stopping early if the game ends or is paused. Returns the number of
+    /// steps taken.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::models::UntilTick;
+    ///
+    /// let config = GameConfig {
+    ///     seed: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let mut sim = config.build().unwrap();
+    /// assert_eq!(sim.run(&mut UntilTick(3)), 3);
+    ///
+    /// sim.set_paused(true);
+    /// assert_eq!(sim.run(&mut UntilTick(6)), 0);
+    /// ```
+    pub fn run(&mut self, source: &mut impl TickSource) -> u32 {
+        let mut steps = 0;
+
+        // The source is asked first, so it sees pauses and can react to them
+        while self.result().is_none() && source.due(self) && !self.paused {
+            self.advance();
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// Carries out every scheduled event triggered on the current tick.
+    fn run_events(&mut self) {
+        // Taken out for the duration, since actions need the simulation mutably
+        let events = std::mem::take(&mut self.events);
+
+        for event in events.iter() {
+            let triggered = match event.trigger {
+                EventTrigger::At(tick) => self.tick == tick,
+                EventTrigger::Every { start, period } => {
+                    self.tick >= start && (self.tick - start).is_multiple_of(period.max(1))
+                }
+                EventTrigger::Randomly { one_in } => self.rng.random_ratio(1, one_in.max(1)),
+            };
+
+            if triggered {
+                self.apply_event(&event.action);
+            }
         }
 
-        let spawn_result = self.random_valid_food_position();
-        if let Some(position) = spawn_result {
+        self.events = events;
+    }
+
+    fn apply_event(&mut self, action: &EventAction) {
+        match action {
+            EventAction::SpawnObstacles { count } => {
+                // Don't drop an obstacle right in front of a snake
+                let near_heads = self.near_heads(3);
+
+                for _ in 0..*count {
+                    let cell = free_cells(
+                        &self.board,
+                        &self.snakes,
+                        &self.obstacles,
+                        &self.power_ups,
+                        &self.hazard,
+                    )
+                    .excluding(&self.food_position)
+                    .excluding(&near_heads)
+                    .choose(&mut self.rng);
+
+                    let Some(cell) = cell else {
+                        break;
+                    };
+
+                    self.obstacles.insert(cell);
+                    self.changes.push((cell, self.view().cell(&cell)));
+                }
+            }
+            EventAction::SpawnPowerUp(power_up) => {
+                let near_heads = self.near_heads(3);
+                let cell = free_cells(
+                    &self.board,
+                    &self.snakes,
+                    &self.obstacles,
+                    &self.power_ups,
+                    &self.hazard,
+                )
+                .excluding(&self.food_position)
+                .excluding(&near_heads)
+                .choose(&mut self.rng);
+
+                if let Some(cell) = cell {
+                    self.power_ups.insert(cell, *power_up);
+                    self.changes.push((cell, self.view().cell(&cell)));
+                }
+            }
+            EventAction::ClearObstacles => {
+                // Whatever the obstacles stood on shows again, like terrain or
+                // the hazard zone
+                let cleared: Vec<_> = self.obstacles.drain().collect();
+                for cell in cleared {
+                    self.changes.push((cell, self.view().cell(&cell)));
+                }
+            }
+            EventAction::SetSpeed(speed) => self.speed = *speed,
+        }
+    }
+
+    /// Moves the food to the free neighbouring cell furthest from the nearest
+    /// head of a [`Snake`] in play, as long as that is further than where it
+    /// is now.
+    fn flee_food(&mut self) {
+        let distance = |cell: Vector2| {
+            self.snakes
+                .iter()
+                .filter(|contender| contender.in_play())
+                .map(|contender| contender.snake.head().manhattan_distance(cell))
+                .min()
+        };
+
+        let free = free_cells(
+            &self.board,
+            &self.snakes,
+            &self.obstacles,
+            &self.power_ups,
+            &self.hazard,
+        );
+
+        let from = self.food_position;
+        let to = Direction::ALL
+            .into_iter()
+            .filter_map(|direction| match self.board.step(&from, direction) {
+                BoardStep::To(cell) if free.contains(&cell) => Some(cell),
+                _ => None,
+            })
+            .max_by_key(|cell| distance(*cell));
+
+        let Some(to) = to.filter(|to| distance(*to) > distance(from)) else {
+            return;
+        };
+
+        self.food_position = to;
+        self.changes.push((from, self.view().cell(&from)));
+        self.changes.push((to, self.view().cell(&to)));
+    }
+
+    /// Returns `true` if another of the board's rings is due to close on the
+    /// current tick. The innermost ring never closes.
+    fn ring_due(&self) -> bool {
+        let Some(shrink) = self.rules.shrink else {
+            return false;
+        };
+
+        self.closed_rings + 1 < self.board.ring_count()
+            && self.tick >= shrink.after
+            && (self.tick - shrink.after).is_multiple_of(shrink.every.max(1))
+    }
+
+    /// Turns the outermost ring still open into hazard, clearing out whatever
+    /// was in it. Every [`Snake`] still in play with any part of it in the
+    /// ring dies, even an invulnerable one. Returns those with lives to spare,
+    /// so they can respawn. Food in the ring moves elsewhere, unless there is
+    /// no room left for it.
+    fn close_ring(&mut self) -> Vec<(usize, SimulationResult)> {
+        let ring = self.closed_rings;
+        self.closed_rings += 1;
+
+        let cells: Vec<Vector2> = self
+            .board
+            .cell_iter()
+            .filter(|cell| self.board.ring(cell) == Some(ring))
+            .collect();
+
+        for cell in &cells {
+            self.hazard.insert(*cell);
+            self.obstacles.remove(cell);
+            self.power_ups.remove(cell);
+        }
+
+        let mut respawning = Vec::new();
+        let result = SimulationResult::Died(DeathReason::Hazard);
+        for (index, contender) in self.snakes.iter_mut().enumerate() {
+            let caught = contender
+                .snake
+                .body_iter()
+                .any(|cell| self.hazard.contains(cell));
+            if !contender.in_play() || !caught {
+                continue;
+            }
+
+            contender.lives = contender.lives.saturating_sub(1);
+            if contender.lives > 0 {
+                respawning.push((index, result));
+            } else {
+                contender.result = Some(result);
+            }
+        }
+
+        if self.hazard.contains(&self.food_position)
+            && let Some(position) = self.random_valid_food_position()
+        {
             self.food_position = position;
-            None
-        } else {
-            // Failed to spawn food, can only happen when the snake fills the entire board.
-            // So if we get here, the player has actually won.
-            self.terminate(SimulationResult::Won)
+            self.changes.push((position, self.view().cell(&position)));
+        }
+
+        for cell in cells {
+            self.changes.push((cell, self.view().cell(&cell)));
+        }
+
+        respawning
+    }
+
+    /// Determines what, if anything, the snake with index `snake` would die
+    /// from if its head moved to `speculative_head` on the next step.
+    fn collision_at(&self, snake: usize, speculative_head: &Vector2) -> Option<DeathReason> {
+        if !self.board.contains(speculative_head) || self.obstacles.contains(speculative_head) {
+            return Some(DeathReason::HitWall);
+        }
+
+        if self.hazard.contains(speculative_head) {
+            return Some(DeathReason::Hazard);
+        }
+
+        // Moving onto the tail is fine, since it moves out of the way, unless the
+        // snake is about to grow and the tail stays put.
+        let contender = &self.snakes[snake];
+        let snake_will_grow =
+            contender.pending_growth > 0 || speculative_head == &self.food_position;
+        let snake_will_hit_tail = speculative_head == contender.snake.tail();
+
+        if contender.snake.contains(speculative_head) && (!snake_will_hit_tail || snake_will_grow) {
+            return Some(DeathReason::HitSelf);
+        }
+
+        // Other snakes' tails are never safe, since they might not move
+        let other =
+            self.snakes.iter().enumerate().position(|(index, other)| {
+                index != snake && other.snake.contains(speculative_head)
+            });
+
+        other.map(DeathReason::HitSnake)
+    }
+
+    /// Puts the snake with index `snake` back on the board after it died, at
+    /// length 1 and growing back to half the length it was. It goes somewhere
+    /// free, away from other heads, with a free cell to move on to. Returns
+    /// `false` if there is no such place.
+    fn respawn(&mut self, snake: usize) -> bool {
+        let near_heads = self.near_heads(3);
+        let free = free_cells(
+            &self.board,
+            &self.snakes,
+            &self.obstacles,
+            &self.power_ups,
+            &self.hazard,
+        )
+        .excluding(&self.food_position)
+        .excluding(&near_heads);
+
+        let spots: Vec<(Vector2, Direction)> = free
+            .iter()
+            .filter_map(|cell| {
+                let facing = Direction::ALL.into_iter().find(|&direction| {
+                    matches!(self.board.step(&cell, direction), BoardStep::To(next) if free.contains(&next))
+                })?;
+
+                Some((cell, facing))
+            })
+            .collect();
+
+        if spots.is_empty() {
+            return false;
+        }
+
+        let (head, facing) = spots[self.rng.random_range(0..spots.len())];
+        let contender = &mut self.snakes[snake];
+        let appearance = contender.snake.appearance().clone();
+        let old = std::mem::replace(
+            &mut contender.snake,
+            Snake::new(head, facing).with_appearance(appearance),
+        );
+
+        contender.pending_growth = ((old.len() / 2).max(1) - 1) as u32;
+        contender.grace = self.rules.respawn_grace;
+
+        for cell in old.body_iter().chain([&head]) {
+            self.changes.push((*cell, self.view().cell(cell)));
         }
+
+        true
+    }
+
+    /// Gets the cells closer than `min_distance` to the head of a [`Snake`]
+    /// still in play.
+    fn near_heads(&self, min_distance: u32) -> Vec<Proximity> {
+        self.snakes
+            .iter()
+            .filter(|contender| contender.in_play())
+            .map(|contender| Proximity {
+                center: *contender.snake.head(),
+                min_distance,
+            })
+            .collect()
+    }
+
+    /// Finds where to put a new piece of food: the next cell of the food
+    /// sequence, or the free cell nearest to it if it is taken. Without a
+    /// sequence, the cell is picked at random.
+    fn next_food_position(&mut self) -> Option<Vector2> {
+        let Some(cell) = self.food_sequence.pop_front() else {
+            return self.random_valid_food_position();
+        };
+
+        // Ties go to the first cell in reading order
+        free_cells(
+            &self.board,
+            &self.snakes,
+            &self.obstacles,
+            &self.power_ups,
+            &self.hazard,
+        )
+        .iter()
+        .min_by_key(|free| free.manhattan_distance(cell))
     }
 
     /// Attempts to find a random valid location to put a new piece of snake
-    /// food. Returns a [`Vector2`] representing the generated position if
-    /// at least one free cell exists, otherwise a [`FoodSpawnError`] indicating
-    /// the failure reason
-    fn random_valid_food_position(&self) -> Option<Vector2> {
-        self.board
-            .random_free_cell(self.snake.len(), |cell| self.snake.contains(cell))
+    /// food, according to the [`SpawnStrategy`]. Returns a [`Vector2`]
+    /// representing the generated position if at least one free cell exists,
+    /// otherwise [`None`].
+    fn random_valid_food_position(&mut self) -> Option<Vector2> {
+        let near_heads = match self.spawn_strategy {
+            SpawnStrategy::Uniform => Vec::new(),
+            SpawnStrategy::AwayFromHead { min_distance } => self.near_heads(min_distance),
+        };
+
+        let anywhere = free_cells(
+            &self.board,
+            &self.snakes,
+            &self.obstacles,
+            &self.power_ups,
+            &self.hazard,
+        );
+
+        let outside_region = self.food_region.as_ref().map(OutsideOf);
+        let free_cells = match &outside_region {
+            Some(outside) => anywhere.clone().excluding(outside),
+            None => anywhere.clone(),
+        };
+
+        // Nothing is chosen from an empty query, so the fallbacks only use
+        // up randomness when they are needed
+        free_cells
+            .clone()
+            .excluding(&near_heads)
+            .choose(&mut self.rng)
+            .or_else(|| free_cells.choose(&mut self.rng))
+            .or_else(|| anywhere.choose(&mut self.rng))
     }
 
-    /// Set the simulation result and return it back to the caller.
+    /// Records which of the `touched` cells now differ from how they were
+    /// `before`. Cells already recorded are brought up to date instead, so
+    /// neither duplicates nor several moves in one step leave stale changes.
+    fn record_changes(&mut self, touched: &[Vector2], before: &[Cell]) {
+        for (point, old) in touched.iter().zip(before) {
+            let new = self.view().cell(point);
+
+            match self
+                .changes
+                .iter_mut()
+                .find(|(changed, _)| changed == point)
+            {
+                Some((_, recorded)) => *recorded = new,
+                None if new != *old => self.changes.push((*point, new)),
+                None => {}
+            }
+        }
+    }
+
+    /// Ends the game with each of `winners` having won, and every other
+    /// [`Snake`] still in play having lost.
     #[must_use]
-    fn terminate(&mut self, result: SimulationResult) -> Option<&SimulationResult> {
-        self.simulation_result = Some(result);
-        self.simulation_result.as_ref()
+    fn win(&mut self, winners: &[usize]) -> Option<&SimulationResult> {
+        for (index, contender) in self.snakes.iter_mut().enumerate() {
+            if winners.contains(&index) {
+                contender.result = Some(SimulationResult::Won);
+            } else if contender.in_play() {
+                contender.result = Some(SimulationResult::Lost);
+            }
+        }
+
+        self.end_if_decided()
+    }
+
+    /// Ends the game if nobody is left in play, or if only one snake is left
+    /// of several, in which case it wins. Returns the result if the game is
+    /// over.
+    #[must_use]
+    fn end_if_decided(&mut self) -> Option<&SimulationResult> {
+        let several = self.snakes.len() > 1;
+        let mut in_play = self.snakes.iter_mut().filter(|c| c.in_play());
+
+        match (in_play.next(), in_play.next()) {
+            (None, _) => {}
+            (Some(last), None) if several => last.result = Some(SimulationResult::Won),
+            _ => return None,
+        }
+
+        self.simulation_result = self.snakes[0].result;
+        self.result()
+    }
+}
+
+/// Draws the board as the player sees it, as plain text. See [`TextFrame`].
+impl Display for SnakeSimulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let view = self.view();
+        TextFrame::new(view.board(), &view.frame()).fmt(f)
+    }
+}
+
+/// Where each snake is headed on a step. See [`SnakeSimulation::plan_moves`].
+struct Plan {
+    /// The snakes that move, and the cell each moves to.
+    moves: Vec<(usize, Vector2)>,
+
+    /// The snakes that hold still rather than dying.
+    held: Vec<usize>,
+
+    /// The snakes that died with lives to spare, and how.
+    respawning: Vec<(usize, SimulationResult)>,
+}
+
+/// Gets every cell on `board` not covered by an obstacle, a [`PowerUp`], the
+/// hazard zone or a [`Snake`].
+fn free_cells<'a>(
+    board: &'a Board,
+    snakes: &'a [Contender],
+    obstacles: &'a HashSet<Vector2>,
+    power_ups: &'a HashMap<Vector2, PowerUp>,
+    hazard: &'a HashSet<Vector2>,
+) -> FreeCells<'a> {
+    snakes
+        .iter()
+        .fold(board.free_cells(), |free, contender| {
+            free.excluding(&contender.snake)
+        })
+        .excluding(obstacles)
+        .excluding(power_ups)
+        .excluding(hazard)
+}
+
+/// Occupies every cell not in the given set, to keep things inside it.
+struct OutsideOf<'a>(&'a HashSet<Vector2>);
+
+impl Occupancy for OutsideOf<'_> {
+    fn is_occupied(&self, cell: &Vector2) -> bool {
+        !self.0.contains(cell)
+    }
+}
+
+/// 64-bit FNV-1a over explicitly little-endian values, so that
+/// [`SnakeSimulation::state_hash`] does not depend on the platform or on
+/// [`std::hash::Hash`] implementations.
+struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StateHasher {
+    fn write(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_point(&mut self, point: &Vector2) {
+        self.write(point.x as u64);
+        self.write(point.y as u64);
+    }
+
+    /// Writes a set of cells row by row, since set iteration order isn't
+    /// stable.
+    fn write_cells(&mut self, cells: &HashSet<Vector2>) {
+        let mut cells: Vec<_> = cells.iter().collect();
+        cells.sort_by_key(|cell| (cell.y, cell.x));
+
+        self.write(cells.len() as u64);
+        for cell in cells {
+            self.write_point(cell);
+        }
+    }
+
+    fn write_board(&mut self, board: &Board) {
+        let (x_range, y_range) = (board.x_range(), board.y_range());
+        self.write(x_range.start as u64);
+        self.write(x_range.end as u64);
+        self.write(y_range.start as u64);
+        self.write(y_range.end as u64);
+
+        self.write_option(board.mask().map(|mask| mask.len() as u64));
+        if let Some(mask) = board.mask() {
+            self.write_cells(mask);
+        }
+
+        // The first doorway spanning a cell is the one taken, so their order
+        // matters
+        self.write(board.doorways().len() as u64);
+        for doorway in board.doorways() {
+            self.write(doorway.side as u64);
+            self.write(doorway.span.start as u64);
+            self.write(doorway.span.end as u64);
+            self.write(match doorway.leads_to {
+                DoorwayTarget::Wrap => 0,
+                DoorwayTarget::Board(target) => 1 + ((target as u64) << 8),
+            });
+        }
+
+        // No two portals share an end, so their order doesn't matter, nor
+        // which end is which
+        let mut portals: Vec<_> = board
+            .portals()
+            .iter()
+            .map(|portal| {
+                let key = |cell: &Vector2| (cell.y, cell.x);
+                let (a, b) = (portal.a, portal.b);
+                if key(&a) <= key(&b) { (a, b) } else { (b, a) }
+            })
+            .collect();
+        portals.sort_by_key(|(a, b)| (a.y, a.x, b.y, b.x));
+        self.write(portals.len() as u64);
+        for (a, b) in portals {
+            self.write_point(&a);
+            self.write_point(&b);
+        }
+
+        let mut terrain: Vec<_> = board.terrain().iter().collect();
+        terrain.sort_by_key(|(cell, _)| (cell.y, cell.x));
+        self.write(terrain.len() as u64);
+        for (cell, terrain) in terrain {
+            self.write_point(cell);
+            self.write(*terrain as u64);
+        }
+    }
+
+    fn write_speed(&mut self, speed: &SpeedCurve) {
+        self.write(speed.initial_interval_ms);
+        self.write(speed.minimum_interval_ms);
+        self.write(speed.speedup_per_food_ms);
+    }
+
+    fn write_power_up(&mut self, power_up: &PowerUp) {
+        match *power_up {
+            PowerUp::Shield => self.write(0),
+            PowerUp::Mirror { ticks } => self.write(1 + (u64::from(ticks) << 8)),
+        }
+    }
+
+    /// Writes the events in the order they were added, which is the order
+    /// they happen in on the same tick.
+    fn write_events(&mut self, events: &EventSchedule) {
+        self.write(events.iter().count() as u64);
+        for event in events.iter() {
+            match event.trigger {
+                EventTrigger::At(tick) => {
+                    self.write(0);
+                    self.write(tick);
+                }
+                EventTrigger::Every { start, period } => {
+                    self.write(1);
+                    self.write(start);
+                    self.write(period);
+                }
+                EventTrigger::Randomly { one_in } => {
+                    self.write(2);
+                    self.write(one_in.into());
+                }
+            }
+
+            match &event.action {
+                EventAction::SpawnObstacles { count } => {
+                    self.write(0);
+                    self.write((*count).into());
+                }
+                EventAction::ClearObstacles => self.write(1),
+                EventAction::SpawnPowerUp(power_up) => {
+                    self.write(2);
+                    self.write_power_up(power_up);
+                }
+                EventAction::SetSpeed(speed) => {
+                    self.write(3);
+                    self.write_speed(speed);
+                }
+            }
+        }
+    }
+
+    /// Writes a marker for whether `value` is there, so `None` and
+    /// `Some(0)` hash differently.
+    fn write_option(&mut self, value: Option<u64>) {
+        match value {
+            None => self.write(0),
+            Some(value) => {
+                self.write(1);
+                self.write(value);
+            }
+        }
+    }
+
+    fn write_rules(&mut self, rules: &Rules) {
+        // Destructured so that a new rule can't be forgotten here
+        let Rules {
+            growth_per_food,
+            one_turn_per_tick,
+            assist,
+            food_goal,
+            move_limit,
+            time_limit,
+            lives,
+            respawn_grace,
+            combo_window,
+            shrink,
+            fleeing_food,
+            fog,
+        } = rules;
+
+        self.write((*growth_per_food).into());
+        self.write((*one_turn_per_tick).into());
+        self.write((*assist).into());
+        self.write_option(food_goal.map(u64::from));
+        self.write_option(move_limit.map(u64::from));
+        self.write_option(time_limit.map(|limit| limit.steps));
+        self.write_option(time_limit.map(|limit| limit.bonus_per_food));
+        self.write((*lives).into());
+        self.write((*respawn_grace).into());
+        self.write_option(combo_window.map(u64::from));
+        self.write_option(shrink.map(|shrink| shrink.after));
+        self.write_option(shrink.map(|shrink| shrink.every));
+        self.write_option(fleeing_food.map(u64::from));
+        self.write_option(fog.map(u64::from));
+    }
+
+    fn write_result(&mut self, result: Option<&SimulationResult>) {
+        self.write(match result {
+            None => 0,
+            Some(SimulationResult::Died(DeathReason::HitWall)) => 1,
+            Some(SimulationResult::Died(DeathReason::HitSelf)) => 2,
+            Some(SimulationResult::Won) => 3,
+            Some(SimulationResult::LeftBoard(target)) => 4 + ((*target as u64) << 8),
+            Some(SimulationResult::ManuallyTerminated(reason)) => 5 + ((*reason as u64) << 8),
+            Some(SimulationResult::Died(DeathReason::HitSnake(other))) => {
+                6 + ((*other as u64) << 8)
+            }
+            Some(SimulationResult::Died(DeathReason::HeadOn(other))) => 7 + ((*other as u64) << 8),
+            Some(SimulationResult::Lost) => 8,
+            Some(SimulationResult::Died(DeathReason::Hazard)) => 9,
+            Some(SimulationResult::OutOfMoves) => 10,
+            Some(SimulationResult::TimeExpired) => 11,
+        });
     }
 }
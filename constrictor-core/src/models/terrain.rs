@@ -0,0 +1,34 @@
+/// What the ground is like in a cell of a [`Board`](crate::models::Board),
+/// and how it changes the way a snake moves while its head is there. Cells
+/// without terrain are plain ground.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation, Terrain};
+///
+/// let board = Board::new((0, 10), (0, 1))
+///     .with_terrain(Vector2 { x: 1, y: 0 }, Terrain::Sticky)
+///     .with_terrain(Vector2 { x: 3, y: 0 }, Terrain::Boost);
+/// let snake = Snake::new(Vector2 { x: 0, y: 0 }, Direction::Right);
+/// let mut sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 9, y: 0 }, 0).unwrap();
+///
+/// let mut heads = Vec::new();
+/// for _ in 0..5 {
+///     sim.advance();
+///     heads.push(sim.snake().head().x);
+/// }
+///
+/// // Stuck for a step on the sticky cell, then sped along by the boost
+/// assert_eq!(heads, [1, 1, 2, 3, 5]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Terrain {
+    /// Slows the snake to half speed: it holds still every other step while
+    /// its head is here.
+    Sticky,
+
+    /// Speeds the snake up: it moves two cells in one step from here.
+    Boost,
+}
@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use crate::models::SnakeSimulation;
+
+/// Decides when a [`SnakeSimulation`] takes its next step. The simulation
+/// only counts the steps it takes, so whatever owns the clock (wall time, a
+/// network host, a replay, or the player in a turn-based game) supplies a
+/// [`TickSource`], and the tick-based rules such as events and speed play out
+/// identically under all of them.
+pub trait TickSource {
+    /// Returns `true` if `sim` should take another step right now. This is
+    /// asked repeatedly by [`SnakeSimulation::run`] until it answers `false`.
+    fn due(&mut self, sim: &SnakeSimulation) -> bool;
+}
+
+/// Steps in real time, at the pace given by
+/// [`SnakeSimulation::tick_interval`]. The caller reports how much time has
+/// passed with [`Clock::elapse`]. Time that passes while the simulation is
+/// paused is dropped rather than caught up on.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::models::Clock;
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+/// let mut clock = Clock::new();
+///
+/// clock.elapse(sim.tick_interval() * 3);
+/// assert_eq!(sim.run(&mut clock), 3);
+/// assert_eq!(sim.run(&mut clock), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    pending: Duration,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `time` has passed since the last call.
+    pub fn elapse(&mut self, time: Duration) {
+        self.pending += time;
+    }
+
+    /// Gets how much time has passed without being spent on a step.
+    pub const fn pending(&self) -> Duration {
+        self.pending
+    }
+}
+
+impl TickSource for Clock {
+    fn due(&mut self, sim: &SnakeSimulation) -> bool {
+        if sim.is_paused() {
+            self.pending = Duration::ZERO;
+            return false;
+        }
+
+        // The interval is looked up each step, since eating can change it
+        let interval = sim.tick_interval();
+        if interval.is_zero() || self.pending < interval {
+            return false;
+        }
+
+        self.pending -= interval;
+        true
+    }
+}
+
+/// Steps only when asked to, as in a turn-based game. Each request is one
+/// step.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::models::OnDemand;
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+/// let mut turns = OnDemand::default();
+///
+/// assert_eq!(sim.run(&mut turns), 0);
+///
+/// turns.request();
+/// assert_eq!(sim.run(&mut turns), 1);
+/// assert_eq!(sim.tick(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OnDemand {
+    requested: u32,
+}
+
+impl OnDemand {
+    /// Asks for one more step.
+    pub fn request(&mut self) {
+        self.requested += 1;
+    }
+}
+
+impl TickSource for OnDemand {
+    fn due(&mut self, _sim: &SnakeSimulation) -> bool {
+        let due = self.requested > 0;
+        self.requested = self.requested.saturating_sub(1);
+        due
+    }
+}
+
+/// Steps until the simulation reaches a target tick, such as the tick a
+/// network host or a replay says the game is on.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::models::UntilTick;
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+///
+/// sim.run(&mut UntilTick(4));
+/// assert_eq!(sim.tick(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UntilTick(pub u64);
+
+impl TickSource for UntilTick {
+    fn due(&mut self, sim: &SnakeSimulation) -> bool {
+        sim.tick() < self.0
+    }
+}
+
+/// Defers to another [`TickSource`], but holds the simulation once it
+/// reaches the tick `stop`, so that something can be done before that tick
+/// is played. The held steps are not lost: a [`Clock`] keeps the time it
+/// hasn't spent, and steps as soon as the hold is lifted.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::models::{Clock, StopAt};
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+/// let mut clock = Clock::new();
+/// clock.elapse(sim.tick_interval() * 5);
+///
+/// assert_eq!(sim.run(&mut StopAt::new(&mut clock, 2)), 2);
+/// assert_eq!(sim.run(&mut StopAt::new(&mut clock, 2)), 0);
+/// assert_eq!(sim.run(&mut clock), 3);
+/// ```
+#[derive(Debug)]
+pub struct StopAt<'a, S> {
+    source: &'a mut S,
+    stop: u64,
+}
+
+impl<'a, S: TickSource> StopAt<'a, S> {
+    pub fn new(source: &'a mut S, stop: u64) -> Self {
+        Self { source, stop }
+    }
+}
+
+impl<S: TickSource> TickSource for StopAt<'_, S> {
+    fn due(&mut self, sim: &SnakeSimulation) -> bool {
+        // Checked first, so the source doesn't count a step that isn't taken
+        sim.tick() < self.stop && self.source.due(sim)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    math::Direction,
+    models::{OnDemand, SnakeSimulation},
+};
+
+/// Gathers the moves a crowd votes for, for a communal game where the snake
+/// is steered by majority. In a turn-based game the simulation only steps
+/// when a turn ends, with [`TurnBatch::end_turn`], rather than on a timer.
+///
+/// Each voter, told apart by a `V` such as a user id, has one vote per turn,
+/// and voting again changes it. A tie goes to whichever of the tied
+/// directions was voted for first.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::math::Direction;
+/// use constrictor_core::models::TurnBatch;
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+/// let mut turn = TurnBatch::new();
+///
+/// turn.vote(1, Direction::Up);
+/// turn.vote(2, Direction::Down);
+/// turn.vote(3, Direction::Down);
+/// turn.vote(1, Direction::Down);
+///
+/// assert_eq!(turn.end_turn(&mut sim), Some(Direction::Down));
+/// assert_eq!(sim.tick(), 1);
+/// assert_eq!(turn.votes(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TurnBatch<V = u64> {
+    /// Who voted for what, in the order their votes were first cast.
+    ballots: Vec<(V, Direction)>,
+}
+
+impl<V> Default for TurnBatch<V> {
+    fn default() -> Self {
+        Self {
+            ballots: Vec::new(),
+        }
+    }
+}
+
+impl<V: PartialEq> TurnBatch<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `voter` wants to turn towards `direction`, replacing
+    /// any vote they already cast this turn.
+    pub fn vote(&mut self, voter: V, direction: Direction) {
+        match self.ballots.iter_mut().find(|(other, _)| *other == voter) {
+            Some(ballot) => ballot.1 = direction,
+            None => self.ballots.push((voter, direction)),
+        }
+    }
+
+    /// Gets how many voters have voted this turn.
+    pub fn votes(&self) -> usize {
+        self.ballots.len()
+    }
+
+    /// Gets the direction with the most votes so far, if anyone voted.
+    pub fn leader(&self) -> Option<Direction> {
+        let mut counts: Vec<(Direction, usize)> = Vec::new();
+        for (_, direction) in &self.ballots {
+            match counts.iter_mut().find(|(other, _)| other == direction) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*direction, 1)),
+            }
+        }
+
+        // `max_by_key` keeps the last of equals, so look from the back
+        counts
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map(|(direction, _)| direction)
+    }
+
+    /// Gives the winning direction, if anyone voted, and starts a new turn.
+    pub fn take(&mut self) -> Option<Direction> {
+        let winner = self.leader();
+        self.ballots.clear();
+        winner
+    }
+
+    /// Ends the turn: turns the player's snake towards the winning
+    /// direction, takes one step, and starts a new turn. Gives the winning
+    /// direction, if anyone voted. Nothing steps while the game is paused
+    /// or over.
+    pub fn end_turn(&mut self, sim: &mut SnakeSimulation) -> Option<Direction> {
+        let winner = self.take();
+        if let Some(direction) = winner {
+            sim.change_player_move_direction(direction);
+        }
+
+        let mut turns = OnDemand::default();
+        turns.request();
+        sim.run(&mut turns);
+
+        winner
+    }
+}
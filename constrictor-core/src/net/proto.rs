@@ -0,0 +1,761 @@
+//! The messages of `proto/constrictor.proto`, for encoding the network
+//! protocol as Protocol Buffers. They mirror the schema field for field, and
+//! convert to and from the [`ClientMessage`] and [`ServerMessage`] the rest
+//! of the crate works with.
+//!
+//! The schema is maintained by hand, so a change to either it or this file
+//! must be made to the other as well.
+//!
+//! [`ClientMessage`]: crate::net::ClientMessage
+//! [`ServerMessage`]: crate::net::ServerMessage
+
+use std::{error, fmt::Display};
+
+use prost::Message;
+
+use crate::{math, models, net, render};
+
+/// Describes why a Protocol Buffers message could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtoError {
+    /// The bytes aren't a valid encoding of the message.
+    Decode(prost::DecodeError),
+
+    /// A field that must be set wasn't.
+    Missing(&'static str),
+
+    /// A field holds a value the schema doesn't allow, such as an enum
+    /// value this version doesn't know.
+    Invalid(&'static str),
+}
+
+impl error::Error for ProtoError {}
+impl Display for ProtoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode(e) => e.fmt(f),
+            Self::Missing(field) => write!(f, "message is missing `{field}`"),
+            Self::Invalid(field) => write!(f, "message has an invalid `{field}`"),
+        }
+    }
+}
+
+impl From<prost::DecodeError> for ProtoError {
+    fn from(value: prost::DecodeError) -> Self {
+        Self::Decode(value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Vector2 {
+    #[prost(sint32, tag = "1")]
+    pub x: i32,
+    #[prost(sint32, tag = "2")]
+    pub y: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Unspecified = 0,
+    Up = 1,
+    Right = 2,
+    Down = 3,
+    Left = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Terrain {
+    Unspecified = 0,
+    Sticky = 1,
+    Boost = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum PowerUpKind {
+    Unspecified = 0,
+    Shield = 1,
+    Mirror = 2,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct PowerUp {
+    #[prost(enumeration = "PowerUpKind", tag = "1")]
+    pub kind: i32,
+    #[prost(uint32, tag = "2")]
+    pub mirror_ticks: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum CellKind {
+    Empty = 0,
+    Food = 1,
+    PracticeFood = 2,
+    Obstacle = 3,
+    PowerUp = 4,
+    Portal = 5,
+    Hazard = 6,
+    Terrain = 7,
+    Hidden = 8,
+    SnakeHead = 9,
+    SnakeBody = 10,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Cell {
+    #[prost(enumeration = "CellKind", tag = "1")]
+    pub kind: i32,
+    #[prost(uint32, tag = "2")]
+    pub snake: u32,
+    #[prost(enumeration = "Direction", tag = "3")]
+    pub facing: i32,
+    #[prost(uint32, tag = "4")]
+    pub pair: u32,
+    #[prost(message, optional, tag = "5")]
+    pub power_up: Option<PowerUp>,
+    #[prost(enumeration = "Terrain", tag = "6")]
+    pub terrain: i32,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Change {
+    #[prost(message, optional, tag = "1")]
+    pub position: Option<Vector2>,
+    #[prost(message, optional, tag = "2")]
+    pub cell: Option<Cell>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum QuitReason {
+    Unspecified = 0,
+    UserQuit = 1,
+    ConnectionLost = 2,
+    HostShutdown = 3,
+    Error = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ResultKind {
+    Unspecified = 0,
+    HitWall = 1,
+    HitSelf = 2,
+    HitSnake = 3,
+    HeadOn = 4,
+    Hazard = 5,
+    Quit = 6,
+    Won = 7,
+    Lost = 8,
+    LeftBoard = 9,
+    OutOfMoves = 10,
+    TimeExpired = 11,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Result {
+    #[prost(enumeration = "ResultKind", tag = "1")]
+    pub kind: i32,
+    #[prost(uint32, tag = "2")]
+    pub snake: u32,
+    #[prost(uint32, tag = "3")]
+    pub board: u32,
+    #[prost(enumeration = "QuitReason", tag = "4")]
+    pub quit_reason: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Snapshot {
+    #[prost(uint64, tag = "1")]
+    pub tick: u64,
+    #[prost(int32, tag = "2")]
+    pub width: i32,
+    #[prost(int32, tag = "3")]
+    pub height: i32,
+    #[prost(message, repeated, tag = "4")]
+    pub cells: Vec<Change>,
+    #[prost(uint64, tag = "5")]
+    pub score: u64,
+    #[prost(message, optional, tag = "6")]
+    pub result: Option<Result>,
+    #[prost(uint64, repeated, tag = "7")]
+    pub scores: Vec<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Delta {
+    #[prost(uint64, tag = "1")]
+    pub tick: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub changes: Vec<Change>,
+    #[prost(uint64, tag = "3")]
+    pub score: u64,
+    #[prost(message, optional, tag = "4")]
+    pub result: Option<Result>,
+    #[prost(uint64, repeated, tag = "5")]
+    pub scores: Vec<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ClientMessage {
+    #[prost(oneof = "client_message::Message", tags = "1, 2, 3, 4, 5")]
+    pub message: Option<client_message::Message>,
+}
+
+pub mod client_message {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Message {
+        #[prost(message, tag = "1")]
+        Join(super::Join),
+        #[prost(message, tag = "2")]
+        Leave(super::Leave),
+        #[prost(message, tag = "3")]
+        Input(super::Input),
+        #[prost(message, tag = "4")]
+        Restart(super::Restart),
+        #[prost(message, tag = "5")]
+        Resync(super::Resync),
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Join {
+    #[prost(string, tag = "1")]
+    pub lobby: String,
+    #[prost(uint32, tag = "2")]
+    pub version: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Leave {}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Input {
+    #[prost(uint64, tag = "1")]
+    pub tick: u64,
+    #[prost(enumeration = "Direction", tag = "2")]
+    pub direction: i32,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Restart {}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Resync {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ServerMessage {
+    #[prost(oneof = "server_message::Message", tags = "1, 2, 3, 4, 5, 6, 7")]
+    pub message: Option<server_message::Message>,
+}
+
+pub mod server_message {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Message {
+        #[prost(message, tag = "1")]
+        Joined(super::Joined),
+        #[prost(message, tag = "2")]
+        Left(super::Left),
+        #[prost(message, tag = "3")]
+        Seated(super::Seated),
+        #[prost(message, tag = "4")]
+        Started(super::Started),
+        #[prost(message, tag = "5")]
+        Delta(super::Delta),
+        #[prost(message, tag = "6")]
+        Snapshot(super::Snapshot),
+        #[prost(message, tag = "7")]
+        Error(super::Error),
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Joined {
+    #[prost(string, tag = "1")]
+    pub lobby: String,
+    #[prost(uint32, tag = "2")]
+    pub version: u32,
+    #[prost(uint32, optional, tag = "3")]
+    pub seat: Option<u32>,
+    #[prost(message, optional, tag = "4")]
+    pub snapshot: Option<Snapshot>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Left {
+    #[prost(string, tag = "1")]
+    pub lobby: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct Seated {
+    #[prost(uint32, optional, tag = "1")]
+    pub seat: Option<u32>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Started {
+    #[prost(message, optional, tag = "1")]
+    pub snapshot: Option<Snapshot>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Error {
+    #[prost(string, tag = "1")]
+    pub message: String,
+}
+
+impl net::ClientMessage {
+    /// Encodes the message as a Protocol Buffers `ClientMessage`.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::Direction;
+    /// use constrictor_core::net::ClientMessage;
+    ///
+    /// let message = ClientMessage::Input {
+    ///     tick: 12,
+    ///     direction: Direction::Left,
+    /// };
+    ///
+    /// let bytes = message.to_proto();
+    /// assert_eq!(ClientMessage::from_proto(&bytes), Ok(message));
+    /// ```
+    pub fn to_proto(&self) -> Vec<u8> {
+        ClientMessage::from(self).encode_to_vec()
+    }
+
+    /// Decodes a Protocol Buffers `ClientMessage`.
+    pub fn from_proto(bytes: &[u8]) -> std::result::Result<Self, ProtoError> {
+        ClientMessage::decode(bytes)?.try_into()
+    }
+}
+
+impl net::ServerMessage {
+    /// Encodes the message as a Protocol Buffers `ServerMessage`.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::config::GameConfig;
+    /// use constrictor_core::net::{ServerMessage, Snapshot};
+    ///
+    /// let config = GameConfig {
+    ///     seed: Some(7),
+    ///     ..Default::default()
+    /// };
+    /// let sim = config.build().unwrap();
+    ///
+    /// let message = ServerMessage::Joined {
+    ///     lobby: "friday".to_string(),
+    ///     version: 1,
+    ///     seat: Some(0),
+    ///     snapshot: Snapshot::from(&sim.view()),
+    /// };
+    ///
+    /// let bytes = message.to_proto();
+    /// assert_eq!(ServerMessage::from_proto(&bytes), Ok(message));
+    /// ```
+    pub fn to_proto(&self) -> Vec<u8> {
+        ServerMessage::from(self).encode_to_vec()
+    }
+
+    /// Decodes a Protocol Buffers `ServerMessage`.
+    pub fn from_proto(bytes: &[u8]) -> std::result::Result<Self, ProtoError> {
+        ServerMessage::decode(bytes)?.try_into()
+    }
+}
+
+impl From<&net::ClientMessage> for ClientMessage {
+    fn from(value: &net::ClientMessage) -> Self {
+        use client_message::Message as Proto;
+
+        let message = match value {
+            net::ClientMessage::Join { lobby, version } => Proto::Join(Join {
+                lobby: lobby.clone(),
+                version: *version,
+            }),
+            net::ClientMessage::Leave => Proto::Leave(Leave {}),
+            net::ClientMessage::Input { tick, direction } => Proto::Input(Input {
+                tick: *tick,
+                direction: Direction::from(*direction) as i32,
+            }),
+            net::ClientMessage::Restart => Proto::Restart(Restart {}),
+            net::ClientMessage::Resync => Proto::Resync(Resync {}),
+        };
+
+        Self {
+            message: Some(message),
+        }
+    }
+}
+
+impl TryFrom<ClientMessage> for net::ClientMessage {
+    type Error = ProtoError;
+
+    fn try_from(value: ClientMessage) -> std::result::Result<Self, ProtoError> {
+        use client_message::Message as Proto;
+
+        Ok(match value.message.ok_or(ProtoError::Missing("message"))? {
+            Proto::Join(Join { lobby, version }) => Self::Join { lobby, version },
+            Proto::Leave(_) => Self::Leave,
+            Proto::Input(Input { tick, direction }) => Self::Input {
+                tick,
+                direction: direction_from(direction, "direction")?,
+            },
+            Proto::Restart(_) => Self::Restart,
+            Proto::Resync(_) => Self::Resync,
+        })
+    }
+}
+
+impl From<&net::ServerMessage> for ServerMessage {
+    fn from(value: &net::ServerMessage) -> Self {
+        use server_message::Message as Proto;
+
+        let message = match value {
+            net::ServerMessage::Joined {
+                lobby,
+                version,
+                seat,
+                snapshot,
+            } => Proto::Joined(Joined {
+                lobby: lobby.clone(),
+                version: *version,
+                seat: seat.map(|seat| seat as u32),
+                snapshot: Some(snapshot.into()),
+            }),
+            net::ServerMessage::Left { lobby } => Proto::Left(Left {
+                lobby: lobby.clone(),
+            }),
+            net::ServerMessage::Seated { seat } => Proto::Seated(Seated {
+                seat: seat.map(|seat| seat as u32),
+            }),
+            net::ServerMessage::Started { snapshot } => Proto::Started(Started {
+                snapshot: Some(snapshot.into()),
+            }),
+            net::ServerMessage::Delta(delta) => Proto::Delta(delta.into()),
+            net::ServerMessage::Snapshot(snapshot) => Proto::Snapshot(snapshot.into()),
+            net::ServerMessage::Error { message } => Proto::Error(Error {
+                message: message.clone(),
+            }),
+        };
+
+        Self {
+            message: Some(message),
+        }
+    }
+}
+
+impl TryFrom<ServerMessage> for net::ServerMessage {
+    type Error = ProtoError;
+
+    fn try_from(value: ServerMessage) -> std::result::Result<Self, ProtoError> {
+        use server_message::Message as Proto;
+
+        let snapshot = |snapshot: Option<Snapshot>| {
+            snapshot.ok_or(ProtoError::Missing("snapshot"))?.try_into()
+        };
+
+        Ok(match value.message.ok_or(ProtoError::Missing("message"))? {
+            Proto::Joined(joined) => Self::Joined {
+                lobby: joined.lobby,
+                version: joined.version,
+                seat: joined.seat.map(|seat| seat as usize),
+                snapshot: snapshot(joined.snapshot)?,
+            },
+            Proto::Left(Left { lobby }) => Self::Left { lobby },
+            Proto::Seated(Seated { seat }) => Self::Seated {
+                seat: seat.map(|seat| seat as usize),
+            },
+            Proto::Started(started) => Self::Started {
+                snapshot: snapshot(started.snapshot)?,
+            },
+            Proto::Delta(delta) => Self::Delta(delta.try_into()?),
+            Proto::Snapshot(snapshot) => Self::Snapshot(snapshot.try_into()?),
+            Proto::Error(Error { message }) => Self::Error { message },
+        })
+    }
+}
+
+impl From<&net::Snapshot> for Snapshot {
+    fn from(value: &net::Snapshot) -> Self {
+        Self {
+            tick: value.tick,
+            width: value.width,
+            height: value.height,
+            cells: value.cells.iter().map(Change::from).collect(),
+            score: value.score as u64,
+            scores: value.scores.iter().map(|score| *score as u64).collect(),
+            result: value.result.as_ref().map(Result::from),
+        }
+    }
+}
+
+impl TryFrom<Snapshot> for net::Snapshot {
+    type Error = ProtoError;
+
+    fn try_from(value: Snapshot) -> std::result::Result<Self, ProtoError> {
+        Ok(Self {
+            tick: value.tick,
+            width: value.width,
+            height: value.height,
+            cells: changes_from(value.cells)?,
+            score: value.score as usize,
+            scores: value.scores.iter().map(|score| *score as usize).collect(),
+            result: value.result.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<&net::Delta> for Delta {
+    fn from(value: &net::Delta) -> Self {
+        Self {
+            tick: value.tick,
+            changes: value.changes.iter().map(Change::from).collect(),
+            score: value.score as u64,
+            scores: value.scores.iter().map(|score| *score as u64).collect(),
+            result: value.result.as_ref().map(Result::from),
+        }
+    }
+}
+
+impl TryFrom<Delta> for net::Delta {
+    type Error = ProtoError;
+
+    fn try_from(value: Delta) -> std::result::Result<Self, ProtoError> {
+        Ok(Self {
+            tick: value.tick,
+            changes: changes_from(value.changes)?,
+            score: value.score as usize,
+            scores: value.scores.iter().map(|score| *score as usize).collect(),
+            result: value.result.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<&(math::Vector2, render::Cell)> for Change {
+    fn from((position, cell): &(math::Vector2, render::Cell)) -> Self {
+        Self {
+            position: Some(Vector2 {
+                x: position.x,
+                y: position.y,
+            }),
+            cell: Some(cell.into()),
+        }
+    }
+}
+
+fn changes_from(
+    changes: Vec<Change>,
+) -> std::result::Result<Vec<(math::Vector2, render::Cell)>, ProtoError> {
+    changes
+        .into_iter()
+        .map(|change| {
+            let Vector2 { x, y } = change.position.ok_or(ProtoError::Missing("position"))?;
+
+            // An empty cell has every field at its default, which encodes as
+            // nothing at all
+            let cell = change.cell.unwrap_or_default().try_into()?;
+            Ok((math::Vector2 { x, y }, cell))
+        })
+        .collect()
+}
+
+impl From<math::Direction> for Direction {
+    fn from(value: math::Direction) -> Self {
+        match value {
+            math::Direction::Up => Self::Up,
+            math::Direction::Right => Self::Right,
+            math::Direction::Down => Self::Down,
+            math::Direction::Left => Self::Left,
+        }
+    }
+}
+
+fn direction_from(
+    value: i32,
+    field: &'static str,
+) -> std::result::Result<math::Direction, ProtoError> {
+    match Direction::try_from(value) {
+        Ok(Direction::Up) => Ok(math::Direction::Up),
+        Ok(Direction::Right) => Ok(math::Direction::Right),
+        Ok(Direction::Down) => Ok(math::Direction::Down),
+        Ok(Direction::Left) => Ok(math::Direction::Left),
+        Ok(Direction::Unspecified) | Err(_) => Err(ProtoError::Invalid(field)),
+    }
+}
+
+impl From<&render::Cell> for Cell {
+    fn from(value: &render::Cell) -> Self {
+        let cell = |kind: CellKind| Self {
+            kind: kind as i32,
+            ..Default::default()
+        };
+
+        match *value {
+            render::Cell::Empty => cell(CellKind::Empty),
+            render::Cell::Food => cell(CellKind::Food),
+            render::Cell::PracticeFood => cell(CellKind::PracticeFood),
+            render::Cell::Obstacle => cell(CellKind::Obstacle),
+            render::Cell::PowerUp(power_up) => Self {
+                power_up: Some(match power_up {
+                    models::PowerUp::Shield => PowerUp {
+                        kind: PowerUpKind::Shield as i32,
+                        mirror_ticks: 0,
+                    },
+                    models::PowerUp::Mirror { ticks } => PowerUp {
+                        kind: PowerUpKind::Mirror as i32,
+                        mirror_ticks: ticks,
+                    },
+                }),
+                ..cell(CellKind::PowerUp)
+            },
+            render::Cell::Portal { pair } => Self {
+                pair: pair as u32,
+                ..cell(CellKind::Portal)
+            },
+            render::Cell::Hazard => cell(CellKind::Hazard),
+            render::Cell::Terrain(terrain) => Self {
+                terrain: match terrain {
+                    models::Terrain::Sticky => Terrain::Sticky,
+                    models::Terrain::Boost => Terrain::Boost,
+                } as i32,
+                ..cell(CellKind::Terrain)
+            },
+            render::Cell::Hidden => cell(CellKind::Hidden),
+            render::Cell::SnakeHead { snake, facing } => Self {
+                snake: snake as u32,
+                facing: Direction::from(facing) as i32,
+                ..cell(CellKind::SnakeHead)
+            },
+            render::Cell::SnakeBody { snake } => Self {
+                snake: snake as u32,
+                ..cell(CellKind::SnakeBody)
+            },
+        }
+    }
+}
+
+impl TryFrom<Cell> for render::Cell {
+    type Error = ProtoError;
+
+    fn try_from(value: Cell) -> std::result::Result<Self, ProtoError> {
+        let kind = CellKind::try_from(value.kind).map_err(|_| ProtoError::Invalid("kind"))?;
+
+        Ok(match kind {
+            CellKind::Empty => Self::Empty,
+            CellKind::Food => Self::Food,
+            CellKind::PracticeFood => Self::PracticeFood,
+            CellKind::Obstacle => Self::Obstacle,
+            CellKind::PowerUp => {
+                let power_up = value.power_up.ok_or(ProtoError::Missing("power_up"))?;
+                Self::PowerUp(match PowerUpKind::try_from(power_up.kind) {
+                    Ok(PowerUpKind::Shield) => models::PowerUp::Shield,
+                    Ok(PowerUpKind::Mirror) => models::PowerUp::Mirror {
+                        ticks: power_up.mirror_ticks,
+                    },
+                    Ok(PowerUpKind::Unspecified) | Err(_) => {
+                        return Err(ProtoError::Invalid("power_up"));
+                    }
+                })
+            }
+            CellKind::Portal => Self::Portal {
+                pair: value.pair as usize,
+            },
+            CellKind::Hazard => Self::Hazard,
+            CellKind::Terrain => Self::Terrain(match Terrain::try_from(value.terrain) {
+                Ok(Terrain::Sticky) => models::Terrain::Sticky,
+                Ok(Terrain::Boost) => models::Terrain::Boost,
+                Ok(Terrain::Unspecified) | Err(_) => return Err(ProtoError::Invalid("terrain")),
+            }),
+            CellKind::Hidden => Self::Hidden,
+            CellKind::SnakeHead => Self::SnakeHead {
+                snake: value.snake as usize,
+                facing: direction_from(value.facing, "facing")?,
+            },
+            CellKind::SnakeBody => Self::SnakeBody {
+                snake: value.snake as usize,
+            },
+        })
+    }
+}
+
+impl From<&models::SimulationResult> for Result {
+    fn from(value: &models::SimulationResult) -> Self {
+        use models::{DeathReason, SimulationResult};
+
+        let result = |kind: ResultKind| Self {
+            kind: kind as i32,
+            ..Default::default()
+        };
+
+        match *value {
+            SimulationResult::Died(DeathReason::HitWall) => result(ResultKind::HitWall),
+            SimulationResult::Died(DeathReason::HitSelf) => result(ResultKind::HitSelf),
+            SimulationResult::Died(DeathReason::HitSnake(snake)) => Self {
+                snake: snake as u32,
+                ..result(ResultKind::HitSnake)
+            },
+            SimulationResult::Died(DeathReason::HeadOn(snake)) => Self {
+                snake: snake as u32,
+                ..result(ResultKind::HeadOn)
+            },
+            SimulationResult::Died(DeathReason::Hazard) => result(ResultKind::Hazard),
+            SimulationResult::ManuallyTerminated(reason) => Self {
+                quit_reason: match reason {
+                    models::QuitReason::UserQuit => QuitReason::UserQuit,
+                    models::QuitReason::ConnectionLost => QuitReason::ConnectionLost,
+                    models::QuitReason::HostShutdown => QuitReason::HostShutdown,
+                    models::QuitReason::Error => QuitReason::Error,
+                } as i32,
+                ..result(ResultKind::Quit)
+            },
+            SimulationResult::Won => result(ResultKind::Won),
+            SimulationResult::Lost => result(ResultKind::Lost),
+            SimulationResult::LeftBoard(board) => Self {
+                board: board as u32,
+                ..result(ResultKind::LeftBoard)
+            },
+            SimulationResult::OutOfMoves => result(ResultKind::OutOfMoves),
+            SimulationResult::TimeExpired => result(ResultKind::TimeExpired),
+        }
+    }
+}
+
+impl TryFrom<Result> for models::SimulationResult {
+    type Error = ProtoError;
+
+    fn try_from(value: Result) -> std::result::Result<Self, ProtoError> {
+        use models::DeathReason;
+
+        let kind = ResultKind::try_from(value.kind).map_err(|_| ProtoError::Invalid("kind"))?;
+
+        Ok(match kind {
+            ResultKind::Unspecified => return Err(ProtoError::Invalid("kind")),
+            ResultKind::HitWall => Self::Died(DeathReason::HitWall),
+            ResultKind::HitSelf => Self::Died(DeathReason::HitSelf),
+            ResultKind::HitSnake => Self::Died(DeathReason::HitSnake(value.snake as usize)),
+            ResultKind::HeadOn => Self::Died(DeathReason::HeadOn(value.snake as usize)),
+            ResultKind::Hazard => Self::Died(DeathReason::Hazard),
+            ResultKind::Quit => {
+                Self::ManuallyTerminated(match QuitReason::try_from(value.quit_reason) {
+                    Ok(QuitReason::UserQuit) => models::QuitReason::UserQuit,
+                    Ok(QuitReason::ConnectionLost) => models::QuitReason::ConnectionLost,
+                    Ok(QuitReason::HostShutdown) => models::QuitReason::HostShutdown,
+                    Ok(QuitReason::Error) => models::QuitReason::Error,
+                    Ok(QuitReason::Unspecified) | Err(_) => {
+                        return Err(ProtoError::Invalid("quit_reason"));
+                    }
+                })
+            }
+            ResultKind::Won => Self::Won,
+            ResultKind::Lost => Self::Lost,
+            ResultKind::LeftBoard => Self::LeftBoard(value.board as usize),
+            ResultKind::OutOfMoves => Self::OutOfMoves,
+            ResultKind::TimeExpired => Self::TimeExpired,
+        })
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    math::{Direction, Vector2},
+    models::{SimulationResult, SimulationView},
+    render::{Cell, FrameBuffer},
+};
+
+/// The version of the protocol spoken by this release. A client names the
+/// version it speaks when joining, and servers turn away any other.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What a client can ask of a server. Serialized with a `type` naming which
+/// kind of message it is, so as JSON:
+///
+/// ```json
+/// {"type": "join", "lobby": "friday", "version": 1}
+/// {"type": "input", "tick": 12, "direction": "Left"}
+/// ```
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use constrictor_core::math::Direction;
+/// use constrictor_core::net::ClientMessage;
+///
+/// let message = ClientMessage::Input {
+///     tick: 12,
+///     direction: Direction::Left,
+/// };
+///
+/// let json = serde_json::to_string(&message).unwrap();
+/// assert_eq!(json, r#"{"type":"input","tick":12,"direction":"Left"}"#);
+/// assert_eq!(serde_json::from_str::<ClientMessage>(&json).unwrap(), message);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ClientMessage {
+    /// Join the lobby with this name, creating it if nobody is in it yet.
+    /// `version` is the [`PROTOCOL_VERSION`] the client speaks.
+    Join { lobby: String, version: u32 },
+
+    /// Leave the client's lobby, staying connected to join another.
+    Leave,
+
+    /// Turn the client's snake towards `direction`, as of `tick`. A server
+    /// that has already played `tick` applies it as soon as it can.
+    Input { tick: u64, direction: Direction },
+
+    /// Start a new game in the client's lobby, once the current one is over.
+    Restart,
+
+    /// Ask for a [`ServerMessage::Snapshot`] of the game, to catch up after
+    /// missing some [`Delta`]s.
+    Resync,
+}
+
+/// What a server tells its clients, tagged in the same way as a
+/// [`ClientMessage`].
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::net::{Delta, ServerMessage};
+/// use constrictor_core::render::Cell;
+///
+/// let message = ServerMessage::Delta(Delta {
+///     tick: 3,
+///     changes: vec![(Vector2 { x: 1, y: 2 }, Cell::Empty)],
+///     score: 0,
+///     scores: vec![0, 2],
+///     result: None,
+/// });
+///
+/// let json = serde_json::to_string(&message).unwrap();
+/// assert!(json.starts_with(r#"{"type":"delta","tick":3,"#));
+/// assert_eq!(serde_json::from_str::<ServerMessage>(&json).unwrap(), message);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ServerMessage {
+    /// The client is now in `lobby`. `seat` is the snake it steers, or
+    /// `None` if it is only watching.
+    Joined {
+        lobby: String,
+        version: u32,
+        seat: Option<usize>,
+        snapshot: Snapshot,
+    },
+
+    /// The client left `lobby`, and can join another.
+    Left { lobby: String },
+
+    /// The client's seat changed, because a player left and freed theirs
+    /// up. A [`ServerMessage::Snapshot`] from the new seat's point of view
+    /// follows.
+    Seated { seat: Option<usize> },
+
+    /// A new game started in the client's lobby.
+    Started { snapshot: Snapshot },
+
+    /// The game stepped forward.
+    Delta(Delta),
+
+    /// The whole game as it stands, as asked for with
+    /// [`ClientMessage::Resync`].
+    Snapshot(Snapshot),
+
+    /// Something the client sent could not be acted on.
+    Error { message: String },
+}
+
+/// Everything needed to draw a game from scratch, as the player whose
+/// [`SimulationView`] it was taken from sees it. Later [`Delta`]s only carry
+/// what changed, and bring a snapshot up to date with [`Snapshot::apply`].
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::models::Rules;
+/// use constrictor_core::net::{Delta, Snapshot};
+///
+/// // Fog hides most of the board, and shows more of it as the snake moves
+/// let config = GameConfig {
+///     seed: Some(7),
+///     rules: Rules {
+///         fog: Some(3),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let mut sim = config.build().unwrap();
+///
+/// let mut snapshot = Snapshot::from(&sim.view());
+/// let mut frame = sim.view().frame();
+/// for _ in 0..10 {
+///     sim.advance();
+///     snapshot.apply(&Delta::since(&mut frame, &sim.view()));
+///     assert_eq!(snapshot, Snapshot::from(&sim.view()));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    pub tick: u64,
+    pub width: i32,
+    pub height: i32,
+
+    /// Every cell that isn't empty, row by row.
+    pub cells: Vec<(Vector2, Cell)>,
+
+    /// The score of the snake the game is seen from.
+    pub score: usize,
+
+    /// Every snake's score, by index.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scores: Vec<usize>,
+
+    /// How the game ended for the snake it is seen from, once it is over.
+    pub result: Option<SimulationResult>,
+}
+
+/// How one step changed the game, as one player sees it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delta {
+    /// The tick the game is on after the step.
+    pub tick: u64,
+
+    /// The cells that look different after the step, and what they show now.
+    /// Under fog, that includes cells coming into sight or going out of it.
+    pub changes: Vec<(Vector2, Cell)>,
+
+    /// The score of the snake the game is seen from.
+    pub score: usize,
+
+    /// Every snake's score, by index.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scores: Vec<usize>,
+
+    /// How the game ended for the snake it is seen from, once it is over.
+    pub result: Option<SimulationResult>,
+}
+
+impl From<&SimulationView<'_>> for Snapshot {
+    fn from(view: &SimulationView<'_>) -> Self {
+        Self {
+            tick: view.tick(),
+            width: view.board().width(),
+            height: view.board().height(),
+            cells: view
+                .frame()
+                .cells()
+                .filter(|(_, cell)| *cell != Cell::Empty)
+                .collect(),
+            score: view.score(),
+            scores: scores(view),
+            result: result(view),
+        }
+    }
+}
+
+impl Delta {
+    /// Describes how the game in `view` looks different from `previous`, the
+    /// [`SimulationView::frame`] from the same point of view as of the last
+    /// [`Snapshot`] or [`Delta`], then brings `previous` up to date for the
+    /// next. Nothing hidden by fog is given away.
+    pub fn since(previous: &mut FrameBuffer, view: &SimulationView<'_>) -> Self {
+        let frame = view.frame();
+        let changes = frame.changes_since(previous).collect();
+        *previous = frame;
+
+        Self {
+            tick: view.tick(),
+            changes,
+            score: view.score(),
+            scores: scores(view),
+            result: result(view),
+        }
+    }
+}
+
+impl Snapshot {
+    /// Brings the snapshot forward by the step `delta` describes.
+    pub fn apply(&mut self, delta: &Delta) {
+        for (cell, contents) in &delta.changes {
+            self.cells.retain(|(other, _)| other != cell);
+            if *contents != Cell::Empty {
+                self.cells.push((*cell, *contents));
+            }
+        }
+
+        self.cells.sort_by_key(|(cell, _)| (cell.y, cell.x));
+        self.tick = delta.tick;
+        self.score = delta.score;
+        self.scores.clone_from(&delta.scores);
+        self.result = delta.result;
+    }
+}
+
+/// Gets every snake's score in `view`, by index.
+fn scores(view: &SimulationView<'_>) -> Vec<usize> {
+    view.snakes().map(|snake| snake.score()).collect()
+}
+
+/// Gets how the game in `view` ended for the snake it is seen from, if it is
+/// over.
+fn result(view: &SimulationView<'_>) -> Option<SimulationResult> {
+    let game = view.result()?;
+    Some(*view.player().result().unwrap_or(game))
+}
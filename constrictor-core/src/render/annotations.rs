@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::math::Vector2;
+
+/// How an annotated cell is highlighted. Frontends pick the actual colours.
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tint {
+    Good,
+    Bad,
+    Info,
+}
+
+/// A note attached to a single cell, drawn over whatever occupies it. The
+/// label is meant to be very short, such as a glyph or a small score, since
+/// frontends may only have room for a character or two.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    pub tint: Option<Tint>,
+    pub label: Option<String>,
+}
+
+impl Annotation {
+    /// Creates an [`Annotation`] that only highlights its cell.
+    pub fn tint(tint: Tint) -> Self {
+        Self {
+            tint: Some(tint),
+            label: None,
+        }
+    }
+
+    /// Creates an [`Annotation`] that writes `label` over its cell.
+    pub fn label(label: impl Into<String>) -> Self {
+        Self {
+            tint: None,
+            label: Some(label.into()),
+        }
+    }
+
+    /// Creates an [`Annotation`] that writes a numeric `score` over its cell.
+    pub fn score(score: i64) -> Self {
+        Self::label(score.to_string())
+    }
+
+    /// Sets the [`Tint`] of this [`Annotation`].
+    pub fn with_tint(mut self, tint: Tint) -> Self {
+        self.tint = Some(tint);
+        self
+    }
+}
+
+/// [`Annotation`]s for any number of cells, to overlay on a frame. These come
+/// from a bot's [`Controller::explain`](crate::bots::Controller::explain), or
+/// can be built from the [`analysis`](crate::analysis) module to see what it
+/// sees.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+///
+/// use constrictor_core::analysis::reachable_cells;
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::models::Board;
+/// use constrictor_core::render::{Annotation, Annotations, Tint};
+///
+/// let board = Board::new((0, 3), (0, 3));
+/// let reachable = reachable_cells(&board, Vector2 { x: 0, y: 0 }, &HashSet::new());
+///
+/// let annotations: Annotations = reachable
+///     .into_iter()
+///     .map(|cell| (cell, Annotation::tint(Tint::Info)))
+///     .collect();
+///
+/// assert_eq!(annotations.len(), 9);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Annotations {
+    cells: HashMap<Vector2, Annotation>,
+}
+
+impl Annotations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches `annotation` to `cell`, replacing any already there.
+    pub fn set(&mut self, cell: Vector2, annotation: Annotation) {
+        self.cells.insert(cell, annotation);
+    }
+
+    /// Gets the [`Annotation`] attached to `cell`, if any.
+    pub fn get(&self, cell: &Vector2) -> Option<&Annotation> {
+        self.cells.get(cell)
+    }
+
+    /// Returns an [`Iterator`] over every annotated cell, in no particular
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vector2, &Annotation)> {
+        self.cells.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl FromIterator<(Vector2, Annotation)> for Annotations {
+    fn from_iter<T: IntoIterator<Item = (Vector2, Annotation)>>(iter: T) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    math::Vector2,
+    models::Board,
+    render::{Cell, FrameBuffer},
+};
+
+/// The bit for each dot of a braille character, by column then row, as
+/// Unicode numbers them.
+const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// A [`FrameBuffer`] packed into Unicode braille characters, each showing a
+/// block of 2 by 4 cells as dots, so that a board needs an eighth as many
+/// characters as it has cells. Every occupied cell is a dot, and cells a
+/// mask leaves unplayable count as obstacles. Terrain and fog are left out,
+/// since a dot can't tell them apart from anything else. Walls are not part
+/// of the frame.
+///
+/// Each character also carries the most prominent [`Cell`] in its block,
+/// for frontends to colour it by: heads above food, food above power-ups,
+/// and so on down to obstacles.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+/// use constrictor_core::render::{BrailleFrame, Cell};
+///
+/// let board = Board::new((0, 5), (0, 3));
+/// let snake = Snake::new(Vector2 { x: 1, y: 1 }, Direction::Right);
+/// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 3, y: 1 }, 7).unwrap();
+///
+/// let view = sim.view();
+/// let braille = BrailleFrame::new(view.board(), &view.frame());
+/// assert_eq!((braille.width(), braille.height()), (3, 1));
+///
+/// let row: String = (0..3).map(|column| braille.get(column, 0).unwrap().to_char()).collect();
+/// assert_eq!(row, "⠐⠐⠀");
+/// assert_eq!(braille.get(1, 0).unwrap().cell, Cell::Food);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrailleFrame {
+    width: usize,
+    height: usize,
+    chars: Vec<BrailleChar>,
+}
+
+/// One character of a [`BrailleFrame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BrailleChar {
+    /// Which of the eight dots are raised, in Unicode's order.
+    pub dots: u8,
+
+    /// The most prominent cell in the block, or [`Cell::Empty`] if none are
+    /// drawn.
+    pub cell: Cell,
+}
+
+impl BrailleChar {
+    /// Gets the braille character with these dots raised.
+    pub fn to_char(self) -> char {
+        char::from_u32(0x2800 + u32::from(self.dots)).unwrap_or(' ')
+    }
+}
+
+impl BrailleFrame {
+    /// Packs `frame`, which covers `board`.
+    pub fn new(board: &Board, frame: &FrameBuffer) -> Self {
+        let width = frame.width().div_ceil(2);
+        let height = frame.height().div_ceil(4);
+        let mut chars = vec![BrailleChar::default(); width * height];
+
+        for y in board.y_range() {
+            for x in board.x_range() {
+                let point = Vector2 { x, y };
+                let cell = if board.contains(&point) {
+                    frame.get(&point).unwrap_or_default()
+                } else {
+                    Cell::Obstacle
+                };
+
+                if prominence(cell) == 0 {
+                    continue;
+                }
+
+                let column = (x - board.x_range().start) as usize;
+                let row = (y - board.y_range().start) as usize;
+                let char = &mut chars[row / 4 * width + column / 2];
+
+                char.dots |= DOTS[column % 2][row % 4];
+                if prominence(cell) > prominence(char.cell) {
+                    char.cell = cell;
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            chars,
+        }
+    }
+
+    /// Gets the number of characters in each row.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the number of rows.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the character in `column` of `row`, counted from the top left,
+    /// or [`None`] if it is outside the frame.
+    pub fn get(&self, column: usize, row: usize) -> Option<BrailleChar> {
+        (column < self.width && row < self.height).then(|| self.chars[row * self.width + column])
+    }
+
+    /// Returns an [`Iterator`] over every character alongside its column and
+    /// row, row by row.
+    pub fn chars(&self) -> impl Iterator<Item = (usize, usize, BrailleChar)> + '_ {
+        self.chars.iter().enumerate().map(|(index, char)| {
+            let width = self.width.max(1);
+            (index % width, index / width, *char)
+        })
+    }
+
+    /// Returns an [`Iterator`] over the characters that differ from
+    /// `previous`, in the same way as [`FrameBuffer::changes_since`].
+    pub fn changes_since<'a>(
+        &'a self,
+        previous: &'a BrailleFrame,
+    ) -> impl Iterator<Item = (usize, usize, BrailleChar)> + 'a {
+        let comparable = self.width == previous.width && self.height == previous.height;
+
+        self.chars().filter(move |(column, row, char)| {
+            !comparable || previous.chars[row * self.width + column] != *char
+        })
+    }
+}
+
+/// How much `cell` matters to the player, deciding which cell colours a
+/// character it shares with others. Cells that aren't drawn at all come
+/// out as 0.
+fn prominence(cell: Cell) -> u8 {
+    match cell {
+        Cell::Empty | Cell::Terrain(_) | Cell::Hidden => 0,
+        Cell::Obstacle => 1,
+        Cell::Hazard => 2,
+        Cell::SnakeBody { .. } => 3,
+        Cell::Portal { .. } => 4,
+        Cell::PracticeFood => 5,
+        Cell::PowerUp(_) => 6,
+        Cell::Food => 7,
+        Cell::SnakeHead { snake, .. } if snake > 0 => 8,
+        Cell::SnakeHead { .. } => 9,
+    }
+}
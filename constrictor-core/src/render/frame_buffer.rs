@@ -0,0 +1,206 @@
+use crate::{
+    math::{Direction, Vector2},
+    models::{Board, PowerUp, Terrain},
+};
+
+/// What occupies a single cell of a [`FrameBuffer`]. Frontends decide how
+/// each kind is actually drawn.
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cell {
+    #[default]
+    Empty,
+
+    Food,
+
+    /// Food that can be eaten without any effect. See
+    /// [`FoodKind::Practice`](crate::models::FoodKind::Practice).
+    PracticeFood,
+
+    /// A cell blocked off inside the board.
+    Obstacle,
+
+    /// A power-up waiting to be picked up.
+    PowerUp(PowerUp),
+
+    /// One end of the `pair`th [`Portal`](crate::models::Portal) on the
+    /// board. Both ends of a portal share the same `pair`.
+    Portal {
+        pair: usize,
+    },
+
+    /// Part of the hazard zone of a shrinking board. See
+    /// [`Rules::shrink`](crate::models::Rules::shrink).
+    Hazard,
+
+    /// Otherwise empty ground with some [`Terrain`].
+    Terrain(Terrain),
+
+    /// A cell the player can't see for the fog, whatever is in it. See
+    /// [`Rules::fog`](crate::models::Rules::fog).
+    Hidden,
+
+    /// The head of the `snake`th snake, which is moving towards `facing`.
+    SnakeHead {
+        snake: usize,
+        facing: Direction,
+    },
+
+    /// Any other segment of the `snake`th snake.
+    SnakeBody {
+        snake: usize,
+    },
+}
+
+/// A renderer-agnostic picture of the playing area of a [`Board`], one
+/// [`Cell`] per board cell. Frontends fill one from a
+/// [`SimulationView`](crate::models::SimulationView) and draw from it, rather
+/// than each interpreting the simulation themselves. Walls are not part of
+/// the buffer and are drawn from the [`Board`] directly.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::Vector2;
+/// use constrictor_core::models::Board;
+/// use constrictor_core::render::{Cell, FrameBuffer};
+///
+/// let mut frame = FrameBuffer::new(&Board::new((0, 4), (0, 3)));
+/// assert_eq!((frame.width(), frame.height()), (4, 3));
+///
+/// frame.set(&Vector2 { x: 1, y: 2 }, Cell::Food);
+/// assert_eq!(frame.get(&Vector2 { x: 1, y: 2 }), Some(Cell::Food));
+/// assert_eq!(frame.get(&Vector2 { x: 4, y: 0 }), None);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FrameBuffer {
+    origin: Vector2,
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl FrameBuffer {
+    /// Creates an empty [`FrameBuffer`] covering every cell of `board`.
+    pub fn new(board: &Board) -> Self {
+        let width = board.width().max(0) as usize;
+        let height = board.height().max(0) as usize;
+
+        Self {
+            origin: Vector2 {
+                x: board.x_range().start,
+                y: board.y_range().start,
+            },
+            width,
+            height,
+            cells: vec![Cell::Empty; width * height],
+        }
+    }
+
+    /// Gets the number of cells in each row.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the number of rows.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the buffer covers exactly the cells of `board`.
+    pub fn fits(&self, board: &Board) -> bool {
+        self.origin.x == board.x_range().start
+            && self.origin.y == board.y_range().start
+            && self.width as i32 == board.width()
+            && self.height as i32 == board.height()
+    }
+
+    /// Gets the [`Cell`] at `point`, in board coordinates, or [`None`] if the
+    /// point is outside the buffer.
+    pub fn get(&self, point: &Vector2) -> Option<Cell> {
+        self.index(point).map(|index| self.cells[index])
+    }
+
+    /// Sets the [`Cell`] at `point`, in board coordinates. Returns `false`,
+    /// leaving the buffer untouched, if the point is outside the buffer.
+    pub fn set(&mut self, point: &Vector2, cell: Cell) -> bool {
+        let Some(index) = self.index(point) else {
+            return false;
+        };
+
+        self.cells[index] = cell;
+        true
+    }
+
+    /// Sets every cell in `changes`, such as those from
+    /// [`SnakeSimulation::changes`](crate::models::SnakeSimulation::changes).
+    /// Changes outside the buffer are ignored.
+    pub fn apply<'a>(&mut self, changes: impl IntoIterator<Item = &'a (Vector2, Cell)>) {
+        for (point, cell) in changes {
+            self.set(point, *cell);
+        }
+    }
+
+    /// Resets every cell to [`Cell::Empty`].
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::Empty);
+    }
+
+    /// Returns an [`Iterator`] over the rows of the buffer, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns an [`Iterator`] over every cell alongside its position in board
+    /// coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Vector2, Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.point(index), *cell))
+    }
+
+    /// Returns an [`Iterator`] over the cells that differ from `previous`,
+    /// alongside their position in board coordinates. Every cell is returned
+    /// if the two buffers do not cover the same area.
+    ///
+    /// # Example
+    /// ```
+    /// use constrictor_core::math::Vector2;
+    /// use constrictor_core::models::Board;
+    /// use constrictor_core::render::{Cell, FrameBuffer};
+    ///
+    /// let before = FrameBuffer::new(&Board::new((0, 4), (0, 3)));
+    /// let mut after = before.clone();
+    /// after.set(&Vector2 { x: 1, y: 2 }, Cell::Food);
+    ///
+    /// let changed: Vec<_> = after.changes_since(&before).collect();
+    /// assert_eq!(changed, [(Vector2 { x: 1, y: 2 }, Cell::Food)]);
+    /// ```
+    pub fn changes_since<'a>(
+        &'a self,
+        previous: &'a FrameBuffer,
+    ) -> impl Iterator<Item = (Vector2, Cell)> + 'a {
+        let comparable = self.origin == previous.origin
+            && self.width == previous.width
+            && self.height == previous.height;
+
+        self.cells()
+            .enumerate()
+            .filter(move |(index, (_, cell))| !comparable || previous.cells[*index] != *cell)
+            .map(|(_, change)| change)
+    }
+
+    fn index(&self, point: &Vector2) -> Option<usize> {
+        let x = usize::try_from(point.x - self.origin.x).ok()?;
+        let y = usize::try_from(point.y - self.origin.y).ok()?;
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn point(&self, index: usize) -> Vector2 {
+        Vector2 {
+            x: self.origin.x + (index % self.width) as i32,
+            y: self.origin.y + (index / self.width) as i32,
+        }
+    }
+}
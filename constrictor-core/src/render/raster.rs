@@ -0,0 +1,154 @@
+use crate::{
+    math::Vector2,
+    models::{Board, Terrain},
+    render::{Cell, FrameBuffer},
+};
+
+/// The colours a [`Raster`] is drawn in, as RGB. Each pixel of a raster is
+/// an index into this palette, ready for indexed image formats such as GIF.
+pub const PALETTE: [[u8; 3]; 15] = [
+    [0x1b, 0x1b, 0x1b], // background
+    [0x42, 0x42, 0x42], // wall
+    [0xe5, 0x39, 0x35], // food
+    [0x9e, 0x9e, 0x9e], // practice food
+    [0x61, 0x61, 0x61], // obstacle
+    [0x29, 0xb6, 0xf6], // power-up
+    [0xab, 0x47, 0xbc], // portal
+    [0x4e, 0x23, 0x23], // hazard
+    [0x4e, 0x34, 0x2e], // sticky terrain
+    [0x00, 0x4d, 0x40], // boost terrain
+    [0x0d, 0x0d, 0x0d], // fog
+    [0x8b, 0xc3, 0x4a], // the player's head
+    [0x4c, 0xaf, 0x50], // the player's body
+    [0xff, 0xf1, 0x76], // another snake's head
+    [0xfb, 0xc0, 0x2d], // another snake's body
+];
+
+const BACKGROUND: u8 = 0;
+const WALL: u8 = 1;
+
+/// A picture of a [`FrameBuffer`] drawn a pixel at a time, without a
+/// terminal, for exporting images and animations. Each cell is a square of
+/// `cell_size` pixels, and the board has a wall one cell thick around it.
+/// Pixels are indices into [`PALETTE`], row by row from the top left.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::render::{PALETTE, Raster};
+///
+/// let config = GameConfig {
+///     seed: Some(1),
+///     ..Default::default()
+/// };
+/// let sim = config.build().unwrap();
+/// let view = sim.view();
+/// let raster = Raster::new(view.board(), &view.frame(), 4);
+///
+/// let cells = view.board().width() as u32 + 2;
+/// assert_eq!(raster.width(), cells * 4);
+///
+/// // The top left corner is wall
+/// let corner = raster.pixels()[0];
+/// assert_eq!(PALETTE[corner as usize], [0x42, 0x42, 0x42]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Draws `frame`, which covers `board`, with each cell `cell_size`
+    /// pixels square. A cell size of 0 is treated as 1.
+    pub fn new(board: &Board, frame: &FrameBuffer, cell_size: u32) -> Self {
+        let cell_size = cell_size.max(1);
+        let columns = board.width().max(0) as u32 + 2;
+        let rows = board.height().max(0) as u32 + 2;
+
+        let mut raster = Self {
+            width: columns * cell_size,
+            height: rows * cell_size,
+            pixels: vec![WALL; (columns * rows * cell_size * cell_size) as usize],
+        };
+
+        for (row, y) in board.y_range().enumerate() {
+            for (column, x) in board.x_range().enumerate() {
+                let point = Vector2 { x, y };
+
+                // Cells a mask leaves unplayable are part of the wall
+                if !board.contains(&point) {
+                    continue;
+                }
+
+                let shade = shade(frame.get(&point).unwrap_or_default());
+                raster.fill(column as u32 + 1, row as u32 + 1, cell_size, shade);
+            }
+        }
+
+        raster
+    }
+
+    /// Gets the width of the picture, in pixels.
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height of the picture, in pixels.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the pixels, as indices into [`PALETTE`], row by row.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Gets the pixels as RGB, three bytes each, for image formats without
+    /// a palette.
+    pub fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|index| PALETTE[*index as usize])
+            .collect()
+    }
+
+    /// Paints the cell in `column` and `row` of the picture. Anything but the
+    /// background leaves a pixel of it around the edge, so neighbouring
+    /// cells such as a snake's segments can be told apart, as long as cells
+    /// are big enough to spare it.
+    fn fill(&mut self, column: u32, row: u32, cell_size: u32, shade: u8) {
+        let inset = u32::from(shade != BACKGROUND && cell_size >= 3);
+
+        for y in 0..cell_size {
+            for x in 0..cell_size {
+                let inside = (inset..cell_size - inset).contains(&x)
+                    && (inset..cell_size - inset).contains(&y);
+
+                let pixel = (row * cell_size + y) * self.width + column * cell_size + x;
+                self.pixels[pixel as usize] = if inside { shade } else { BACKGROUND };
+            }
+        }
+    }
+}
+
+/// Gets the index into [`PALETTE`] that `cell` is drawn in.
+fn shade(cell: Cell) -> u8 {
+    match cell {
+        Cell::Empty => BACKGROUND,
+        Cell::Food => 2,
+        Cell::PracticeFood => 3,
+        Cell::Obstacle => 4,
+        Cell::PowerUp(_) => 5,
+        Cell::Portal { .. } => 6,
+        Cell::Hazard => 7,
+        Cell::Terrain(Terrain::Sticky) => 8,
+        Cell::Terrain(Terrain::Boost) => 9,
+        Cell::Hidden => 10,
+        Cell::SnakeHead { snake: 0, .. } => 11,
+        Cell::SnakeBody { snake: 0 } => 12,
+        Cell::SnakeHead { .. } => 13,
+        Cell::SnakeBody { .. } => 14,
+    }
+}
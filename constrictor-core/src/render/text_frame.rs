@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+use crate::{
+    math::Vector2,
+    models::{Board, Terrain},
+    render::{Cell, FrameBuffer},
+};
+
+/// A [`FrameBuffer`] drawn as plain text, one character per cell and no
+/// escape codes, for tests and anywhere else a terminal isn't involved. The
+/// board has a wall one cell thick around it, and each cell is drawn as:
+///
+/// | Cell                     | Glyph                              |
+/// |--------------------------|------------------------------------|
+/// | wall, or masked out      | `#`                                |
+/// | empty                    | `.`                                |
+/// | food                     | `F`                                |
+/// | practice food            | `f`                                |
+/// | obstacle                 | `X`                                |
+/// | power-up                 | `!`                                |
+/// | portal                   | `a` for the first pair, `b`, ...   |
+/// | hazard                   | `~`                                |
+/// | sticky terrain           | `:`                                |
+/// | boost terrain            | `>`                                |
+/// | hidden by fog            | `?`                                |
+/// | the player's snake       | `@` for its head, `*` for its body |
+/// | any other snake          | its number for its head, `=` for its body |
+///
+/// [`SnakeSimulation`](crate::models::SnakeSimulation) is displayed this way.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::{Direction, Vector2};
+/// use constrictor_core::models::{Board, Snake, SnakeSimulation};
+/// use constrictor_core::render::TextFrame;
+///
+/// let board = Board::new((0, 5), (0, 3));
+/// let snake = Snake::new(Vector2 { x: 1, y: 1 }, Direction::Right);
+/// let sim = SnakeSimulation::seeded(board, snake, Vector2 { x: 3, y: 1 }, 7).unwrap();
+///
+/// let view = sim.view();
+/// let text = TextFrame::new(view.board(), &view.frame());
+/// assert_eq!(
+///     text.rows(),
+///     [
+///         "#######",
+///         "#.....#",
+///         "#.@.F.#",
+///         "#.....#",
+///         "#######",
+///     ]
+/// );
+///
+/// // Each row ends with a line break when displayed
+/// assert!(sim.to_string().starts_with("#######\n#.....#\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextFrame {
+    rows: Vec<String>,
+}
+
+impl TextFrame {
+    /// Draws `frame`, which covers `board`.
+    pub fn new(board: &Board, frame: &FrameBuffer) -> Self {
+        let wall = "#".repeat(board.width().max(0) as usize + 2);
+        let mut rows = vec![wall.clone()];
+
+        for y in board.y_range() {
+            let mut row = String::from('#');
+            for x in board.x_range() {
+                let point = Vector2 { x, y };
+
+                // Cells a mask leaves unplayable are part of the wall
+                if board.contains(&point) {
+                    row.push(glyph(frame.get(&point).unwrap_or_default()));
+                } else {
+                    row.push('#');
+                }
+            }
+            row.push('#');
+            rows.push(row);
+        }
+
+        rows.push(wall);
+        Self { rows }
+    }
+
+    /// Gets the rows of text, from the top, without line breaks.
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+}
+
+impl Display for TextFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            writeln!(f, "{row}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn glyph(cell: Cell) -> char {
+    match cell {
+        Cell::Empty => '.',
+        Cell::Food => 'F',
+        Cell::PracticeFood => 'f',
+        Cell::Obstacle => 'X',
+        Cell::PowerUp(_) => '!',
+        Cell::Portal { pair } => (b'a'..=b'z').nth(pair).map_or('%', char::from),
+        Cell::Hazard => '~',
+        Cell::Terrain(Terrain::Sticky) => ':',
+        Cell::Terrain(Terrain::Boost) => '>',
+        Cell::Hidden => '?',
+        Cell::SnakeHead { snake: 0, .. } => '@',
+        Cell::SnakeBody { snake: 0 } => '*',
+        Cell::SnakeHead { snake, .. } => char::from_digit(snake as u32, 10).unwrap_or('&'),
+        Cell::SnakeBody { .. } => '=',
+    }
+}
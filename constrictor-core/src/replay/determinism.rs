@@ -0,0 +1,195 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    config::ConfigError,
+    models::{Checkpoint, SnakeSimulation},
+    replay::Replay,
+};
+
+/// The [`SnakeSimulation::state_hash`] before a replay's first step and after
+/// every step from then on. Two plays of the same [`Replay`] must record the
+/// same trace, whatever machine they run on, or replays and lockstep games
+/// fall apart; [`Trace::compare`] finds where they stop agreeing.
+///
+/// A trace is small enough to send elsewhere, so one recorded on another
+/// platform can be checked against one recorded here.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::math::Direction;
+/// use constrictor_core::replay::{InputLog, Replay, Trace};
+///
+/// let mut inputs = InputLog::new();
+/// inputs.record(3, Direction::Down);
+///
+/// let replay = Replay {
+///     config: GameConfig {
+///         seed: Some(7),
+///         ..Default::default()
+///     },
+///     inputs,
+///     ticks: 10,
+///     result: None,
+/// };
+///
+/// let trace = replay.check_determinism().unwrap();
+/// assert_eq!(trace.checkpoints().len(), 11);
+///
+/// // A different game diverges as soon as its inputs do
+/// let mut other = replay.clone();
+/// other.inputs = InputLog::new();
+/// let divergence = Trace::record(&other).unwrap().compare(&trace).unwrap_err();
+/// assert_eq!(divergence.tick(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Trace {
+    checkpoints: Vec<Checkpoint>,
+}
+
+/// Describes where two [`Trace`]s of the same replay first disagree.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Divergence {
+    /// Both runs took `step`, but disagree about the state of the game after
+    /// it. `tick` is where the expected run was.
+    Hash {
+        step: usize,
+        tick: u64,
+        expected: u64,
+        actual: u64,
+    },
+
+    /// One run stopped after `steps`, while the other went on to play
+    /// `tick`.
+    Length { steps: usize, tick: u64 },
+}
+
+impl Divergence {
+    /// Gets the first tick the runs disagree on.
+    pub const fn tick(&self) -> u64 {
+        match self {
+            Self::Hash { tick, .. } | Self::Length { tick, .. } => *tick,
+        }
+    }
+}
+
+impl Error for Divergence {}
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hash {
+                step,
+                tick,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "state diverged at tick {tick}, step {step} (expected hash {expected:016x}, \
+                 got {actual:016x})"
+            ),
+            Self::Length { steps, tick } => write!(
+                f,
+                "one run stopped after {steps} steps, the other went on to tick {tick}"
+            ),
+        }
+    }
+}
+
+/// Describes why [`Replay::check_determinism`] failed.
+#[derive(PartialEq, Eq, Debug)]
+pub enum DeterminismError {
+    /// The replay couldn't be played at all.
+    Config(ConfigError),
+    Diverged(Divergence),
+}
+
+impl Error for DeterminismError {}
+impl Display for DeterminismError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config(e) => e.fmt(f),
+            Self::Diverged(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<ConfigError> for DeterminismError {
+    fn from(value: ConfigError) -> Self {
+        Self::Config(value)
+    }
+}
+
+impl From<Divergence> for DeterminismError {
+    fn from(value: Divergence) -> Self {
+        Self::Diverged(value)
+    }
+}
+
+impl Trace {
+    /// Plays `replay` through, taking a [`Checkpoint`] before the first step
+    /// and after each one.
+    pub fn record(replay: &Replay) -> Result<Self, ConfigError> {
+        let mut playback = replay.play()?;
+        let checkpoint = |sim: &SnakeSimulation| Checkpoint {
+            tick: sim.tick(),
+            hash: sim.state_hash(),
+        };
+
+        let mut checkpoints = vec![checkpoint(playback.simulation())];
+        while playback.step() {
+            checkpoints.push(checkpoint(playback.simulation()));
+        }
+
+        Ok(Self { checkpoints })
+    }
+
+    /// Gets the [`Checkpoint`]s, the first taken before any step.
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Checks `self` against the `expected` trace of the same replay,
+    /// failing at the first step where they disagree.
+    pub fn compare(&self, expected: &Self) -> Result<(), Divergence> {
+        let pairs = expected.checkpoints.iter().zip(&self.checkpoints);
+        for (step, (expected, actual)) in pairs.enumerate() {
+            if expected != actual {
+                return Err(Divergence::Hash {
+                    step,
+                    tick: expected.tick,
+                    expected: expected.hash,
+                    actual: actual.hash,
+                });
+            }
+        }
+
+        let steps = expected.checkpoints.len().min(self.checkpoints.len());
+        let longer = if expected.checkpoints.len() > steps {
+            expected
+        } else {
+            self
+        };
+
+        match longer.checkpoints.get(steps) {
+            Some(extra) => Err(Divergence::Length {
+                steps: steps.saturating_sub(1),
+                tick: extra.tick,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Replay {
+    /// Plays the replay twice and checks that both plays agree about the
+    /// state of the game on every tick, giving back the [`Trace`] so it can
+    /// be checked against other platforms too.
+    pub fn check_determinism(&self) -> Result<Trace, DeterminismError> {
+        let expected = Trace::record(self)?;
+        Trace::record(self)?.compare(&expected)?;
+
+        Ok(expected)
+    }
+}
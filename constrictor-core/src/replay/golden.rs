@@ -0,0 +1,237 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{config::ConfigError, replay::Replay};
+
+/// Set to anything to have [`GoldenFrames::check`] rewrite golden files
+/// instead of comparing against them.
+pub const UPDATE_GOLDEN_VAR: &str = "CONSTRICTOR_UPDATE_GOLDEN";
+
+/// Every frame of a replay, drawn as plain text the way a
+/// [`SnakeSimulation`](crate::models::SnakeSimulation) is displayed, for
+/// snapshot tests. Checking a replay's frames against a golden file kept
+/// alongside the tests catches any change to how games play out or are
+/// drawn, however small.
+///
+/// Golden files hold each frame under a line naming its step and tick:
+///
+/// ```text
+/// --- step 0, tick 0
+/// #######
+/// #.@.F.#
+/// #######
+/// --- step 1, tick 1
+/// ...
+/// ```
+///
+/// When a change is meant to alter the frames, run the tests again with
+/// [`UPDATE_GOLDEN_VAR`] set to regenerate the files, and review the
+/// difference. This crate's own golden files are in `tests/golden`:
+///
+/// ```sh
+/// CONSTRICTOR_UPDATE_GOLDEN=1 cargo test -p constrictor-core --test golden
+/// ```
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::replay::{GoldenError, GoldenFrames, InputLog, Replay};
+///
+/// let replay = Replay {
+///     config: GameConfig {
+///         seed: Some(7),
+///         ..Default::default()
+///     },
+///     inputs: InputLog::new(),
+///     ticks: 5,
+///     result: None,
+/// };
+///
+/// let path = std::env::temp_dir().join("constrictor-golden-example.txt");
+/// let frames = GoldenFrames::record(&replay).unwrap();
+/// assert_eq!(frames.frames().len(), 6);
+///
+/// frames.update(&path).unwrap();
+/// frames.compare(&path).unwrap();
+///
+/// // A game that ends sooner doesn't match
+/// let mut other = replay.clone();
+/// other.ticks = 3;
+/// let error = GoldenFrames::record(&other).unwrap().compare(&path);
+/// assert!(matches!(error, Err(GoldenError::Length { expected: 5, actual: 3 })));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GoldenFrames {
+    frames: Vec<GoldenFrame>,
+}
+
+/// One frame of a [`GoldenFrames`], and the tick it was drawn on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenFrame {
+    pub tick: u64,
+    pub text: String,
+}
+
+/// Describes why frames didn't match their golden file.
+#[derive(Debug)]
+pub enum GoldenError {
+    /// The golden file couldn't be read or written.
+    Io(PathBuf, io::Error),
+
+    /// The golden file isn't in the expected format. The line is counted
+    /// from 1.
+    Parse(PathBuf, usize),
+
+    /// The frame after `step` differs from the golden one.
+    Mismatch {
+        step: usize,
+        expected: GoldenFrame,
+        actual: GoldenFrame,
+    },
+
+    /// The game went on for `actual` steps, rather than `expected`.
+    Length { expected: usize, actual: usize },
+}
+
+impl Error for GoldenError {}
+impl Display for GoldenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(
+                f,
+                "could not access golden file {} ({e}); set {UPDATE_GOLDEN_VAR} to create it",
+                path.display()
+            ),
+            Self::Parse(path, line) => {
+                write!(f, "{}:{line}: expected a `--- step` line", path.display())
+            }
+            Self::Mismatch {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "frame {step} differs from the golden one\n\
+                 expected (tick {}):\n{}\
+                 actual (tick {}):\n{}",
+                expected.tick, expected.text, actual.tick, actual.text
+            ),
+            Self::Length { expected, actual } => write!(
+                f,
+                "the game went on for {actual} steps, but the golden file has {expected}"
+            ),
+        }
+    }
+}
+
+impl GoldenFrames {
+    /// Plays `replay` through, drawing the game before the first step and
+    /// after each one.
+    pub fn record(replay: &Replay) -> Result<Self, ConfigError> {
+        let mut playback = replay.play()?;
+        let frame = |playback: &crate::replay::Playback| GoldenFrame {
+            tick: playback.simulation().tick(),
+            text: playback.simulation().to_string(),
+        };
+
+        let mut frames = vec![frame(&playback)];
+        while playback.step() {
+            frames.push(frame(&playback));
+        }
+
+        Ok(Self { frames })
+    }
+
+    /// Gets the frames, the first drawn before any step.
+    pub fn frames(&self) -> &[GoldenFrame] {
+        &self.frames
+    }
+
+    /// Compares the frames against the golden file at `path`, or rewrites
+    /// the file with them if [`UPDATE_GOLDEN_VAR`] is set. This is what
+    /// tests should call.
+    pub fn check(&self, path: &Path) -> Result<(), GoldenError> {
+        if env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+            self.update(path)
+        } else {
+            self.compare(path)
+        }
+    }
+
+    /// Compares the frames against the golden file at `path`, failing at
+    /// the first that differs.
+    pub fn compare(&self, path: &Path) -> Result<(), GoldenError> {
+        let text = fs::read_to_string(path).map_err(|e| GoldenError::Io(path.to_owned(), e))?;
+        let expected =
+            Self::parse(&text).map_err(|line| GoldenError::Parse(path.to_owned(), line))?;
+
+        for (step, (expected, actual)) in expected.frames.iter().zip(&self.frames).enumerate() {
+            if expected != actual {
+                return Err(GoldenError::Mismatch {
+                    step,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+
+        if expected.frames.len() != self.frames.len() {
+            return Err(GoldenError::Length {
+                expected: expected.frames.len().saturating_sub(1),
+                actual: self.frames.len().saturating_sub(1),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Writes the frames to the golden file at `path`, replacing whatever
+    /// was there.
+    pub fn update(&self, path: &Path) -> Result<(), GoldenError> {
+        fs::write(path, self.to_string()).map_err(|e| GoldenError::Io(path.to_owned(), e))
+    }
+
+    /// Reads frames in the golden file format. On failure, gives the number
+    /// of the offending line.
+    fn parse(text: &str) -> Result<Self, usize> {
+        let mut frames: Vec<GoldenFrame> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            if let Some(header) = line.strip_prefix("--- step ") {
+                let tick = header
+                    .split_once(", tick ")
+                    .and_then(|(_, tick)| tick.parse().ok())
+                    .ok_or(index + 1)?;
+
+                frames.push(GoldenFrame {
+                    tick,
+                    text: String::new(),
+                });
+                continue;
+            }
+
+            let frame = frames.last_mut().ok_or(index + 1)?;
+            frame.text.push_str(line);
+            frame.text.push('\n');
+        }
+
+        Ok(Self { frames })
+    }
+}
+
+impl Display for GoldenFrames {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (step, frame) in self.frames.iter().enumerate() {
+            writeln!(f, "--- step {step}, tick {}", frame.tick)?;
+            write!(f, "{}", frame.text)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::math::Direction;
+
+/// A direction change, tagged with the tick it was made on. The change is
+/// applied before the simulation advances from that tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedInput {
+    pub tick: u64,
+    pub direction: Direction,
+}
+
+/// Every direction change made during a game, in the order they were made.
+/// Together with the seeded [`GameConfig`](crate::config::GameConfig) the
+/// game was built from, this is enough to reproduce the game exactly.
+///
+/// # Example
+/// ```
+/// use constrictor_core::math::Direction;
+/// use constrictor_core::replay::InputLog;
+///
+/// let mut log = InputLog::new();
+/// log.record(0, Direction::Up);
+/// log.record(3, Direction::Left);
+/// log.record(3, Direction::Down);
+///
+/// assert_eq!(log.len(), 3);
+/// assert_eq!(log.at(3).collect::<Vec<_>>(), [Direction::Left, Direction::Down]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct InputLog {
+    inputs: Vec<TimedInput>,
+}
+
+impl InputLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the player turned towards `direction` on `tick`.
+    pub fn record(&mut self, tick: u64, direction: Direction) {
+        self.inputs.push(TimedInput { tick, direction });
+    }
+
+    /// Returns an [`Iterator`] over every recorded input, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &TimedInput> {
+        self.inputs.iter()
+    }
+
+    /// Returns an [`Iterator`] over the directions recorded on `tick`, in
+    /// order.
+    pub fn at(&self, tick: u64) -> impl Iterator<Item = Direction> + '_ {
+        self.inputs
+            .iter()
+            .filter(move |input| input.tick == tick)
+            .map(|input| input.direction)
+    }
+
+    /// Gets the number of recorded inputs.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+}
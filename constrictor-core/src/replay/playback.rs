@@ -0,0 +1,118 @@
+use crate::{
+    config::{ConfigError, GameConfig},
+    models::{SimulationResult, SnakeSimulation},
+    replay::InputLog,
+};
+
+/// A recorded game: the [`GameConfig`] it was built from, the player's
+/// [`InputLog`], and how long it lasted and how it ended.
+///
+/// # Example
+/// ```
+/// use constrictor_core::config::GameConfig;
+/// use constrictor_core::math::Direction;
+/// use constrictor_core::replay::{InputLog, Replay};
+///
+/// let config = GameConfig {
+///     seed: Some(7),
+///     ..Default::default()
+/// };
+///
+/// // Play a game, recording the inputs
+/// let mut sim = config.build().unwrap();
+/// let mut inputs = InputLog::new();
+/// for (tick, direction) in [(2, Direction::Up), (5, Direction::Left)] {
+///     while sim.tick() < tick {
+///         sim.advance();
+///     }
+///
+///     inputs.record(sim.tick(), direction);
+///     sim.change_player_move_direction(direction);
+/// }
+/// sim.advance();
+///
+/// let replay = Replay {
+///     config,
+///     inputs,
+///     ticks: sim.tick(),
+///     result: sim.result().copied(),
+/// };
+///
+/// let mut playback = replay.play().unwrap();
+/// while playback.step() {}
+///
+/// assert_eq!(playback.simulation().state_hash(), sim.state_hash());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    /// The configuration the game was built from. This must have a seed for
+    /// the game to be reproducible.
+    pub config: GameConfig,
+
+    /// Every direction change the player made.
+    pub inputs: InputLog,
+
+    /// The tick the game ended on.
+    pub ticks: u64,
+
+    /// How the game ended, if it did.
+    pub result: Option<SimulationResult>,
+}
+
+impl Replay {
+    /// Starts playing back the replay from its first tick.
+    pub fn play(&self) -> Result<Playback<'_>, ConfigError> {
+        if self.config.seed.is_none() {
+            return Err(ConfigError::MissingSeed);
+        }
+
+        Ok(Playback {
+            replay: self,
+            sim: self.config.build()?,
+        })
+    }
+}
+
+/// Steps through a [`Replay`] one tick at a time.
+#[derive(Debug, Clone)]
+pub struct Playback<'a> {
+    replay: &'a Replay,
+    sim: SnakeSimulation,
+}
+
+impl Playback<'_> {
+    /// Gets the simulation as of the current tick.
+    pub const fn simulation(&self) -> &SnakeSimulation {
+        &self.sim
+    }
+
+    /// Applies the inputs recorded for the current tick and advances the
+    /// simulation. Returns `false`, leaving the simulation untouched, once
+    /// the replay has finished.
+    pub fn step(&mut self) -> bool {
+        if self.sim.result().is_some() {
+            return false;
+        }
+
+        if self.sim.tick() >= self.replay.ticks {
+            match self.replay.result {
+                None => return false,
+                Some(SimulationResult::ManuallyTerminated(reason)) => {
+                    self.sim.quit(reason);
+                    return true;
+                }
+                // Dying doesn't advance the tick, so the fatal step still
+                // needs to be played
+                Some(_) => {}
+            }
+        }
+
+        for direction in self.replay.inputs.at(self.sim.tick()) {
+            self.sim.change_player_move_direction(direction);
+        }
+
+        self.sim.advance();
+        true
+    }
+}
@@ -0,0 +1,54 @@
+//! Checks recorded games against the golden files in `tests/golden`, so any
+//! change to how they play out or are drawn shows up as a failing test.
+//!
+//! When a change is meant to alter the frames, regenerate the files and
+//! review the difference before committing them:
+//!
+//! ```sh
+//! CONSTRICTOR_UPDATE_GOLDEN=1 cargo test -p constrictor-core --test golden
+//! ```
+
+use std::path::PathBuf;
+
+use constrictor_core::{
+    config::{BoardSize, GameConfig},
+    math::Direction,
+    models::{DeathReason, SimulationResult},
+    replay::{GoldenFrames, InputLog, Replay},
+};
+
+fn golden_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+}
+
+/// A short game on a small board that eats once, turns about and runs into
+/// the wall.
+#[test]
+fn small_board() {
+    let mut inputs = InputLog::new();
+    inputs.record(6, Direction::Up);
+    inputs.record(8, Direction::Left);
+    inputs.record(11, Direction::Down);
+    inputs.record(14, Direction::Right);
+
+    let replay = Replay {
+        config: GameConfig {
+            board: BoardSize {
+                width: 8,
+                height: 6,
+            },
+            seed: Some(1353),
+            ..Default::default()
+        },
+        inputs,
+        ticks: 17,
+        result: Some(SimulationResult::Died(DeathReason::HitWall)),
+    };
+
+    let frames = GoldenFrames::record(&replay).unwrap();
+    if let Err(e) = frames.check(&golden_file("small_board.txt")) {
+        panic!("{e}");
+    }
+}
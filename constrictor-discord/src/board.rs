@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use constrictor_core::{
+    math::Vector2,
+    models::{DeathReason, SimulationResult, SimulationView, Terrain},
+    render::Cell,
+};
+
+/// The most characters Discord allows in a message.
+pub const MAX_MESSAGE_LEN: usize = 2000;
+
+const WALL: &str = "⬜";
+const EMPTY: &str = "⬛";
+
+/// Draws the game in `view` as a grid of emoji, one per cell with a wall
+/// around it, followed by a status line.
+pub fn render(view: &SimulationView) -> String {
+    let board = view.board();
+    let frame = view.frame();
+    let mut message = String::new();
+
+    let wall_row = WALL.repeat(board.width() as usize + 2);
+    message.push_str(&wall_row);
+    message.push('\n');
+
+    for y in board.y_range() {
+        message.push_str(WALL);
+        for x in board.x_range() {
+            let cell = Vector2 { x, y };
+
+            // Cells a mask leaves unplayable are part of the wall
+            if !board.contains(&cell) {
+                message.push_str(WALL);
+                continue;
+            }
+
+            message.push_str(emoji(frame.get(&cell).unwrap_or_default()));
+        }
+        message.push_str(WALL);
+        message.push('\n');
+    }
+
+    message.push_str(&wall_row);
+    message.push('\n');
+
+    _ = match view.result() {
+        Some(result) => write!(
+            message,
+            "**{}** Final score: {}. A new game starts next turn.",
+            describe(result),
+            view.score()
+        ),
+        None => write!(
+            message,
+            "Score: {} · Turn {} · React with an arrow to vote on where to go next",
+            view.score(),
+            view.tick()
+        ),
+    };
+
+    message
+}
+
+fn emoji(cell: Cell) -> &'static str {
+    match cell {
+        Cell::Empty => EMPTY,
+        Cell::Food => "🍎",
+        Cell::PracticeFood => "🍏",
+        Cell::Obstacle => "🧱",
+        Cell::PowerUp(_) => "⭐",
+        Cell::Portal { .. } => "🌀",
+        Cell::Hazard => "🟥",
+        Cell::Terrain(Terrain::Sticky) => "🟫",
+        Cell::Terrain(Terrain::Boost) => "🟦",
+        Cell::Hidden => "🌫️",
+        Cell::SnakeHead { snake: 0, .. } => "🟢",
+        Cell::SnakeBody { snake: 0 } => "🟩",
+        Cell::SnakeHead { .. } => "🟡",
+        Cell::SnakeBody { .. } => "🟨",
+    }
+}
+
+fn describe(result: &SimulationResult) -> &'static str {
+    match result {
+        SimulationResult::Died(DeathReason::HitWall) => "The snake hit a wall.",
+        SimulationResult::Died(DeathReason::HitSelf) => "The snake ran into itself.",
+        SimulationResult::Died(DeathReason::HitSnake(_)) => "The snake ran into another snake.",
+        SimulationResult::Died(DeathReason::HeadOn(_)) => "The snakes collided head-on.",
+        SimulationResult::Died(DeathReason::Hazard) => "The board closed in on the snake.",
+        SimulationResult::Won => "You win!",
+        SimulationResult::Lost => "You lose.",
+        SimulationResult::LeftBoard(_) => "The snake left the board.",
+        SimulationResult::OutOfMoves => "The snake ran out of moves.",
+        SimulationResult::TimeExpired => "Time's up!",
+        SimulationResult::ManuallyTerminated(_) => "Game over.",
+    }
+}
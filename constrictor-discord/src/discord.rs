@@ -0,0 +1,203 @@
+use std::{error::Error, fmt::Display, thread::sleep, time::Duration};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::json;
+use ureq::{
+    Agent, Body, RequestBuilder,
+    http::{Response, StatusCode},
+};
+
+const API: &str = "https://discord.com/api/v10";
+
+/// How many times a request is tried again after being rate limited.
+const MAX_RETRIES: u32 = 3;
+
+/// Describes why a request to Discord failed.
+#[derive(Debug)]
+pub enum DiscordError {
+    /// Discord couldn't be reached, or sent back something unreadable.
+    Http(ureq::Error),
+
+    /// Discord turned the request down, with its explanation.
+    Status(u16, String),
+
+    /// Discord kept asking to slow down.
+    RateLimited,
+
+    /// Discord sent back an id that isn't a number.
+    Id(String),
+}
+
+impl Error for DiscordError {}
+impl Display for DiscordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(e) => e.fmt(f),
+            Self::Status(status, message) => write!(f, "Discord answered {status}: {message}"),
+            Self::RateLimited => write!(f, "rate limited by Discord"),
+            Self::Id(id) => write!(f, "Discord sent an invalid id `{id}`"),
+        }
+    }
+}
+
+impl From<ureq::Error> for DiscordError {
+    fn from(value: ureq::Error) -> Self {
+        Self::Http(value)
+    }
+}
+
+/// Something Discord gives an id, such as a user or a message.
+#[derive(Deserialize)]
+struct Object {
+    id: String,
+}
+
+impl Object {
+    /// Discord sends ids as strings, since they don't fit in a JSON number.
+    fn id(&self) -> Result<u64, DiscordError> {
+        self.id
+            .parse()
+            .map_err(|_| DiscordError::Id(self.id.clone()))
+    }
+}
+
+#[derive(Deserialize)]
+struct RateLimit {
+    /// How many seconds to wait before trying again.
+    retry_after: f64,
+}
+
+/// The little of Discord's REST API the bot needs, acting as a bot user in
+/// one channel.
+pub struct Discord {
+    agent: Agent,
+    authorization: String,
+    channel: u64,
+}
+
+impl Discord {
+    pub fn new(token: &str, channel: u64) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        Self {
+            agent,
+            authorization: format!("Bot {token}"),
+            channel,
+        }
+    }
+
+    /// Gets the id of the bot's own user, so its reactions can be told
+    /// apart from votes.
+    pub fn user_id(&self) -> Result<u64, DiscordError> {
+        let url = format!("{API}/users/@me");
+        let user: Object = self.read(|| self.authorize(self.agent.get(&url)).call())?;
+        user.id()
+    }
+
+    /// Posts `content` to the channel, giving back the new message's id.
+    pub fn post(&self, content: &str) -> Result<u64, DiscordError> {
+        let url = format!("{API}/channels/{}/messages", self.channel);
+        let body = json!({ "content": content });
+        let message: Object =
+            self.read(|| self.authorize(self.agent.post(&url)).send_json(&body))?;
+        message.id()
+    }
+
+    /// Replaces the content of the bot's message `message`.
+    pub fn edit(&self, message: u64, content: &str) -> Result<(), DiscordError> {
+        let url = format!("{API}/channels/{}/messages/{message}", self.channel);
+        let body = json!({ "content": content });
+        self.send(|| self.authorize(self.agent.patch(&url)).send_json(&body))
+            .map(drop)
+    }
+
+    /// Reacts to `message` with `emoji`, as the bot.
+    pub fn react(&self, message: u64, emoji: &str) -> Result<(), DiscordError> {
+        let url = format!("{}/@me", self.reactions_url(message, emoji));
+        self.send(|| self.authorize(self.agent.put(&url)).send_empty())
+            .map(drop)
+    }
+
+    /// Gets the ids of the users who reacted to `message` with `emoji`, up
+    /// to the first 100.
+    pub fn reactions(&self, message: u64, emoji: &str) -> Result<Vec<u64>, DiscordError> {
+        let url = format!("{}?limit=100", self.reactions_url(message, emoji));
+        let users: Vec<Object> = self.read(|| self.authorize(self.agent.get(&url)).call())?;
+        users.iter().map(Object::id).collect()
+    }
+
+    /// Removes every reaction from `message`. Needs the Manage Messages
+    /// permission in the channel.
+    pub fn clear_reactions(&self, message: u64) -> Result<(), DiscordError> {
+        let url = format!(
+            "{API}/channels/{}/messages/{message}/reactions",
+            self.channel
+        );
+        self.send(|| self.authorize(self.agent.delete(&url)).call())
+            .map(drop)
+    }
+
+    fn reactions_url(&self, message: u64, emoji: &str) -> String {
+        format!(
+            "{API}/channels/{}/messages/{message}/reactions/{}",
+            self.channel,
+            percent_encode(emoji)
+        )
+    }
+
+    /// Signs `request` in as the bot.
+    fn authorize<B>(&self, request: RequestBuilder<B>) -> RequestBuilder<B> {
+        request.header("Authorization", &self.authorization)
+    }
+
+    /// Sends a request and reads the JSON Discord answers with.
+    fn read<T: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> Result<Response<Body>, ureq::Error>,
+    ) -> Result<T, DiscordError> {
+        Ok(self.send(request)?.body_mut().read_json()?)
+    }
+
+    /// Sends a request, waiting and sending it again for as long as Discord
+    /// asks to slow down.
+    fn send(
+        &self,
+        request: impl Fn() -> Result<Response<Body>, ureq::Error>,
+    ) -> Result<Response<Body>, DiscordError> {
+        for _ in 0..=MAX_RETRIES {
+            let mut response = request()?;
+            let status = response.status();
+
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                let limit: RateLimit = response.body_mut().read_json()?;
+                sleep(Duration::from_secs_f64(limit.retry_after.clamp(0.0, 60.0)));
+                continue;
+            }
+
+            if !status.is_success() {
+                let message = response.body_mut().read_to_string().unwrap_or_default();
+                return Err(DiscordError::Status(status.as_u16(), message));
+            }
+
+            return Ok(response);
+        }
+
+        Err(DiscordError::RateLimited)
+    }
+}
+
+/// Escapes `text` for a URL path, as emoji must be.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+//! A Discord bot that plays a communal, slow-paced game of Constrictor in a
+//! channel.
+//!
+//! ```sh
+//! DISCORD_TOKEN=... cargo run -p constrictor-discord -- --channel 123456789
+//! ```
+//!
+//! The board is posted as a grid of emoji, with an arrow reaction for each
+//! direction. Whoever is in the channel votes by reacting, and at the end of
+//! every turn the most popular direction is taken and the message edited to
+//! show the new board. Nothing moves during a turn nobody votes in.
+//!
+//! The bot needs permission to send messages, add reactions and manage
+//! messages in the channel, the last so it can clear the votes after each
+//! turn.
+
+mod board;
+mod discord;
+
+use std::{env, error::Error, thread::sleep, time::Duration};
+
+use clap::Parser;
+use constrictor_core::{
+    config::{GameConfig, WallStyle},
+    math::Direction,
+    models::{SnakeSimulation, TurnBatch},
+};
+
+use board::MAX_MESSAGE_LEN;
+use discord::{Discord, DiscordError};
+
+/// The reactions voted with, in the order they are added to the board.
+const ARROWS: [(&str, Direction); 4] = [
+    ("⬆️", Direction::Up),
+    ("➡️", Direction::Right),
+    ("⬇️", Direction::Down),
+    ("⬅️", Direction::Left),
+];
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Plays a communal game of Constrictor in a Discord channel"
+)]
+struct Cli {
+    /// Id of the channel to play in. The bot's token is read from the
+    /// DISCORD_TOKEN environment variable.
+    #[arg(long)]
+    channel: u64,
+
+    /// Seconds each turn lasts, and so how long there is to vote.
+    #[arg(long, default_value_t = 30)]
+    turn: u64,
+
+    /// Width of the board, in cells. A message only fits so many emoji.
+    #[arg(long, default_value_t = 10)]
+    width: u16,
+
+    /// Height of the board, in cells.
+    #[arg(long, default_value_t = 10)]
+    height: u16,
+
+    /// Seed every game is played with. Each game gets a random one if
+    /// omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Remove the walls, so the snake wraps around to the opposite side.
+    #[arg(long)]
+    wrap: bool,
+}
+
+impl Cli {
+    fn config(&self) -> GameConfig {
+        let mut config = GameConfig::default();
+        config.board.width = self.width;
+        config.board.height = self.height;
+        config.seed = self.seed;
+
+        if self.wrap {
+            config.walls = WallStyle::Wrap;
+        }
+
+        config
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let config = cli.config();
+    let turn = Duration::from_secs(cli.turn.max(1));
+
+    // Fail now rather than after joining the channel
+    let board = board::render(&config.build()?.view());
+    if board.chars().count() > MAX_MESSAGE_LEN {
+        return Err("the board is too big to fit in a Discord message; try a smaller one".into());
+    }
+
+    let token = env::var("DISCORD_TOKEN").map_err(|_| "DISCORD_TOKEN must be set")?;
+    let discord = Discord::new(&token, cli.channel);
+    let bot = discord.user_id()?;
+
+    loop {
+        let mut sim = config.build()?;
+        let message = discord.post(&board::render(&sim.view()))?;
+        add_arrows(&discord, message)?;
+
+        while sim.result().is_none() {
+            sleep(turn);
+
+            // A hiccup on Discord's side only costs a turn
+            if let Err(e) = play_turn(&discord, message, bot, &mut sim) {
+                eprintln!("Couldn't play the turn: {e}");
+            }
+        }
+
+        // Leave the result up for a turn before starting over
+        sleep(turn);
+    }
+}
+
+/// Counts the votes on `message` and, if anyone voted, takes a step and
+/// shows the new board with the votes cleared.
+fn play_turn(
+    discord: &Discord,
+    message: u64,
+    bot: u64,
+    sim: &mut SnakeSimulation,
+) -> Result<(), DiscordError> {
+    // Someone who reacted with more than one arrow only has the last counted
+    let mut votes = TurnBatch::new();
+    for (emoji, direction) in ARROWS {
+        for user in discord.reactions(message, emoji)? {
+            if user != bot {
+                votes.vote(user, direction);
+            }
+        }
+    }
+
+    if votes.votes() == 0 {
+        return Ok(());
+    }
+
+    votes.end_turn(sim);
+    discord.edit(message, &board::render(&sim.view()))?;
+    discord.clear_reactions(message)?;
+
+    match sim.result() {
+        Some(_) => Ok(()),
+        None => add_arrows(discord, message),
+    }
+}
+
+fn add_arrows(discord: &Discord, message: u64) -> Result<(), DiscordError> {
+    for (emoji, _) in ARROWS {
+        discord.react(message, emoji)?;
+    }
+
+    Ok(())
+}
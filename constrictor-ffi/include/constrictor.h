@@ -0,0 +1,182 @@
+#ifndef CONSTRICTOR_H
+#define CONSTRICTOR_H
+
+/* Generated by cbindgen from constrictor-ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The state of a simulation: still running, or how it ended.
+ */
+typedef enum ConstrictorStatus {
+  CONSTRICTOR_STATUS_RUNNING,
+  CONSTRICTOR_STATUS_HIT_WALL,
+  CONSTRICTOR_STATUS_HIT_SELF,
+  CONSTRICTOR_STATUS_WON,
+  CONSTRICTOR_STATUS_QUIT,
+  CONSTRICTOR_STATUS_LEFT_BOARD,
+  CONSTRICTOR_STATUS_HIT_SNAKE,
+  CONSTRICTOR_STATUS_LOST,
+  CONSTRICTOR_STATUS_OUT_OF_MOVES,
+  CONSTRICTOR_STATUS_TIME_EXPIRED,
+} ConstrictorStatus;
+
+/**
+ * A direction the snake can face. Passed across the boundary as a plain
+ * `uint32_t`, so a value outside the enum can't reach Rust as one.
+ */
+enum ConstrictorDirection
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  CONSTRICTOR_DIRECTION_UP,
+  CONSTRICTOR_DIRECTION_RIGHT,
+  CONSTRICTOR_DIRECTION_DOWN,
+  CONSTRICTOR_DIRECTION_LEFT,
+};
+#ifndef __cplusplus
+typedef uint32_t ConstrictorDirection;
+#endif // __cplusplus
+
+/**
+ * An opaque handle to a running simulation.
+ */
+typedef struct ConstrictorSim ConstrictorSim;
+
+/**
+ * A cell on the board.
+ */
+typedef struct ConstrictorPoint {
+  int32_t x;
+  int32_t y;
+} ConstrictorPoint;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a simulation on a `width` by `height` board, with all randomness
+ * derived from `seed`. When `wrap` is true the board has no walls. Returns
+ * null if the board is too small.
+ */
+struct ConstrictorSim *constrictor_sim_new(uint16_t width,
+                                           uint16_t height,
+                                           uint64_t seed,
+                                           bool wrap);
+
+/**
+ * Releases a simulation created by `constrictor_sim_new`.
+ *
+ * # Safety
+ * `sim` must be null or a pointer returned by [`constrictor_sim_new`] that
+ * has not already been freed.
+ */
+void constrictor_sim_free(struct ConstrictorSim *sim);
+
+/**
+ * Advances the simulation by one step and returns its status afterwards.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+enum ConstrictorStatus constrictor_sim_advance(struct ConstrictorSim *sim);
+
+/**
+ * Requests the snake turn towards `direction`, one of the
+ * [`ConstrictorDirection`] values, on the next step. Turning back on itself
+ * is ignored. Returns false, doing nothing, if `direction` isn't one of them.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+bool constrictor_sim_set_direction(struct ConstrictorSim *sim, uint32_t direction);
+
+/**
+ * Ends the simulation as if the player quit.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+void constrictor_sim_quit(struct ConstrictorSim *sim);
+
+/**
+ * Gets the status of the simulation.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+enum ConstrictorStatus constrictor_sim_status(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the number of steps the simulation has advanced.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+uint64_t constrictor_sim_tick(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the player's score. Unless combos are on, this is the number of
+ * pieces of food eaten.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+size_t constrictor_sim_score(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the width of the board, in cells.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+int32_t constrictor_sim_board_width(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the height of the board, in cells.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+int32_t constrictor_sim_board_height(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the position of the food.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+struct ConstrictorPoint constrictor_sim_food(const struct ConstrictorSim *sim);
+
+/**
+ * Gets the length of the snake.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+ */
+size_t constrictor_sim_snake_len(const struct ConstrictorSim *sim);
+
+/**
+ * Copies up to `capacity` cells of the snake's body, from head to tail, into
+ * `out`. Returns the full length of the snake, so callers can detect that
+ * `out` was too small.
+ *
+ * # Safety
+ * `sim` must be null or a live pointer returned by [`constrictor_sim_new`],
+ * and `out` must be null or valid for writes of `capacity` points.
+ */
+size_t constrictor_sim_snake_body(const struct ConstrictorSim *sim,
+                                  struct ConstrictorPoint *out,
+                                  size_t capacity);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CONSTRICTOR_H */
@@ -0,0 +1,292 @@
+//! A stable C ABI over `constrictor-core`, so the simulation can be embedded
+//! in C, C++, C# and other frontends. The matching header lives at
+//! `include/constrictor.h`.
+//!
+//! Simulations are handed out as opaque [`ConstrictorSim`] pointers, created
+//! with [`constrictor_sim_new`] and released with [`constrictor_sim_free`].
+//! Every other function takes such a pointer, and does nothing (returning a
+//! zeroed value) when given null.
+
+use std::ptr;
+
+use constrictor_core::{
+    config::{BoardSize, GameConfig, WallStyle},
+    math::{Direction, Vector2},
+    models::{DeathReason, QuitReason, SimulationResult, SnakeSimulation},
+};
+
+/// An opaque handle to a running simulation.
+pub struct ConstrictorSim(SnakeSimulation);
+
+/// A cell on the board.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConstrictorPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl From<&Vector2> for ConstrictorPoint {
+    fn from(value: &Vector2) -> Self {
+        Self {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+/// A direction the snake can face. Passed across the boundary as a plain
+/// `uint32_t`, so a value outside the enum can't reach Rust as one.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstrictorDirection {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl ConstrictorDirection {
+    /// Gets the [`Direction`] `value` stands for, if it is one of the
+    /// variants.
+    fn from_raw(value: u32) -> Option<Direction> {
+        const ALL: [(ConstrictorDirection, Direction); 4] = [
+            (ConstrictorDirection::Up, Direction::Up),
+            (ConstrictorDirection::Right, Direction::Right),
+            (ConstrictorDirection::Down, Direction::Down),
+            (ConstrictorDirection::Left, Direction::Left),
+        ];
+
+        ALL.into_iter()
+            .find(|(raw, _)| *raw as u32 == value)
+            .map(|(_, direction)| direction)
+    }
+}
+
+/// The state of a simulation: still running, or how it ended.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstrictorStatus {
+    Running,
+    HitWall,
+    HitSelf,
+    Won,
+    Quit,
+    LeftBoard,
+    HitSnake,
+    Lost,
+    OutOfMoves,
+    TimeExpired,
+}
+
+impl From<Option<&SimulationResult>> for ConstrictorStatus {
+    fn from(value: Option<&SimulationResult>) -> Self {
+        match value {
+            None => Self::Running,
+            Some(SimulationResult::Died(DeathReason::HitWall | DeathReason::Hazard)) => {
+                Self::HitWall
+            }
+            Some(SimulationResult::Died(DeathReason::HitSelf)) => Self::HitSelf,
+            Some(SimulationResult::Died(DeathReason::HitSnake(_) | DeathReason::HeadOn(_))) => {
+                Self::HitSnake
+            }
+            Some(SimulationResult::Lost) => Self::Lost,
+            Some(SimulationResult::Won) => Self::Won,
+            Some(SimulationResult::ManuallyTerminated(_)) => Self::Quit,
+            Some(SimulationResult::LeftBoard(_)) => Self::LeftBoard,
+            Some(SimulationResult::OutOfMoves) => Self::OutOfMoves,
+            Some(SimulationResult::TimeExpired) => Self::TimeExpired,
+        }
+    }
+}
+
+/// Creates a simulation on a `width` by `height` board, with all randomness
+/// derived from `seed`. When `wrap` is true the board has no walls. Returns
+/// null if the board is too small.
+#[unsafe(no_mangle)]
+pub extern "C" fn constrictor_sim_new(
+    width: u16,
+    height: u16,
+    seed: u64,
+    wrap: bool,
+) -> *mut ConstrictorSim {
+    let config = GameConfig {
+        board: BoardSize { width, height },
+        walls: if wrap {
+            WallStyle::Wrap
+        } else {
+            WallStyle::Solid
+        },
+        seed: Some(seed),
+        ..Default::default()
+    };
+
+    match config.build() {
+        Ok(sim) => Box::into_raw(Box::new(ConstrictorSim(sim))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Releases a simulation created by `constrictor_sim_new`.
+///
+/// # Safety
+/// `sim` must be null or a pointer returned by [`constrictor_sim_new`] that
+/// has not already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_free(sim: *mut ConstrictorSim) {
+    if !sim.is_null() {
+        // SAFETY: the caller guarantees `sim` came from `Box::into_raw` and is live.
+        drop(unsafe { Box::from_raw(sim) });
+    }
+}
+
+/// Advances the simulation by one step and returns its status afterwards.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_advance(sim: *mut ConstrictorSim) -> ConstrictorStatus {
+    // SAFETY: upheld by the caller.
+    match unsafe { sim.as_mut() } {
+        Some(sim) => sim.0.advance().into(),
+        None => ConstrictorStatus::Running,
+    }
+}
+
+/// Requests the snake turn towards `direction`, one of the
+/// [`ConstrictorDirection`] values, on the next step. Turning back on itself
+/// is ignored. Returns false, doing nothing, if `direction` isn't one of them.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_set_direction(
+    sim: *mut ConstrictorSim,
+    direction: u32,
+) -> bool {
+    // SAFETY: upheld by the caller.
+    let (Some(sim), Some(direction)) = (
+        unsafe { sim.as_mut() },
+        ConstrictorDirection::from_raw(direction),
+    ) else {
+        return false;
+    };
+
+    sim.0.change_player_move_direction(direction);
+    true
+}
+
+/// Ends the simulation as if the player quit.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_quit(sim: *mut ConstrictorSim) {
+    // SAFETY: upheld by the caller.
+    if let Some(sim) = unsafe { sim.as_mut() } {
+        sim.0.quit(QuitReason::UserQuit);
+    }
+}
+
+/// Gets the status of the simulation.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_status(sim: *const ConstrictorSim) -> ConstrictorStatus {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }
+        .and_then(|sim| sim.0.result())
+        .into()
+}
+
+/// Gets the number of steps the simulation has advanced.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_tick(sim: *const ConstrictorSim) -> u64 {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or(0, |sim| sim.0.tick())
+}
+
+/// Gets the player's score. Unless combos are on, this is the number of
+/// pieces of food eaten.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_score(sim: *const ConstrictorSim) -> usize {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or(0, |sim| sim.0.score())
+}
+
+/// Gets the width of the board, in cells.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_board_width(sim: *const ConstrictorSim) -> i32 {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or(0, |sim| sim.0.board().width())
+}
+
+/// Gets the height of the board, in cells.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_board_height(sim: *const ConstrictorSim) -> i32 {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or(0, |sim| sim.0.board().height())
+}
+
+/// Gets the position of the food.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_food(sim: *const ConstrictorSim) -> ConstrictorPoint {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or_else(Default::default, |sim| sim.0.food_position().into())
+}
+
+/// Gets the length of the snake.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_snake_len(sim: *const ConstrictorSim) -> usize {
+    // SAFETY: upheld by the caller.
+    unsafe { sim.as_ref() }.map_or(0, |sim| sim.0.snake().len())
+}
+
+/// Copies up to `capacity` cells of the snake's body, from head to tail, into
+/// `out`. Returns the full length of the snake, so callers can detect that
+/// `out` was too small.
+///
+/// # Safety
+/// `sim` must be null or a live pointer returned by [`constrictor_sim_new`],
+/// and `out` must be null or valid for writes of `capacity` points.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn constrictor_sim_snake_body(
+    sim: *const ConstrictorSim,
+    out: *mut ConstrictorPoint,
+    capacity: usize,
+) -> usize {
+    // SAFETY: upheld by the caller.
+    let Some(sim) = (unsafe { sim.as_ref() }) else {
+        return 0;
+    };
+
+    let snake = sim.0.snake();
+
+    if !out.is_null() {
+        for (i, segment) in snake.body_iter().take(capacity).enumerate() {
+            // SAFETY: the caller guarantees `out` has room for `capacity` points.
+            unsafe { out.add(i).write(segment.into()) };
+        }
+    }
+
+    snake.len()
+}
@@ -0,0 +1,47 @@
+use constrictor_core::models::Board;
+use pyo3::prelude::*;
+
+use crate::convert::{Point, from_point};
+
+/// The board a game takes place on.
+#[pyclass(name = "Board", module = "constrictor", frozen)]
+pub struct PyBoard(pub Board);
+
+#[pymethods]
+impl PyBoard {
+    #[getter]
+    fn width(&self) -> i32 {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> i32 {
+        self.0.height()
+    }
+
+    /// The inclusive lower and exclusive upper bound of the x-coordinates.
+    #[getter]
+    fn x_range(&self) -> (i32, i32) {
+        let range = self.0.x_range();
+        (range.start, range.end)
+    }
+
+    /// The inclusive lower and exclusive upper bound of the y-coordinates.
+    #[getter]
+    fn y_range(&self) -> (i32, i32) {
+        let range = self.0.y_range();
+        (range.start, range.end)
+    }
+
+    fn __contains__(&self, cell: Point) -> bool {
+        self.0.contains(&from_point(cell))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Board(width={}, height={})",
+            self.0.width(),
+            self.0.height()
+        )
+    }
+}
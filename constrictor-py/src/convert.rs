@@ -0,0 +1,57 @@
+use constrictor_core::{
+    math::{Direction, Vector2},
+    models::{DeathReason, QuitReason, SimulationResult},
+};
+use pyo3::{PyResult, exceptions::PyValueError};
+
+/// A [`Vector2`] as seen from Python.
+pub type Point = (i32, i32);
+
+pub const fn to_point(vector: &Vector2) -> Point {
+    (vector.x, vector.y)
+}
+
+pub const fn from_point((x, y): Point) -> Vector2 {
+    Vector2 { x, y }
+}
+
+/// Parses a direction name, ignoring case.
+pub fn parse_direction(name: &str) -> PyResult<Direction> {
+    match name.to_ascii_lowercase().as_str() {
+        "up" => Ok(Direction::Up),
+        "right" => Ok(Direction::Right),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown direction {name:?}, expected one of up, right, down or left"
+        ))),
+    }
+}
+
+pub const fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "up",
+        Direction::Right => "right",
+        Direction::Down => "down",
+        Direction::Left => "left",
+    }
+}
+
+pub const fn result_name(result: &SimulationResult) -> &'static str {
+    match result {
+        SimulationResult::Died(DeathReason::HitWall) => "hit_wall",
+        SimulationResult::Died(DeathReason::HitSelf) => "hit_self",
+        SimulationResult::Died(DeathReason::HitSnake(_)) => "hit_snake",
+        SimulationResult::Died(DeathReason::HeadOn(_)) => "head_on",
+        SimulationResult::Died(DeathReason::Hazard) => "hazard",
+        SimulationResult::ManuallyTerminated(QuitReason::UserQuit) => "quit",
+        SimulationResult::ManuallyTerminated(QuitReason::ConnectionLost) => "connection_lost",
+        SimulationResult::ManuallyTerminated(QuitReason::HostShutdown) => "host_shutdown",
+        SimulationResult::ManuallyTerminated(QuitReason::Error) => "error",
+        SimulationResult::Won => "won",
+        SimulationResult::Lost => "lost",
+        SimulationResult::LeftBoard(_) => "left_board",
+        SimulationResult::OutOfMoves => "out_of_moves",
+        SimulationResult::TimeExpired => "time_expired",
+    }
+}
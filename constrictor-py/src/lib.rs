@@ -0,0 +1,24 @@
+//! Python bindings for `constrictor-core`, built as the `constrictor` module.
+//!
+//! Positions are exposed as `(x, y)` tuples and directions as the strings
+//! `"up"`, `"right"`, `"down"` and `"left"`, so scripts never need to touch a
+//! wrapper type just to steer the snake.
+
+mod board;
+mod convert;
+mod simulation;
+mod snake;
+
+use pyo3::prelude::*;
+
+pub use board::PyBoard;
+pub use simulation::PySimulation;
+pub use snake::PySnake;
+
+#[pymodule]
+fn constrictor(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySimulation>()?;
+    m.add_class::<PyBoard>()?;
+    m.add_class::<PySnake>()?;
+    Ok(())
+}
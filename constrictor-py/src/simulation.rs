@@ -0,0 +1,128 @@
+use constrictor_core::{
+    config::{BoardSize, GameConfig, WallStyle},
+    models::{Rules, SnakeSimulation},
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    PyBoard, PySnake,
+    convert::{Point, from_point, parse_direction, result_name, to_point},
+};
+
+/// A game of Snake, driven one step at a time.
+///
+/// ```python
+/// import constrictor
+///
+/// sim = constrictor.Simulation(width=16, height=16, seed=7)
+/// while sim.result is None:
+///     sim.step("up" if sim.snake.head[1] > 2 else None)
+/// ```
+#[pyclass(name = "Simulation", module = "constrictor")]
+pub struct PySimulation {
+    config: GameConfig,
+    sim: SnakeSimulation,
+}
+
+#[pymethods]
+impl PySimulation {
+    #[new]
+    #[pyo3(signature = (width = 32, height = 32, seed = None, wrap = false, fog = None))]
+    fn new(
+        width: u16,
+        height: u16,
+        seed: Option<u64>,
+        wrap: bool,
+        fog: Option<u32>,
+    ) -> PyResult<Self> {
+        let config = GameConfig {
+            board: BoardSize { width, height },
+            walls: if wrap {
+                WallStyle::Wrap
+            } else {
+                WallStyle::Solid
+            },
+            seed,
+            rules: Rules {
+                fog,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let sim = build(&config)?;
+        Ok(Self { config, sim })
+    }
+
+    /// Turns towards `direction` (if given) and advances one step. Returns the
+    /// result of the game once it is over, otherwise `None`.
+    #[pyo3(signature = (direction = None))]
+    fn step(&mut self, direction: Option<&str>) -> PyResult<Option<&'static str>> {
+        if let Some(direction) = direction {
+            self.sim
+                .change_player_move_direction(parse_direction(direction)?);
+        }
+
+        Ok(self.sim.advance().map(result_name))
+    }
+
+    /// Starts a new game with the same setup. The original seed is reused
+    /// unless a new one is given.
+    #[pyo3(signature = (seed = None))]
+    fn reset(&mut self, seed: Option<u64>) -> PyResult<()> {
+        if seed.is_some() {
+            self.config.seed = seed;
+        }
+
+        self.sim = build(&self.config)?;
+        Ok(())
+    }
+
+    /// Whether moving towards `direction` on the next step would be fatal.
+    fn is_fatal_move(&self, direction: &str) -> PyResult<bool> {
+        Ok(self.sim.is_fatal_move(parse_direction(direction)?))
+    }
+
+    /// Whether the player can see the cell at `point`, which is only those
+    /// near its head when the board is fogged.
+    fn is_visible(&self, point: Point) -> bool {
+        self.sim.view().is_visible(&from_point(point))
+    }
+
+    #[getter]
+    fn board(&self) -> PyBoard {
+        PyBoard(self.sim.board().clone())
+    }
+
+    #[getter]
+    fn snake(&self) -> PySnake {
+        PySnake(self.sim.snake().clone())
+    }
+
+    #[getter]
+    fn food(&self) -> Point {
+        to_point(self.sim.food_position())
+    }
+
+    #[getter]
+    fn score(&self) -> usize {
+        self.sim.score()
+    }
+
+    #[getter]
+    fn tick(&self) -> u64 {
+        self.sim.tick()
+    }
+
+    /// The result of the game once it is over, otherwise `None`.
+    #[getter]
+    fn result(&self) -> Option<&'static str> {
+        self.sim.result().map(result_name)
+    }
+}
+
+fn build(config: &GameConfig) -> PyResult<SnakeSimulation> {
+    config
+        .build()
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
@@ -0,0 +1,49 @@
+use constrictor_core::models::Snake;
+use pyo3::prelude::*;
+
+use crate::convert::{Point, direction_name, from_point, to_point};
+
+/// A snapshot of a snake.
+#[pyclass(name = "Snake", module = "constrictor", frozen)]
+pub struct PySnake(pub Snake);
+
+#[pymethods]
+impl PySnake {
+    #[getter]
+    fn head(&self) -> Point {
+        to_point(self.0.head())
+    }
+
+    #[getter]
+    fn tail(&self) -> Point {
+        to_point(self.0.tail())
+    }
+
+    /// Every cell of the snake, from head to tail.
+    #[getter]
+    fn body(&self) -> Vec<Point> {
+        self.0.body_iter().map(to_point).collect()
+    }
+
+    #[getter]
+    fn facing(&self) -> &'static str {
+        direction_name(self.0.facing())
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __contains__(&self, cell: Point) -> bool {
+        self.0.contains(&from_point(cell))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Snake(head={:?}, len={}, facing={:?})",
+            self.head(),
+            self.0.len(),
+            self.facing()
+        )
+    }
+}
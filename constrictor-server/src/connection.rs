@@ -0,0 +1,103 @@
+use std::{io, net::TcpStream, time::Duration};
+
+use constrictor_core::net::{ClientMessage, ServerMessage};
+use tungstenite::{Error as WsError, Message, WebSocket};
+
+/// How long a new client gets to finish the WebSocket handshake. The server
+/// waits for it, so this holds up every game while it lasts.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A client's WebSocket. Reads and writes never block, so one slow client
+/// can't hold up the games; a client that falls too far behind is dropped.
+pub struct Connection {
+    socket: WebSocket<TcpStream>,
+    connected: bool,
+
+    /// Messages put back to be received again, for whoever handles the
+    /// client next.
+    unread: Vec<ClientMessage>,
+}
+
+impl Connection {
+    /// Completes the WebSocket handshake on a newly accepted `stream`.
+    pub fn accept(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+        let socket = tungstenite::accept(stream).map_err(io::Error::other)?;
+        socket.get_ref().set_read_timeout(None)?;
+        socket.get_ref().set_nonblocking(true)?;
+
+        Ok(Self {
+            socket,
+            connected: true,
+            unread: Vec::new(),
+        })
+    }
+
+    /// Whether the client is still there. Once this is `false`, the
+    /// connection can be dropped.
+    pub const fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Takes every message the client has sent since the last call. Frames
+    /// that aren't valid messages are answered with a
+    /// [`ServerMessage::Error`] and skipped.
+    pub fn receive(&mut self) -> Vec<ClientMessage> {
+        let mut messages = std::mem::take(&mut self.unread);
+
+        while self.connected {
+            let text = match self.socket.read() {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => {
+                    self.connected = false;
+                    break;
+                }
+                Ok(_) => continue,
+                Err(WsError::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(_) => {
+                    self.connected = false;
+                    break;
+                }
+            };
+
+            match serde_json::from_str(text.as_str()) {
+                Ok(message) => messages.push(message),
+                Err(e) => self.send(&ServerMessage::Error {
+                    message: format!("invalid message: {e}"),
+                }),
+            }
+        }
+
+        // Replies to pings are queued by reading, and sent from here
+        self.flush();
+        messages
+    }
+
+    /// Puts `messages` back to be received again, ahead of anything new.
+    pub fn put_back(&mut self, messages: Vec<ClientMessage>) {
+        self.unread.splice(0..0, messages);
+    }
+
+    /// Queues `message` for the client and sends as much as it will take.
+    pub fn send(&mut self, message: &ServerMessage) {
+        let Ok(text) = serde_json::to_string(message) else {
+            return;
+        };
+
+        match self.socket.write(Message::text(text)) {
+            Ok(()) => self.flush(),
+            Err(WsError::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(_) => self.connected = false,
+        }
+    }
+
+    fn flush(&mut self) {
+        match self.socket.flush() {
+            Ok(()) => {}
+            Err(WsError::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(_) => self.connected = false,
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::{mem, time::Instant};
+
+use constrictor_core::{
+    bots::{Controller, FloodFillBot},
+    config::{ConfigError, GameConfig},
+    models::{Clock, SnakeSimulation, StopAt},
+    net::{ClientMessage, Delta, PROTOCOL_VERSION, ServerMessage, Snapshot},
+    render::FrameBuffer,
+};
+
+use crate::connection::Connection;
+
+/// A named room whose players share one game. There is a seat for each snake
+/// in the game, taken by players in the order they join, and everyone after
+/// that watches. A seat nobody has taken is steered by a bot until someone
+/// does, and when a player leaves, whoever has been watching longest takes
+/// their seat.
+pub struct Lobby {
+    name: String,
+    config: GameConfig,
+
+    /// Whether the config came with a seed. If not, every game gets a new
+    /// one.
+    fixed_seed: bool,
+
+    sim: SnakeSimulation,
+    clock: Clock,
+    previous: Instant,
+
+    /// Everyone in the lobby, in the order they joined.
+    players: Vec<Player>,
+}
+
+/// Someone in a [`Lobby`].
+struct Player {
+    connection: Connection,
+
+    /// The snake the player steers, or [`None`] if they are watching.
+    seat: Option<usize>,
+
+    /// The game as the player was last told it looks, for working out what
+    /// to send them next.
+    frame: FrameBuffer,
+}
+
+impl Player {
+    /// Gets the whole game as it stands for the player, which the
+    /// [`Delta`]s sent to them next build on.
+    fn snapshot(&mut self, sim: &SnakeSimulation) -> Snapshot {
+        let view = sim.view_as(self.seat.unwrap_or(0));
+        self.frame = view.frame();
+        Snapshot::from(&view)
+    }
+
+    /// Gets how the game has changed for the player since they were last
+    /// told.
+    fn delta(&mut self, sim: &SnakeSimulation) -> Delta {
+        Delta::since(&mut self.frame, &sim.view_as(self.seat.unwrap_or(0)))
+    }
+}
+
+impl Lobby {
+    pub fn new(name: String, mut config: GameConfig) -> Result<Self, ConfigError> {
+        let fixed_seed = config.seed.is_some();
+        config.seed.get_or_insert_with(rand::random);
+        let sim = config.build()?;
+
+        Ok(Self {
+            name,
+            config,
+            fixed_seed,
+            sim,
+            clock: Clock::new(),
+            previous: Instant::now(),
+            players: Vec::new(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// Adds `connection` to the lobby in the first free seat, if there is
+    /// one, and sends them the game as it stands.
+    pub fn join(&mut self, connection: Connection) {
+        let mut player = Player {
+            connection,
+            seat: self.free_seats().next(),
+            frame: FrameBuffer::new(self.sim.view().board()),
+        };
+
+        let snapshot = player.snapshot(&self.sim);
+        player.connection.send(&ServerMessage::Joined {
+            lobby: self.name.clone(),
+            version: PROTOCOL_VERSION,
+            seat: player.seat,
+            snapshot,
+        });
+
+        self.players.push(player);
+    }
+
+    /// Acts on what the players have sent, steps the game as far as the time
+    /// since the last update allows, and sends everyone what changed. Gives
+    /// back the players who left, to join another lobby.
+    pub fn update(&mut self) -> Result<Vec<Connection>, ConfigError> {
+        let mut leavers = Vec::new();
+        for index in 0..self.players.len() {
+            let mut messages = self.players[index].connection.receive().into_iter();
+            while let Some(message) = messages.next() {
+                if message == ClientMessage::Leave {
+                    // Whatever followed is for wherever the player goes next
+                    self.players[index].connection.put_back(messages.collect());
+                    leavers.push(index);
+                    break;
+                }
+
+                self.handle(index, message)?;
+            }
+        }
+
+        let left = self.remove(&leavers);
+
+        let now = Instant::now();
+        self.clock.elapse(now - self.previous);
+        self.previous = now;
+
+        // One step at a time, so every tick's changes go out on their own
+        loop {
+            let empty: Vec<_> = self.free_seats().collect();
+            for seat in empty {
+                FloodFillBot.steer(&mut self.sim, seat);
+            }
+
+            let tick = self.sim.tick() + 1;
+            if self.sim.run(&mut StopAt::new(&mut self.clock, tick)) == 0 {
+                break;
+            }
+
+            for player in &mut self.players {
+                let delta = player.delta(&self.sim);
+                player.connection.send(&ServerMessage::Delta(delta));
+            }
+        }
+
+        Ok(left)
+    }
+
+    fn handle(&mut self, index: usize, message: ClientMessage) -> Result<(), ConfigError> {
+        let seat = self.players[index].seat;
+
+        match message {
+            ClientMessage::Join { .. } => {
+                self.players[index].connection.send(&ServerMessage::Error {
+                    message: format!("already in lobby `{}`", self.name),
+                })
+            }
+            // The server's game is the one that counts, so turns take effect
+            // on its current tick, whichever the client made them on
+            ClientMessage::Input { direction, .. } if let Some(seat) = seat => {
+                self.sim.change_move_direction(seat, direction)
+            }
+            ClientMessage::Input { .. } => {
+                self.players[index].connection.send(&ServerMessage::Error {
+                    message: "spectators can't steer".to_string(),
+                })
+            }
+            ClientMessage::Restart if self.sim.result().is_some() => {
+                if !self.fixed_seed {
+                    self.config.seed = Some(rand::random());
+                }
+
+                self.sim = self.config.build()?;
+                self.clock = Clock::new();
+                for player in &mut self.players {
+                    let snapshot = player.snapshot(&self.sim);
+                    player.connection.send(&ServerMessage::Started { snapshot });
+                }
+            }
+            ClientMessage::Restart => self.players[index].connection.send(&ServerMessage::Error {
+                message: "the game isn't over yet".to_string(),
+            }),
+            ClientMessage::Resync => {
+                let player = &mut self.players[index];
+                let snapshot = player.snapshot(&self.sim);
+                player.connection.send(&ServerMessage::Snapshot(snapshot));
+            }
+            // Handled by `update`, which has to take the player out
+            ClientMessage::Leave => {}
+        }
+
+        Ok(())
+    }
+
+    /// Takes out the players at `leavers` and drops those who have gone,
+    /// handing the seats they leave to whoever has been watching longest.
+    /// Gives back the players who left.
+    fn remove(&mut self, leavers: &[usize]) -> Vec<Connection> {
+        let mut left = Vec::new();
+        for (index, mut player) in mem::take(&mut self.players).into_iter().enumerate() {
+            if !player.connection.is_connected() {
+                continue;
+            }
+
+            if leavers.contains(&index) {
+                player.connection.send(&ServerMessage::Left {
+                    lobby: self.name.clone(),
+                });
+                left.push(player.connection);
+            } else {
+                self.players.push(player);
+            }
+        }
+
+        let free: Vec<_> = self.free_seats().collect();
+        let watching = self
+            .players
+            .iter_mut()
+            .filter(|player| player.seat.is_none());
+        for (player, seat) in watching.zip(free) {
+            player.seat = Some(seat);
+
+            // They see the game from their own snake now
+            let snapshot = player.snapshot(&self.sim);
+            player
+                .connection
+                .send(&ServerMessage::Seated { seat: Some(seat) });
+            player.connection.send(&ServerMessage::Snapshot(snapshot));
+        }
+
+        left
+    }
+
+    /// Returns an [`Iterator`] over the seats nobody has taken, lowest
+    /// first.
+    fn free_seats(&self) -> impl Iterator<Item = usize> + use<'_> {
+        (0..self.sim.snakes().len())
+            .filter(|seat| !self.players.iter().any(|player| player.seat == Some(*seat)))
+    }
+}
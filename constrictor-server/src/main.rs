@@ -0,0 +1,161 @@
+//! A multiplayer server for Constrictor, speaking JSON over WebSockets.
+//!
+//! ```sh
+//! cargo run -p constrictor-server -- --listen 127.0.0.1:9001
+//! ```
+//!
+//! Clients connect, send `{"type": "join", "lobby": "<name>", "version": 1}`,
+//! and are then sent every tick of that lobby's game. The first `--players`
+//! clients in a lobby each steer a snake, and the rest watch. See
+//! [`constrictor_core::net`] for the rest of the messages.
+
+mod connection;
+mod lobby;
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    error::Error,
+    io,
+    net::{SocketAddr, TcpListener},
+    thread::sleep,
+    time::Duration,
+};
+
+use clap::Parser;
+use constrictor_core::{
+    config::{GameConfig, WallStyle},
+    models::Appearance,
+    net::{ClientMessage, PROTOCOL_VERSION, ServerMessage},
+};
+
+use connection::Connection;
+use lobby::Lobby;
+
+/// The longest the server goes without checking for new connections and
+/// messages.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The most snakes a lobby's game can have.
+const MAX_PLAYERS: u8 = 8;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Hosts Constrictor games for WebSocket clients")]
+struct Cli {
+    /// Address to accept connections on.
+    #[arg(long, default_value = "127.0.0.1:9001")]
+    listen: SocketAddr,
+
+    /// Width of every lobby's board, in cells.
+    #[arg(long)]
+    width: Option<u16>,
+
+    /// Height of every lobby's board, in cells.
+    #[arg(long)]
+    height: Option<u16>,
+
+    /// Seed every game is played with. Each game gets a random one if
+    /// omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Remove the walls, so snakes wrap around to the opposite side.
+    #[arg(long)]
+    wrap: bool,
+
+    /// Number of snakes in every lobby's game, each steered by one player.
+    /// A bot steers any snake without a player.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=i64::from(MAX_PLAYERS)))]
+    players: u8,
+}
+
+impl Cli {
+    fn config(&self) -> GameConfig {
+        let mut config = GameConfig::default();
+        config.board.width = self.width.unwrap_or(config.board.width);
+        config.board.height = self.height.unwrap_or(config.board.height);
+        config.seed = self.seed;
+        config.opponents = (1..self.players)
+            .map(|seat| Appearance {
+                color: seat.into(),
+                ..Default::default()
+            })
+            .collect();
+
+        if self.wrap {
+            config.walls = WallStyle::Wrap;
+        }
+
+        config
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let config = cli.config();
+
+    // Fail now rather than when the first lobby opens
+    config.build()?;
+
+    let listener = TcpListener::bind(cli.listen)?;
+    listener.set_nonblocking(true)?;
+
+    // Connected clients that haven't joined a lobby yet
+    let mut pending: Vec<Connection> = Vec::new();
+    let mut lobbies: HashMap<String, Lobby> = HashMap::new();
+
+    loop {
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => match Connection::accept(stream) {
+                    Ok(connection) => pending.push(connection),
+                    Err(e) => eprintln!("Handshake failed: {e}"),
+                },
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let mut waiting = Vec::with_capacity(pending.len());
+        for mut connection in pending.drain(..) {
+            let mut joined = None;
+            for message in connection.receive() {
+                match message {
+                    ClientMessage::Join { version, .. } if version != PROTOCOL_VERSION => {
+                        connection.send(&ServerMessage::Error {
+                            message: format!(
+                                "this server speaks protocol version {PROTOCOL_VERSION}, \
+                                 not {version}"
+                            ),
+                        });
+                    }
+                    ClientMessage::Join { lobby, .. } if joined.is_none() => joined = Some(lobby),
+                    ClientMessage::Join { .. } => {}
+                    _ => connection.send(&ServerMessage::Error {
+                        message: "join a lobby first".to_string(),
+                    }),
+                }
+            }
+
+            match joined {
+                Some(name) => {
+                    let lobby = match lobbies.entry(name.clone()) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => entry.insert(Lobby::new(name, config.clone())?),
+                    };
+
+                    lobby.join(connection);
+                }
+                None if connection.is_connected() => waiting.push(connection),
+                None => {}
+            }
+        }
+        pending = waiting;
+
+        for lobby in lobbies.values_mut() {
+            pending.extend(lobby.update()?);
+        }
+
+        lobbies.retain(|_, lobby| !lobby.is_empty());
+        sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,218 @@
+//! JavaScript bindings for `constrictor-core`, built with `wasm-bindgen`.
+//!
+//! ```sh
+//! wasm-pack build constrictor-wasm --target web
+//! ```
+//!
+//! ```js
+//! import init, { Game } from "./pkg/constrictor_wasm.js";
+//!
+//! await init();
+//! const game = new Game(32, 32, Math.random() * 2 ** 32, false);
+//! game.turn("up");
+//! game.step();
+//! console.log(game.state().snake);
+//! ```
+
+use constrictor_core::{
+    config::{BoardSize, GameConfig, WallStyle},
+    math::{Direction, Vector2},
+    models::{DeathReason, QuitReason, SimulationResult, SnakeSimulation},
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = r#"
+export type Direction = "up" | "right" | "down" | "left";
+
+export type GameResult =
+    | "hit_wall"
+    | "hit_self"
+    | "hit_snake"
+    | "head_on"
+    | "won"
+    | "lost"
+    | "left_board"
+    | "out_of_moves"
+    | "time_expired"
+    | "quit"
+    | "connection_lost"
+    | "host_shutdown"
+    | "error";
+
+export interface Point {
+    x: number;
+    y: number;
+}
+
+export interface TickState {
+    tick: number;
+    score: number;
+    board: { width: number; height: number };
+    /** From head to tail. */
+    snake: Point[];
+    facing: Direction;
+    /** Only the food the snake can see, when the board is fogged. */
+    food: Point[];
+    result: GameResult | null;
+}
+"#;
+
+/// A game of Snake, driven one step at a time from JavaScript.
+#[wasm_bindgen]
+pub struct Game {
+    config: GameConfig,
+    sim: SnakeSimulation,
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Creates a game on a `width` by `height` board, with all randomness
+    /// derived from `seed`. When `wrap` is true the board has no walls.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u16, height: u16, seed: u32, wrap: bool) -> Result<Game, JsError> {
+        let config = GameConfig {
+            board: BoardSize { width, height },
+            walls: if wrap {
+                WallStyle::Wrap
+            } else {
+                WallStyle::Solid
+            },
+            seed: Some(seed.into()),
+            ..Default::default()
+        };
+
+        let sim = config.build()?;
+        Ok(Self { config, sim })
+    }
+
+    /// Requests the snake turn towards `direction` on the next step.
+    pub fn turn(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Direction")] direction: &str,
+    ) -> Result<(), JsError> {
+        self.sim
+            .change_player_move_direction(parse_direction(direction)?);
+        Ok(())
+    }
+
+    /// Ends the game as if the player quit.
+    pub fn quit(&mut self) {
+        self.sim.quit(QuitReason::UserQuit);
+    }
+
+    /// Advances one step. Returns the result of the game once it is over,
+    /// otherwise `undefined`.
+    #[wasm_bindgen(unchecked_return_type = "GameResult | undefined")]
+    pub fn step(&mut self) -> Option<String> {
+        self.sim
+            .advance()
+            .map(|result| result_name(result).to_owned())
+    }
+
+    /// Starts a new game with the same setup. The original seed is reused
+    /// unless a new one is given.
+    pub fn reset(&mut self, seed: Option<u32>) -> Result<(), JsError> {
+        if let Some(seed) = seed {
+            self.config.seed = Some(seed.into());
+        }
+
+        self.sim = self.config.build()?;
+        Ok(())
+    }
+
+    /// Exports everything needed to draw the current state of the game.
+    #[wasm_bindgen(unchecked_return_type = "TickState")]
+    pub fn state(&self) -> Result<JsValue, JsError> {
+        let view = self.sim.view();
+        let player = view.player();
+
+        let state = TickState {
+            tick: view.tick(),
+            score: view.score(),
+            board: BoardState {
+                width: view.board().width(),
+                height: view.board().height(),
+            },
+            snake: player.body().copied().collect(),
+            facing: direction_name(player.facing()),
+            food: view
+                .food()
+                .filter(|food| view.is_visible(food))
+                .copied()
+                .collect(),
+            result: view.result().map(result_name),
+        };
+
+        Ok(serde_wasm_bindgen::to_value(&state)?)
+    }
+}
+
+/// Accessors for Rust frontends that link these bindings directly rather than
+/// going through JavaScript.
+impl Game {
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn simulation(&self) -> &SnakeSimulation {
+        &self.sim
+    }
+}
+
+/// Mirrors the `TickState` TypeScript interface.
+#[derive(Serialize)]
+struct TickState {
+    tick: u64,
+    score: usize,
+    board: BoardState,
+    snake: Vec<Vector2>,
+    facing: &'static str,
+    food: Vec<Vector2>,
+    result: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct BoardState {
+    width: i32,
+    height: i32,
+}
+
+fn parse_direction(name: &str) -> Result<Direction, JsError> {
+    match name {
+        "up" => Ok(Direction::Up),
+        "right" => Ok(Direction::Right),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        _ => Err(JsError::new(&format!("unknown direction {name:?}"))),
+    }
+}
+
+const fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "up",
+        Direction::Right => "right",
+        Direction::Down => "down",
+        Direction::Left => "left",
+    }
+}
+
+const fn result_name(result: &SimulationResult) -> &'static str {
+    match result {
+        SimulationResult::Died(DeathReason::HitWall) => "hit_wall",
+        SimulationResult::Died(DeathReason::HitSelf) => "hit_self",
+        SimulationResult::Died(DeathReason::HitSnake(_)) => "hit_snake",
+        SimulationResult::Died(DeathReason::HeadOn(_)) => "head_on",
+        SimulationResult::Died(DeathReason::Hazard) => "hazard",
+        SimulationResult::Won => "won",
+        SimulationResult::Lost => "lost",
+        SimulationResult::LeftBoard(_) => "left_board",
+        SimulationResult::OutOfMoves => "out_of_moves",
+        SimulationResult::TimeExpired => "time_expired",
+        SimulationResult::ManuallyTerminated(QuitReason::UserQuit) => "quit",
+        SimulationResult::ManuallyTerminated(QuitReason::ConnectionLost) => "connection_lost",
+        SimulationResult::ManuallyTerminated(QuitReason::HostShutdown) => "host_shutdown",
+        SimulationResult::ManuallyTerminated(QuitReason::Error) => "error",
+    }
+}
@@ -0,0 +1,236 @@
+//! A minimal in-browser frontend for Constrictor, drawing to a `<canvas>`.
+//!
+//! ```sh
+//! wasm-pack build constrictor-web --target web --out-dir www/pkg
+//! python3 -m http.server -d constrictor-web/www
+//! ```
+//!
+//! The page must contain a `<canvas id="constrictor">`. An optional element
+//! with id `constrictor-status` receives the score and the game result.
+
+use std::{cell::RefCell, rc::Rc};
+
+use constrictor_core::{
+    math::Vector2,
+    models::{DeathReason, SimulationResult, Terrain},
+    render::Cell,
+};
+use constrictor_wasm::Game;
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, Element, KeyboardEvent, Window};
+
+const BOARD_WIDTH: u16 = 32;
+const BOARD_HEIGHT: u16 = 32;
+const CELL_SIZE: f64 = 16.0;
+
+const BACKGROUND: &str = "#1b1b1b";
+const SNAKE: &str = "#4caf50";
+const SNAKE_HEAD: &str = "#8bc34a";
+const FOOD: &str = "#e53935";
+const PRACTICE_FOOD: &str = "#9e9e9e";
+const OBSTACLE: &str = "#616161";
+const POWER_UP: &str = "#29b6f6";
+const HAZARD: &str = "#4e2323";
+const PORTAL: &str = "#ab47bc";
+const STICKY: &str = "#4e342e";
+const BOOST: &str = "#004d40";
+const FOG: &str = "#0d0d0d";
+const WALL: &str = "#424242";
+
+/// Entry point, run by the generated JavaScript glue as soon as the module
+/// is instantiated.
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("no global window")?;
+    let document = window.document().ok_or("no document")?;
+
+    let canvas = document
+        .get_element_by_id("constrictor")
+        .ok_or("missing <canvas id=\"constrictor\">")?
+        .dyn_into::<web_sys::HtmlCanvasElement>()?;
+    canvas.set_width((f64::from(BOARD_WIDTH) * CELL_SIZE) as u32);
+    canvas.set_height((f64::from(BOARD_HEIGHT) * CELL_SIZE) as u32);
+
+    let context = canvas
+        .get_context("2d")?
+        .ok_or("canvas has no 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    let app = Rc::new(RefCell::new(App {
+        game: Game::new(BOARD_WIDTH, BOARD_HEIGHT, random_seed(), false)?,
+        context,
+        status: document.get_element_by_id("constrictor-status"),
+    }));
+
+    app.borrow().draw();
+    listen_for_keys(&window, app.clone())?;
+    schedule_tick(window, app)
+}
+
+struct App {
+    game: Game,
+    context: CanvasRenderingContext2d,
+    status: Option<Element>,
+}
+
+impl App {
+    /// Acts on `key`, returning whether the game has a use for it.
+    fn handle_key(&mut self, key: &str) -> Result<bool, JsValue> {
+        let direction = match key {
+            "ArrowUp" | "w" => "up",
+            "ArrowRight" | "d" => "right",
+            "ArrowDown" | "s" => "down",
+            "ArrowLeft" | "a" => "left",
+            "r" => {
+                self.game.reset(Some(random_seed()))?;
+                self.draw();
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+
+        self.game.turn(direction)?;
+        Ok(true)
+    }
+
+    fn draw(&self) {
+        let view = self.game.simulation().view();
+        let ctx = &self.context;
+
+        ctx.set_fill_style_str(BACKGROUND);
+        ctx.fill_rect(
+            0.0,
+            0.0,
+            f64::from(view.board().width()) * CELL_SIZE,
+            f64::from(view.board().height()) * CELL_SIZE,
+        );
+
+        // Cells a mask leaves unplayable are part of the wall
+        let board = view.board();
+        if board.mask().is_some() {
+            ctx.set_fill_style_str(WALL);
+            for y in board.y_range() {
+                for x in board.x_range() {
+                    let cell = Vector2 { x, y };
+                    if !board.contains(&cell) {
+                        fill_cell(ctx, &cell);
+                    }
+                }
+            }
+        }
+
+        for (point, cell) in view.frame().cells() {
+            let color = match cell {
+                Cell::Empty => continue,
+                Cell::Food => FOOD,
+                Cell::PracticeFood => PRACTICE_FOOD,
+                Cell::Obstacle => OBSTACLE,
+                Cell::PowerUp(_) => POWER_UP,
+                Cell::Portal { .. } => PORTAL,
+                Cell::Hazard => HAZARD,
+                Cell::Terrain(Terrain::Sticky) => STICKY,
+                Cell::Terrain(Terrain::Boost) => BOOST,
+                Cell::Hidden => FOG,
+                Cell::SnakeHead { .. } => SNAKE_HEAD,
+                Cell::SnakeBody { .. } => SNAKE,
+            };
+
+            ctx.set_fill_style_str(color);
+            fill_cell(ctx, &point);
+        }
+
+        if let Some(status) = &self.status {
+            let text = match view.result() {
+                Some(result) => format!(
+                    "{} Final score: {}. Press R to restart",
+                    describe(result),
+                    view.score()
+                ),
+                None => format!("Score: {}", view.score()),
+            };
+            status.set_text_content(Some(&text));
+        }
+    }
+}
+
+fn describe(result: &SimulationResult) -> &'static str {
+    match result {
+        SimulationResult::Died(DeathReason::HitWall) => "You hit a wall.",
+        SimulationResult::Died(DeathReason::HitSelf) => "You ran into yourself.",
+        SimulationResult::Died(DeathReason::HitSnake(_)) => "You ran into another snake.",
+        SimulationResult::Died(DeathReason::HeadOn(_)) => "You collided head-on.",
+        SimulationResult::Died(DeathReason::Hazard) => "The board closed in on you.",
+        SimulationResult::Won => "You win!",
+        SimulationResult::Lost => "You lose.",
+        SimulationResult::LeftBoard(_) => "You left the board.",
+        SimulationResult::OutOfMoves => "You ran out of moves.",
+        SimulationResult::TimeExpired => "Time's up!",
+        SimulationResult::ManuallyTerminated(_) => "Game over.",
+    }
+}
+
+fn fill_cell(ctx: &CanvasRenderingContext2d, cell: &Vector2) {
+    ctx.fill_rect(
+        f64::from(cell.x) * CELL_SIZE + 1.0,
+        f64::from(cell.y) * CELL_SIZE + 1.0,
+        CELL_SIZE - 2.0,
+        CELL_SIZE - 2.0,
+    );
+}
+
+fn listen_for_keys(window: &Window, app: Rc<RefCell<App>>) -> Result<(), JsValue> {
+    let on_key = Closure::<dyn FnMut(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+        // Leave shortcuts like Ctrl+R to the browser
+        if event.ctrl_key() || event.alt_key() || event.meta_key() {
+            return;
+        }
+
+        // Only keys the game uses are kept from the rest of the page
+        if let Ok(true) = app.borrow_mut().handle_key(&event.key()) {
+            event.prevent_default();
+        }
+    });
+
+    window.add_event_listener_with_callback("keydown", on_key.as_ref().unchecked_ref())?;
+
+    // The listener lives as long as the page does.
+    on_key.forget();
+    Ok(())
+}
+
+/// Advances the game once, redraws, then schedules itself again after the
+/// configured tick interval. Once the game is over it keeps polling, so a
+/// restart picks up without re-arming the timer.
+fn schedule_tick(window: Window, app: Rc<RefCell<App>>) -> Result<(), JsValue> {
+    let interval = {
+        let app = app.borrow();
+        let config = app.game.config();
+        config
+            .speed
+            .tick_interval(app.game.simulation().food_eaten())
+    };
+
+    let next_window = window.clone();
+    let on_tick = Closure::once_into_js(move || {
+        {
+            let mut app = app.borrow_mut();
+            app.game.step();
+            app.draw();
+        }
+
+        // There is nowhere useful to report a failure to, and the only way
+        // this can fail is if the window is being torn down.
+        let _ = schedule_tick(next_window, app);
+    });
+
+    window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        on_tick.unchecked_ref(),
+        interval.as_millis() as i32,
+    )?;
+
+    Ok(())
+}
+
+fn random_seed() -> u32 {
+    (js_sys::Math::random() * f64::from(u32::MAX)) as u32
+}
@@ -0,0 +1,64 @@
+//! Plays arbitrary moves on arbitrary small boards, checking the simulation's
+//! invariants after every step.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use constrictor_core::{
+    config::{BoardSize, GameConfig, WallStyle},
+    math::Direction,
+    models::{Rules, SpawnStrategy},
+};
+use libfuzzer_sys::fuzz_target;
+
+/// A game and the moves played in it. Boards are kept small, so games are
+/// cheap and often end.
+#[derive(Debug, Arbitrary)]
+struct Game {
+    width: u8,
+    height: u8,
+    wrap: bool,
+    rules: Rules,
+    spawn: SpawnStrategy,
+    seed: u64,
+    moves: Vec<Option<Direction>>,
+}
+
+fuzz_target!(|game: Game| {
+    let config = GameConfig {
+        board: BoardSize {
+            width: (game.width % 24).into(),
+            height: (game.height % 24).into(),
+        },
+        walls: if game.wrap {
+            WallStyle::Wrap
+        } else {
+            WallStyle::Solid
+        },
+        rules: game.rules.clone(),
+        spawn: game.spawn,
+        seed: Some(game.seed),
+        ..Default::default()
+    };
+
+    // Boards too small to start on are rejected up front
+    let Ok(mut sim) = config.build() else {
+        return;
+    };
+
+    for (step, direction) in game.moves.iter().enumerate() {
+        if let Some(direction) = direction {
+            sim.change_player_move_direction(*direction);
+        }
+
+        sim.advance();
+
+        if let Err(violation) = sim.check_invariants() {
+            panic!("{violation} after step {step} of {game:?}");
+        }
+
+        if sim.result().is_some() {
+            break;
+        }
+    }
+});
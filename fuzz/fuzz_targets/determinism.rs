@@ -0,0 +1,82 @@
+//! Plays arbitrary moves on arbitrary small boards, then checks that playing
+//! the recorded replay back is deterministic and arrives at the same game.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use constrictor_core::{
+    config::{BoardSize, GameConfig, WallStyle},
+    math::Direction,
+    models::{Rules, SpawnStrategy},
+    replay::{InputLog, Replay},
+};
+use libfuzzer_sys::fuzz_target;
+
+/// A game and the moves played in it. Boards are kept small, so games are
+/// cheap and often end.
+#[derive(Debug, Arbitrary)]
+struct Game {
+    width: u8,
+    height: u8,
+    wrap: bool,
+    rules: Rules,
+    spawn: SpawnStrategy,
+    seed: u64,
+    moves: Vec<Option<Direction>>,
+}
+
+fuzz_target!(|game: Game| {
+    let config = GameConfig {
+        board: BoardSize {
+            width: (game.width % 24).into(),
+            height: (game.height % 24).into(),
+        },
+        walls: if game.wrap {
+            WallStyle::Wrap
+        } else {
+            WallStyle::Solid
+        },
+        rules: game.rules.clone(),
+        spawn: game.spawn,
+        seed: Some(game.seed),
+        ..Default::default()
+    };
+
+    // Boards too small to start on are rejected up front
+    let Ok(mut sim) = config.build() else {
+        return;
+    };
+
+    let mut inputs = InputLog::new();
+    for direction in &game.moves {
+        if let Some(direction) = direction {
+            inputs.record(sim.tick(), *direction);
+            sim.change_player_move_direction(*direction);
+        }
+
+        sim.advance();
+
+        if sim.result().is_some() {
+            break;
+        }
+    }
+
+    let replay = Replay {
+        config,
+        inputs,
+        ticks: sim.tick(),
+        result: sim.result().copied(),
+    };
+
+    let trace = match replay.check_determinism() {
+        Ok(trace) => trace,
+        Err(e) => panic!("{e} while playing back {replay:?}"),
+    };
+
+    let last = trace.checkpoints().last().expect("a trace is never empty");
+    assert_eq!(
+        last.hash,
+        sim.state_hash(),
+        "playing back {replay:?} arrived at a different game"
+    );
+});
@@ -0,0 +1,42 @@
+//! Parses arbitrary bytes as a replay file and plays back whatever parses,
+//! checking the simulation's invariants after every step.
+
+#![no_main]
+
+use constrictor_core::replay::Replay;
+use libfuzzer_sys::fuzz_target;
+
+/// Replays on boards larger than this are skipped. They are valid, but too
+/// slow to be worth fuzzing.
+const MAX_BOARD_CELLS: u32 = 64 * 64;
+
+/// Playback is cut short after this many steps, since a replay can claim any
+/// number of ticks.
+const MAX_STEPS: usize = 10_000;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(replay) = serde_json::from_slice::<Replay>(data) else {
+        return;
+    };
+
+    let board = replay.config.board;
+    if u32::from(board.width) * u32::from(board.height) > MAX_BOARD_CELLS {
+        return;
+    }
+
+    let Ok(mut playback) = replay.play() else {
+        return;
+    };
+
+    for _ in 0..MAX_STEPS {
+        let stepped = playback.step();
+
+        if let Err(violation) = playback.simulation().check_invariants() {
+            panic!("{violation} while playing back {replay:?}");
+        }
+
+        if !stepped {
+            break;
+        }
+    }
+});
@@ -0,0 +1,54 @@
+#!/usr/bin/env sh
+# Checks that every combination of each crate's cargo features builds, so that
+# optional subsystems can't quietly start depending on one another. The core
+# crate's doctests are run under every combination too, since its examples
+# are what document each feature set.
+#
+# Usage: scripts/feature-matrix.sh [crate...]
+set -eu
+
+cd "$(dirname "$0")/.."
+
+# Defaults to every member of the workspace.
+crates="${*:-$(cargo metadata --no-deps --format-version 1 | python3 -c '
+import json, sys
+print(" ".join(p["name"] for p in json.load(sys.stdin)["packages"]))
+')}"
+
+# Lists the non-default features declared by a workspace crate.
+features_of() {
+    cargo metadata --no-deps --format-version 1 | python3 -c '
+import json, sys
+crate = sys.argv[1]
+pkg = next(p for p in json.load(sys.stdin)["packages"] if p["name"] == crate)
+print(" ".join(f for f in pkg["features"] if f != "default"))
+' "$1"
+}
+
+for crate in $crates; do
+    set -- $(features_of "$crate")
+    count=$#
+    combos=$((1 << count))
+
+    mask=0
+    while [ "$mask" -lt "$combos" ]; do
+        selected=""
+        bit=0
+        for feature in "$@"; do
+            if [ $(((mask >> bit) & 1)) -eq 1 ]; then
+                selected="$selected,$feature"
+            fi
+            bit=$((bit + 1))
+        done
+        selected="${selected#,}"
+
+        echo ">> $crate [${selected:-no features}]"
+        cargo check --quiet -p "$crate" --all-targets --no-default-features --features "$selected"
+
+        if [ "$crate" = constrictor-core ]; then
+            cargo test --quiet -p "$crate" --doc --no-default-features --features "$selected"
+        fi
+
+        mask=$((mask + 1))
+    done
+done